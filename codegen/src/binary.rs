@@ -0,0 +1,82 @@
+use crate::specs::AMQProtocolDefinition;
+
+use serde_json::Value;
+
+use std::{fs, path::Path};
+
+impl AMQProtocolDefinition {
+    /// Serialize the protocol definition as a compact binary blob
+    pub fn to_binary(&self) -> Result<Vec<u8>, String> {
+        let mut blob = Vec::new();
+        ciborium::into_writer(self, &mut blob)
+            .map_err(|e| format!("Failed to serialize specs: {}", e))?;
+        Ok(blob)
+    }
+
+    /// Deserialize a protocol definition from a blob generated by [`to_binary`](Self::to_binary)
+    pub fn from_binary(blob: &[u8]) -> Result<AMQProtocolDefinition, String> {
+        ciborium::from_reader(blob).map_err(|e| format!("Failed to deserialize specs: {}", e))
+    }
+}
+
+/// Embed the protocol definition as a binary blob instead of generating the expanded code.
+///
+/// This writes `{target}.bin` holding the specs and `{target}.rs` defining a
+/// `protocol_definition()` function, which deserializes the blob on first access.
+/// The generated code requires depending on this crate with the `binary` feature enabled.
+pub fn binary_codegen(out_dir: &str, target: &str, metadata: Option<Value>) {
+    try_binary_codegen(out_dir, target, metadata).unwrap_or_else(|e| panic!("{}", e));
+}
+
+/// Like [`binary_codegen`], failing instead of panicking if the metadata is invalid or if
+/// writing fails.
+pub fn try_binary_codegen(
+    out_dir: &str,
+    target: &str,
+    metadata: Option<Value>,
+) -> Result<(), String> {
+    let blob_path = Path::new(out_dir).join(format!("{}.bin", target));
+    let dest_path = Path::new(out_dir).join(format!("{}.rs", target));
+    let blob = AMQProtocolDefinition::try_load(metadata)?.to_binary()?;
+
+    fs::write(&blob_path, blob)
+        .map_err(|err| format!("Failed to create {:?}: {}", blob_path, err))?;
+    fs::write(&dest_path, binary_accessor(&blob_path))
+        .map_err(|e| format!("Failed to generate {}.rs: {}", target, e))
+}
+
+fn binary_accessor(blob_path: &Path) -> String {
+    format!(
+        r#"/// The AMQP protocol definition, lazily deserialized from the embedded blob
+pub fn protocol_definition() -> &'static amq_protocol_codegen::AMQProtocolDefinition {{
+    static DEFINITION: std::sync::OnceLock<amq_protocol_codegen::AMQProtocolDefinition> =
+        std::sync::OnceLock::new();
+    DEFINITION.get_or_init(|| {{
+        amq_protocol_codegen::AMQProtocolDefinition::from_binary(include_bytes!({:?}))
+            .unwrap_or_else(|e| panic!("{{}}", e))
+    }})
+}}
+"#,
+        blob_path
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let specs = AMQProtocolDefinition::load(None);
+        let blob = specs.to_binary().unwrap();
+        assert!(blob.len() < serde_json::to_vec(&specs).unwrap().len());
+        assert_eq!(AMQProtocolDefinition::from_binary(&blob), Ok(specs));
+    }
+
+    #[test]
+    fn test_binary_accessor() {
+        let accessor = binary_accessor(Path::new("/out/protocol.bin"));
+        assert!(accessor.contains(r#"include_bytes!("/out/protocol.bin")"#));
+        assert!(accessor.contains("pub fn protocol_definition()"));
+    }
+}
@@ -115,6 +115,22 @@ const RECEIVE_ONLY: &[(&str, &[&str])] = &[
     ("tx", &["commit-ok", "rollback-ok", "select-ok"]),
 ];
 
+/* Classes which are not needed to establish a connection and can be compiled out */
+const FEATURE_GATED: &[&str] = &["access", "confirm", "exchange", "queue", "tx"];
+
+type StringEnum = (
+    &'static str,
+    &'static [&'static str],
+    &'static [(&'static str, &'static str, &'static str)],
+);
+
+/* The well-known values of each enum, and the (class, method, argument) holding one */
+const STRING_ENUMS: &[StringEnum] = &[(
+    "exchange-type",
+    &["direct", "fanout", "topic", "headers"],
+    &[("exchange", "declare", "type")],
+)];
+
 /* Modified version of AMQProtocolDefinition to handle deserialization */
 #[derive(Debug, Deserialize)]
 pub struct _AMQProtocolDefinition {
@@ -132,20 +148,26 @@ pub struct _AMQProtocolDefinition {
 }
 
 impl _AMQProtocolDefinition {
-    pub fn into_specs(self, metadata: &Value) -> AMQProtocolDefinition {
-        let domains = self
+    pub fn into_specs(self, metadata: &Value) -> Result<AMQProtocolDefinition, String> {
+        let mut domains = self
             .domains
             .iter()
             .fold(BTreeMap::new(), |mut domains, domain| {
                 domains.insert(domain.0.clone(), domain.1.to_specs());
                 domains
             });
-        let classes = self
+        domains.extend(domain_overrides(metadata)?);
+        let derives = derives(metadata)?;
+        let mut classes = self
             .classes
             .iter()
             .map(|klass| klass.to_specs(&domains, metadata))
-            .collect();
-        AMQProtocolDefinition {
+            .filter(|klass| klass.as_ref().map_or(true, |klass| !klass.metadata.skip))
+            .collect::<Result<Vec<_>, _>>()?;
+        if derives.copy {
+            classes.iter_mut().for_each(derive_copy);
+        }
+        Ok(AMQProtocolDefinition {
             name: self.name,
             major_version: self.major_version,
             minor_version: self.minor_version,
@@ -189,11 +211,144 @@ impl _AMQProtocolDefinition {
                     }
                 })
                 .collect(),
+            string_enums: string_enums(metadata, &classes),
             classes,
+            server: server(metadata)?,
+            derives,
+        })
+    }
+}
+
+/* Domains added or remapped by the "domains" metadata, using the types names of the spec */
+fn domain_overrides(metadata: &Value) -> Result<BTreeMap<String, AMQPType>, String> {
+    metadata
+        .get("domains")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, amqp_type)| {
+            _AMQPType::deserialize(amqp_type)
+                .map(|amqp_type| (name.clone(), amqp_type.to_specs()))
+                .map_err(|e| format!("Invalid type for the {} domain: {}", name, e))
+        })
+        .collect()
+}
+
+/* The server side helpers settings, from the "server" metadata */
+fn server(metadata: &Value) -> Result<Option<AMQPServer>, String> {
+    match metadata.get("server") {
+        None | Some(Value::Null) | Some(Value::Bool(false)) => Ok(None),
+        Some(Value::Bool(true)) => Ok(Some(AMQPServer::default())),
+        Some(server) => AMQPServer::deserialize(server)
+            .map(Some)
+            .map_err(|e| format!("Invalid server metadata: {}", e)),
+    }
+}
+
+/* The derives of the generated code, from the "derives" metadata */
+fn derives(metadata: &Value) -> Result<AMQPDerives, String> {
+    metadata.get("derives").map_or_else(
+        || Ok(AMQPDerives::default()),
+        |derives| {
+            AMQPDerives::deserialize(derives)
+                .map_err(|e| format!("Invalid derives metadata: {}", e))
+        },
+    )
+}
+
+/* Add Copy to the methods only carrying Copy fields, and to the class if they all do */
+fn derive_copy(class: &mut AMQPClass) {
+    fn add_copy(metadata: &mut Metadata) {
+        if !metadata.derives.iter().any(|derive| derive == "Copy") {
+            metadata.derives.push("Copy".to_string());
+        }
+    }
+
+    let mut all_copy = true;
+    for method in &mut class.methods {
+        let copy = method.fields().iter().all(|field| {
+            !matches!(
+                field.amqp_type,
+                AMQPType::ShortString
+                    | AMQPType::LongString
+                    | AMQPType::FieldArray
+                    | AMQPType::FieldTable
+                    | AMQPType::ByteArray
+            )
+        });
+        if copy {
+            add_copy(&mut method.metadata);
         }
+        all_copy &= copy;
+    }
+    if all_copy {
+        add_copy(&mut class.metadata);
     }
 }
 
+/* Well-known string enums, extended or overridden by the "string_enums" metadata. Only the
+ * arguments of the methods which get generated are kept */
+fn string_enums(metadata: &Value, classes: &[AMQPClass]) -> Vec<AMQPStringEnum> {
+    let mut string_enums = STRING_ENUMS
+        .iter()
+        .map(|(name, values, arguments)| {
+            (
+                name.to_string(),
+                (
+                    values.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    *arguments,
+                ),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+    if let Some(extra) = metadata.get("string_enums").and_then(Value::as_object) {
+        for (name, values) in extra {
+            let values = values
+                .as_array()
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(ToString::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            string_enums.entry(name.clone()).or_default().0 = values;
+        }
+    }
+    string_enums
+        .into_iter()
+        .map(|(name, (values, arguments))| AMQPStringEnum {
+            name,
+            values,
+            arguments: arguments
+                .iter()
+                .filter_map(|(class, method, argument)| {
+                    let klass = classes.iter().find(|klass| klass.name == *class)?;
+                    klass.methods.iter().any(|m| m.name == *method).then(|| {
+                        AMQPStringEnumArgument {
+                            class: class.to_string(),
+                            method: method.to_string(),
+                            name: argument.to_string(),
+                            feature: klass.feature.clone(),
+                        }
+                    })
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn parse_metadata(name: &str, metadata: Option<&Value>) -> Result<Metadata, String> {
+    metadata
+        .map(|metadata| {
+            Metadata::deserialize(metadata)
+                .map_err(|e| format!("Invalid metadata for {}: {}", name, e))
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
 /* Defined as a two-elems array in the spec */
 #[derive(Debug, Deserialize)]
 struct _AMQPDomain(String, _AMQPType);
@@ -278,12 +433,13 @@ struct _AMQPClass {
 }
 
 impl _AMQPClass {
-    fn to_specs(&self, domains: &BTreeMap<String, AMQPType>, metadata: &Value) -> AMQPClass {
+    fn to_specs(
+        &self,
+        domains: &BTreeMap<String, AMQPType>,
+        metadata: &Value,
+    ) -> Result<AMQPClass, String> {
         let class_md = metadata.get(&self.name);
-        let metadata = class_md
-            .and_then(|c| c.get("metadata"))
-            .cloned()
-            .unwrap_or_default();
+        let metadata = parse_metadata(&self.name, class_md.and_then(|c| c.get("metadata")))?;
         let defaults = (|name| {
             for (class, defaults) in ENFORCED_DEFAULTS {
                 if class == name {
@@ -312,20 +468,54 @@ impl _AMQPClass {
             Some(ref properties) => properties.iter().map(_AMQPProperty::to_specs).collect(),
             None => Vec::default(),
         };
-        AMQPClass {
+        let methods = self
+            .methods
+            .iter()
+            .map(|method| method.to_specs(domains, class_md, defaults, send_only, receive_only))
+            .filter(|method| method.as_ref().map_or(true, |method| !method.metadata.skip))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(AMQPClass {
             id: self.id,
-            methods: self
-                .methods
-                .iter()
-                .map(|method| method.to_specs(domains, class_md, defaults, send_only, receive_only))
-                .collect(),
-            name: self.name.clone(),
+            methods: link_replies(methods),
+            name: metadata.rename.clone().unwrap_or_else(|| self.name.clone()),
             properties,
             metadata,
-        }
+            feature: FEATURE_GATED
+                .contains(&self.name.as_str())
+                .then(|| format!("class-{}", self.name)),
+        })
     }
 }
 
+/* Link each "foo-ok" method to the "foo" one, to generate the conversion from the request */
+fn link_replies(mut methods: Vec<AMQPMethod>) -> Vec<AMQPMethod> {
+    let requests = methods
+        .iter()
+        .map(|method| (method.name.clone(), method.fields()))
+        .collect::<BTreeMap<_, _>>();
+    for method in &mut methods {
+        let Some((name, request_fields)) = method
+            .name
+            .strip_suffix("-ok")
+            .and_then(|name| requests.get_key_value(name))
+        else {
+            continue;
+        };
+        let fields = method.fields();
+        let copied = fields
+            .iter()
+            .filter(|field| request_fields.contains(field))
+            .cloned()
+            .collect::<Vec<_>>();
+        method.request = Some(AMQPRequest {
+            name: name.clone(),
+            partial: copied.len() < fields.len(),
+            fields: copied,
+        });
+    }
+    methods
+}
+
 #[derive(Debug, Deserialize)]
 struct _AMQPMethod {
     id: Identifier,
@@ -343,7 +533,7 @@ impl _AMQPMethod {
         defaults: Option<&'static [(&'static str, &'static [&'static str])]>,
         send_only: Option<&'static [&'static str]>,
         receive_only: Option<&'static [&'static str]>,
-    ) -> AMQPMethod {
+    ) -> Result<AMQPMethod, String> {
         let defaults = defaults.and_then(|defaults| {
             for (method, defaults) in defaults {
                 if method == &self.name.as_str() {
@@ -352,64 +542,66 @@ impl _AMQPMethod {
             }
             None
         });
-        let arguments = self.arguments_to_specs(domains, defaults);
+        let arguments = self.arguments_to_specs(domains, defaults)?;
         let is_reply = self.name.ends_with("-ok");
-        let mut metadata = class_md
-            .and_then(|c| c.get(&self.name))
-            .and_then(|m| m.get("metadata"))
-            .cloned()
-            .unwrap_or_default();
-        if is_reply && metadata.get("state").is_none() {
-            if let Some(state) = class_md
+        let mut metadata = parse_metadata(
+            &self.name,
+            class_md
+                .and_then(|c| c.get(&self.name))
+                .and_then(|m| m.get("metadata")),
+        )?;
+        if is_reply && metadata.state.is_none() {
+            metadata.state = class_md
                 .and_then(|c| c.get(self.name.replace("-ok", "")))
                 .and_then(|m| m.get("metadata"))
                 .and_then(|m| m.get("state"))
-            {
-                metadata["state"] = state.clone();
-            }
+                .cloned();
         }
-        if is_reply && metadata.get("confirmation").is_none() {
-            if let Some(confirmation) = class_md
+        if is_reply && metadata.confirmation.is_none() {
+            metadata.confirmation = class_md
                 .and_then(|c| c.get(self.name.replace("-ok", "")))
                 .and_then(|m| m.get("metadata"))
                 .and_then(|m| m.get("confirmation"))
-            {
-                metadata["confirmation"] = confirmation.clone();
-            }
+                .cloned();
         }
         let ignore_args = arguments.iter().all(AMQPArgument::force_default);
+        let has_field_tables = arguments.iter().any(|argument| {
+            matches!(argument, AMQPArgument::Value(value) if value.amqp_type == AMQPType::FieldTable)
+        });
         let c2s = !receive_only
             .map(|receive_only| receive_only.contains(&self.name.as_str()))
             .unwrap_or(false);
         let s2c = !send_only
             .map(|send_only| send_only.contains(&self.name.as_str()))
             .unwrap_or(false);
-        AMQPMethod {
+        Ok(AMQPMethod {
             id: self.id,
             arguments,
-            name: self.name.clone(),
+            name: metadata.rename.clone().unwrap_or_else(|| self.name.clone()),
             synchronous: self.synchronous.unwrap_or(false),
             content: self.content.unwrap_or(false),
             metadata,
             is_reply,
             ignore_args,
+            has_field_tables,
             c2s,
             s2c,
-        }
+            request: None,
+        })
     }
 
     fn arguments_to_specs(
         &self,
         domains: &BTreeMap<String, AMQPType>,
         defaults: Option<&'static [&'static str]>,
-    ) -> Vec<AMQPArgument> {
+    ) -> Result<Vec<AMQPArgument>, String> {
         let mut arguments = Vec::default();
         let mut flags: Option<Vec<AMQPFlagArgument>> = None;
         for argument in &self.arguments {
             let force_default = defaults
                 .map(|defaults| defaults.contains(&argument.name.as_str()))
                 .unwrap_or(false);
-            let amqp_type = argument.get_type(domains);
+            let amqp_type = argument.get_type(domains)?;
             if amqp_type == AMQPType::Boolean {
                 let mut flgs = flags.take().unwrap_or_default();
                 flgs.push(argument.to_flag_specs(force_default));
@@ -434,7 +626,7 @@ impl _AMQPMethod {
                 flags,
             }));
         }
-        arguments
+        Ok(arguments)
     }
 }
 
@@ -455,8 +647,7 @@ impl _AMQPArgument {
             default_value: self
                 .default_value
                 .as_ref()
-                .and_then(Value::as_u64)
-                .map(|u| u != 0)
+                .and_then(|v| v.as_bool().or_else(|| v.as_u64().map(|u| u != 0)))
                 .unwrap_or(false),
             force_default,
         }
@@ -478,17 +669,18 @@ impl _AMQPArgument {
             .and_then(|v| AMQPValue::try_from(v, amqp_type))
     }
 
-    fn get_type(&self, domains: &BTreeMap<String, AMQPType>) -> AMQPType {
+    fn get_type(&self, domains: &BTreeMap<String, AMQPType>) -> Result<AMQPType, String> {
         match self.amqp_type {
-            Some(ref amqp_type) => amqp_type.to_specs(),
+            Some(ref amqp_type) => Ok(amqp_type.to_specs()),
             None => {
-                let domain = match self.domain {
-                    Some(ref domain) => domain,
-                    None => panic!("{} has no type nor domain", self.name),
-                };
-                *domains
+                let domain = self
+                    .domain
+                    .as_ref()
+                    .ok_or_else(|| format!("{} has no type nor domain", self.name))?;
+                domains
                     .get(domain)
-                    .unwrap_or_else(|| panic!("No {} domain exists", domain))
+                    .copied()
+                    .ok_or_else(|| format!("No {} domain exists", domain))
             }
         }
     }
@@ -601,20 +793,233 @@ mod test {
                     name: "meth1".to_string(),
                     synchronous: false,
                     content: false,
-                    metadata: Value::default(),
+                    metadata: Metadata::default(),
                     is_reply: false,
                     ignore_args: false,
+                    has_field_tables: false,
                     c2s: true,
                     s2c: true,
+                    request: None,
                 }],
                 name: "class1".to_string(),
                 properties: vec![AMQPProperty {
                     amqp_type: AMQPType::ShortShortUInt,
                     name: "prop1".to_string(),
                 }],
-                metadata: Value::default(),
+                metadata: Metadata::default(),
+                feature: None,
+            }],
+            string_enums: vec![AMQPStringEnum {
+                name: "exchange-type".to_string(),
+                values: vec![
+                    "direct".to_string(),
+                    "fanout".to_string(),
+                    "topic".to_string(),
+                    "headers".to_string(),
+                ],
+                arguments: Vec::new(),
             }],
+            server: None,
+            derives: AMQPDerives::default(),
+        };
+        assert_eq!(def.into_specs(&Value::default()), Ok(expected));
+    }
+
+    #[test]
+    fn test_server_metadata() {
+        assert_eq!(server(&Value::default()), Ok(None));
+        assert_eq!(
+            server(&serde_json::json!({ "server": true })),
+            Ok(Some(AMQPServer::default()))
+        );
+        assert_eq!(
+            server(&serde_json::json!({ "server": { "heartbeat": 30 } })),
+            Ok(Some(AMQPServer {
+                heartbeat: 30,
+                ..AMQPServer::default()
+            }))
+        );
+        assert!(server(&serde_json::json!({ "server": "yes" })).is_err());
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        let argument = _AMQPArgument {
+            amqp_type: None,
+            name: "arg1".to_string(),
+            default_value: None,
+            domain: Some("unknown".to_string()),
         };
-        assert_eq!(def.into_specs(&Value::default()), expected);
+        assert_eq!(
+            argument.get_type(&BTreeMap::new()),
+            Err("No unknown domain exists".to_string())
+        );
+    }
+
+    #[test]
+    fn test_string_enums_metadata() {
+        let metadata = serde_json::json!({
+            "string_enums": {
+                "exchange-type": ["direct", "x-consistent-hash"],
+                "queue-type": ["classic", "quorum", "stream"],
+            }
+        });
+        let specs = AMQProtocolDefinition::load(None);
+        assert_eq!(
+            string_enums(&metadata, &specs.classes),
+            vec![
+                AMQPStringEnum {
+                    name: "exchange-type".to_string(),
+                    values: vec!["direct".to_string(), "x-consistent-hash".to_string()],
+                    arguments: vec![AMQPStringEnumArgument {
+                        class: "exchange".to_string(),
+                        method: "declare".to_string(),
+                        name: "type".to_string(),
+                        feature: Some("class-exchange".to_string()),
+                    }],
+                },
+                AMQPStringEnum {
+                    name: "queue-type".to_string(),
+                    values: vec![
+                        "classic".to_string(),
+                        "quorum".to_string(),
+                        "stream".to_string(),
+                    ],
+                    arguments: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_derives_metadata() {
+        let specs = AMQProtocolDefinition::load(Some(serde_json::json!({
+            "derives": { "debug": false, "copy": true }
+        })));
+        assert_eq!(
+            specs.derives,
+            AMQPDerives {
+                debug: false,
+                copy: true
+            }
+        );
+        let class = |name| {
+            specs
+                .classes
+                .iter()
+                .find(|klass| klass.name == name)
+                .unwrap()
+        };
+        let is_copy = |metadata: &Metadata| metadata.derives.contains(&"Copy".to_string());
+        let basic = class("basic");
+        assert!(!is_copy(&basic.metadata));
+        for (method, copy) in [("qos", true), ("ack", true), ("publish", false)] {
+            let method = basic.methods.iter().find(|m| m.name == method).unwrap();
+            assert_eq!(is_copy(&method.metadata), copy, "{}", method.name);
+        }
+        assert!(is_copy(&class("tx").metadata));
+        assert_eq!(
+            AMQProtocolDefinition::load(None).derives,
+            AMQPDerives::default()
+        );
+        assert!(AMQProtocolDefinition::try_load(Some(serde_json::json!({
+            "derives": { "debug": "no" }
+        })))
+        .is_err());
+    }
+
+    #[test]
+    fn test_domain_overrides() {
+        let specs = AMQProtocolDefinition::load(Some(serde_json::json!({
+            "domains": {
+                "priority": "octet",
+                "queue-name": "longstr"
+            }
+        })));
+        assert_eq!(
+            specs.resolve_domain("priority"),
+            Ok(AMQPType::ShortShortUInt)
+        );
+        assert_eq!(specs.resolve_domain("queue-name"), Ok(AMQPType::LongString));
+        assert_eq!(
+            specs.resolve_domain("exchange-name"),
+            Ok(AMQPType::ShortString)
+        );
+        assert!(specs.resolve_domain("unknown").is_err());
+        let consume = specs
+            .classes
+            .iter()
+            .find(|klass| klass.name == "basic")
+            .and_then(|klass| klass.methods.iter().find(|m| m.name == "consume"))
+            .unwrap();
+        assert!(consume.fields().contains(&AMQPField {
+            amqp_type: AMQPType::LongString,
+            name: "queue".into(),
+        }));
+        assert!(AMQProtocolDefinition::try_load(Some(serde_json::json!({
+            "domains": { "priority": "float" }
+        })))
+        .is_err());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let metadata = serde_json::json!({
+            "derives": ["Eq"],
+            "rename": "custom",
+            "state": { "some": "state" },
+            "extra": { "hook": "on_declare" },
+        });
+        let metadata = parse_metadata("class1", Some(&metadata)).unwrap();
+        assert_eq!(metadata.derives, vec!["Eq".to_string()]);
+        assert_eq!(metadata.rename.as_deref(), Some("custom"));
+        assert!(!metadata.skip);
+        assert_eq!(
+            metadata.get("state"),
+            Some(&serde_json::json!({ "some": "state" }))
+        );
+        assert_eq!(metadata.get("hook"), Some(&serde_json::json!("on_declare")));
+        assert_eq!(parse_metadata("class1", None), Ok(Metadata::default()));
+    }
+
+    #[test]
+    fn test_link_replies() {
+        let specs = AMQProtocolDefinition::load(None);
+        let methods = &specs
+            .classes
+            .iter()
+            .find(|class| class.name == "queue")
+            .unwrap()
+            .methods;
+        let declare_ok = methods.iter().find(|m| m.name == "declare-ok").unwrap();
+        assert_eq!(
+            declare_ok.request,
+            Some(AMQPRequest {
+                name: "declare".to_string(),
+                fields: vec![AMQPField {
+                    amqp_type: AMQPType::ShortString,
+                    name: "queue".to_string(),
+                }],
+                partial: true,
+            })
+        );
+        let declare = methods.iter().find(|m| m.name == "declare").unwrap();
+        assert_eq!(declare.request, None);
+    }
+
+    #[test]
+    fn test_invalid_metadata() {
+        assert!(
+            parse_metadata("class1", Some(&serde_json::json!({ "skip": "yes" })))
+                .is_err_and(|e| e.starts_with("Invalid metadata for class1"))
+        );
+        assert!(
+            parse_metadata("class1", Some(&serde_json::json!({ "derive": ["Eq"] })))
+                .is_err_and(|e| e.contains("unknown field `derive`"))
+        );
+        assert!(AMQProtocolDefinition::try_load(Some(serde_json::json!({
+            "basic": { "metadata": { "skip": "yes" } }
+        })))
+        .is_err());
     }
 }
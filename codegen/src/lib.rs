@@ -5,9 +5,14 @@
 //! amq-protocol-codegen is a library aiming at providing tools to generate
 //! code from official AMQP specs definition.
 
+#[cfg(feature = "binary")]
+mod binary;
 mod internal;
 mod specs;
 mod templating;
 mod util;
 
 pub use crate::{specs::*, templating::*, util::*};
+
+#[cfg(feature = "binary")]
+pub use crate::binary::*;
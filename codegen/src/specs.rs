@@ -2,7 +2,7 @@ use crate::internal::*;
 
 use amq_protocol_types::*;
 use serde::{Deserialize, Serialize};
-use serde_json::{from_str, Value};
+use serde_json::{from_str, Map, Value};
 
 use std::collections::BTreeMap;
 
@@ -31,20 +31,117 @@ pub struct AMQProtocolDefinition {
     pub hard_errors: Vec<AMQPConstant>,
     /// The classes defined by the protocol specification
     pub classes: Vec<AMQPClass>,
+    /// The string domains with well-known values
+    pub string_enums: Vec<AMQPStringEnum>,
+    /// The settings of the server side helpers, only generated when the "server" metadata is set
+    pub server: Option<AMQPServer>,
+    /// The derives of the generated classes, methods, properties and enums
+    #[serde(default)]
+    pub derives: AMQPDerives,
 }
 
 impl AMQProtocolDefinition {
     /// Load protocol definition from reference specification
+    ///
+    /// Panics if the metadata is invalid, see [`try_load`](Self::try_load).
     pub fn load(metadata: Option<Value>) -> AMQProtocolDefinition {
+        Self::try_load(metadata).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Load protocol definition from reference specification, failing if the metadata is invalid
+    pub fn try_load(metadata: Option<Value>) -> Result<AMQProtocolDefinition, String> {
         let specs = include_str!(concat!(
             env!("CARGO_MANIFEST_DIR"),
             "/specs/amqp-rabbitmq-0.9.1.json"
         ));
 
         from_str::<_AMQProtocolDefinition>(specs)
-            .expect("Failed to parse AMQP specs file")
+            .map_err(|e| format!("Failed to parse AMQP specs file: {}", e))?
             .into_specs(&metadata.unwrap_or_default())
     }
+
+    /// Get the type a domain resolves to, including the ones added or remapped by the "domains"
+    /// metadata
+    pub fn resolve_domain(&self, name: &str) -> Result<AMQPType, String> {
+        self.domains
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("No {} domain exists", name))
+    }
+
+    /// Serialize the definition as pretty JSON with sorted keys, showing exactly the data the
+    /// templates receive once the domains are resolved and the metadata is merged
+    pub fn to_json_pretty(&self) -> Result<String, String> {
+        let value =
+            serde_json::to_value(self).map_err(|e| format!("Failed to serialize specs: {}", e))?;
+        serde_json::to_string_pretty(&sort_keys(value))
+            .map_err(|e| format!("Failed to serialize specs: {}", e))
+    }
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries = object.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+/// The settings of the helpers for the server side of the protocol
+///
+/// Setting the "server" metadata to true uses the default values, which are RabbitMQ's ones,
+/// while setting it to an object overrides some of them.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AMQPServer {
+    /// The channel_max proposed in connection.tune
+    pub channel_max: ShortUInt,
+    /// The frame_max proposed in connection.tune
+    pub frame_max: LongUInt,
+    /// The heartbeat proposed in connection.tune
+    pub heartbeat: ShortUInt,
+}
+
+impl Default for AMQPServer {
+    fn default() -> Self {
+        Self {
+            channel_max: 2047,
+            frame_max: 131_072,
+            heartbeat: 60,
+        }
+    }
+}
+
+/// The derives of the generated classes, methods, properties and enums, from the "derives" metadata
+///
+/// Dropping Debug saves its formatting machinery in embedded binaries, but the amq-protocol
+/// crate itself needs it. Copy is only added to the methods whose fields are all Copy, and to
+/// the methods enum of the classes whose methods all are.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AMQPDerives {
+    /// Derive Debug
+    pub debug: bool,
+    /// Derive Copy where possible
+    pub copy: bool,
+}
+
+impl Default for AMQPDerives {
+    fn default() -> Self {
+        Self {
+            debug: true,
+            copy: false,
+        }
+    }
 }
 
 /// A constant as defined in the AMQP specification
@@ -59,6 +156,31 @@ pub struct AMQPConstant {
     pub amqp_type: AMQPType,
 }
 
+/// A string domain with a finite set of well-known values, generated as an enum
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AMQPStringEnum {
+    /// The name of the enum
+    pub name: String,
+    /// The well-known values
+    pub values: Vec<String>,
+    /// The method arguments holding one of these values, getting typed accessors
+    #[serde(default)]
+    pub arguments: Vec<AMQPStringEnumArgument>,
+}
+
+/// A method argument holding the value of a string enum
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AMQPStringEnumArgument {
+    /// The name of the class
+    pub class: String,
+    /// The name of the method
+    pub method: String,
+    /// The name of the argument
+    pub name: String,
+    /// The cargo feature gating the class, if any
+    pub feature: Option<String>,
+}
+
 /// A class as defined in the AMQP specification
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct AMQPClass {
@@ -71,7 +193,50 @@ pub struct AMQPClass {
     /// The properties of the class
     pub properties: Vec<AMQPProperty>,
     /// Extra metadata for code generation
-    pub metadata: Value,
+    pub metadata: Metadata,
+    /// The cargo feature gating this class, if any
+    pub feature: Option<String>,
+}
+
+/// Extra metadata for code generation, attached to a class or a method
+///
+/// Unknown keys are rejected so that typos get reported, the custom metadata of templates going
+/// in extra.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Metadata {
+    /// Extra traits to derive on the generated type
+    #[serde(default)]
+    pub derives: Vec<String>,
+    /// The name to use in the generated code instead of the one from the specification
+    #[serde(default)]
+    pub rename: Option<String>,
+    /// Don't generate any code for this item
+    #[serde(default)]
+    pub skip: bool,
+    /// Extra impl blocks to add after the generated type
+    #[serde(default)]
+    pub extra_impls: Vec<String>,
+    /// The state tracked by the template for this method, inherited by its reply
+    #[serde(default)]
+    pub state: Option<Value>,
+    /// The confirmation expected by the template for this method, inherited by its reply
+    #[serde(default)]
+    pub confirmation: Option<Value>,
+    /// Any other metadata, for custom templates
+    #[serde(default)]
+    pub extra: Map<String, Value>,
+}
+
+impl Metadata {
+    /// Get the value of a metadata entry which is only known to the templates
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match key {
+            "state" => self.state.as_ref(),
+            "confirmation" => self.confirmation.as_ref(),
+            key => self.extra.get(key),
+        }
+    }
 }
 
 /// A method as defined in the AMQP specification
@@ -88,15 +253,67 @@ pub struct AMQPMethod {
     /// Whether this method carries some content frames with it
     pub content: Boolean,
     /// Extra metadata for code generation
-    pub metadata: Value,
+    pub metadata: Metadata,
     /// Whether this method is a reply or not
     pub is_reply: bool,
     /// Whether all the arguments have force_default or not
     pub ignore_args: bool,
+    /// Whether some arguments are field tables, which parsing options apply to
+    pub has_field_tables: bool,
     /// Whether this method can be sent from client to server
     pub c2s: bool,
     /// Whether this method can be received from server to client
     pub s2c: bool,
+    /// The request this method is a reply to, if any
+    pub request: Option<AMQPRequest>,
+}
+
+impl AMQPMethod {
+    /// The fields of the generated struct, leaving out the arguments with a forced default value
+    pub fn fields(&self) -> Vec<AMQPField> {
+        self.arguments
+            .iter()
+            .flat_map(|argument| match argument {
+                AMQPArgument::Value(value) => (!value.force_default)
+                    .then(|| AMQPField {
+                        amqp_type: value.amqp_type,
+                        name: value.name.clone(),
+                    })
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+                AMQPArgument::Flags(flags) => flags
+                    .flags
+                    .iter()
+                    .filter(|flag| !flag.force_default)
+                    .map(|flag| AMQPField {
+                        amqp_type: AMQPType::Boolean,
+                        name: flag.name.clone(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// The request a reply method answers, from the same class
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AMQPRequest {
+    /// The name of the request method
+    pub name: String,
+    /// The fields of the reply also found in the request, with the same type
+    pub fields: Vec<AMQPField>,
+    /// Whether the reply has fields which are not found in the request
+    pub partial: bool,
+}
+
+/// A field of a generated method struct
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AMQPField {
+    /// The type of the field
+    #[serde(rename = "type")]
+    pub amqp_type: AMQPType,
+    /// The name of the field
+    pub name: String,
 }
 
 /// An argument as defined in the AMQP specification
@@ -168,3 +385,22 @@ pub struct AMQPProperty {
     /// The name of the property
     pub name: String,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_json_pretty() {
+        let specs = AMQProtocolDefinition::load(Some(serde_json::json!({"server": true})));
+        let json = specs.to_json_pretty().unwrap();
+        assert!(json.starts_with("{\n  \"classes\": ["));
+        assert!(json.find("\"copyright\"") < json.find("\"domains\""));
+        assert!(json.contains("\"frame_max\": 131072"));
+        assert_eq!(specs.to_json_pretty(), Ok(json.clone()));
+        assert_eq!(
+            serde_json::from_str::<AMQProtocolDefinition>(&json).unwrap(),
+            specs
+        );
+    }
+}
@@ -8,7 +8,7 @@ use handlebars::{
 use serde_json::{self, Value};
 
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     fs::{self, File},
     io::Write,
     path::Path,
@@ -42,7 +42,56 @@ pub trait HandlebarsAMQPExtension {
         template: &str,
         var_name: &str,
         data: Option<Value>,
-    );
+    ) {
+        Self::try_simple_codegen_with_data(
+            out_dir,
+            target,
+            template_name,
+            template,
+            var_name,
+            data,
+        )
+        .unwrap_or_else(|e| panic!("{}", e));
+    }
+    /// Generate code like [simple_codegen_with_data](#method.simple_codegen_with_data), failing
+    /// instead of panicking if the metadata or the template is invalid, or if writing fails.
+    fn try_simple_codegen_with_data(
+        out_dir: &str,
+        target: &str,
+        template_name: &str,
+        template: &str,
+        var_name: &str,
+        data: Option<Value>,
+    ) -> Result<(), String>;
+    /// Generate code like [simple_codegen_with_data](#method.simple_codegen_with_data) but
+    /// using this generator, so that the partials registered on it are available to the template.
+    ///
+    /// The template can declare overridable sections using partial blocks, e.g.
+    /// `{{#> method_struct}}default content{{/method_struct}}`, the default content being used
+    /// unless a partial with that name has been registered.
+    fn codegen_with_data(
+        &mut self,
+        out_dir: &str,
+        target: &str,
+        template_name: &str,
+        template: &str,
+        var_name: &str,
+        data: Option<Value>,
+    ) {
+        self.try_codegen_with_data(out_dir, target, template_name, template, var_name, data)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+    /// Generate code like [codegen_with_data](#method.codegen_with_data), failing instead of
+    /// panicking if the metadata or the template is invalid, or if writing fails.
+    fn try_codegen_with_data(
+        &mut self,
+        out_dir: &str,
+        target: &str,
+        template_name: &str,
+        template: &str,
+        var_name: &str,
+        data: Option<Value>,
+    ) -> Result<(), String>;
 }
 
 impl<'a> HandlebarsAMQPExtension for CodeGenerator<'a> {
@@ -58,45 +107,57 @@ impl<'a> HandlebarsAMQPExtension for CodeGenerator<'a> {
         self.register_helper("use_bytes_ref", Box::new(UseBytesRefHelper));
         self.register_helper("each_argument", Box::new(EachArgumentHelper));
         self.register_helper("amqp_value_ref", Box::new(AMQPValueRefHelper));
+        self.register_helper("amqp_value_expr", Box::new(AMQPValueExprHelper));
+        self.register_helper("amqp_field_expr", Box::new(AMQPFieldExprHelper));
         self
     }
 
-    fn simple_codegen_with_data(
+    fn try_simple_codegen_with_data(
         out_dir: &str,
         target: &str,
         template_name: &str,
         template: &str,
         var_name: &str,
         metadata: Option<Value>,
-    ) {
-        let dest_path = Path::new(out_dir).join(format!("{}.rs", target));
-        let mut f = File::create(&dest_path)
-            .unwrap_or_else(|err| panic!("Failed to create {:?}: {}", dest_path, err));
-        let specs = AMQProtocolDefinition::load(metadata);
-        let mut codegen = CodeGenerator::default().register_amqp_helpers();
-        let mut data = HashMap::new();
+    ) -> Result<(), String> {
+        CodeGenerator::default()
+            .register_amqp_helpers()
+            .try_codegen_with_data(out_dir, target, template_name, template, var_name, metadata)
+    }
+
+    fn try_codegen_with_data(
+        &mut self,
+        out_dir: &str,
+        target: &str,
+        template_name: &str,
+        template: &str,
+        var_name: &str,
+        metadata: Option<Value>,
+    ) -> Result<(), String> {
+        let specs = AMQProtocolDefinition::try_load(metadata)?;
+        /* The specs only hold vecs and ordered maps, and nothing like a timestamp gets added, so
+         * the output only depends on the template, the specs and the metadata */
+        let mut data = BTreeMap::new();
 
-        codegen.set_strict_mode(true);
-        codegen
-            .register_template_string(template_name, template)
-            .unwrap_or_else(|e| panic!("Failed to register {} template: {}", template_name, e));
+        self.set_strict_mode(true);
+        self.register_template_string(template_name, template)
+            .map_err(|e| format!("Failed to register {} template: {}", template_name, e))?;
         data.insert(
             var_name.to_string(),
-            serde_json::to_value(specs)
-                .unwrap_or_else(|e| panic!("Failed to serialize specs: {}", e)),
+            serde_json::to_value(specs).map_err(|e| format!("Failed to serialize specs: {}", e))?,
         );
 
-        writeln!(
-            f,
-            "{}",
-            codegen
-                .render(template_name, &data)
-                .unwrap_or_else(|err| panic!(
-                    "Failed to render {} template: {}",
-                    template_name, err
-                ))
-        )
-        .unwrap_or_else(|e| panic!("Failed to generate {}.rs: {}", target, e));
+        let code = self
+            .render(template_name, &data)
+            .map_err(|err| format!("Failed to render {} template: {}", template_name, err))?;
+        #[cfg(feature = "pretty")]
+        let code = pretty_print(&code)?;
+
+        let dest_path = Path::new(out_dir).join(format!("{}.rs", target));
+        let mut f = File::create(&dest_path)
+            .map_err(|err| format!("Failed to create {:?}: {}", dest_path, err))?;
+        writeln!(f, "{}", code.trim_end())
+            .map_err(|e| format!("Failed to generate {}.rs: {}", target, e))
     }
 }
 
@@ -415,6 +476,126 @@ impl HelperDef for AMQPValueRefHelper {
     }
 }
 
+/// Helper for the rust expression building an amqp_value
+pub struct AMQPValueExprHelper;
+impl HelperDef for AMQPValueExprHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'_>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let arg = h
+            .param(0)
+            .ok_or_else(|| RenderErrorReason::ParamNotFoundForIndex("amqp_value_expr", 0))?;
+        let param = serde_json::from_value(arg.value().clone()).map_err(|_| {
+            RenderErrorReason::ParamTypeMismatchForName(
+                "amqp_value_expr",
+                "AMQPValue".to_string(),
+                "value".to_string(),
+            )
+        })?;
+        let expr = value_expr(&param).ok_or_else(|| {
+            RenderErrorReason::Other(format!("Cannot build an expression for {:?}", param))
+        })?;
+        Ok(ScopedJson::Derived(JsonValue::from(expr)))
+    }
+}
+
+/// Helper for the rust expression initializing a field to an amqp_value
+pub struct AMQPFieldExprHelper;
+impl HelperDef for AMQPFieldExprHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'_>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let arg = h
+            .param(0)
+            .ok_or_else(|| RenderErrorReason::ParamNotFoundForIndex("amqp_field_expr", 0))?;
+        let param = serde_json::from_value(arg.value().clone()).map_err(|_| {
+            RenderErrorReason::ParamTypeMismatchForName(
+                "amqp_field_expr",
+                "AMQPValue".to_string(),
+                "value".to_string(),
+            )
+        })?;
+        let expr = field_expr(&param).ok_or_else(|| {
+            RenderErrorReason::Other(format!("Cannot build an expression for {:?}", param))
+        })?;
+        Ok(ScopedJson::Derived(JsonValue::from(expr)))
+    }
+}
+
+/* Integers are left untyped, the specification not always giving them the type of their field */
+fn field_expr(val: &AMQPValue) -> Option<String> {
+    Some(match val {
+        AMQPValue::Boolean(v) => v.to_string(),
+        AMQPValue::ShortShortInt(v) => v.to_string(),
+        AMQPValue::ShortShortUInt(v) => v.to_string(),
+        AMQPValue::ShortInt(v) => v.to_string(),
+        AMQPValue::ShortUInt(v) => v.to_string(),
+        AMQPValue::LongInt(v) => v.to_string(),
+        AMQPValue::LongUInt(v) => v.to_string(),
+        AMQPValue::LongLongInt(v) => v.to_string(),
+        AMQPValue::Float(v) => format!("{:?}", v),
+        AMQPValue::Double(v) => format!("{:?}", v),
+        AMQPValue::ShortString(v) => format!("{:?}.into()", v.as_str()),
+        AMQPValue::LongString(v) => format!(
+            "{:?}.into()",
+            String::from_utf8(v.as_bytes().to_vec()).ok()?
+        ),
+        AMQPValue::Timestamp(v) => v.to_string(),
+        AMQPValue::FieldArray(v) if v.as_slice().is_empty() => "Default::default()".to_string(),
+        AMQPValue::FieldTable(v) if v.inner().is_empty() => "Default::default()".to_string(),
+        AMQPValue::ByteArray(v) if v.as_slice().is_empty() => "Default::default()".to_string(),
+        AMQPValue::DecimalValue(v) => {
+            format!("DecimalValue {{ scale: {}, value: {} }}", v.scale, v.value)
+        }
+        _ => return None,
+    })
+}
+
+/* Only empty collections are supported, which is all the specification uses */
+fn value_expr(val: &AMQPValue) -> Option<String> {
+    Some(match val {
+        AMQPValue::Boolean(v) => format!("AMQPValue::Boolean({})", v),
+        AMQPValue::ShortShortInt(v) => format!("AMQPValue::ShortShortInt({})", v),
+        AMQPValue::ShortShortUInt(v) => format!("AMQPValue::ShortShortUInt({})", v),
+        AMQPValue::ShortInt(v) => format!("AMQPValue::ShortInt({})", v),
+        AMQPValue::ShortUInt(v) => format!("AMQPValue::ShortUInt({})", v),
+        AMQPValue::LongInt(v) => format!("AMQPValue::LongInt({})", v),
+        AMQPValue::LongUInt(v) => format!("AMQPValue::LongUInt({})", v),
+        AMQPValue::LongLongInt(v) => format!("AMQPValue::LongLongInt({})", v),
+        AMQPValue::Float(v) => format!("AMQPValue::Float({:?})", v),
+        AMQPValue::Double(v) => format!("AMQPValue::Double({:?})", v),
+        AMQPValue::DecimalValue(v) => format!(
+            "AMQPValue::DecimalValue(DecimalValue {{ scale: {}, value: {} }})",
+            v.scale, v.value
+        ),
+        AMQPValue::ShortString(v) => format!("AMQPValue::ShortString({:?}.into())", v.as_str()),
+        AMQPValue::LongString(v) => format!(
+            "AMQPValue::LongString({:?}.into())",
+            String::from_utf8(v.as_bytes().to_vec()).ok()?
+        ),
+        AMQPValue::Timestamp(v) => format!("AMQPValue::Timestamp({})", v),
+        AMQPValue::FieldArray(v) if v.as_slice().is_empty() => {
+            "AMQPValue::FieldArray(FieldArray::default())".to_string()
+        }
+        AMQPValue::FieldTable(v) if v.inner().is_empty() => {
+            "AMQPValue::FieldTable(FieldTable::default())".to_string()
+        }
+        AMQPValue::ByteArray(v) if v.as_slice().is_empty() => {
+            "AMQPValue::ByteArray(ByteArray::default())".to_string()
+        }
+        AMQPValue::Void => "AMQPValue::Void".to_string(),
+        _ => return None,
+    })
+}
+
 fn json_value(val: AMQPValue) -> serde_json::Result<serde_json::Value> {
     match val {
         AMQPValue::Boolean(v) => serde_json::to_value(v),
@@ -524,25 +705,31 @@ synchronous: {{method.synchronous}}
                     name: "method1".to_string(),
                     synchronous: true,
                     content: false,
-                    metadata: Value::default(),
+                    metadata: Metadata::default(),
                     is_reply: false,
                     ignore_args: false,
+                    has_field_tables: false,
                     c2s: true,
                     s2c: true,
+                    request: None,
                 }],
                 name: "class1".to_string(),
                 properties: vec![AMQPProperty {
                     amqp_type: AMQPType::LongString,
                     name: "property1".to_string(),
                 }],
-                metadata: Value::default(),
+                metadata: Metadata::default(),
+                feature: None,
             }],
+            string_enums: Vec::default(),
+            server: None,
+            derives: AMQPDerives::default(),
         }
     }
 
     #[test]
     fn main_template() {
-        let mut data = HashMap::new();
+        let mut data = BTreeMap::new();
         let mut codegen = CodeGenerator::default().register_amqp_helpers();
         data.insert("protocol".to_string(), specs());
         assert!(codegen.register_template_string("main", TEMPLATE).is_ok());
@@ -565,4 +752,45 @@ flag2: false
 "#
         );
     }
+
+    /* pretty would fail to parse the output which is not rust code */
+    #[cfg(not(feature = "pretty"))]
+    #[test]
+    fn test_reproducible_output() {
+        let template = "{{#each protocol.domains ~}}\n{{@key}}: {{this}}\n{{/each ~}}\n{{#each protocol.classes as |class| ~}}\n{{class.name}}:{{#each class.metadata.extra}} {{@key}}{{/each}}\n{{/each ~}}";
+        let out_dir = std::env::temp_dir().join(format!("amq-codegen-{}", std::process::id()));
+        fs::create_dir_all(&out_dir).unwrap();
+        let out_dir = out_dir.to_str().unwrap();
+        let metadata = || {
+            Some(serde_json::json!({
+                "basic": { "metadata": { "extra": { "zzz": 1, "aaa": 2, "mmm": 3 } } },
+                "domains": { "zzz-domain": "long", "aaa-domain": "octet" },
+            }))
+        };
+        let generate = |target| {
+            CodeGenerator::default()
+                .register_amqp_helpers()
+                .try_codegen_with_data(out_dir, target, "main", template, "protocol", metadata())
+                .unwrap();
+            fs::read_to_string(Path::new(out_dir).join(format!("{}.rs", target))).unwrap()
+        };
+        let first = generate("first");
+        assert_eq!(first, generate("second"));
+        assert!(first.find("aaa-domain") < first.find("zzz-domain"));
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    #[test]
+    fn partial_override() {
+        let template = "{{#each protocol.classes as |class| ~}}\n{{#> class_header}}default {{class.name}}{{/class_header}}\n{{/each ~}}";
+        let mut data = BTreeMap::new();
+        let mut codegen = CodeGenerator::default().register_amqp_helpers();
+        data.insert("protocol".to_string(), specs());
+        assert!(codegen.register_template_string("main", template).is_ok());
+        assert_eq!(codegen.render("main", &data).unwrap(), "default class1\n");
+        assert!(codegen
+            .register_partial("class_header", "custom {{class.id}}")
+            .is_ok());
+        assert_eq!(codegen.render("main", &data).unwrap(), "custom 42\n");
+    }
 }
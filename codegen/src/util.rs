@@ -54,6 +54,16 @@ pub fn snake_case(name: &str, raw: bool) -> String {
     }
 }
 
+/// Pretty-print generated rust code so that the output doesn't depend on the template layout
+///
+/// Only doc comments are kept.
+#[cfg(feature = "pretty")]
+pub fn pretty_print(code: &str) -> Result<String, String> {
+    syn::parse_file(code)
+        .map(|file| prettyplease::unparse(&file))
+        .map_err(|e| format!("Failed to parse generated code: {}", e))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -81,4 +91,13 @@ mod test {
         assert_eq!(snake_case("UInt", true), "uint");
         assert_eq!(snake_case("LongUInt", true), "long_uint");
     }
+
+    #[cfg(feature = "pretty")]
+    #[test]
+    fn test_pretty_print() {
+        let code = pretty_print("/// doc\npub   fn foo( ) -> u8 {   42 }\n\n\n").unwrap();
+        assert_eq!(code, "/// doc\npub fn foo() -> u8 {\n    42\n}\n");
+        assert_eq!(pretty_print(&code), Ok(code));
+        assert!(pretty_print("fn {").is_err());
+    }
 }
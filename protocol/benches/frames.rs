@@ -0,0 +1,69 @@
+use amq_protocol::{
+    bench_data::{self, Corpus},
+    frame::{gen_frame, parse_frame, parse_frame_from_buf},
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for corpus in bench_data::all().expect("invalid corpora") {
+        group.throughput(Throughput::Bytes(corpus.bytes.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("slice", corpus.name),
+            &corpus.bytes[..],
+            |b, bytes| {
+                b.iter(|| {
+                    let mut input = bytes;
+                    while !input.is_empty() {
+                        let (rest, frame) = parse_frame(input).expect("invalid corpus");
+                        black_box(frame);
+                        input = rest;
+                    }
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("buf", corpus.name),
+            &corpus.bytes[..],
+            |b, bytes| {
+                b.iter(|| {
+                    let mut buf = bytes;
+                    while let Some(frame) = parse_frame_from_buf(&mut buf).expect("invalid corpus")
+                    {
+                        black_box(frame);
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+    for Corpus {
+        name,
+        frames,
+        bytes,
+    } in bench_data::all().expect("invalid corpora")
+    {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("vec", name), &frames, |b, frames| {
+            let mut buffer = Vec::with_capacity(bytes.len());
+            b.iter(|| {
+                buffer.clear();
+                for frame in frames {
+                    buffer =
+                        cookie_factory::gen_simple(gen_frame(frame), std::mem::take(&mut buffer))
+                            .expect("failed to serialize");
+                }
+                black_box(&buffer);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, serialize);
+criterion_main!(benches);
@@ -9,6 +9,7 @@ fn main() {
 #[cfg(feature = "codegen-internal")]
 fn codegen() {
     use amq_protocol_codegen::{CodeGenerator, HandlebarsAMQPExtension};
+    use serde_json::json;
 
     let out_dir = std::env::var("AMQ_PROTOCOL_CODEGEN_DIR")
         .or(std::env::var("OUT_DIR"))
@@ -20,5 +21,12 @@ fn codegen() {
         "/templates/protocol.rs"
     ));
 
-    CodeGenerator::simple_codegen(&out_dir, &out_file, "protocol", template, "protocol");
+    CodeGenerator::simple_codegen_with_data(
+        &out_dir,
+        &out_file,
+        "protocol",
+        template,
+        "protocol",
+        Some(json!({ "server": std::env::var_os("CARGO_FEATURE_SERVER").is_some() })),
+    );
 }
@@ -0,0 +1,97 @@
+use super::StreamOffset;
+use crate::types::{AMQPValue, FieldTable, LongInt};
+
+/// Typed builder for the arguments of basic.consume
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConsumeArguments {
+    priority: Option<i64>,
+    stream_offset: Option<StreamOffset>,
+}
+
+impl ConsumeArguments {
+    /// The key of the consumer priority argument
+    pub const PRIORITY_KEY: &'static str = "x-priority";
+
+    /// Deliver messages to this consumer before the ones with a lower priority (x-priority).
+    /// On a single active consumer queue, the consumer with the highest priority becomes the
+    /// active one. Both positive and negative priorities are accepted, the default being 0.
+    pub fn with_priority(mut self, priority: i64) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Where to start consuming a stream from (x-stream-offset)
+    pub fn with_stream_offset(mut self, offset: StreamOffset) -> Self {
+        self.stream_offset = Some(offset);
+        self
+    }
+
+    /// Build the FieldTable to pass to basic.consume, checking the ranges the server accepts
+    pub fn build(&self) -> Result<FieldTable, String> {
+        let mut arguments = FieldTable::default();
+        if let Some(priority) = self.priority {
+            let priority = LongInt::try_from(priority).map_err(|_| {
+                format!(
+                    "{} must be between {} and {}, not {}",
+                    Self::PRIORITY_KEY,
+                    LongInt::MIN,
+                    LongInt::MAX,
+                    priority
+                )
+            })?;
+            arguments.insert(Self::PRIORITY_KEY.into(), AMQPValue::LongInt(priority));
+        }
+        if let Some(offset) = self.stream_offset {
+            arguments.insert(StreamOffset::KEY.into(), offset.to_value()?);
+        }
+        Ok(arguments)
+    }
+}
+
+impl TryFrom<ConsumeArguments> for FieldTable {
+    type Error = String;
+
+    fn try_from(arguments: ConsumeArguments) -> Result<Self, Self::Error> {
+        arguments.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_consume_arguments() {
+        let arguments = ConsumeArguments::default()
+            .with_priority(-5)
+            .with_stream_offset(StreamOffset::First)
+            .build()
+            .unwrap();
+        assert_eq!(
+            arguments.inner().get("x-priority"),
+            Some(&AMQPValue::LongInt(-5))
+        );
+        assert_eq!(
+            arguments.inner().get("x-stream-offset"),
+            Some(&AMQPValue::LongString("first".into()))
+        );
+        assert_eq!(
+            ConsumeArguments::default().build(),
+            Ok(FieldTable::default())
+        );
+    }
+
+    #[test]
+    fn test_invalid_consume_arguments() {
+        assert_eq!(
+            ConsumeArguments::default().with_priority(1 << 32).build(),
+            Err(
+                "x-priority must be between -2147483648 and 2147483647, not 4294967296".to_string()
+            )
+        );
+        assert!(ConsumeArguments::default()
+            .with_stream_offset(StreamOffset::Offset(u64::MAX))
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::types::{AMQPValue, FieldTable};
+
+/// Typed builder for the arguments of exchange.declare
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExchangeDeclareArguments {
+    alternate_exchange: Option<String>,
+}
+
+impl ExchangeDeclareArguments {
+    /// Route the messages which cannot be routed by this exchange to the given one
+    pub fn with_alternate_exchange(mut self, exchange: impl Into<String>) -> Self {
+        self.alternate_exchange = Some(exchange.into());
+        self
+    }
+
+    /// Build the FieldTable to pass to exchange.declare
+    pub fn build(&self) -> Result<FieldTable, String> {
+        let mut arguments = FieldTable::default();
+        if let Some(exchange) = self.alternate_exchange.as_deref() {
+            if exchange.is_empty() {
+                return Err("alternate-exchange cannot be the default exchange".to_string());
+            }
+            arguments.insert(
+                "alternate-exchange".into(),
+                AMQPValue::LongString(exchange.into()),
+            );
+        }
+        Ok(arguments)
+    }
+}
+
+impl TryFrom<ExchangeDeclareArguments> for FieldTable {
+    type Error = String;
+
+    fn try_from(arguments: ExchangeDeclareArguments) -> Result<Self, Self::Error> {
+        arguments.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alternate_exchange() {
+        let arguments = ExchangeDeclareArguments::default()
+            .with_alternate_exchange("unroutable")
+            .build()
+            .unwrap();
+        assert_eq!(
+            arguments.inner().get("alternate-exchange"),
+            Some(&AMQPValue::LongString("unroutable".into()))
+        );
+        assert!(ExchangeDeclareArguments::default()
+            .with_alternate_exchange("")
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::types::{AMQPValue, FieldTable, ShortString};
+use std::fmt;
+
+/// How the headers of a message are matched against a headers exchange binding (x-match)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum XMatch {
+    /// All the headers must match
+    #[default]
+    All,
+    /// At least one header must match
+    Any,
+    /// All the headers must match, including the ones starting with x-
+    AllWithX,
+    /// At least one header must match, including the ones starting with x-
+    AnyWithX,
+}
+
+impl XMatch {
+    /// Get the value expected by the server
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            XMatch::All => "all",
+            XMatch::Any => "any",
+            XMatch::AllWithX => "all-with-x",
+            XMatch::AnyWithX => "any-with-x",
+        }
+    }
+
+    fn with_x(self) -> bool {
+        matches!(self, XMatch::AllWithX | XMatch::AnyWithX)
+    }
+}
+
+impl fmt::Display for XMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Typed builder for the arguments of a binding to a headers exchange
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeadersBindingArguments {
+    x_match: XMatch,
+    headers: Vec<(ShortString, AMQPValue)>,
+}
+
+impl HeadersBindingArguments {
+    /// Create a binding matching with the given mode
+    pub fn new(x_match: XMatch) -> Self {
+        Self {
+            x_match,
+            headers: Vec::default(),
+        }
+    }
+
+    /// Create a binding requiring all the headers to match
+    pub fn all() -> Self {
+        Self::new(XMatch::All)
+    }
+
+    /// Create a binding requiring at least one header to match
+    pub fn any() -> Self {
+        Self::new(XMatch::Any)
+    }
+
+    /// Add a header to match
+    pub fn with_header(mut self, key: impl Into<ShortString>, value: AMQPValue) -> Self {
+        self.headers.push((key.into(), value));
+        self
+    }
+
+    /// Build the FieldTable to pass to queue.bind or exchange.bind, checking that the headers
+    /// can actually be matched by the server
+    pub fn build(&self) -> Result<FieldTable, String> {
+        let mut arguments = FieldTable::default();
+        arguments.insert(
+            "x-match".into(),
+            AMQPValue::LongString(self.x_match.as_str().into()),
+        );
+        for (key, value) in &self.headers {
+            if key.as_str() == "x-match" {
+                return Err("x-match cannot be used as a header".to_string());
+            }
+            if key.as_str().starts_with("x-") && !self.x_match.with_x() {
+                return Err(format!(
+                    "header {} is ignored unless using x-match all-with-x or any-with-x",
+                    key
+                ));
+            }
+            if matches!(value, AMQPValue::FieldArray(_) | AMQPValue::FieldTable(_)) {
+                return Err(format!(
+                    "header {} cannot be matched against a {}",
+                    key,
+                    value.get_type()
+                ));
+            }
+            if arguments.contains_key(key.as_str()) {
+                return Err(format!("header {} is specified twice", key));
+            }
+            arguments.insert(key.clone(), value.clone());
+        }
+        Ok(arguments)
+    }
+}
+
+impl TryFrom<HeadersBindingArguments> for FieldTable {
+    type Error = String;
+
+    fn try_from(arguments: HeadersBindingArguments) -> Result<Self, Self::Error> {
+        arguments.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_headers_binding() {
+        let arguments = HeadersBindingArguments::any()
+            .with_header("format", AMQPValue::LongString("pdf".into()))
+            .with_header("type", AMQPValue::LongString("report".into()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            arguments.inner().get("x-match"),
+            Some(&AMQPValue::LongString("any".into()))
+        );
+        assert_eq!(arguments.inner().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_headers_binding() {
+        assert!(HeadersBindingArguments::all()
+            .with_header("x-match", AMQPValue::LongString("any".into()))
+            .build()
+            .is_err());
+        assert!(HeadersBindingArguments::all()
+            .with_header("x-origin", AMQPValue::Boolean(true))
+            .build()
+            .is_err());
+        assert!(HeadersBindingArguments::new(XMatch::AllWithX)
+            .with_header("x-origin", AMQPValue::Boolean(true))
+            .build()
+            .is_ok());
+        assert!(HeadersBindingArguments::all()
+            .with_header("nested", AMQPValue::FieldTable(FieldTable::default()))
+            .build()
+            .is_err());
+        assert!(HeadersBindingArguments::all()
+            .with_header("a", AMQPValue::Boolean(true))
+            .with_header("a", AMQPValue::Boolean(false))
+            .build()
+            .is_err());
+    }
+}
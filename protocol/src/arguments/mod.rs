@@ -0,0 +1,36 @@
+mod consume;
+mod exchange;
+mod headers;
+mod policy;
+mod queue;
+mod stream;
+
+pub use self::{consume::*, exchange::*, headers::*, policy::*, queue::*, stream::*};
+
+use crate::types::{AMQPValue, LongLongInt, LongLongUInt};
+use std::time::Duration;
+
+fn millis(name: &str, duration: Duration) -> Result<AMQPValue, String> {
+    LongLongInt::try_from(duration.as_millis())
+        .map(AMQPValue::LongLongInt)
+        .map_err(|_| format!("{} is too large: {:?}", name, duration))
+}
+
+pub(crate) fn as_count(value: &AMQPValue) -> Option<LongLongUInt> {
+    match *value {
+        AMQPValue::ShortShortInt(v) => v.try_into().ok(),
+        AMQPValue::ShortShortUInt(v) => Some(v.into()),
+        AMQPValue::ShortInt(v) => v.try_into().ok(),
+        AMQPValue::ShortUInt(v) => Some(v.into()),
+        AMQPValue::LongInt(v) => v.try_into().ok(),
+        AMQPValue::LongUInt(v) => Some(v.into()),
+        AMQPValue::LongLongInt(v) => v.try_into().ok(),
+        _ => None,
+    }
+}
+
+fn count(name: &str, count: u64) -> Result<AMQPValue, String> {
+    LongLongInt::try_from(count)
+        .map(AMQPValue::LongLongInt)
+        .map_err(|_| format!("{} is too large: {}", name, count))
+}
@@ -0,0 +1,280 @@
+use super::{as_count, count, millis, Overflow};
+use crate::types::{AMQPValue, FieldArray, FieldTable};
+use std::time::Duration;
+
+/// The mirroring of classic queues across the nodes of a cluster (ha-mode and ha-params)
+///
+/// Classic queue mirroring was removed in RabbitMQ 4.0 in favour of quorum queues, but the
+/// policies of older clusters and their exported definitions still use it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HaMode {
+    /// Mirror the queues on all the nodes
+    All,
+    /// Mirror the queues on this number of nodes, the leader included
+    Exactly(u64),
+    /// Mirror the queues on the nodes with these names
+    Nodes(Vec<String>),
+}
+
+impl HaMode {
+    /// Get the value of ha-mode expected by the server
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HaMode::All => "all",
+            HaMode::Exactly(_) => "exactly",
+            HaMode::Nodes(_) => "nodes",
+        }
+    }
+}
+
+/// Typed model of the keys of a policy definition which affect the protocol behaviour
+///
+/// Policies use the keys of the queue arguments, without their x- prefix. The keys which are
+/// not modelled here are kept as is in extra.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PolicyDefinition {
+    /// The mirroring of classic queues (ha-mode and ha-params)
+    pub ha_mode: Option<HaMode>,
+    /// Discard or dead-letter the messages staying in the queues longer than this (message-ttl)
+    pub message_ttl: Option<Duration>,
+    /// Delete the queues after they have been unused for this long (expires)
+    pub expires: Option<Duration>,
+    /// The maximum number of ready messages in the queues (max-length)
+    pub max_length: Option<u64>,
+    /// The maximum total size of the ready messages bodies in the queues (max-length-bytes)
+    pub max_length_bytes: Option<u64>,
+    /// What to do once the maximum length is reached (overflow)
+    pub overflow: Option<Overflow>,
+    /// The exchange to dead-letter messages to (dead-letter-exchange)
+    pub dead_letter_exchange: Option<String>,
+    /// The routing key to dead-letter messages with (dead-letter-routing-key)
+    pub dead_letter_routing_key: Option<String>,
+    /// The other keys of the definition
+    pub extra: FieldTable,
+}
+
+impl PolicyDefinition {
+    /// Build the FieldTable of the definition
+    pub fn to_field_table(&self) -> Result<FieldTable, String> {
+        let mut definition = self.extra.clone();
+        if let Some(ha_mode) = &self.ha_mode {
+            definition.insert(
+                "ha-mode".into(),
+                AMQPValue::LongString(ha_mode.as_str().into()),
+            );
+            match ha_mode {
+                HaMode::All => {}
+                HaMode::Exactly(replicas) => {
+                    definition.insert("ha-params".into(), count("ha-params", *replicas)?);
+                }
+                HaMode::Nodes(nodes) => {
+                    let nodes = nodes
+                        .iter()
+                        .map(|node| AMQPValue::LongString(node.as_str().into()))
+                        .collect::<Vec<_>>();
+                    definition.insert("ha-params".into(), AMQPValue::FieldArray(nodes.into()));
+                }
+            }
+        }
+        if let Some(ttl) = self.message_ttl {
+            definition.insert("message-ttl".into(), millis("message-ttl", ttl)?);
+        }
+        if let Some(expires) = self.expires {
+            definition.insert("expires".into(), millis("expires", expires)?);
+        }
+        if let Some(max_length) = self.max_length {
+            definition.insert("max-length".into(), count("max-length", max_length)?);
+        }
+        if let Some(max_length_bytes) = self.max_length_bytes {
+            definition.insert(
+                "max-length-bytes".into(),
+                count("max-length-bytes", max_length_bytes)?,
+            );
+        }
+        if let Some(overflow) = self.overflow {
+            definition.insert(
+                "overflow".into(),
+                AMQPValue::LongString(overflow.as_str().into()),
+            );
+        }
+        for (key, value) in [
+            ("dead-letter-exchange", &self.dead_letter_exchange),
+            ("dead-letter-routing-key", &self.dead_letter_routing_key),
+        ] {
+            if let Some(value) = value.as_deref() {
+                definition.insert(key.into(), AMQPValue::LongString(value.into()));
+            }
+        }
+        Ok(definition)
+    }
+
+    /// Read the definition of a policy from its JSON representation
+    #[cfg(feature = "definitions")]
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, String> {
+        match AMQPValue::from_json(json) {
+            AMQPValue::FieldTable(definition) => Self::try_from(&definition),
+            _ => Err(format!(
+                "A policy definition must be an object, not {}",
+                json
+            )),
+        }
+    }
+
+    /// Get the JSON representation of the definition
+    #[cfg(feature = "definitions")]
+    pub fn to_json(&self) -> Result<serde_json::Value, String> {
+        self.to_field_table()
+            .map(|definition| AMQPValue::FieldTable(definition).to_json())
+    }
+}
+
+impl TryFrom<&FieldTable> for PolicyDefinition {
+    type Error = String;
+
+    fn try_from(definition: &FieldTable) -> Result<Self, Self::Error> {
+        let mut policy = Self::default();
+        let mut ha_params = None;
+        for (key, value) in definition {
+            let invalid = || format!("Invalid {}: {}", key, value);
+            let duration = || {
+                as_count(value)
+                    .map(Duration::from_millis)
+                    .ok_or_else(invalid)
+            };
+            let string = || as_string(value).ok_or_else(invalid);
+            match key.as_str() {
+                "ha-mode" => {
+                    policy.ha_mode = Some(match string()?.as_str() {
+                        "all" => HaMode::All,
+                        "exactly" => HaMode::Exactly(0),
+                        "nodes" => HaMode::Nodes(Vec::new()),
+                        _ => return Err(invalid()),
+                    })
+                }
+                "ha-params" => ha_params = Some(value),
+                "message-ttl" => policy.message_ttl = Some(duration()?),
+                "expires" => policy.expires = Some(duration()?),
+                "max-length" => policy.max_length = Some(as_count(value).ok_or_else(invalid)?),
+                "max-length-bytes" => {
+                    policy.max_length_bytes = Some(as_count(value).ok_or_else(invalid)?)
+                }
+                "overflow" => policy.overflow = Some(string()?.parse()?),
+                "dead-letter-exchange" => policy.dead_letter_exchange = Some(string()?),
+                "dead-letter-routing-key" => policy.dead_letter_routing_key = Some(string()?),
+                _ => {
+                    policy.extra.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        let invalid_params = |value: &AMQPValue| format!("Invalid ha-params: {}", value);
+        match (&mut policy.ha_mode, ha_params) {
+            (Some(HaMode::All), None) => {}
+            (Some(HaMode::Exactly(replicas)), Some(value)) => {
+                *replicas = as_count(value).ok_or_else(|| invalid_params(value))?;
+            }
+            (Some(HaMode::Nodes(nodes)), Some(value)) => {
+                *nodes = value
+                    .as_array()
+                    .map(FieldArray::as_slice)
+                    .ok_or_else(|| invalid_params(value))?
+                    .iter()
+                    .map(as_string)
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| invalid_params(value))?;
+            }
+            (Some(ha_mode), None) => {
+                return Err(format!("ha-mode {} requires ha-params", ha_mode.as_str()))
+            }
+            (_, Some(_)) => return Err("ha-params requires ha-mode exactly or nodes".to_string()),
+            (None, None) => {}
+        }
+        Ok(policy)
+    }
+}
+
+impl TryFrom<PolicyDefinition> for FieldTable {
+    type Error = String;
+
+    fn try_from(definition: PolicyDefinition) -> Result<Self, Self::Error> {
+        definition.to_field_table()
+    }
+}
+
+fn as_string(value: &AMQPValue) -> Option<String> {
+    match value {
+        AMQPValue::ShortString(s) => Some(s.to_string()),
+        AMQPValue::LongString(s) => s.as_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_policy_definition() {
+        let policy = PolicyDefinition {
+            ha_mode: Some(HaMode::Nodes(vec!["rabbit@a".into(), "rabbit@b".into()])),
+            message_ttl: Some(Duration::from_secs(60)),
+            max_length: Some(1000),
+            overflow: Some(Overflow::RejectPublish),
+            ..PolicyDefinition::default()
+        };
+        let mut definition = policy.to_field_table().unwrap();
+        assert_eq!(
+            definition.inner().get("overflow"),
+            Some(&AMQPValue::LongString("reject-publish".into()))
+        );
+        assert_eq!(
+            definition.inner().get("message-ttl"),
+            Some(&AMQPValue::LongLongInt(60000))
+        );
+        assert_eq!(PolicyDefinition::try_from(&definition), Ok(policy.clone()));
+
+        definition.insert(
+            "ha-sync-mode".into(),
+            AMQPValue::LongString("automatic".into()),
+        );
+        let parsed = PolicyDefinition::try_from(&definition).unwrap();
+        assert_eq!(parsed.extra.inner().len(), 1);
+        assert_eq!(parsed.to_field_table(), Ok(definition));
+    }
+
+    #[test]
+    fn test_invalid_policy_definition() {
+        let definition = |entries: &[(&str, AMQPValue)]| {
+            let mut definition = FieldTable::default();
+            for (key, value) in entries {
+                definition.insert((*key).into(), value.clone());
+            }
+            PolicyDefinition::try_from(&definition)
+        };
+        assert_eq!(
+            definition(&[("ha-mode", AMQPValue::LongString("exactly".into()))]),
+            Err("ha-mode exactly requires ha-params".to_string())
+        );
+        assert_eq!(
+            definition(&[
+                ("ha-mode", AMQPValue::LongString("exactly".into())),
+                ("ha-params", AMQPValue::LongLongInt(2)),
+            ])
+            .map(|policy| policy.ha_mode),
+            Ok(Some(HaMode::Exactly(2)))
+        );
+        assert!(definition(&[("ha-params", AMQPValue::LongLongInt(2))]).is_err());
+        assert!(definition(&[("message-ttl", AMQPValue::LongLongInt(-1))]).is_err());
+        assert!(definition(&[("overflow", AMQPValue::LongString("drop-tail".into()))]).is_err());
+    }
+
+    #[cfg(feature = "definitions")]
+    #[test]
+    fn test_policy_definition_json() {
+        let json = serde_json::json!({"ha-mode": "all", "max-length-bytes": 1048576});
+        let policy = PolicyDefinition::from_json(&json).unwrap();
+        assert_eq!(policy.ha_mode, Some(HaMode::All));
+        assert_eq!(policy.max_length_bytes, Some(1048576));
+        assert_eq!(policy.to_json(), Ok(json));
+        assert!(PolicyDefinition::from_json(&serde_json::json!([])).is_err());
+    }
+}
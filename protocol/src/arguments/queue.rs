@@ -0,0 +1,395 @@
+use super::{count, millis};
+use crate::types::{AMQPValue, FieldTable};
+use std::{fmt, str::FromStr, time::Duration};
+
+/// The type of a queue (x-queue-type)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueueType {
+    /// A classic queue
+    #[default]
+    Classic,
+    /// A replicated quorum queue
+    Quorum,
+    /// An append-only stream
+    Stream,
+}
+
+impl QueueType {
+    /// Get the value expected by the server
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueType::Classic => "classic",
+            QueueType::Quorum => "quorum",
+            QueueType::Stream => "stream",
+        }
+    }
+}
+
+impl fmt::Display for QueueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What to do when a queue reaches its maximum length (x-overflow)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Drop or dead-letter the messages from the head of the queue
+    #[default]
+    DropHead,
+    /// Reject the newly published messages
+    RejectPublish,
+    /// Reject and dead-letter the newly published messages
+    RejectPublishDlx,
+}
+
+impl Overflow {
+    /// Get the value expected by the server
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Overflow::DropHead => "drop-head",
+            Overflow::RejectPublish => "reject-publish",
+            Overflow::RejectPublishDlx => "reject-publish-dlx",
+        }
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Overflow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop-head" => Ok(Overflow::DropHead),
+            "reject-publish" => Ok(Overflow::RejectPublish),
+            "reject-publish-dlx" => Ok(Overflow::RejectPublishDlx),
+            s => Err(format!("Invalid overflow: {}", s)),
+        }
+    }
+}
+
+/// Typed builder for the arguments of queue.declare
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueueDeclareArguments {
+    message_ttl: Option<Duration>,
+    expires: Option<Duration>,
+    max_length: Option<u64>,
+    max_length_bytes: Option<u64>,
+    overflow: Option<Overflow>,
+    dead_letter_exchange: Option<String>,
+    dead_letter_routing_key: Option<String>,
+    queue_type: Option<QueueType>,
+    single_active_consumer: Option<bool>,
+}
+
+impl QueueDeclareArguments {
+    /// Preset for a classic queue
+    pub fn classic() -> Self {
+        Self::default().with_queue_type(QueueType::Classic)
+    }
+
+    /// Preset for a quorum queue: it must be durable, neither exclusive nor auto-delete, and
+    /// cannot use the reject-publish-dlx overflow
+    pub fn quorum() -> Self {
+        Self::default().with_queue_type(QueueType::Quorum)
+    }
+
+    /// Preset for a stream: it must be durable, neither exclusive nor auto-delete, and only
+    /// accepts x-max-length-bytes as a retention limit
+    pub fn stream() -> Self {
+        Self::default().with_queue_type(QueueType::Stream)
+    }
+
+    /// Discard or dead-letter the messages staying in the queue longer than ttl (x-message-ttl)
+    pub fn with_message_ttl(mut self, ttl: Duration) -> Self {
+        self.message_ttl = Some(ttl);
+        self
+    }
+
+    /// Delete the queue after it has been unused for this long (x-expires)
+    pub fn with_expires(mut self, expires: Duration) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// The maximum number of ready messages in the queue (x-max-length)
+    pub fn with_max_length(mut self, max_length: u64) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// The maximum total size of the ready messages bodies in the queue (x-max-length-bytes)
+    pub fn with_max_length_bytes(mut self, max_length_bytes: u64) -> Self {
+        self.max_length_bytes = Some(max_length_bytes);
+        self
+    }
+
+    /// What to do once the maximum length is reached (x-overflow)
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = Some(overflow);
+        self
+    }
+
+    /// The exchange to dead-letter messages to (x-dead-letter-exchange)
+    pub fn with_dead_letter_exchange(mut self, exchange: impl Into<String>) -> Self {
+        self.dead_letter_exchange = Some(exchange.into());
+        self
+    }
+
+    /// The routing key to dead-letter messages with (x-dead-letter-routing-key)
+    pub fn with_dead_letter_routing_key(mut self, routing_key: impl Into<String>) -> Self {
+        self.dead_letter_routing_key = Some(routing_key.into());
+        self
+    }
+
+    /// The type of the queue (x-queue-type)
+    pub fn with_queue_type(mut self, queue_type: QueueType) -> Self {
+        self.queue_type = Some(queue_type);
+        self
+    }
+
+    /// Only deliver messages to one consumer at a time (x-single-active-consumer)
+    pub fn with_single_active_consumer(mut self, single_active_consumer: bool) -> Self {
+        self.single_active_consumer = Some(single_active_consumer);
+        self
+    }
+
+    /// Check that the server accepts a queue.declare with these arguments and the given flags
+    #[cfg(feature = "class-queue")]
+    pub fn check_declare(&self, declare: &crate::protocol::queue::Declare) -> Result<(), String> {
+        if let Some(queue_type @ (QueueType::Quorum | QueueType::Stream)) = self.queue_type {
+            if !declare.durable {
+                return Err(format!("{} queues must be durable", queue_type));
+            }
+            if declare.exclusive {
+                return Err(format!("{} queues cannot be exclusive", queue_type));
+            }
+            if declare.auto_delete {
+                return Err(format!("{} queues cannot be auto-delete", queue_type));
+            }
+        }
+        self.build().map(|_| ())
+    }
+
+    fn check_queue_type(&self) -> Result<(), String> {
+        match self.queue_type {
+            Some(QueueType::Quorum) if self.overflow == Some(Overflow::RejectPublishDlx) => {
+                Err(format!(
+                    "quorum queues don't support x-overflow {}",
+                    Overflow::RejectPublishDlx
+                ))
+            }
+            Some(QueueType::Stream) => {
+                let unsupported = [
+                    ("x-message-ttl", self.message_ttl.is_some()),
+                    ("x-expires", self.expires.is_some()),
+                    ("x-max-length", self.max_length.is_some()),
+                    ("x-overflow", self.overflow.is_some()),
+                    (
+                        "x-dead-letter-exchange",
+                        self.dead_letter_exchange.is_some(),
+                    ),
+                    (
+                        "x-single-active-consumer",
+                        self.single_active_consumer.is_some(),
+                    ),
+                ];
+                match unsupported.iter().find(|(_, set)| *set) {
+                    Some((key, _)) => Err(format!("streams don't support {}", key)),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Build the FieldTable to pass to queue.declare, checking the arguments consistency
+    pub fn build(&self) -> Result<FieldTable, String> {
+        self.check_queue_type()?;
+        let mut arguments = FieldTable::default();
+        if let Some(ttl) = self.message_ttl {
+            arguments.insert("x-message-ttl".into(), millis("x-message-ttl", ttl)?);
+        }
+        if let Some(expires) = self.expires {
+            if expires.as_millis() == 0 {
+                return Err("x-expires must be at least 1 millisecond".to_string());
+            }
+            arguments.insert("x-expires".into(), millis("x-expires", expires)?);
+        }
+        if let Some(max_length) = self.max_length {
+            arguments.insert("x-max-length".into(), count("x-max-length", max_length)?);
+        }
+        if let Some(max_length_bytes) = self.max_length_bytes {
+            arguments.insert(
+                "x-max-length-bytes".into(),
+                count("x-max-length-bytes", max_length_bytes)?,
+            );
+        }
+        if let Some(overflow) = self.overflow {
+            arguments.insert(
+                "x-overflow".into(),
+                AMQPValue::LongString(overflow.as_str().into()),
+            );
+        }
+        if let Some(exchange) = self.dead_letter_exchange.as_deref() {
+            arguments.insert(
+                "x-dead-letter-exchange".into(),
+                AMQPValue::LongString(exchange.into()),
+            );
+        }
+        if let Some(routing_key) = self.dead_letter_routing_key.as_deref() {
+            if self.dead_letter_exchange.is_none() {
+                return Err("x-dead-letter-routing-key requires x-dead-letter-exchange".to_string());
+            }
+            arguments.insert(
+                "x-dead-letter-routing-key".into(),
+                AMQPValue::LongString(routing_key.into()),
+            );
+        }
+        if let Some(queue_type) = self.queue_type {
+            arguments.insert(
+                "x-queue-type".into(),
+                AMQPValue::LongString(queue_type.as_str().into()),
+            );
+        }
+        if let Some(single_active_consumer) = self.single_active_consumer {
+            arguments.insert(
+                "x-single-active-consumer".into(),
+                AMQPValue::Boolean(single_active_consumer),
+            );
+        }
+        Ok(arguments)
+    }
+}
+
+impl TryFrom<QueueDeclareArguments> for FieldTable {
+    type Error = String;
+
+    fn try_from(arguments: QueueDeclareArguments) -> Result<Self, Self::Error> {
+        arguments.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_queue_declare_arguments() {
+        let arguments = QueueDeclareArguments::default()
+            .with_message_ttl(Duration::from_secs(60))
+            .with_max_length(1000)
+            .with_overflow(Overflow::RejectPublish)
+            .with_dead_letter_exchange("dlx")
+            .with_dead_letter_routing_key("failed")
+            .with_queue_type(QueueType::Quorum)
+            .with_single_active_consumer(true)
+            .build()
+            .unwrap();
+        let get = |key| arguments.inner().get(key).cloned();
+        assert_eq!(get("x-message-ttl"), Some(AMQPValue::LongLongInt(60000)));
+        assert_eq!(get("x-max-length"), Some(AMQPValue::LongLongInt(1000)));
+        assert_eq!(
+            get("x-overflow"),
+            Some(AMQPValue::LongString("reject-publish".into()))
+        );
+        assert_eq!(
+            get("x-queue-type"),
+            Some(AMQPValue::LongString("quorum".into()))
+        );
+        assert_eq!(
+            get("x-single-active-consumer"),
+            Some(AMQPValue::Boolean(true))
+        );
+        assert_eq!(arguments.inner().len(), 7);
+    }
+
+    #[test]
+    fn test_invalid_queue_declare_arguments() {
+        assert_eq!(
+            QueueDeclareArguments::default()
+                .with_dead_letter_routing_key("failed")
+                .build(),
+            Err("x-dead-letter-routing-key requires x-dead-letter-exchange".to_string())
+        );
+        assert!(QueueDeclareArguments::default()
+            .with_expires(Duration::ZERO)
+            .build()
+            .is_err());
+        assert!(QueueDeclareArguments::default()
+            .with_max_length(u64::MAX)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_queue_presets() {
+        assert_eq!(
+            QueueDeclareArguments::quorum()
+                .build()
+                .unwrap()
+                .inner()
+                .get("x-queue-type"),
+            Some(&AMQPValue::LongString("quorum".into()))
+        );
+        assert!(QueueDeclareArguments::quorum()
+            .with_overflow(Overflow::RejectPublish)
+            .with_dead_letter_exchange("dlx")
+            .build()
+            .is_ok());
+        assert!(QueueDeclareArguments::quorum()
+            .with_overflow(Overflow::RejectPublishDlx)
+            .build()
+            .is_err());
+        assert!(QueueDeclareArguments::classic()
+            .with_overflow(Overflow::RejectPublishDlx)
+            .build()
+            .is_ok());
+        assert!(QueueDeclareArguments::stream()
+            .with_max_length_bytes(1 << 30)
+            .build()
+            .is_ok());
+        assert_eq!(
+            QueueDeclareArguments::stream()
+                .with_message_ttl(Duration::from_secs(60))
+                .build(),
+            Err("streams don't support x-message-ttl".to_string())
+        );
+    }
+
+    #[cfg(feature = "class-queue")]
+    #[test]
+    fn test_check_declare() {
+        use crate::protocol::queue::Declare;
+
+        let durable = Declare {
+            durable: true,
+            ..Declare::default()
+        };
+        assert!(QueueDeclareArguments::quorum()
+            .check_declare(&durable)
+            .is_ok());
+        assert_eq!(
+            QueueDeclareArguments::quorum().check_declare(&Declare {
+                exclusive: true,
+                ..durable.clone()
+            }),
+            Err("quorum queues cannot be exclusive".to_string())
+        );
+        assert!(QueueDeclareArguments::stream()
+            .check_declare(&Declare::default())
+            .is_err());
+        assert!(QueueDeclareArguments::classic()
+            .check_declare(&Declare {
+                exclusive: true,
+                auto_delete: true,
+                ..Declare::default()
+            })
+            .is_ok());
+    }
+}
@@ -0,0 +1,218 @@
+use super::as_count;
+use crate::types::{AMQPValue, LongLongInt, Timestamp};
+use std::{fmt, str::FromStr};
+
+/// The unit of a stream offset interval
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntervalUnit {
+    /// Years (Y)
+    Years,
+    /// Months (M)
+    Months,
+    /// Days (D)
+    Days,
+    /// Hours (h)
+    Hours,
+    /// Minutes (m)
+    Minutes,
+    /// Seconds (s)
+    Seconds,
+}
+
+impl IntervalUnit {
+    fn as_char(self) -> char {
+        match self {
+            IntervalUnit::Years => 'Y',
+            IntervalUnit::Months => 'M',
+            IntervalUnit::Days => 'D',
+            IntervalUnit::Hours => 'h',
+            IntervalUnit::Minutes => 'm',
+            IntervalUnit::Seconds => 's',
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'Y' => Some(IntervalUnit::Years),
+            'M' => Some(IntervalUnit::Months),
+            'D' => Some(IntervalUnit::Days),
+            'h' => Some(IntervalUnit::Hours),
+            'm' => Some(IntervalUnit::Minutes),
+            's' => Some(IntervalUnit::Seconds),
+            _ => None,
+        }
+    }
+}
+
+/// Where to start consuming a stream from (x-stream-offset)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamOffset {
+    /// The first available message
+    First,
+    /// The last written chunk of messages
+    Last,
+    /// The next message to be written
+    Next,
+    /// The message at the given offset
+    Offset(u64),
+    /// The first message written after the given POSIX timestamp (in seconds)
+    Timestamp(Timestamp),
+    /// The messages written in the given interval before now, e.g. 7 days
+    Interval(u32, IntervalUnit),
+}
+
+impl StreamOffset {
+    /// The argument key for the stream offset in basic.consume
+    pub const KEY: &'static str = "x-stream-offset";
+
+    /// Encode the offset as expected by the server: a long long int for offsets, a timestamp for
+    /// timestamps and a long string otherwise
+    pub fn to_value(&self) -> Result<AMQPValue, String> {
+        Ok(match *self {
+            StreamOffset::Offset(offset) => AMQPValue::LongLongInt(
+                LongLongInt::try_from(offset)
+                    .map_err(|_| format!("Stream offset is too large: {}", offset))?,
+            ),
+            StreamOffset::Timestamp(timestamp) => AMQPValue::Timestamp(timestamp),
+            offset => AMQPValue::LongString(offset.to_string().into()),
+        })
+    }
+}
+
+impl fmt::Display for StreamOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamOffset::First => f.write_str("first"),
+            StreamOffset::Last => f.write_str("last"),
+            StreamOffset::Next => f.write_str("next"),
+            StreamOffset::Offset(offset) => write!(f, "{}", offset),
+            StreamOffset::Timestamp(timestamp) => write!(f, "timestamp({})", timestamp),
+            StreamOffset::Interval(value, unit) => write!(f, "{}{}", value, unit.as_char()),
+        }
+    }
+}
+
+impl FromStr for StreamOffset {
+    type Err = String;
+
+    /// Parse the string forms accepted by the server: first, last, next and intervals such as 7D,
+    /// along with the offsets and timestamp(seconds) forms produced by Display
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first" => Ok(StreamOffset::First),
+            "last" => Ok(StreamOffset::Last),
+            "next" => Ok(StreamOffset::Next),
+            s if s.starts_with("timestamp(") => s
+                .strip_prefix("timestamp(")
+                .and_then(|s| s.strip_suffix(')'))
+                .and_then(|timestamp| timestamp.parse().ok())
+                .map(StreamOffset::Timestamp)
+                .ok_or_else(|| format!("Invalid stream offset: {}", s)),
+            s if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => s
+                .parse()
+                .map(StreamOffset::Offset)
+                .map_err(|_| format!("Invalid stream offset: {}", s)),
+            s => s
+                .char_indices()
+                .last()
+                .and_then(|(idx, unit)| {
+                    Some(StreamOffset::Interval(
+                        s[..idx].parse().ok()?,
+                        IntervalUnit::from_char(unit)?,
+                    ))
+                })
+                .ok_or_else(|| format!("Invalid stream offset: {}", s)),
+        }
+    }
+}
+
+impl TryFrom<StreamOffset> for AMQPValue {
+    type Error = String;
+
+    fn try_from(offset: StreamOffset) -> Result<Self, Self::Error> {
+        offset.to_value()
+    }
+}
+
+impl TryFrom<&AMQPValue> for StreamOffset {
+    type Error = String;
+
+    fn try_from(value: &AMQPValue) -> Result<Self, Self::Error> {
+        match value {
+            AMQPValue::Timestamp(timestamp) => Ok(StreamOffset::Timestamp(*timestamp)),
+            AMQPValue::LongString(s) => s
+                .as_str()
+                .ok_or_else(|| format!("Invalid stream offset: {}", s))?
+                .parse(),
+            AMQPValue::ShortString(s) => s.as_str().parse(),
+            value => as_count(value)
+                .map(StreamOffset::Offset)
+                .ok_or_else(|| format!("Invalid stream offset: {}", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stream_offset_encoding() {
+        assert_eq!(
+            StreamOffset::First.to_value(),
+            Ok(AMQPValue::LongString("first".into()))
+        );
+        assert_eq!(
+            StreamOffset::Offset(42).to_value(),
+            Ok(AMQPValue::LongLongInt(42))
+        );
+        assert_eq!(
+            StreamOffset::Timestamp(1700000000).to_value(),
+            Ok(AMQPValue::Timestamp(1700000000))
+        );
+        assert_eq!(
+            StreamOffset::Interval(7, IntervalUnit::Days).to_value(),
+            Ok(AMQPValue::LongString("7D".into()))
+        );
+        assert!(StreamOffset::Offset(u64::MAX).to_value().is_err());
+    }
+
+    #[test]
+    fn test_stream_offset_parsing() {
+        for offset in [
+            StreamOffset::Next,
+            StreamOffset::Offset(12),
+            StreamOffset::Timestamp(1700000000),
+            StreamOffset::Interval(30, IntervalUnit::Minutes),
+        ] {
+            assert_eq!(
+                StreamOffset::try_from(&offset.to_value().unwrap()),
+                Ok(offset)
+            );
+        }
+        assert_eq!(
+            "2Y".parse(),
+            Ok(StreamOffset::Interval(2, IntervalUnit::Years))
+        );
+        assert!("D".parse::<StreamOffset>().is_err());
+        assert!("timestamp(7D)".parse::<StreamOffset>().is_err());
+        assert!("7w".parse::<StreamOffset>().is_err());
+        assert!(StreamOffset::try_from(&AMQPValue::LongInt(-1)).is_err());
+    }
+
+    #[test]
+    fn test_stream_offset_display_roundtrip() {
+        for offset in [
+            StreamOffset::First,
+            StreamOffset::Last,
+            StreamOffset::Next,
+            StreamOffset::Offset(0),
+            StreamOffset::Offset(u64::MAX),
+            StreamOffset::Timestamp(1700000000),
+            StreamOffset::Interval(7, IntervalUnit::Days),
+            StreamOffset::Interval(1, IntervalUnit::Months),
+        ] {
+            assert_eq!(offset.to_string().parse(), Ok(offset));
+        }
+    }
+}
@@ -1,8 +1,10 @@
 pub use crate::uri::SASLMechanism;
 use crate::{
-    types::{generation::gen_field_table, AMQPValue, FieldTable},
+    types::{generation::gen_field_table, AMQPValue, FieldTable, LongString},
     uri::AMQPUserInfo,
 };
+use cookie_factory::gen_simple;
+use std::fmt;
 
 /// Structure holding the username and password for authentication
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -43,8 +45,6 @@ impl Credentials {
     }
 
     fn amqplain_auth_string(&self) -> String {
-        let needed_len = 4 /* FieldTable length */ + 15 /* LOGIN + PASSWORD + 2 * 1 (length) */ + 5 /* type + length */ + self.username().as_bytes().len() + 5 /* type + length */ + self.password().as_bytes().len();
-        let mut buf = vec![0; needed_len];
         let mut table = FieldTable::default();
         table.insert(
             "LOGIN".into(),
@@ -54,13 +54,94 @@ impl Credentials {
             "PASSWORD".into(),
             AMQPValue::LongString(self.password().into()),
         );
-        gen_field_table(&table)((&mut buf[..]).into())
-            .expect("miscalculated AMQPLAIN string length");
-        // skip the FieldTable length
-        String::from_utf8_lossy(&buf.as_slice()[4..]).to_string()
+        /* Serializing into a Vec cannot run out of space */
+        let buf = gen_simple(gen_field_table(&table), Vec::new()).unwrap_or_default();
+        /* Skip the FieldTable length */
+        String::from_utf8_lossy(buf.get(4..).unwrap_or_default()).to_string()
     }
 }
 
+/// A SASL mechanism advertised by the server in connection.start
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mechanism {
+    /// A mechanism we know how to authenticate with
+    Supported(SASLMechanism),
+    /// A mechanism we don't support, kept with the spelling of the server
+    Unsupported(String),
+}
+
+impl Mechanism {
+    /// The SASL mechanism, if we support it
+    pub fn supported(&self) -> Option<SASLMechanism> {
+        match self {
+            Mechanism::Supported(mechanism) => Some(*mechanism),
+            Mechanism::Unsupported(_) => None,
+        }
+    }
+}
+
+impl From<&str> for Mechanism {
+    fn from(mechanism: &str) -> Self {
+        mechanism.parse().map_or_else(
+            |_| Mechanism::Unsupported(mechanism.into()),
+            Mechanism::Supported,
+        )
+    }
+}
+
+impl fmt::Display for Mechanism {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mechanism::Supported(mechanism) => mechanism.fmt(f),
+            Mechanism::Unsupported(mechanism) => f.write_str(mechanism),
+        }
+    }
+}
+
+/// Split the space-separated mechanisms list of connection.start
+pub fn parse_mechanisms(mechanisms: &LongString) -> Vec<Mechanism> {
+    mechanisms
+        .to_str_lossy()
+        .split_whitespace()
+        .map(Mechanism::from)
+        .collect()
+}
+
+/// Render a mechanisms list the way connection.start carries it
+pub fn render_mechanisms(mechanisms: &[Mechanism]) -> LongString {
+    mechanisms
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into()
+}
+
+/// Select the SASL mechanism to use for authentication
+///
+/// Pick the first of the preferred mechanisms (usually coming from the auth_mechanism
+/// URI query parameters) supported by the server, which lists the space-separated
+/// mechanisms it supports in connection.start. Plain is preferred when none is given.
+pub fn select_mechanism(
+    preferred: &[SASLMechanism],
+    server_mechanisms: &LongString,
+) -> Option<SASLMechanism> {
+    let supported = parse_mechanisms(server_mechanisms)
+        .iter()
+        .filter_map(Mechanism::supported)
+        .collect::<Vec<_>>();
+    let default = [SASLMechanism::default()];
+    let preferred = if preferred.is_empty() {
+        &default[..]
+    } else {
+        preferred
+    };
+    preferred
+        .iter()
+        .find(|mechanism| supported.contains(mechanism))
+        .copied()
+}
+
 impl Default for Credentials {
     fn default() -> Self {
         Self::new("guest".into(), "guest".into())
@@ -88,4 +169,42 @@ mod test {
                 .to_string()
         );
     }
+
+    #[test]
+    fn test_select_mechanism() {
+        assert_eq!(
+            select_mechanism(&[], &"AMQPLAIN PLAIN".into()),
+            Some(SASLMechanism::Plain)
+        );
+        assert_eq!(
+            select_mechanism(
+                &[SASLMechanism::External, SASLMechanism::AMQPlain],
+                &"PLAIN AMQPLAIN".into()
+            ),
+            Some(SASLMechanism::AMQPlain)
+        );
+        assert_eq!(
+            select_mechanism(&[SASLMechanism::External], &"PLAIN AMQPLAIN".into()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_mechanisms() {
+        let mechanisms = parse_mechanisms(&"PLAIN  amqplain X-OAUTH2\n".into());
+        assert_eq!(
+            mechanisms,
+            vec![
+                Mechanism::Supported(SASLMechanism::Plain),
+                Mechanism::Supported(SASLMechanism::AMQPlain),
+                Mechanism::Unsupported("X-OAUTH2".into()),
+            ]
+        );
+        assert_eq!(mechanisms[2].supported(), None);
+        assert_eq!(
+            render_mechanisms(&mechanisms),
+            "PLAIN AMQPLAIN X-OAUTH2".into()
+        );
+        assert_eq!(parse_mechanisms(&"".into()), Vec::new());
+    }
 }
@@ -0,0 +1,134 @@
+use crate::{
+    dump::serialize_frames,
+    frame::{split_content, AMQPFrame, Bytes},
+    protocol::{basic, AMQPClass},
+    types::{AMQPValue, FieldTable},
+};
+
+/// A sequence of frames and its serialization, used as input for the benchmarks
+#[derive(Clone, Debug, PartialEq)]
+pub struct Corpus {
+    /// A short name describing the traffic
+    pub name: &'static str,
+    /// The frames
+    pub frames: Vec<AMQPFrame>,
+    /// The serialized frames
+    pub bytes: Vec<u8>,
+}
+
+impl Corpus {
+    fn new(name: &'static str, frames: Vec<AMQPFrame>) -> Result<Self, String> {
+        let bytes = serialize_frames(&frames)?;
+        Ok(Self {
+            name,
+            frames,
+            bytes,
+        })
+    }
+}
+
+/// All the corpora
+pub fn all() -> Result<Vec<Corpus>, String> {
+    Ok(vec![
+        small_publish()?,
+        header_heavy_publish()?,
+        deliver_burst()?,
+    ])
+}
+
+/// A basic.publish of a small message with a few properties
+pub fn small_publish() -> Result<Corpus, String> {
+    let properties = basic::AMQPProperties::default()
+        .with_content_type("application/json".into())
+        .with_delivery_mode(2);
+    Corpus::new(
+        "small-publish",
+        publish(properties, Bytes::from_static(br#"{"id":42}"#))?,
+    )
+}
+
+/// A basic.publish whose properties carry many headers of various types
+pub fn header_heavy_publish() -> Result<Corpus, String> {
+    let mut headers = FieldTable::default();
+    for i in 0..32 {
+        headers.insert(
+            format!("x-header-{}", i).into(),
+            match i % 4 {
+                0 => AMQPValue::LongString(format!("value {}", i).into()),
+                1 => AMQPValue::LongLongInt(i),
+                2 => AMQPValue::Boolean(i % 3 == 0),
+                _ => AMQPValue::Timestamp(1_700_000_000 + i as u64),
+            },
+        );
+    }
+    let properties = basic::AMQPProperties::default()
+        .with_content_type("text/plain".into())
+        .with_message_id("3f2a0c1e-5a9b-4a3b-8a6e-0c1d2e3f4a5b".into())
+        .with_correlation_id("request-1".into())
+        .with_reply_to("amq.rabbitmq.reply-to".into())
+        .with_timestamp(1_700_000_000)
+        .with_headers(headers);
+    Corpus::new(
+        "header-heavy-publish",
+        publish(properties, Bytes::from_static(b"hello world"))?,
+    )
+}
+
+/// A hundred basic.deliver with their content, as received by a busy consumer
+pub fn deliver_burst() -> Result<Corpus, String> {
+    let mut frames = Vec::new();
+    for delivery_tag in 1..=100 {
+        frames.push(AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::Deliver(basic::Deliver {
+                consumer_tag: "amq.ctag-bench".into(),
+                delivery_tag,
+                redelivered: false,
+                exchange: "events".into(),
+                routing_key: "events.created".into(),
+            })),
+        ));
+        frames.extend(content(
+            basic::AMQPProperties::default().with_delivery_mode(2),
+            Bytes::from(vec![b'x'; 256]),
+        )?);
+    }
+    Corpus::new("deliver-burst", frames)
+}
+
+fn publish(properties: basic::AMQPProperties, body: Bytes) -> Result<Vec<AMQPFrame>, String> {
+    let mut frames = vec![AMQPFrame::Method(
+        1,
+        AMQPClass::Basic(basic::AMQPMethod::Publish(basic::Publish {
+            exchange: "events".into(),
+            routing_key: "events.created".into(),
+            mandatory: false,
+            immediate: false,
+        })),
+    )];
+    frames.extend(content(properties, body)?);
+    Ok(frames)
+}
+
+fn content(properties: basic::AMQPProperties, body: Bytes) -> Result<Vec<AMQPFrame>, String> {
+    split_content(1, properties, body, 0).map_err(|e| format!("Failed to split content: {}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dump::parse_dump;
+
+    #[test]
+    fn test_corpora_roundtrip() {
+        for corpus in all().unwrap() {
+            assert_eq!(
+                parse_dump(&corpus.bytes).as_ref(),
+                Ok(&corpus.frames),
+                "{}",
+                corpus.name
+            );
+        }
+        assert_eq!(deliver_burst().unwrap().frames.len(), 300);
+    }
+}
@@ -0,0 +1,55 @@
+//! Decode AMQP frames dumped as hexadecimal, base64 or raw bytes
+//!
+//! Usage: amq-decode [--hex|--base64|--raw] [FILE]
+//!
+//! The dump is read from FILE, or from stdin when it is missing or "-". Its format is guessed
+//! unless given explicitly.
+
+use amq_protocol::dump::{decode_dump, parse_dump, DumpFormat};
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
+
+const USAGE: &str = "Usage: amq-decode [--hex|--base64|--raw] [FILE]";
+
+fn run() -> Result<(), String> {
+    let mut format = None;
+    let mut path = None;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "--hex" | "--base64" | "--raw" => format = Some(arg[2..].parse::<DumpFormat>()?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let dump = match path.as_deref() {
+        None | Some("-") => {
+            let mut dump = Vec::new();
+            io::stdin()
+                .read_to_end(&mut dump)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+            dump
+        }
+        Some(path) => fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?,
+    };
+    let format = format.unwrap_or_else(|| DumpFormat::detect(&dump));
+    let frames = parse_dump(&decode_dump(&dump, format)?)?;
+    for frame in frames {
+        println!("{:#?}", frame);
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
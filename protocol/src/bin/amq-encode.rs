@@ -0,0 +1,61 @@
+//! Craft AMQP frames from their JSON description
+//!
+//! Usage: amq-encode [--hex|--base64|--raw] [FILE]
+//!
+//! The description is read from FILE, or from stdin when it is missing or "-". The frames are
+//! written to stdout as raw bytes unless another format is given.
+
+use amq_protocol::dump::{encode_dump, frames_from_json, serialize_frames, DumpFormat};
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    process,
+};
+
+const USAGE: &str = "Usage: amq-encode [--hex|--base64|--raw] [FILE]";
+
+fn run() -> Result<(), String> {
+    let mut format = DumpFormat::Raw;
+    let mut path = None;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "--hex" | "--base64" | "--raw" => format = arg[2..].parse::<DumpFormat>()?,
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let json = match path.as_deref() {
+        None | Some("-") => {
+            let mut json = String::new();
+            io::stdin()
+                .read_to_string(&mut json)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+            json
+        }
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?
+        }
+    };
+    let bytes = serialize_frames(&frames_from_json(&json)?)?;
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(&encode_dump(&bytes, format))
+        .and_then(|()| match format {
+            DumpFormat::Raw => Ok(()),
+            _ => writeln!(stdout),
+        })
+        .and_then(|()| stdout.flush())
+        .map_err(|e| format!("Failed to write the frames: {}", e))
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{frame::Bytes, protocol::BasicProperties};
+use flate2::{write::ZlibEncoder, Compression, Decompress, FlushDecompress, Status};
+use std::{fmt, io::Write, str::FromStr};
+use zstd::stream::raw::{InBuffer, Operation, OutBuffer};
+
+/* Size of the buffer the chunks of the body are decompressed through */
+const SCRATCH_SIZE: usize = 32 * 1024;
+
+/// The compressions of message bodies, named after the content_encoding they are stored in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContentEncoding {
+    /// zlib-wrapped deflate, like HTTP's deflate content encoding
+    Deflate,
+    /// Zstandard
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Compress a whole body
+    pub fn compress(self, body: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            ContentEncoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body).and_then(|()| encoder.finish())
+            }
+            ContentEncoding::Zstd => zstd::encode_all(body, 0),
+        }
+        .map_err(|e| format!("Failed to compress body with {}: {}", self, e))
+    }
+
+    /// Decompress a whole body
+    pub fn decompress(self, body: &[u8]) -> Result<Vec<u8>, String> {
+        BodyDecoder::new(self)?.decode(body)
+    }
+
+    /// Decompress a whole body, failing if it is larger than max_size bytes once decompressed
+    pub fn decompress_with_max_size(self, body: &[u8], max_size: usize) -> Result<Vec<u8>, String> {
+        BodyDecoder::new(self)?.with_max_size(max_size).decode(body)
+    }
+}
+
+impl fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Zstd => "zstd",
+        })
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "deflate" => Ok(ContentEncoding::Deflate),
+            "zstd" => Ok(ContentEncoding::Zstd),
+            s => Err(format!("Unsupported content encoding: {}", s)),
+        }
+    }
+}
+
+/// Decompress a body as its frames are received, without waiting for the whole content
+pub struct BodyDecoder {
+    encoding: ContentEncoding,
+    inner: Decoder,
+    max_size: Option<usize>,
+    size: usize,
+    ended: bool,
+}
+
+enum Decoder {
+    Deflate(Decompress),
+    Zstd(zstd::stream::raw::Decoder<'static>),
+}
+
+impl BodyDecoder {
+    /// Create a decoder for the given encoding
+    pub fn new(encoding: ContentEncoding) -> Result<Self, String> {
+        let inner = match encoding {
+            ContentEncoding::Deflate => Decoder::Deflate(Decompress::new(true)),
+            ContentEncoding::Zstd => Decoder::Zstd(
+                zstd::stream::raw::Decoder::new()
+                    .map_err(|e| format!("Failed to create zstd decoder: {}", e))?,
+            ),
+        };
+        Ok(Self {
+            encoding,
+            inner,
+            max_size: None,
+            size: 0,
+            ended: false,
+        })
+    }
+
+    /// Fail once the decompressed body gets larger than max_size bytes
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// The encoding being decompressed
+    pub fn encoding(&self) -> ContentEncoding {
+        self.encoding
+    }
+
+    /// Decompress the next chunk of the body, returning the bytes decompressed so far
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<u8>, String> {
+        let mut decompressed = Vec::new();
+        let mut scratch = [0; SCRATCH_SIZE];
+        let mut input = chunk;
+        loop {
+            let (consumed, produced) = self
+                .step(input, &mut scratch)
+                .map_err(|e| format!("Failed to decompress body with {}: {}", self.encoding, e))?;
+            input = &input[consumed..];
+            self.size += produced;
+            if let Some(max_size) = self.max_size.filter(|max_size| self.size > *max_size) {
+                return Err(format!(
+                    "Body compressed with {} is larger than {} bytes once decompressed",
+                    self.encoding, max_size
+                ));
+            }
+            decompressed.extend_from_slice(&scratch[..produced]);
+            /* A full scratch buffer means more output may be pending */
+            if produced < scratch.len() && (input.is_empty() || consumed == 0) {
+                break;
+            }
+        }
+        Ok(decompressed)
+    }
+
+    /// Check that the whole body has been fed, returning the last decompressed bytes
+    pub fn finish(self) -> Result<Vec<u8>, String> {
+        if !self.ended {
+            return Err(format!(
+                "Body compressed with {} is truncated",
+                self.encoding
+            ));
+        }
+        Ok(Vec::new())
+    }
+
+    fn decode(mut self, body: &[u8]) -> Result<Vec<u8>, String> {
+        let mut decompressed = self.feed(body)?;
+        decompressed.extend(self.finish()?);
+        Ok(decompressed)
+    }
+
+    /* Decompress what fits in output, returning the number of bytes consumed and produced */
+    fn step(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize), String> {
+        match &mut self.inner {
+            Decoder::Deflate(decompress) => {
+                if self.ended {
+                    return match input {
+                        [] => Ok((0, 0)),
+                        _ => Err("trailing data after the end of the stream".to_string()),
+                    };
+                }
+                let (total_in, total_out) = (decompress.total_in(), decompress.total_out());
+                let status = decompress
+                    .decompress(input, output, FlushDecompress::None)
+                    .map_err(|e| e.to_string())?;
+                self.ended = status == Status::StreamEnd;
+                Ok((
+                    (decompress.total_in() - total_in) as usize,
+                    (decompress.total_out() - total_out) as usize,
+                ))
+            }
+            Decoder::Zstd(decoder) => {
+                let mut input = InBuffer::around(input);
+                let mut output = OutBuffer::around(output);
+                let hint = decoder
+                    .run(&mut input, &mut output)
+                    .map_err(|e| e.to_string())?;
+                /* A zero hint means that the frame has been entirely decoded and flushed, a run
+                 * without any progress tells nothing */
+                if input.pos() > 0 || output.pos() > 0 {
+                    self.ended = hint == 0;
+                }
+                Ok((input.pos(), output.pos()))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BodyDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyDecoder")
+            .field("encoding", &self.encoding)
+            .field("max_size", &self.max_size)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl BasicProperties {
+    /// Set content-encoding from a typed ContentEncoding
+    pub fn with_typed_content_encoding(self, encoding: ContentEncoding) -> Self {
+        self.with_content_encoding(encoding.to_string().into())
+    }
+
+    /// Get content-encoding as a typed ContentEncoding, None if unset or not a compression
+    pub fn typed_content_encoding(&self) -> Option<ContentEncoding> {
+        self.content_encoding()
+            .as_ref()
+            .and_then(|encoding| encoding.as_str().parse().ok())
+    }
+}
+
+/// Compress a body and record its encoding in the properties
+///
+/// This fails if the properties already have a content_encoding, as it would be lost.
+pub fn compress_body(
+    properties: BasicProperties,
+    body: &[u8],
+    encoding: ContentEncoding,
+) -> Result<(BasicProperties, Bytes), String> {
+    if let Some(current) = properties.content_encoding() {
+        return Err(format!("Body is already encoded with {}", current));
+    }
+    let body = encoding.compress(body)?;
+    Ok((
+        properties.with_typed_content_encoding(encoding),
+        body.into(),
+    ))
+}
+
+/// Decompress a body according to the content_encoding of its properties, returning it as is
+/// if it is not compressed
+pub fn decompress_body(properties: &BasicProperties, body: Bytes) -> Result<Bytes, String> {
+    decompress_body_with_max_size(properties, body, usize::MAX)
+}
+
+/// Decompress a body like decompress_body, failing if it is larger than max_size bytes once
+/// decompressed
+pub fn decompress_body_with_max_size(
+    properties: &BasicProperties,
+    body: Bytes,
+    max_size: usize,
+) -> Result<Bytes, String> {
+    match properties.typed_content_encoding() {
+        Some(encoding) => encoding
+            .decompress_with_max_size(&body, max_size)
+            .map(Bytes::from),
+        None => Ok(body),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_compress_body() {
+        let body = b"hello world ".repeat(100);
+        for encoding in [ContentEncoding::Deflate, ContentEncoding::Zstd] {
+            let (properties, compressed) =
+                compress_body(BasicProperties::default(), &body, encoding).unwrap();
+            assert!(compressed.len() < body.len(), "{}", encoding);
+            assert_eq!(properties.typed_content_encoding(), Some(encoding));
+            assert_eq!(
+                decompress_body(&properties, compressed),
+                Ok(Bytes::from(body.clone()))
+            );
+        }
+        let properties = BasicProperties::default().with_content_encoding("utf-8".into());
+        assert_eq!(properties.typed_content_encoding(), None);
+        assert!(compress_body(properties.clone(), &body, ContentEncoding::Zstd).is_err());
+        assert_eq!(
+            decompress_body(&properties, Bytes::from_static(b"plain")),
+            Ok(Bytes::from_static(b"plain"))
+        );
+    }
+
+    #[test]
+    fn test_body_decoder() {
+        let body = (0..10_000u32)
+            .flat_map(|i| i.to_be_bytes())
+            .collect::<Vec<_>>();
+        for encoding in [ContentEncoding::Deflate, ContentEncoding::Zstd] {
+            let compressed = encoding.compress(&body).unwrap();
+            let mut decoder = BodyDecoder::new(encoding).unwrap();
+            let mut decompressed = Vec::new();
+            for chunk in compressed.chunks(100) {
+                decompressed.extend(decoder.feed(chunk).unwrap());
+            }
+            decompressed.extend(decoder.finish().unwrap());
+            assert_eq!(decompressed, body, "{}", encoding);
+        }
+        assert!(ContentEncoding::Deflate.decompress(b"not deflate").is_err());
+        assert!(ContentEncoding::Zstd.decompress(b"not zstd").is_err());
+    }
+
+    #[test]
+    fn test_truncated_body() {
+        let body = b"hello world ".repeat(100);
+        for encoding in [ContentEncoding::Deflate, ContentEncoding::Zstd] {
+            let compressed = encoding.compress(&body).unwrap();
+            let truncated = &compressed[..compressed.len() - 4];
+            assert_eq!(
+                encoding.decompress(truncated),
+                Err(format!("Body compressed with {} is truncated", encoding))
+            );
+            let mut decoder = BodyDecoder::new(encoding).unwrap();
+            decoder.feed(truncated).unwrap();
+            assert!(decoder.finish().is_err(), "{}", encoding);
+            assert!(BodyDecoder::new(encoding).unwrap().finish().is_err());
+        }
+        let compressed = ContentEncoding::Deflate.compress(&body).unwrap();
+        assert!(ContentEncoding::Deflate
+            .decompress(&[&compressed[..], &b"trailing"[..]].concat())
+            .is_err());
+    }
+
+    #[test]
+    fn test_max_size() {
+        let body = vec![0; 1_000_000];
+        for encoding in [ContentEncoding::Deflate, ContentEncoding::Zstd] {
+            let compressed = encoding.compress(&body).unwrap();
+            assert_eq!(
+                encoding.decompress_with_max_size(&compressed, body.len()),
+                Ok(body.clone())
+            );
+            assert_eq!(
+                encoding.decompress_with_max_size(&compressed, body.len() - 1),
+                Err(format!(
+                    "Body compressed with {} is larger than 999999 bytes once decompressed",
+                    encoding
+                ))
+            );
+            let mut decoder = BodyDecoder::new(encoding).unwrap().with_max_size(1024);
+            assert!(decoder.feed(&compressed).is_err(), "{}", encoding);
+            let properties = BasicProperties::default().with_typed_content_encoding(encoding);
+            assert!(decompress_body_with_max_size(&properties, compressed.into(), 1024).is_err());
+        }
+    }
+}
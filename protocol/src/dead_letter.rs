@@ -0,0 +1,277 @@
+use crate::{
+    arguments::as_count,
+    types::{AMQPValue, FieldTable, LongLongUInt},
+};
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Why a message was dead-lettered
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DeathReason {
+    /// The message was rejected or negatively acknowledged without requeue
+    Rejected,
+    /// The TTL of the message or of the queue expired
+    Expired,
+    /// The queue exceeded its length limit
+    MaxLen,
+    /// The message exceeded the delivery limit of a quorum queue
+    DeliveryLimit,
+    /// A reason unknown to this library
+    Other(String),
+}
+
+impl DeathReason {
+    /// Get the value used by the server
+    pub fn as_str(&self) -> &str {
+        match self {
+            DeathReason::Rejected => "rejected",
+            DeathReason::Expired => "expired",
+            DeathReason::MaxLen => "maxlen",
+            DeathReason::DeliveryLimit => "delivery_limit",
+            DeathReason::Other(reason) => reason,
+        }
+    }
+}
+
+impl From<&str> for DeathReason {
+    fn from(reason: &str) -> Self {
+        match reason {
+            "rejected" => DeathReason::Rejected,
+            "expired" => DeathReason::Expired,
+            "maxlen" => DeathReason::MaxLen,
+            "delivery_limit" => DeathReason::DeliveryLimit,
+            _ => DeathReason::Other(reason.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for DeathReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The dead-lettering of a message from a queue for a given reason, as reported by an x-death
+/// entry
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Death {
+    /// The queue the message was dead-lettered from
+    pub queue: String,
+    /// Why the message was dead-lettered
+    pub reason: DeathReason,
+    /// The exchange the message had been published to before entering the queue
+    pub exchange: String,
+    /// The routing keys the message had been published with, CC and BCC included
+    pub routing_keys: Vec<String>,
+    /// How many times the message was dead-lettered from this queue for this reason
+    pub count: LongLongUInt,
+    /// When the message was first dead-lettered from this queue for this reason
+    pub time: Option<SystemTime>,
+    /// The expiration property of the message before it was dead-lettered
+    pub original_expiration: Option<String>,
+}
+
+impl Death {
+    fn from_table(death: &FieldTable) -> Result<Self, String> {
+        let string = |key: &str| {
+            death
+                .inner()
+                .get(key)
+                .and_then(as_string)
+                .ok_or_else(|| format!("x-death entry has no {}", key))
+        };
+        Ok(Self {
+            queue: string("queue")?,
+            reason: string("reason")?.as_str().into(),
+            exchange: string("exchange").unwrap_or_default(),
+            routing_keys: death
+                .inner()
+                .get("routing-keys")
+                .and_then(AMQPValue::as_array)
+                .map(|keys| keys.as_slice().iter().filter_map(as_string).collect())
+                .unwrap_or_default(),
+            /* Entries written before counts were introduced stand for a single death */
+            count: match death.inner().get("count") {
+                Some(count) => as_count(count)
+                    .ok_or_else(|| format!("x-death entry has an invalid count: {}", count))?,
+                None => 1,
+            },
+            time: death
+                .inner()
+                .get("time")
+                .and_then(AMQPValue::as_timestamp)
+                .and_then(|time| UNIX_EPOCH.checked_add(Duration::from_secs(time))),
+            original_expiration: string("original-expiration").ok(),
+        })
+    }
+
+    fn merge(&mut self, older: Death) {
+        self.count = self.count.saturating_add(older.count);
+        self.time = match (self.time, older.time) {
+            (Some(time), Some(older)) => Some(time.min(older)),
+            (time, older) => time.or(older),
+        };
+        if self.original_expiration.is_none() {
+            self.original_expiration = older.original_expiration;
+        }
+    }
+}
+
+/// The dead-letter routing chain of a message, reconstructed from its x-death header
+///
+/// The server keeps a single x-death entry per queue and reason, counting how many times the
+/// message was dead-lettered this way and moving the entry first each time. The chain lists
+/// these entries from the oldest to the most recent. Duplicate entries, written by old servers
+/// or by clients copying headers around, are merged the way the server does: counts are
+/// summed and the earliest time is kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeathChain {
+    deaths: Vec<Death>,
+}
+
+impl DeathChain {
+    /// Reconstruct the chain from the headers of a message
+    pub fn from_table(headers: &FieldTable) -> Result<Self, String> {
+        let entries = match headers.inner().get("x-death") {
+            None => return Ok(Self::default()),
+            Some(x_death) => x_death
+                .as_array()
+                .ok_or_else(|| format!("x-death must be an array, not a {}", x_death.get_type()))?,
+        };
+        let mut deaths: Vec<Death> = Vec::new();
+        /* The most recent entry comes first */
+        for entry in entries.as_slice() {
+            let death = Death::from_table(entry.as_field_table().ok_or_else(|| {
+                format!("x-death entries must be tables, not {}", entry.get_type())
+            })?)?;
+            match deaths
+                .iter_mut()
+                .find(|d| d.queue == death.queue && d.reason == death.reason)
+            {
+                Some(newer) => newer.merge(death),
+                None => deaths.push(death),
+            }
+        }
+        deaths.reverse();
+        Ok(Self { deaths })
+    }
+
+    /// The dead-letterings of the message, from the oldest to the most recent
+    pub fn deaths(&self) -> &[Death] {
+        &self.deaths
+    }
+
+    /// The queue and reason of the first dead-lettering
+    pub fn first(&self) -> Option<&Death> {
+        self.deaths.first()
+    }
+
+    /// The queue and reason of the most recent dead-lettering
+    pub fn last(&self) -> Option<&Death> {
+        self.deaths.last()
+    }
+
+    /// The total number of times the message was dead-lettered
+    pub fn total_count(&self) -> LongLongUInt {
+        self.deaths
+            .iter()
+            .fold(0, |total, death| total.saturating_add(death.count))
+    }
+
+    /// How many times the message was dead-lettered from the given queue for the given reason
+    pub fn count(&self, queue: &str, reason: &DeathReason) -> LongLongUInt {
+        self.deaths
+            .iter()
+            .find(|death| death.queue == queue && &death.reason == reason)
+            .map_or(0, |death| death.count)
+    }
+
+    /// Whether the message was never dead-lettered
+    pub fn is_empty(&self) -> bool {
+        self.deaths.is_empty()
+    }
+}
+
+fn as_string(value: &AMQPValue) -> Option<String> {
+    match value {
+        AMQPValue::ShortString(s) => Some(s.to_string()),
+        AMQPValue::LongString(s) => Some(s.to_str_lossy().into()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::{FieldArray, LongLongInt};
+
+    fn death(queue: &str, reason: &str, count: Option<LongLongInt>, time: u64) -> AMQPValue {
+        let mut death = FieldTable::default();
+        death.insert("queue".into(), AMQPValue::LongString(queue.into()));
+        death.insert("reason".into(), AMQPValue::LongString(reason.into()));
+        death.insert("exchange".into(), AMQPValue::LongString("work".into()));
+        death.insert(
+            "routing-keys".into(),
+            AMQPValue::FieldArray(vec![AMQPValue::LongString(queue.into())].into()),
+        );
+        if let Some(count) = count {
+            death.insert("count".into(), AMQPValue::LongLongInt(count));
+        }
+        death.insert("time".into(), AMQPValue::Timestamp(time));
+        AMQPValue::FieldTable(death)
+    }
+
+    fn headers(deaths: Vec<AMQPValue>) -> FieldTable {
+        let mut headers = FieldTable::default();
+        headers.insert(
+            "x-death".into(),
+            AMQPValue::FieldArray(FieldArray::from(deaths)),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_death_chain() {
+        let chain = DeathChain::from_table(&headers(vec![
+            death("retry", "expired", Some(2), 20),
+            death("work", "rejected", Some(3), 10),
+            death("retry", "expired", None, 15),
+        ]))
+        .unwrap();
+        assert_eq!(chain.deaths().len(), 2);
+        let first = chain.first().unwrap();
+        assert_eq!(first.queue, "work");
+        assert_eq!(first.reason, DeathReason::Rejected);
+        assert_eq!(first.routing_keys, vec!["work".to_string()]);
+        let last = chain.last().unwrap();
+        assert_eq!(last.reason, DeathReason::Expired);
+        assert_eq!(last.count, 3);
+        assert_eq!(last.time, Some(UNIX_EPOCH + Duration::from_secs(15)));
+        assert_eq!(chain.total_count(), 6);
+        assert_eq!(chain.count("work", &DeathReason::Rejected), 3);
+        assert_eq!(chain.count("work", &DeathReason::Expired), 0);
+
+        assert!(DeathChain::from_table(&FieldTable::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_invalid_death_chain() {
+        let mut invalid = FieldTable::default();
+        invalid.insert("x-death".into(), AMQPValue::Boolean(true));
+        assert!(DeathChain::from_table(&invalid).is_err());
+        assert!(DeathChain::from_table(&headers(vec![AMQPValue::Void])).is_err());
+        let mut entry = FieldTable::default();
+        entry.insert("queue".into(), AMQPValue::LongString("work".into()));
+        assert_eq!(
+            DeathChain::from_table(&headers(vec![AMQPValue::FieldTable(entry)])),
+            Err("x-death entry has no reason".to_string())
+        );
+        assert!(
+            DeathChain::from_table(&headers(vec![death("work", "rejected", Some(-1), 0)])).is_err()
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use crate::{
+    arguments::PolicyDefinition,
+    types::{AMQPValue, FieldTable},
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+#[cfg(feature = "class-exchange")]
+use crate::protocol::exchange;
+#[cfg(feature = "class-queue")]
+use crate::protocol::queue;
+
+/// The broker definitions, as exported by RabbitMQ in definitions.json
+///
+/// The arguments of queues, exchanges and bindings, the definitions of policies and the values
+/// of parameters are mapped to our types: integers become LongLongInts, other numbers Doubles
+/// and strings LongStrings, as when RabbitMQ imports them.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Definitions {
+    /// The version of RabbitMQ which exported the definitions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rabbitmq_version: Option<String>,
+    /// The version of RabbitMQ which exported the definitions, for older versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rabbit_version: Option<String>,
+    /// The users
+    #[serde(default)]
+    pub users: Vec<User>,
+    /// The virtual hosts
+    #[serde(default)]
+    pub vhosts: Vec<VHost>,
+    /// The permissions of the users on the virtual hosts
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+    /// The permissions of the users on the topic exchanges of the virtual hosts
+    #[serde(default)]
+    pub topic_permissions: Vec<TopicPermission>,
+    /// The runtime parameters, such as shovels and federation upstreams
+    #[serde(default)]
+    pub parameters: Vec<Parameter>,
+    /// The parameters which are not bound to a virtual host, such as the cluster name
+    #[serde(default)]
+    pub global_parameters: Vec<GlobalParameter>,
+    /// The policies
+    #[serde(default)]
+    pub policies: Vec<Policy>,
+    /// The queues
+    #[serde(default)]
+    pub queues: Vec<Queue>,
+    /// The exchanges
+    #[serde(default)]
+    pub exchanges: Vec<Exchange>,
+    /// The bindings
+    #[serde(default)]
+    pub bindings: Vec<Binding>,
+    /// The other fields, such as the operator policies, kept as is
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Definitions {
+    /// Parse the content of a definitions.json file
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid definitions: {}", e))
+    }
+
+    /// Serialize the definitions as the content of a definitions.json file
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize definitions: {}", e))
+    }
+}
+
+/// A user
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct User {
+    /// The name of the user
+    pub name: String,
+    /// The salted hash of the password, base64 encoded
+    #[serde(default)]
+    pub password_hash: String,
+    /// The algorithm used to hash the password
+    #[serde(default)]
+    pub hashing_algorithm: String,
+    /// The tags of the user, such as administrator
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    pub tags: Vec<String>,
+    /// The limits of the user, such as max-connections
+    #[serde(default, skip_serializing_if = "is_empty_table", with = "json_table")]
+    pub limits: FieldTable,
+}
+
+/// A virtual host
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct VHost {
+    /// The name of the virtual host
+    pub name: String,
+    /// The description of the virtual host
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The tags of the virtual host
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_tags"
+    )]
+    pub tags: Vec<String>,
+    /// The type of the queues declared without x-queue-type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_queue_type: Option<String>,
+    /// The metadata of the virtual host, which holds the fields above in recent versions
+    #[serde(default, skip_serializing_if = "is_empty_table", with = "json_table")]
+    pub metadata: FieldTable,
+}
+
+/// The permissions of a user on a virtual host, as regular expressions matching resource names
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Permission {
+    /// The user
+    pub user: String,
+    /// The virtual host
+    pub vhost: String,
+    /// The resources the user can declare and delete
+    pub configure: String,
+    /// The resources the user can publish to or bind from
+    pub write: String,
+    /// The resources the user can consume from or bind to
+    pub read: String,
+}
+
+/// The permissions of a user on a topic exchange, as regular expressions matching routing keys
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TopicPermission {
+    /// The user
+    pub user: String,
+    /// The virtual host
+    pub vhost: String,
+    /// The topic exchange
+    pub exchange: String,
+    /// The routing keys the user can publish with
+    pub write: String,
+    /// The routing keys the user can bind with
+    pub read: String,
+}
+
+/// A runtime parameter of a component
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Parameter {
+    /// The virtual host
+    pub vhost: String,
+    /// The component the parameter is for, such as shovel
+    pub component: String,
+    /// The name of the parameter
+    pub name: String,
+    /// The value of the parameter
+    #[serde(with = "json_value")]
+    pub value: AMQPValue,
+}
+
+/// A parameter which is not bound to a virtual host
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct GlobalParameter {
+    /// The name of the parameter
+    pub name: String,
+    /// The value of the parameter
+    #[serde(with = "json_value")]
+    pub value: AMQPValue,
+}
+
+/// A policy, applying its definition to the queues or exchanges matching its pattern
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Policy {
+    /// The virtual host
+    pub vhost: String,
+    /// The name of the policy
+    pub name: String,
+    /// The regular expression matching the names of the queues or exchanges
+    pub pattern: String,
+    /// What the policy applies to: all, queues, classic_queues, quorum_queues, streams or
+    /// exchanges
+    #[serde(rename = "apply-to", default = "default_apply_to")]
+    pub apply_to: String,
+    /// The keys and values of the policy
+    #[serde(with = "json_table")]
+    pub definition: FieldTable,
+    /// The priority of the policy, the highest one wins when several match
+    #[serde(default)]
+    pub priority: i64,
+}
+
+impl Policy {
+    /// The typed model of the definition
+    pub fn typed_definition(&self) -> Result<PolicyDefinition, String> {
+        PolicyDefinition::try_from(&self.definition)
+    }
+}
+
+/// A queue
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Queue {
+    /// The name of the queue
+    pub name: String,
+    /// The virtual host
+    pub vhost: String,
+    /// The type of the queue, exported by recent versions
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Whether the queue survives broker restarts
+    #[serde(default)]
+    pub durable: bool,
+    /// Whether the queue is deleted once its last consumer is gone
+    #[serde(default)]
+    pub auto_delete: bool,
+    /// The x-arguments of the queue
+    #[serde(default, with = "json_table")]
+    pub arguments: FieldTable,
+}
+
+impl Queue {
+    /// The queue.declare declaring this queue, with an x-queue-type argument if it has a type
+    #[cfg(feature = "class-queue")]
+    pub fn declare(&self) -> queue::Declare {
+        let mut arguments = self.arguments.clone();
+        if let Some(kind) = &self.kind {
+            if !arguments.contains_key("x-queue-type") {
+                arguments.insert(
+                    "x-queue-type".into(),
+                    AMQPValue::LongString(kind.as_str().into()),
+                );
+            }
+        }
+        queue::Declare {
+            queue: self.name.as_str().into(),
+            durable: self.durable,
+            auto_delete: self.auto_delete,
+            arguments,
+            ..queue::Declare::default()
+        }
+    }
+}
+
+/// An exchange
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Exchange {
+    /// The name of the exchange
+    pub name: String,
+    /// The virtual host
+    pub vhost: String,
+    /// The type of the exchange, such as direct or topic
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Whether the exchange survives broker restarts
+    #[serde(default)]
+    pub durable: bool,
+    /// Whether the exchange is deleted once its last binding is gone
+    #[serde(default)]
+    pub auto_delete: bool,
+    /// Whether the exchange can only be published to by other exchanges
+    #[serde(default)]
+    pub internal: bool,
+    /// The x-arguments of the exchange
+    #[serde(default, with = "json_table")]
+    pub arguments: FieldTable,
+}
+
+impl Exchange {
+    /// The exchange.declare declaring this exchange
+    #[cfg(feature = "class-exchange")]
+    pub fn declare(&self) -> exchange::Declare {
+        exchange::Declare {
+            exchange: self.name.as_str().into(),
+            kind: self.kind.as_str().into(),
+            durable: self.durable,
+            auto_delete: self.auto_delete,
+            internal: self.internal,
+            arguments: self.arguments.clone(),
+            ..exchange::Declare::default()
+        }
+    }
+}
+
+/// What a binding routes messages to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DestinationType {
+    /// A queue
+    #[default]
+    Queue,
+    /// An exchange
+    Exchange,
+}
+
+/// A binding, routing messages from an exchange to a queue or another exchange
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Binding {
+    /// The exchange messages are routed from
+    pub source: String,
+    /// The virtual host
+    pub vhost: String,
+    /// The queue or the exchange messages are routed to
+    pub destination: String,
+    /// Whether the destination is a queue or an exchange
+    pub destination_type: DestinationType,
+    /// The routing key
+    #[serde(default)]
+    pub routing_key: String,
+    /// The x-arguments of the binding
+    #[serde(default, with = "json_table")]
+    pub arguments: FieldTable,
+}
+
+fn is_empty_table(table: &FieldTable) -> bool {
+    table.inner().is_empty()
+}
+
+fn default_apply_to() -> String {
+    "all".to_string()
+}
+
+/* Older versions export the tags as a comma separated string */
+fn deserialize_tags<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Tags {
+        List(Vec<String>),
+        String(String),
+    }
+    Ok(match Tags::deserialize(deserializer)? {
+        Tags::List(tags) => tags,
+        Tags::String(tags) => tags
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+/* A FieldTable as a plain JSON object */
+pub(crate) mod json_table {
+    use super::*;
+    use serde::de::Error;
+
+    pub(crate) fn serialize<S: Serializer>(
+        table: &FieldTable,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            table
+                .inner()
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.to_json())),
+        )
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<FieldTable, D::Error> {
+        match AMQPValue::from_json(&Value::deserialize(deserializer)?) {
+            AMQPValue::FieldTable(table) => Ok(table),
+            /* Older versions export empty arguments as an empty list */
+            AMQPValue::FieldArray(array) if array.as_slice().is_empty() => {
+                Ok(FieldTable::default())
+            }
+            AMQPValue::Void => Ok(FieldTable::default()),
+            value => Err(D::Error::custom(format!(
+                "expected an object, got {}",
+                value.to_json()
+            ))),
+        }
+    }
+}
+
+/* An AMQPValue as plain JSON */
+mod json_value {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &AMQPValue,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_json().serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<AMQPValue, D::Error> {
+        Value::deserialize(deserializer).map(|value| AMQPValue::from_json(&value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DEFINITIONS: &str = r#"{
+        "rabbit_version": "3.12.4",
+        "users": [
+            {"name": "admin", "password_hash": "hash", "hashing_algorithm": "rabbit_password_hashing_sha256", "tags": "administrator, monitoring", "limits": {}}
+        ],
+        "vhosts": [{"name": "/", "metadata": {"description": "Default", "tags": []}}],
+        "permissions": [{"user": "admin", "vhost": "/", "configure": ".*", "write": ".*", "read": ".*"}],
+        "topic_permissions": [{"user": "admin", "vhost": "/", "exchange": "events", "write": "^work\\.", "read": ".*"}],
+        "operator_policies": [{"vhost": "/", "name": "limit", "pattern": ".*", "apply-to": "queues", "definition": {"max-length": 100}, "priority": 0}],
+        "global_parameters": [{"name": "cluster_name", "value": "rabbit@localhost"}],
+        "policies": [
+            {"vhost": "/", "name": "ttl", "pattern": "^work$", "apply-to": "queues", "definition": {"message-ttl": 60000}, "priority": 1}
+        ],
+        "queues": [
+            {"name": "work", "vhost": "/", "durable": true, "auto_delete": false, "arguments": {"x-queue-type": "quorum", "x-max-length": 1000}},
+            {"name": "log", "vhost": "/", "type": "stream", "durable": true, "auto_delete": false, "arguments": {}}
+        ],
+        "exchanges": [
+            {"name": "events", "vhost": "/", "type": "topic", "durable": true, "auto_delete": false, "internal": false, "arguments": []}
+        ],
+        "bindings": [
+            {"source": "events", "vhost": "/", "destination": "work", "destination_type": "queue", "routing_key": "work.#", "arguments": {}}
+        ]
+    }"#;
+
+    #[test]
+    fn test_definitions() {
+        let definitions = Definitions::from_json(DEFINITIONS).unwrap();
+        assert_eq!(definitions.rabbit_version.as_deref(), Some("3.12.4"));
+        assert_eq!(
+            definitions.users[0].tags,
+            vec!["administrator", "monitoring"]
+        );
+        assert_eq!(definitions.topic_permissions[0].exchange, "events");
+        assert_eq!(definitions.topic_permissions[0].write, "^work\\.");
+        assert_eq!(
+            definitions.extra.keys().collect::<Vec<_>>(),
+            vec!["operator_policies"]
+        );
+        assert_eq!(
+            definitions.global_parameters[0].value,
+            AMQPValue::LongString("rabbit@localhost".into())
+        );
+        assert_eq!(
+            definitions.policies[0]
+                .definition
+                .inner()
+                .get("message-ttl"),
+            Some(&AMQPValue::LongLongInt(60000))
+        );
+        let queue = &definitions.queues[0];
+        assert_eq!(
+            queue.arguments.inner().get("x-queue-type"),
+            Some(&AMQPValue::LongString("quorum".into()))
+        );
+        assert!(definitions.exchanges[0].arguments.inner().is_empty());
+        assert_eq!(
+            definitions.bindings[0].destination_type,
+            DestinationType::Queue
+        );
+
+        let json = definitions.to_json().unwrap();
+        assert_eq!(Definitions::from_json(&json), Ok(definitions));
+        assert!(Definitions::from_json(
+            r#"{"queues": [{"name": "q", "vhost": "/", "arguments": 1}]}"#
+        )
+        .is_err());
+    }
+
+    #[cfg(all(feature = "class-queue", feature = "class-exchange"))]
+    #[test]
+    fn test_declare() {
+        let definitions = Definitions::from_json(DEFINITIONS).unwrap();
+        let declare = definitions.queues[0].declare();
+        assert_eq!(declare.queue.as_str(), "work");
+        assert!(declare.durable);
+        assert_eq!(declare.arguments, definitions.queues[0].arguments);
+        let declare = definitions.queues[1].declare();
+        assert_eq!(
+            declare.arguments.inner().get("x-queue-type"),
+            Some(&AMQPValue::LongString("stream".into()))
+        );
+        let declare = definitions.exchanges[0].declare();
+        assert_eq!(declare.kind.as_str(), "topic");
+        assert!(!declare.passive);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    dump::decode_hex,
+    frame::{gen_frame, parse_frame, AMQPFrame},
+};
+use std::{fs, path::Path};
+
+/// The environment variable pointing to the directory of reference encodings
+pub const REFERENCE_ENCODINGS_DIR: &str = "AMQ_PROTOCOL_REFERENCE_ENCODINGS";
+
+/// A frame serialized by another client, such as the RabbitMQ Java or Erlang ones
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReferenceEncoding {
+    /// The name of the file the encoding comes from
+    pub name: String,
+    /// The serialized frame
+    pub bytes: Vec<u8>,
+}
+
+/// The outcome of checking a set of reference encodings
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DifferentialReport {
+    /// The encodings we parsed and reproduced identically
+    pub passed: Vec<String>,
+    /// The encodings we failed to parse or to reproduce, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+impl DifferentialReport {
+    /// Whether every encoding passed
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Load the reference encodings from a directory
+///
+/// Files ending in `.bin` hold the raw bytes of a frame, files ending in `.hex` hold them as
+/// hexadecimal, whitespace being ignored. Other files are skipped. Encodings are sorted by name.
+pub fn load_reference_encodings(dir: &Path) -> Result<Vec<ReferenceEncoding>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+    let mut encodings = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?
+            .path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let bytes = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("bin") => {
+                fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            }
+            Some("hex") => fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+                .and_then(|hex| decode_hex(&hex).map_err(|e| format!("{}: {}", name, e)))?,
+            _ => continue,
+        };
+        encodings.push(ReferenceEncoding { name, bytes });
+    }
+    encodings.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(encodings)
+}
+
+/// Check that we parse the reference encoding entirely and serialize it back identically
+pub fn check_reference_encoding(bytes: &[u8]) -> Result<AMQPFrame, String> {
+    let (rest, frame) = parse_frame(bytes).map_err(|e| format!("Failed to parse: {}", e))?;
+    if !rest.is_empty() {
+        return Err(format!(
+            "{} bytes left after parsing {:?}",
+            rest.len(),
+            frame
+        ));
+    }
+    let generated = cookie_factory::gen_simple(gen_frame(&frame), Vec::new())
+        .map_err(|e| format!("Failed to serialize {:?}: {}", frame, e))?;
+    if generated != bytes {
+        return Err(format!(
+            "Serializing {:?} gave {:?} instead of {:?}",
+            frame, generated, bytes
+        ));
+    }
+    Ok(frame)
+}
+
+/// Check all the reference encodings of a directory
+pub fn check_reference_encodings(dir: &Path) -> Result<DifferentialReport, String> {
+    let mut report = DifferentialReport::default();
+    for encoding in load_reference_encodings(dir)? {
+        match check_reference_encoding(&encoding.bytes) {
+            Ok(_) => report.passed.push(encoding.name),
+            Err(err) => report.failed.push((encoding.name, err)),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocol::{basic, AMQPClass};
+
+    #[test]
+    fn test_check_reference_encodings() {
+        let dir = std::env::temp_dir().join(format!(
+            "amq-protocol-reference-encodings-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let frame = AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::Qos(basic::Qos {
+                prefetch_count: 10,
+                global: false,
+            })),
+        );
+        let bytes = cookie_factory::gen_simple(gen_frame(&frame), Vec::new()).unwrap();
+        let hex = bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+        fs::write(dir.join("basic.qos.bin"), &bytes).unwrap();
+        fs::write(dir.join("basic.qos.hex"), hex).unwrap();
+        fs::write(dir.join("trailing.bin"), [&bytes[..], &[0]].concat()).unwrap();
+        fs::write(dir.join("README"), "not an encoding").unwrap();
+
+        assert_eq!(check_reference_encoding(&bytes), Ok(frame));
+        let report = check_reference_encodings(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(report.passed, vec!["basic.qos.bin", "basic.qos.hex"]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "trailing.bin");
+        assert!(!report.is_success());
+    }
+}
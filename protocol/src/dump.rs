@@ -0,0 +1,411 @@
+use crate::frame::{gen_frame, AMQPFrame, FrameAccumulator};
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "cli")]
+use crate::{
+    frame::split_content,
+    protocol::{AMQPClass, BasicProperties},
+    types::{AMQPType, AMQPValue, FieldTable},
+};
+#[cfg(feature = "cli")]
+use serde_json::{Map, Value};
+
+/// The textual or binary format of a frame dump
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Hexadecimal digits, whitespace being ignored
+    Hex,
+    /// Base64, standard or URL-safe, padded or not, whitespace being ignored
+    Base64,
+    /// The raw bytes of the frames
+    Raw,
+}
+
+impl DumpFormat {
+    /// Guess the format of a dump: hexadecimal if it only holds hexadecimal digits, base64 if it
+    /// only holds base64 characters, raw otherwise
+    pub fn detect(dump: &[u8]) -> Self {
+        let mut chars = dump.iter().filter(|c| !c.is_ascii_whitespace()).peekable();
+        if chars.peek().is_none() {
+            return DumpFormat::Raw;
+        }
+        let chars = chars.collect::<Vec<_>>();
+        if chars.iter().all(|c| c.is_ascii_hexdigit()) {
+            DumpFormat::Hex
+        } else if chars
+            .iter()
+            .all(|c| base64_digit(**c).is_some() || **c == b'=')
+        {
+            DumpFormat::Base64
+        } else {
+            DumpFormat::Raw
+        }
+    }
+}
+
+impl FromStr for DumpFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hex" => Ok(DumpFormat::Hex),
+            "base64" => Ok(DumpFormat::Base64),
+            "raw" => Ok(DumpFormat::Raw),
+            _ => Err(format!("Unknown dump format: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for DumpFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DumpFormat::Hex => "hex",
+            DumpFormat::Base64 => "base64",
+            DumpFormat::Raw => "raw",
+        })
+    }
+}
+
+/// Get the bytes of a dump in the given format
+pub fn decode_dump(dump: &[u8], format: DumpFormat) -> Result<Vec<u8>, String> {
+    match format {
+        DumpFormat::Hex => decode_hex(&String::from_utf8_lossy(dump)),
+        DumpFormat::Base64 => decode_base64(&String::from_utf8_lossy(dump)),
+        DumpFormat::Raw => Ok(dump.to_vec()),
+    }
+}
+
+/// Parse all the frames of a dump, failing if it ends in the middle of a frame
+pub fn parse_dump(bytes: &[u8]) -> Result<Vec<AMQPFrame>, String> {
+    let mut accumulator = FrameAccumulator::new();
+    let frames = accumulator.feed_frames(bytes).map_err(|e| e.to_string())?;
+    if !accumulator.is_empty() {
+        return Err(format!(
+            "{} bytes left after the last complete frame",
+            accumulator.buffered()
+        ));
+    }
+    Ok(frames)
+}
+
+/// Write a dump of the bytes in the given format
+pub fn encode_dump(bytes: &[u8], format: DumpFormat) -> Vec<u8> {
+    match format {
+        DumpFormat::Hex => encode_hex(bytes).into_bytes(),
+        DumpFormat::Base64 => encode_base64(bytes).into_bytes(),
+        DumpFormat::Raw => bytes.to_vec(),
+    }
+}
+
+/// Serialize frames back to back, as they would be sent
+pub fn serialize_frames(frames: &[AMQPFrame]) -> Result<Vec<u8>, String> {
+    frames.iter().try_fold(Vec::new(), |bytes, frame| {
+        cookie_factory::gen_simple(gen_frame(frame), bytes)
+            .map_err(|e| format!("Failed to serialize {:?}: {}", frame, e))
+    })
+}
+
+/// Build frames from their JSON description, either an object or an array of objects
+///
+/// Each object describes a method with its "class" and "method" names, as in the specification,
+/// its "arguments" and its "channel", 0 by default. Content-carrying methods are followed by
+/// their content header and body frames, built from the "properties" and the "body". The body
+/// is a string in the "body_format", raw by default.
+///
+/// Arguments are converted to the type the method expects. Field tables, including the headers,
+/// use booleans, LongLongInts, Doubles, LongStrings, FieldArrays, FieldTables and Void.
+#[cfg(feature = "cli")]
+pub fn frames_from_json(json: &str) -> Result<Vec<AMQPFrame>, String> {
+    match serde_json::from_str(json).map_err(|e| format!("Invalid JSON: {}", e))? {
+        Value::Array(descriptions) => {
+            descriptions
+                .iter()
+                .try_fold(Vec::new(), |mut frames, description| {
+                    frames.extend(frames_from_description(description)?);
+                    Ok(frames)
+                })
+        }
+        description => frames_from_description(&description),
+    }
+}
+
+#[cfg(feature = "cli")]
+fn frames_from_description(description: &Value) -> Result<Vec<AMQPFrame>, String> {
+    let description = description
+        .as_object()
+        .ok_or_else(|| format!("Expected an object describing a frame, got {}", description))?;
+    let string = |key: &str| description.get(key).and_then(Value::as_str);
+    let class = string("class").ok_or("Missing the class")?;
+    let method = string("method").ok_or("Missing the method")?;
+    let channel_id = description
+        .get("channel")
+        .map(|channel| {
+            channel
+                .as_u64()
+                .and_then(|channel| channel.try_into().ok())
+                .ok_or_else(|| format!("Invalid channel: {}", channel))
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let descriptors = AMQPClass::arguments(class, method)
+        .ok_or_else(|| format!("Unknown method {}.{}", class, method))?;
+    let mut arguments = FieldTable::default();
+    for (name, value) in description
+        .get("arguments")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let value = match descriptors
+            .iter()
+            .find(|descriptor| descriptor.name == name)
+        {
+            Some(descriptor) if descriptor.amqp_type != AMQPType::FieldTable => {
+                AMQPValue::try_from(value, descriptor.amqp_type).ok_or_else(|| {
+                    format!("Invalid {:?} for {}: {}", descriptor.amqp_type, name, value)
+                })?
+            }
+            _ => AMQPValue::from_json(value),
+        };
+        arguments.insert(name.as_str().into(), value);
+    }
+    let method = AMQPClass::from_arguments(class, method, &arguments)?;
+    let is_content_carrying = method.is_content_carrying();
+    let mut frames = vec![AMQPFrame::Method(channel_id, method)];
+    if is_content_carrying {
+        let properties = description
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(json_to_properties)
+            .transpose()?
+            .unwrap_or_default();
+        let format = string("body_format")
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or(DumpFormat::Raw);
+        let body = decode_dump(string("body").unwrap_or_default().as_bytes(), format)?;
+        frames.extend(
+            split_content(channel_id, properties, body.into(), 0)
+                .map_err(|e| format!("Failed to serialize the content header: {}", e))?,
+        );
+    }
+    Ok(frames)
+}
+
+#[cfg(feature = "cli")]
+fn json_to_properties(properties: &Map<String, Value>) -> Result<BasicProperties, String> {
+    let mut properties = properties.clone();
+    let headers = properties.remove("headers");
+    let properties = serde_json::from_value::<BasicProperties>(Value::Object(properties))
+        .map_err(|e| format!("Invalid properties: {}", e))?;
+    Ok(match headers.as_ref().map(AMQPValue::from_json) {
+        Some(AMQPValue::FieldTable(headers)) => properties.with_headers(headers),
+        Some(headers) => return Err(format!("Invalid headers: {:?}", headers)),
+        None => properties,
+    })
+}
+
+/// Encode bytes as lowercase hexadecimal digits
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Encode bytes as padded standard base64
+pub fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    bytes
+        .chunks(3)
+        .flat_map(|chunk| {
+            let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, byte)| {
+                bits | (u32::from(*byte) << (16 - 8 * i))
+            });
+            (0..4).map(move |i| {
+                if i <= chunk.len() {
+                    ALPHABET[((bits >> (18 - 6 * i)) & 0x3F) as usize] as char
+                } else {
+                    '='
+                }
+            })
+        })
+        .collect()
+}
+
+/// Decode hexadecimal digits, whitespace being ignored
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let digits = hex
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            c.to_digit(16)
+                .map(|digit| digit as u8)
+                .ok_or_else(|| format!("Invalid hexadecimal digit {:?}", c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.len() % 2 != 0 {
+        return Err("Odd number of hexadecimal digits".to_string());
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect())
+}
+
+/// Decode base64, standard or URL-safe, padded or not, whitespace being ignored
+pub fn decode_base64(base64: &str) -> Result<Vec<u8>, String> {
+    let digits = base64
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+    let digits = digits
+        .strip_suffix(b"==")
+        .or_else(|| digits.strip_suffix(b"="))
+        .unwrap_or(&digits)
+        .iter()
+        .map(|c| {
+            base64_digit(*c).ok_or_else(|| format!("Invalid base64 character {:?}", *c as char))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.len() % 4 == 1 {
+        return Err("Truncated base64".to_string());
+    }
+    Ok(digits
+        .chunks(4)
+        .flat_map(|chunk| {
+            let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, digit)| {
+                bits | (u32::from(*digit) << (18 - 6 * i))
+            });
+            bits.to_be_bytes()[1..chunk.len()].to_vec()
+        })
+        .collect())
+}
+
+fn base64_digit(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocol::{basic, AMQPClass};
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex("01 ff\n0A"), Ok(vec![1, 255, 10]));
+        assert!(decode_hex("123").is_err());
+        assert!(decode_hex("0g").is_err());
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("aGVsbG8="), Ok(b"hello".to_vec()));
+        assert_eq!(decode_base64("aGVs\nbG8"), Ok(b"hello".to_vec()));
+        assert_eq!(decode_base64("aGVsbG8h"), Ok(b"hello!".to_vec()));
+        assert_eq!(decode_base64("-_8="), Ok(vec![251, 255]));
+        assert_eq!(decode_base64(""), Ok(Vec::new()));
+        assert!(decode_base64("aGVsb").is_err());
+        assert!(decode_base64("aGV*").is_err());
+    }
+
+    #[test]
+    fn test_parse_dump() {
+        let bytes = [
+            1, 0, 1, 0, 0, 0, 11, 0, 60, 0, 10, 0, 0, 0, 0, 0, 10, 0, 0xCE,
+        ];
+        for (dump, format) in [
+            (
+                &b"0100010000000b 003c000a00000000000a00ce\n"[..],
+                DumpFormat::Hex,
+            ),
+            (&b"AQABAAAACwA8AAoAAAAAAAoAzg=="[..], DumpFormat::Base64),
+            (&bytes[..], DumpFormat::Raw),
+        ] {
+            assert_eq!(DumpFormat::detect(dump), format);
+            assert_eq!(
+                decode_dump(dump, format)
+                    .and_then(|bytes| parse_dump(&bytes))
+                    .unwrap(),
+                vec![AMQPFrame::Method(
+                    1,
+                    AMQPClass::Basic(basic::AMQPMethod::Qos(basic::Qos {
+                        prefetch_count: 10,
+                        global: false,
+                    }))
+                )]
+            );
+        }
+        assert!(parse_dump(&bytes[..10]).is_err());
+        assert_eq!("base64".parse(), Ok(DumpFormat::Base64));
+    }
+
+    #[test]
+    fn test_encode_dump() {
+        for bytes in [&b""[..], b"h", b"he", b"hel", b"hello", &[0, 255, 127, 128]] {
+            for format in [DumpFormat::Hex, DumpFormat::Base64, DumpFormat::Raw] {
+                assert_eq!(
+                    decode_dump(&encode_dump(bytes, format), format),
+                    Ok(bytes.to_vec())
+                );
+            }
+        }
+        assert_eq!(encode_base64(b"hello"), "aGVsbG8=");
+        assert_eq!(encode_hex(&[0, 255, 10]), "00ff0a");
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_frames_from_json() {
+        let frames = frames_from_json(
+            r#"[
+                {"class": "basic", "method": "qos", "channel": 1, "arguments": {"prefetch_count": 10}},
+                {
+                    "class": "basic",
+                    "method": "publish",
+                    "channel": 1,
+                    "arguments": {"routing_key": "queue"},
+                    "properties": {"delivery_mode": 2, "headers": {"retries": 3}},
+                    "body": "aGVsbG8=",
+                    "body_format": "base64"
+                }
+            ]"#,
+        )
+        .unwrap();
+        let bytes = serialize_frames(&frames).unwrap();
+        assert_eq!(parse_dump(&bytes), Ok(frames.clone()));
+        assert_eq!(frames.len(), 4);
+        assert_eq!(
+            frames[1],
+            AMQPFrame::Method(
+                1,
+                AMQPClass::Basic(basic::AMQPMethod::Publish(basic::Publish {
+                    routing_key: "queue".into(),
+                    ..basic::Publish::default()
+                }))
+            )
+        );
+        let AMQPFrame::Header(_, _, header) = &frames[2] else {
+            panic!("Expected a content header, got {:?}", frames[2]);
+        };
+        assert_eq!(header.properties.delivery_mode(), &Some(2));
+        assert_eq!(
+            header
+                .properties
+                .headers()
+                .as_ref()
+                .and_then(|headers| headers.inner().get("retries")),
+            Some(&AMQPValue::LongLongInt(3))
+        );
+        assert_eq!(frames[3], AMQPFrame::Body(1, b"hello"[..].into()));
+        assert!(frames_from_json(r#"{"class": "basic", "method": "nope"}"#).is_err());
+        assert!(frames_from_json(
+            r#"{"class": "basic", "method": "qos", "arguments": {"prefetch_count": "ten"}}"#
+        )
+        .is_err());
+    }
+}
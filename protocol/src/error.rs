@@ -0,0 +1,195 @@
+#[cfg(feature = "tcp")]
+use crate::tcp::TlsError;
+use crate::{
+    frame::{GenError, ParserError, ProtocolVersion},
+    protocol::AMQPError,
+    types::codec::{DecodeError, EncodeError},
+};
+use std::{error, fmt, io};
+
+/// An error coming from any of the amq-protocol crates
+///
+/// The error types of the workspace convert into it, so that downstream crates only need to
+/// handle this one. The original error is available through `source()`.
+#[derive(Debug)]
+pub enum ProtocolError {
+    /// The AMQP URI is invalid
+    Uri(String),
+    /// A frame or a value failed to parse
+    Parsing(ParserError),
+    /// A frame or a value failed to serialize
+    Serialization(GenError),
+    /// A value failed to decode
+    Decode(DecodeError),
+    /// A value failed to encode
+    Encode(EncodeError),
+    /// The transport failed
+    Io(io::Error),
+    /// The TLS handshake failed
+    #[cfg(feature = "tcp")]
+    Tls(TlsError),
+    /// The peer closed the channel or the connection with an error
+    AMQP(AMQPError),
+    /// The peer or the caller broke the protocol, for example with an unexpected frame
+    Protocol(String),
+    /// The peer answered our protocol header with the one of the version it speaks
+    VersionMismatch {
+        /// The version we asked for
+        ours: ProtocolVersion,
+        /// The version the peer speaks
+        theirs: ProtocolVersion,
+    },
+}
+
+impl ProtocolError {
+    /// Wrap the error returned when parsing an AMQP URI
+    pub fn uri<E: Into<String>>(err: E) -> Self {
+        Self::Uri(err.into())
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uri(err) => write!(f, "invalid AMQP URI: {}", err),
+            Self::Parsing(err) => write!(f, "failed to parse: {}", err),
+            Self::Serialization(err) => write!(f, "failed to serialize: {}", err),
+            Self::Decode(err) => write!(f, "failed to decode: {}", err),
+            Self::Encode(err) => err.fmt(f),
+            Self::Io(err) => write!(f, "I/O error: {}", err),
+            #[cfg(feature = "tcp")]
+            Self::Tls(err) => err.fmt(f),
+            Self::AMQP(err) => err.fmt(f),
+            Self::Protocol(err) => write!(f, "protocol error: {}", err),
+            Self::VersionMismatch { ours, theirs } => write!(
+                f,
+                "protocol version mismatch: we asked for {}, the peer speaks {}",
+                ours, theirs
+            ),
+        }
+    }
+}
+
+impl error::Error for ProtocolError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Uri(_) | Self::Protocol(_) | Self::VersionMismatch { .. } => None,
+            Self::Parsing(err) => Some(err),
+            Self::Serialization(err) => Some(err),
+            Self::Decode(err) => Some(err),
+            Self::Encode(err) => Some(err),
+            Self::Io(err) => Some(err),
+            #[cfg(feature = "tcp")]
+            Self::Tls(err) => Some(err),
+            Self::AMQP(err) => Some(err),
+        }
+    }
+}
+
+impl From<ParserError> for ProtocolError {
+    fn from(err: ParserError) -> Self {
+        Self::Parsing(err)
+    }
+}
+
+impl From<GenError> for ProtocolError {
+    fn from(err: GenError) -> Self {
+        Self::Serialization(err)
+    }
+}
+
+impl From<DecodeError> for ProtocolError {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<EncodeError> for ProtocolError {
+    fn from(err: EncodeError) -> Self {
+        Self::Encode(err)
+    }
+}
+
+/// TLS failures wrapped in an io::Error by AMQPUriTcpExt are unwrapped into Tls
+impl From<io::Error> for ProtocolError {
+    fn from(err: io::Error) -> Self {
+        #[cfg(feature = "tcp")]
+        if TlsError::from_io_error(&err).is_some() {
+            let kind = err.kind();
+            return match err.into_inner().map(|inner| inner.downcast::<TlsError>()) {
+                Some(Ok(err)) => Self::Tls(*err),
+                /* Not reachable as we checked the inner error first */
+                Some(Err(inner)) => Self::Io(io::Error::new(kind, inner)),
+                None => Self::Io(kind.into()),
+            };
+        }
+        Self::Io(err)
+    }
+}
+
+#[cfg(feature = "tcp")]
+impl From<TlsError> for ProtocolError {
+    fn from(err: TlsError) -> Self {
+        Self::Tls(err)
+    }
+}
+
+impl From<AMQPError> for ProtocolError {
+    fn from(err: AMQPError) -> Self {
+        Self::AMQP(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        frame::parse_frame,
+        protocol::{AMQPErrorKind, AMQPSoftError},
+        uri::AMQPUri,
+    };
+    use std::error::Error;
+
+    fn parse(uri: &str, frame: &[u8]) -> Result<(), ProtocolError> {
+        uri.parse::<AMQPUri>().map_err(ProtocolError::uri)?;
+        parse_frame(frame)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert!(matches!(parse("foo", &[]), Err(ProtocolError::Uri(_))));
+        let err = parse("amqp://localhost", &[42; 8]).unwrap_err();
+        assert!(matches!(err, ProtocolError::Parsing(_)));
+        assert!(err.source().is_some());
+        let err = ProtocolError::from(DecodeError::Incomplete(Some(2)));
+        assert_eq!(
+            err.to_string(),
+            "failed to decode: incomplete input, 2 more bytes needed"
+        );
+        assert!(err.source().is_some());
+        let err = ProtocolError::from(AMQPError::new(
+            AMQPErrorKind::Soft(AMQPSoftError::NOTFOUND),
+            "no queue".into(),
+        ));
+        assert_eq!(err.to_string(), "AMQP soft error: NOT-FOUND: no queue");
+        assert!(err.source().is_some());
+        let err = ProtocolError::from(io::Error::from(io::ErrorKind::ConnectionReset));
+        assert!(matches!(err, ProtocolError::Io(_)));
+        #[cfg(feature = "tcp")]
+        {
+            let tls = crate::tcp::TlsError::classify(
+                "rabbit.local",
+                io::Error::other("invalid peer certificate: UnknownIssuer"),
+            );
+            let err = ProtocolError::from(io::Error::from(tls));
+            assert!(matches!(err, ProtocolError::Tls(_)));
+            assert!(err
+                .to_string()
+                .starts_with("TLS handshake with rabbit.local failed"));
+        }
+        assert!(ProtocolError::Protocol("unexpected frame".to_string())
+            .source()
+            .is_none());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    error::ProtocolError,
+    frame::{parse_frame_from_buf, AMQPFrame},
+    types::FrameSize,
+};
+use bytes::{Buf, BytesMut};
+
+/// Reassemble frames from the chunks of bytes received from the network
+///
+/// Chunks are cut wherever the transport sees fit: a chunk can end in the middle of a frame
+/// header or payload, or hold several frames. The accumulator buffers the bytes it is fed until
+/// they make complete frames.
+#[derive(Clone, Debug, Default)]
+pub struct FrameAccumulator {
+    buffer: BytesMut,
+    frame_max: FrameSize,
+}
+
+impl FrameAccumulator {
+    /// Create an empty accumulator accepting frames of any size
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject frames larger than frame_max as soon as their header is received, instead of
+    /// buffering their payload. 0 means no limit.
+    pub fn with_frame_max(mut self, frame_max: FrameSize) -> Self {
+        self.frame_max = frame_max;
+        self
+    }
+
+    /// Change the frame_max, for example once it has been negotiated
+    pub fn set_frame_max(&mut self, frame_max: FrameSize) {
+        self.frame_max = frame_max;
+    }
+
+    /// Append a chunk of received bytes
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Get the next complete frame, None if more bytes are needed
+    pub fn next_frame(&mut self) -> Result<Option<AMQPFrame>, ProtocolError> {
+        self.check_frame_max()?;
+        Ok(parse_frame_from_buf(&mut self.buffer)?)
+    }
+
+    /// Append a chunk of received bytes and get all the frames completed by it
+    pub fn feed_frames(&mut self, chunk: &[u8]) -> Result<Vec<AMQPFrame>, ProtocolError> {
+        self.feed(chunk);
+        let mut frames = Vec::new();
+        while let Some(frame) = self.next_frame()? {
+            frames.push(frame);
+        }
+        Ok(frames)
+    }
+
+    /// The number of bytes received which are not part of a complete frame yet
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Whether we are between two frames
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    fn check_frame_max(&self) -> Result<(), ProtocolError> {
+        /* The protocol header starts with 'A' and has no size */
+        if self.frame_max == 0 || self.buffer.len() < 7 || self.buffer[0] == b'A' {
+            return Ok(());
+        }
+        let frame_size = u64::from((&self.buffer[3..7]).get_u32()) + 8;
+        if frame_size > u64::from(self.frame_max) {
+            return Err(ProtocolError::Protocol(format!(
+                "Frame of {} bytes exceeds the frame_max of {} bytes",
+                frame_size, self.frame_max
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::{gen_frame, Bytes, ProtocolVersion};
+
+    fn serialize(frames: &[AMQPFrame]) -> Vec<u8> {
+        frames.iter().fold(Vec::new(), |buffer, frame| {
+            cookie_factory::gen_simple(gen_frame(frame), buffer).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_chunk_boundaries() {
+        let frames = vec![
+            AMQPFrame::ProtocolHeader(ProtocolVersion::amqp_0_9_1()),
+            AMQPFrame::Heartbeat(0),
+            AMQPFrame::Body(1, Bytes::from_static(b"hello world")),
+            AMQPFrame::Heartbeat(0),
+        ];
+        let bytes = serialize(&frames);
+        for chunk_size in 1..=bytes.len() {
+            let mut accumulator = FrameAccumulator::new();
+            let mut received = Vec::new();
+            for chunk in bytes.chunks(chunk_size) {
+                received.extend(accumulator.feed_frames(chunk).unwrap());
+            }
+            assert_eq!(received, frames, "chunks of {} bytes", chunk_size);
+            assert!(accumulator.is_empty());
+        }
+
+        let mut accumulator = FrameAccumulator::new();
+        accumulator.feed(&bytes[..20]);
+        assert_eq!(accumulator.next_frame().unwrap(), Some(frames[0].clone()));
+        assert_eq!(accumulator.next_frame().unwrap(), Some(frames[1].clone()));
+        assert_eq!(accumulator.next_frame().unwrap(), None);
+        assert_eq!(accumulator.buffered(), 4);
+    }
+
+    #[test]
+    fn test_frame_max() {
+        let body = serialize(&[AMQPFrame::Body(1, Bytes::from(vec![0; 100]))]);
+        let mut accumulator = FrameAccumulator::new().with_frame_max(100);
+        assert_eq!(accumulator.feed_frames(&body[..6]).unwrap(), Vec::new());
+        assert!(matches!(
+            accumulator.feed_frames(&body[6..7]),
+            Err(ProtocolError::Protocol(_))
+        ));
+        accumulator.set_frame_max(108);
+        assert_eq!(accumulator.feed_frames(&body[7..]).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    frame::{parsing::*, AMQPContentHeader, AMQPFrame, AMQPFrameType, AMQPRawFrame},
+    protocol::{basic::parse_properties_in, *},
+    types::{arena::Bump, parsing::*, *},
+};
+use nom::{
+    combinator::{all_consuming, flat_map, map, map_res},
+    error::context,
+    Parser,
+};
+
+/// An AMQPFrame whose content header and body are allocated in an arena
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArenaFrame<'a> {
+    /// Protocol header frame
+    ProtocolHeader(ProtocolVersion),
+    /// Method call
+    Method(ChannelId, AMQPClass),
+    /// Content header
+    Header(ChannelId, ArenaContentHeader<'a>),
+    /// Content body
+    Body(ChannelId, &'a [u8]),
+    /// Heartbeat frame
+    Heartbeat(ChannelId),
+}
+
+impl ArenaFrame<'_> {
+    /// Copy the frame out of the arena
+    pub fn to_frame(&self) -> AMQPFrame {
+        match self {
+            ArenaFrame::ProtocolHeader(version) => AMQPFrame::ProtocolHeader(*version),
+            ArenaFrame::Method(channel_id, method) => {
+                AMQPFrame::Method(*channel_id, method.clone())
+            }
+            ArenaFrame::Header(channel_id, header) => AMQPFrame::Header(
+                *channel_id,
+                header.class_id,
+                Box::new(header.to_content_header()),
+            ),
+            ArenaFrame::Body(channel_id, payload) => {
+                AMQPFrame::Body(*channel_id, payload.to_vec().into())
+            }
+            ArenaFrame::Heartbeat(channel_id) => AMQPFrame::Heartbeat(*channel_id),
+        }
+    }
+}
+
+/// A content header whose properties are allocated in an arena
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArenaContentHeader<'a> {
+    /// The class of content
+    pub class_id: Identifier,
+    /// The size of the content's body
+    pub body_size: PayloadSize,
+    /// The AMQP properties associated with the content
+    pub properties: basic::ArenaProperties<'a>,
+}
+
+impl ArenaContentHeader<'_> {
+    /// Copy the content header out of the arena
+    pub fn to_content_header(&self) -> AMQPContentHeader {
+        AMQPContentHeader {
+            class_id: self.class_id,
+            body_size: self.body_size,
+            properties: self.properties.to_properties(),
+        }
+    }
+}
+
+/// Parse a full AMQP Frame using the given options, allocating its content header and body in
+/// the given arena
+pub fn parse_frame_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+    options: ParsingOptions,
+) -> ParserResult<&'i [u8], ArenaFrame<'a>> {
+    context(
+        "parse_frame",
+        flat_map(parse_frame_type, move |frame_type| {
+            move |i: &'i [u8]| match frame_type {
+                AMQPFrameType::ProtocolHeader => {
+                    map(parse_protocol_header, ArenaFrame::ProtocolHeader).parse(i)
+                }
+                frame_type => map_res(
+                    parse_raw_frame(frame_type),
+                    |AMQPRawFrame {
+                         channel_id,
+                         frame_type,
+                         payload,
+                     }: AMQPRawFrame<&'i [u8]>| match frame_type {
+                        AMQPFrameType::ProtocolHeader => {
+                            Ok(ArenaFrame::ProtocolHeader(ProtocolVersion::amqp_0_9_1()))
+                        }
+                        AMQPFrameType::Method => {
+                            all_consuming(|i: &'i [u8]| parse_class_with(i, options))
+                                .parse(payload)
+                                .map(|(_, m)| ArenaFrame::Method(channel_id, m))
+                        }
+                        AMQPFrameType::Header => {
+                            all_consuming(|i: &'i [u8]| parse_content_header_in(arena, i, options))
+                                .parse(payload)
+                                .map(|(_, h)| ArenaFrame::Header(channel_id, h))
+                        }
+                        AMQPFrameType::Body => Ok(ArenaFrame::Body(
+                            channel_id,
+                            arena.alloc_slice_copy(payload),
+                        )),
+                        AMQPFrameType::Heartbeat => Ok(ArenaFrame::Heartbeat(channel_id)),
+                    },
+                )
+                .parse(i),
+            }
+        }),
+    )
+    .parse(i)
+}
+
+/// Parse a content header frame using the given options, allocating its properties in the given
+/// arena
+pub fn parse_content_header_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+    options: ParsingOptions,
+) -> ParserResult<&'i [u8], ArenaContentHeader<'a>> {
+    context(
+        "parse_content_header",
+        map(
+            (
+                parse_id,
+                parse_short_uint,
+                parse_long_long_uint,
+                context("parse_properties", |i: &'i [u8]| {
+                    parse_properties_in(arena, i, options)
+                }),
+            ),
+            |(class_id, _weight, body_size, properties)| ArenaContentHeader {
+                class_id,
+                body_size,
+                properties,
+            },
+        ),
+    )
+    .parse(i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::gen_frame;
+
+    #[test]
+    fn test_parse_frame_in() {
+        let mut headers = FieldTable::default();
+        headers.insert("k".into(), AMQPValue::LongString(b"v\xff".to_vec().into()));
+        let header = AMQPFrame::Header(
+            1,
+            60,
+            Box::new(AMQPContentHeader {
+                class_id: 60,
+                body_size: 4,
+                properties: basic::AMQPProperties::default()
+                    .with_content_type("text/plain".into())
+                    .with_headers(headers),
+            }),
+        );
+        let body = AMQPFrame::Body(1, b"body".to_vec().into());
+        let options = |utf8| ParsingOptions {
+            utf8,
+            ..ParsingOptions::default()
+        };
+        let arena = Bump::new();
+        for frame in [header.clone(), body] {
+            let bytes = cookie_factory::gen_simple(gen_frame(&frame), Vec::new()).unwrap();
+            let (rest, parsed) = parse_frame_in(&arena, &bytes, options(Utf8Policy::Raw)).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(parsed.to_frame(), frame);
+            assert_eq!(
+                parse_frame_in(&arena, &bytes, options(Utf8Policy::Lossy))
+                    .map(|(rest, parsed)| (rest, parsed.to_frame())),
+                parse_frame_with(&bytes[..], options(Utf8Policy::Lossy))
+            );
+        }
+        let bytes = cookie_factory::gen_simple(gen_frame(&header), Vec::new()).unwrap();
+        assert!(parse_frame_in(&arena, &bytes, options(Utf8Policy::Strict)).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    frame::{AMQPFrame, FrameAccumulator},
+    types::FrameSize,
+};
+use std::{collections::HashMap, hash::Hash};
+
+/// Reassemble frames from the interleaved traffic of several connections
+///
+/// Each chunk comes with the identifier of the connection it was captured on, and is fed to the
+/// FrameAccumulator of this connection, created on its first chunk. Frames are yielded along
+/// with the identifier of their connection.
+#[derive(Clone, Debug)]
+pub struct FrameDemux<C> {
+    connections: HashMap<C, FrameAccumulator>,
+    frame_max: FrameSize,
+}
+
+impl<C> Default for FrameDemux<C> {
+    fn default() -> Self {
+        Self {
+            connections: HashMap::new(),
+            frame_max: 0,
+        }
+    }
+}
+
+impl<C: Clone + Eq + Hash> FrameDemux<C> {
+    /// Create a demultiplexer accepting frames of any size
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give this frame_max to the accumulators of new connections. 0 means no limit.
+    pub fn with_frame_max(mut self, frame_max: FrameSize) -> Self {
+        self.frame_max = frame_max;
+        self
+    }
+
+    /// Append a chunk captured on the given connection and get all the frames completed by it
+    ///
+    /// An error leaves the bytes of the connection buffered: the connection should be closed as
+    /// there is no way to find the start of the next frame.
+    pub fn feed(&mut self, connection: C, chunk: &[u8]) -> Result<Vec<(C, AMQPFrame)>, String> {
+        let frame_max = self.frame_max;
+        let frames = self
+            .connections
+            .entry(connection.clone())
+            .or_insert_with(|| FrameAccumulator::new().with_frame_max(frame_max))
+            .feed_frames(chunk)
+            .map_err(|e| e.to_string())?;
+        Ok(frames
+            .into_iter()
+            .map(|frame| (connection.clone(), frame))
+            .collect())
+    }
+
+    /// Change the frame_max of a connection, for example once it has been negotiated
+    pub fn set_frame_max(&mut self, connection: &C, frame_max: FrameSize) {
+        if let Some(accumulator) = self.connections.get_mut(connection) {
+            accumulator.set_frame_max(frame_max);
+        }
+    }
+
+    /// Forget about a connection, returning its accumulator to inspect what was left buffered
+    pub fn close(&mut self, connection: &C) -> Option<FrameAccumulator> {
+        self.connections.remove(connection)
+    }
+
+    /// The accumulator of a connection
+    pub fn connection(&self, connection: &C) -> Option<&FrameAccumulator> {
+        self.connections.get(connection)
+    }
+
+    /// The number of connections being tracked
+    pub fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Whether no connection is being tracked
+    pub fn is_empty(&self) -> bool {
+        self.connections.is_empty()
+    }
+
+    /// The number of bytes received on all the connections which are not part of a complete
+    /// frame yet
+    pub fn buffered(&self) -> usize {
+        self.connections
+            .values()
+            .map(FrameAccumulator::buffered)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::{gen_frame, Bytes};
+
+    #[test]
+    fn test_demux() {
+        let first = cookie_factory::gen_simple(
+            gen_frame(&AMQPFrame::Body(1, Bytes::from_static(b"first"))),
+            Vec::new(),
+        )
+        .unwrap();
+        let second =
+            cookie_factory::gen_simple(gen_frame(&AMQPFrame::Heartbeat(0)), Vec::new()).unwrap();
+        let mut demux = FrameDemux::new().with_frame_max(64);
+        assert_eq!(demux.feed("a", &first[..5]), Ok(Vec::new()));
+        assert_eq!(
+            demux.feed("b", &second),
+            Ok(vec![("b", AMQPFrame::Heartbeat(0))])
+        );
+        assert_eq!(demux.buffered(), 5);
+        assert_eq!(
+            demux.feed("a", &first[5..]),
+            Ok(vec![(
+                "a",
+                AMQPFrame::Body(1, Bytes::from_static(b"first"))
+            )])
+        );
+        assert_eq!(demux.len(), 2);
+        assert!(demux.close(&"b").unwrap().is_empty());
+        assert!(demux.connection(&"b").is_none());
+
+        demux.set_frame_max(&"a", 8);
+        assert!(demux.feed("a", &first).is_err());
+        assert!(demux.feed("c", &first).is_ok());
+    }
+}
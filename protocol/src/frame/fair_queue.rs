@@ -0,0 +1,182 @@
+use crate::{
+    frame::AMQPFrame,
+    protocol::{connection, AMQPClass},
+    types::ChannelId,
+};
+use std::collections::{HashMap, VecDeque};
+
+/// A queue of outgoing frames, interleaving the frames from the different channels fairly
+///
+/// Frames are sent in order for each channel, but each channel only gets to send one frame
+/// at a time in a round-robin fashion so that a busy channel cannot starve the others.
+/// Heartbeats, protocol headers and connection close frames jump the queue.
+/// When paused, only those priority frames get emitted.
+#[derive(Clone, Debug, Default)]
+pub struct FrameQueue {
+    priority: VecDeque<AMQPFrame>,
+    channels: HashMap<ChannelId, VecDeque<AMQPFrame>>,
+    ready: VecDeque<ChannelId>,
+    len: usize,
+    paused: bool,
+}
+
+impl FrameQueue {
+    /// Create an empty FrameQueue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a frame for sending
+    pub fn push(&mut self, frame: AMQPFrame) {
+        self.len += 1;
+        let channel_id = match &frame {
+            AMQPFrame::ProtocolHeader(_)
+            | AMQPFrame::Heartbeat(_)
+            | AMQPFrame::Method(
+                _,
+                AMQPClass::Connection(
+                    connection::AMQPMethod::Close(_) | connection::AMQPMethod::CloseOk(_),
+                ),
+            ) => {
+                self.priority.push_back(frame);
+                return;
+            }
+            AMQPFrame::Method(channel_id, _)
+            | AMQPFrame::Header(channel_id, ..)
+            | AMQPFrame::Body(channel_id, _) => *channel_id,
+        };
+        let frames = self.channels.entry(channel_id).or_default();
+        if frames.is_empty() {
+            self.ready.push_back(channel_id);
+        }
+        frames.push_back(frame);
+    }
+
+    /// Get the next frame to send, if any
+    pub fn pop(&mut self) -> Option<AMQPFrame> {
+        if let Some(frame) = self.priority.pop_front() {
+            self.len -= 1;
+            return Some(frame);
+        }
+        if self.paused {
+            return None;
+        }
+        let channel_id = self.ready.pop_front()?;
+        let frames = self.channels.get_mut(&channel_id)?;
+        let frame = frames.pop_front();
+        if frames.is_empty() {
+            self.channels.remove(&channel_id);
+        } else {
+            self.ready.push_back(channel_id);
+        }
+        if frame.is_some() {
+            self.len -= 1;
+        }
+        frame
+    }
+
+    /// Stop emitting regular frames until resumed, priority frames are still emitted
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume emitting regular frames
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Whether the emission of regular frames is paused or not
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Drop all the pending frames for the given channel, returning them
+    pub fn drain_channel(&mut self, channel_id: ChannelId) -> Vec<AMQPFrame> {
+        let frames = self.channels.remove(&channel_id).unwrap_or_default();
+        self.ready.retain(|id| *id != channel_id);
+        self.len -= frames.len();
+        frames.into()
+    }
+
+    /// The number of frames pending for the given channel, excluding priority frames
+    pub fn channel_len(&self, channel_id: ChannelId) -> usize {
+        self.channels.get(&channel_id).map_or(0, VecDeque::len)
+    }
+
+    /// The total number of pending frames
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no pending frames or not
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Extend<AMQPFrame> for FrameQueue {
+    fn extend<T: IntoIterator<Item = AMQPFrame>>(&mut self, iter: T) {
+        for frame in iter {
+            self.push(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::Bytes;
+
+    fn channels(queue: &mut FrameQueue) -> Vec<ChannelId> {
+        std::iter::from_fn(|| queue.pop())
+            .map(|frame| match frame {
+                AMQPFrame::Body(channel_id, _) | AMQPFrame::Heartbeat(channel_id) => channel_id,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fair_interleaving() {
+        let mut queue = FrameQueue::new();
+        queue.extend((0..3).map(|_| AMQPFrame::Body(1, Bytes::new())));
+        queue.push(AMQPFrame::Body(2, Bytes::new()));
+        queue.push(AMQPFrame::Body(3, Bytes::new()));
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.channel_len(1), 3);
+        assert_eq!(channels(&mut queue), vec![1, 2, 3, 1, 1]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_heartbeat_jumps_the_queue() {
+        let mut queue = FrameQueue::new();
+        queue.push(AMQPFrame::Body(1, Bytes::new()));
+        queue.push(AMQPFrame::Body(2, Bytes::new()));
+        queue.push(AMQPFrame::Heartbeat(0));
+        assert_eq!(channels(&mut queue), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_pause() {
+        let mut queue = FrameQueue::new();
+        queue.push(AMQPFrame::Body(1, Bytes::new()));
+        queue.pause();
+        queue.push(AMQPFrame::Heartbeat(0));
+        assert_eq!(channels(&mut queue), vec![0]);
+        assert_eq!(queue.len(), 1);
+        queue.resume();
+        assert_eq!(channels(&mut queue), vec![1]);
+    }
+
+    #[test]
+    fn test_drain_channel() {
+        let mut queue = FrameQueue::new();
+        queue.push(AMQPFrame::Body(1, Bytes::new()));
+        queue.push(AMQPFrame::Body(2, Bytes::new()));
+        queue.push(AMQPFrame::Body(1, Bytes::new()));
+        assert_eq!(queue.drain_channel(1).len(), 2);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(channels(&mut queue), vec![2]);
+    }
+}
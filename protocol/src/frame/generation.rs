@@ -1,14 +1,132 @@
+#[cfg(feature = "tracing")]
+use crate::frame::{metrics, TracingMetrics};
 use crate::{
-    frame::{AMQPFrame, ProtocolVersion},
+    frame::{AMQPContentHeader, AMQPFrame, Bytes, ProtocolVersion},
     protocol::{basic::gen_properties, *},
     types::{generation::*, *},
 };
-use cookie_factory::{combinator::slice, sequence::tuple};
-use std::io::Write;
+use cookie_factory::{combinator::slice, gen_simple, sequence::tuple};
+use std::{
+    io::{self, IoSlice, Write},
+    ops::Range,
+};
 
 /// Serialize a frame in the given buffer
 pub fn gen_frame<'a, W: Write + BackToTheBuffer + 'a>(
     frame: &'a AMQPFrame,
+) -> impl SerializeFn<W> + 'a {
+    #[cfg(feature = "tracing")]
+    let gen = metrics::report_generated_frame(frame, gen_full_frame(frame), &TracingMetrics);
+    #[cfg(not(feature = "tracing"))]
+    let gen = gen_full_frame(frame);
+    gen
+}
+
+/// Serialize a frame in the given buffer, failing if it would be larger than frame_max.
+/// A frame_max of 0 means no limit
+pub fn gen_frame_with_frame_max<'a, W: Write + BackToTheBuffer + 'a>(
+    frame: &'a AMQPFrame,
+    frame_max: FrameSize,
+) -> impl SerializeFn<W> + 'a {
+    move |x| {
+        if frame_max == 0 {
+            return gen_frame(frame)(x);
+        }
+        match frame {
+            /* Their size is only known once serialized, so check it before writing the bytes */
+            AMQPFrame::Method(..) | AMQPFrame::Header(..) => {
+                let bytes = gen_simple(gen_frame(frame), Vec::new())?;
+                check_frame_max("Frame", bytes.len() as u64, frame_max)?;
+                slice(bytes)(x)
+            }
+            frame => {
+                check_frame_max("Frame", frame_size(frame)?, frame_max)?;
+                gen_frame(frame)(x)
+            }
+        }
+    }
+}
+
+/* Frame type, channel id and payload size before the payload, frame end octet after it */
+const FRAME_OVERHEAD: u64 = 8;
+
+/// The size of the serialized frame
+pub fn frame_size(frame: &AMQPFrame) -> Result<u64, GenError> {
+    match frame {
+        AMQPFrame::ProtocolHeader(_) => Ok(metadata::NAME.len() as u64 + 4),
+        AMQPFrame::Heartbeat(_) => Ok(FRAME_OVERHEAD),
+        AMQPFrame::Body(_, data) => Ok(data.len() as u64 + FRAME_OVERHEAD),
+        /* class id, weight and body size before the properties */
+        AMQPFrame::Header(_, _, header) => {
+            Ok(properties_size(&header.properties)? + 12 + FRAME_OVERHEAD)
+        }
+        frame => Ok(gen_simple(gen_full_frame(frame), Vec::new())?.len() as u64),
+    }
+}
+
+fn properties_size(properties: &basic::AMQPProperties) -> Result<u64, GenError> {
+    Ok(gen_simple(gen_properties(properties), Vec::new())?.len() as u64)
+}
+
+fn check_frame_max(kind: &str, size: u64, frame_max: FrameSize) -> Result<(), GenError> {
+    if frame_max != 0 && size > frame_max.into() {
+        return Err(GenError::IoError(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{} of {} bytes exceeds the frame_max of {} bytes",
+                kind, size, frame_max
+            ),
+        )));
+    }
+    Ok(())
+}
+
+/// Split a content into its header frame and as many body frames as needed to respect
+/// frame_max. A frame_max of 0 means no limit.
+///
+/// Fails if frame_max leaves no room for a body payload or if the header frame itself exceeds it.
+pub fn split_content(
+    channel_id: ChannelId,
+    properties: basic::AMQPProperties,
+    body: Bytes,
+    frame_max: FrameSize,
+) -> Result<Vec<AMQPFrame>, GenError> {
+    if frame_max != 0 && u64::from(frame_max) <= FRAME_OVERHEAD {
+        return Err(GenError::IoError(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "A frame_max of {} bytes leaves no room for the content body",
+                frame_max
+            ),
+        )));
+    }
+    let class_id = properties.class_id();
+    let header = AMQPFrame::Header(
+        channel_id,
+        class_id,
+        Box::new(AMQPContentHeader {
+            class_id,
+            body_size: body.len() as PayloadSize,
+            properties,
+        }),
+    );
+    check_frame_max("Content header frame", frame_size(&header)?, frame_max)?;
+    let chunk_size = match frame_max {
+        0 => body.len().max(1),
+        frame_max => (u64::from(frame_max) - FRAME_OVERHEAD) as usize,
+    };
+    let mut frames = vec![header];
+    let mut offset = 0;
+    while offset < body.len() {
+        let end = body.len().min(offset + chunk_size);
+        frames.push(AMQPFrame::Body(channel_id, body.slice(offset..end)));
+        offset = end;
+    }
+    Ok(frames)
+}
+
+fn gen_full_frame<'a, W: Write + BackToTheBuffer + 'a>(
+    frame: &'a AMQPFrame,
 ) -> impl SerializeFn<W> + 'a {
     move |x| match frame {
         AMQPFrame::ProtocolHeader(version) => gen_protocol_header(*version)(x),
@@ -91,3 +209,253 @@ fn gen_content_body_frame<'a, W: Write + 'a>(
         gen_short_short_uint(constants::FRAME_END),
     ))
 }
+
+/// Frames serialized as a list of buffers for use with `write_vectored`.
+///
+/// The payloads of the body frames are borrowed instead of being copied along with the rest
+/// of the frames.
+#[derive(Clone, Debug, Default)]
+pub struct VectoredFrames<'a> {
+    buffer: Vec<u8>,
+    chunks: Vec<Chunk<'a>>,
+}
+
+#[derive(Clone, Debug)]
+enum Chunk<'a> {
+    Buffer(Range<usize>),
+    Payload(&'a [u8]),
+}
+
+impl<'a> VectoredFrames<'a> {
+    /// Create an empty list of frames
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialize a frame, borrowing its payload if it's a body frame
+    pub fn push(&mut self, frame: &'a AMQPFrame) -> Result<(), GenError> {
+        match frame {
+            AMQPFrame::Body(channel_id, data) => self.push_body(*channel_id, data),
+            frame => self.gen(gen_frame(frame)),
+        }
+    }
+
+    /// Serialize a body frame borrowing the given payload
+    pub fn push_body(&mut self, channel_id: ChannelId, payload: &'a [u8]) -> Result<(), GenError> {
+        self.gen(tuple((
+            gen_short_short_uint(constants::FRAME_BODY),
+            gen_id(channel_id),
+            gen_long_uint(payload.len() as ChunkSize),
+        )))?;
+        if !payload.is_empty() {
+            self.chunks.push(Chunk::Payload(payload));
+        }
+        self.gen(gen_short_short_uint(constants::FRAME_END))
+    }
+
+    /// The buffers to write, in order
+    pub fn io_slices(&self) -> Vec<IoSlice<'_>> {
+        self.io_slices_from(0)
+    }
+
+    /// The total size of the serialized frames
+    pub fn len(&self) -> usize {
+        self.chunks
+            .iter()
+            .map(|chunk| self.chunk(chunk).len())
+            .sum()
+    }
+
+    /// Whether there is nothing to write or not
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Write all the frames using `write_vectored`, handling partial writes
+    pub fn write_all_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let len = self.len();
+        let mut written = 0;
+        while written < len {
+            match writer.write_vectored(&self.io_slices_from(written)) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => written += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    fn gen<F: SerializeFn<Vec<u8>>>(&mut self, f: F) -> Result<(), GenError> {
+        let start = self.buffer.len();
+        self.buffer = gen_simple(f, std::mem::take(&mut self.buffer))?;
+        let end = self.buffer.len();
+        match self.chunks.last_mut() {
+            Some(Chunk::Buffer(range)) if range.end == start => range.end = end,
+            _ => self.chunks.push(Chunk::Buffer(start..end)),
+        }
+        Ok(())
+    }
+
+    fn chunk<'b>(&'b self, chunk: &Chunk<'b>) -> &'b [u8] {
+        match chunk {
+            Chunk::Buffer(range) => &self.buffer[range.clone()],
+            Chunk::Payload(payload) => payload,
+        }
+    }
+
+    fn io_slices_from(&self, mut skip: usize) -> Vec<IoSlice<'_>> {
+        let mut slices = Vec::with_capacity(self.chunks.len());
+        for chunk in &self.chunks {
+            let chunk = self.chunk(chunk);
+            if skip >= chunk.len() {
+                skip -= chunk.len();
+            } else {
+                slices.push(IoSlice::new(&chunk[skip..]));
+                skip = 0;
+            }
+        }
+        slices
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frame_max() {
+        let frame = AMQPFrame::Body(1, Bytes::from_static(&[0; 100]));
+        assert_eq!(frame_size(&frame).unwrap(), 108);
+        assert!(gen_simple(gen_frame_with_frame_max(&frame, 108), Vec::new()).is_ok());
+        assert!(gen_simple(gen_frame_with_frame_max(&frame, 0), Vec::new()).is_ok());
+        assert!(gen_simple(gen_frame_with_frame_max(&frame, 107), Vec::new()).is_err());
+        let method = AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::Publish(basic::Publish {
+                routing_key: "key".into(),
+                ..basic::Publish::default()
+            })),
+        );
+        assert_eq!(
+            frame_size(&method).unwrap(),
+            gen_simple(gen_frame(&method), Vec::new()).unwrap().len() as u64
+        );
+    }
+
+    #[test]
+    fn test_frame_size() {
+        let frames = [
+            AMQPFrame::ProtocolHeader(ProtocolVersion::amqp_0_9_1()),
+            AMQPFrame::Heartbeat(0),
+            AMQPFrame::Header(
+                1,
+                60,
+                Box::new(AMQPContentHeader {
+                    class_id: 60,
+                    body_size: 11,
+                    properties: basic::AMQPProperties::default()
+                        .with_content_type("text/plain".into())
+                        .with_priority(3),
+                }),
+            ),
+        ];
+        for frame in &frames {
+            assert_eq!(
+                frame_size(frame).unwrap(),
+                gen_simple(gen_frame(frame), Vec::new()).unwrap().len() as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_content() {
+        let frames = split_content(
+            1,
+            basic::AMQPProperties::default(),
+            Bytes::from_static(&[42; 25]),
+            22,
+        )
+        .unwrap();
+        assert_eq!(frames.len(), 3);
+        assert!(matches!(&frames[0], AMQPFrame::Header(1, 60, header) if header.class_id == 60));
+        assert_eq!(frames[2], AMQPFrame::Body(1, Bytes::from_static(&[42; 11])));
+        assert!(frames.iter().all(|frame| frame_size(frame).unwrap() <= 22));
+        assert_eq!(
+            split_content(1, basic::AMQPProperties::default(), Bytes::new(), 0)
+                .unwrap()
+                .len(),
+            1
+        );
+        for frame_max in 1..=8 {
+            assert!(split_content(
+                1,
+                basic::AMQPProperties::default(),
+                Bytes::from_static(&[42; 25]),
+                frame_max
+            )
+            .is_err());
+        }
+        /* The 22 bytes header frame doesn't fit */
+        assert!(split_content(
+            1,
+            basic::AMQPProperties::default(),
+            Bytes::from_static(&[42; 25]),
+            21
+        )
+        .is_err());
+    }
+
+    /* Only accepts a few bytes at a time to check the handling of partial writes */
+    struct SlowWriter(Vec<u8>);
+
+    impl Write for SlowWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let len = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_vectored_frames() {
+        let frames = vec![
+            AMQPFrame::Heartbeat(0),
+            AMQPFrame::Method(
+                1,
+                AMQPClass::Basic(basic::AMQPMethod::Publish(basic::Publish {
+                    exchange: "ex".into(),
+                    routing_key: "rk".into(),
+                    mandatory: false,
+                    immediate: false,
+                })),
+            ),
+            AMQPFrame::Header(
+                1,
+                60,
+                Box::new(AMQPContentHeader {
+                    class_id: 60,
+                    body_size: 11,
+                    properties: basic::AMQPProperties::default(),
+                }),
+            ),
+            AMQPFrame::Body(1, Bytes::from_static(b"hello world")),
+            AMQPFrame::Body(1, Bytes::new()),
+        ];
+        let mut expected = Vec::new();
+        let mut vectored = VectoredFrames::new();
+        for frame in &frames {
+            expected = gen_simple(gen_frame(frame), expected).unwrap();
+            vectored.push(frame).unwrap();
+        }
+        assert_eq!(vectored.len(), expected.len());
+        assert_eq!(vectored.io_slices().len(), 3);
+        let mut writer = SlowWriter(Vec::new());
+        vectored.write_all_to(&mut writer).unwrap();
+        assert_eq!(writer.0, expected);
+    }
+}
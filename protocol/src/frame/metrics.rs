@@ -0,0 +1,226 @@
+use crate::{
+    frame::{gen_frame, parse_frame, parsing::traits::ParsableInput, AMQPFrame, AMQPFrameType},
+    types::{
+        generation::{BackToTheBuffer, SerializeFn},
+        parsing::{ParserError, ParserResult},
+        Identifier,
+    },
+};
+use std::{collections::HashMap, io::Write, sync::Mutex};
+
+/// Hooks the frame parser and serializer report into
+///
+/// All the methods do nothing by default.
+pub trait Metrics {
+    /// A frame of `size` bytes has been parsed
+    fn frame_parsed(&self, _frame: &AMQPFrame, _size: usize) {}
+    /// A frame of `size` bytes has been serialized
+    fn frame_generated(&self, _frame: &AMQPFrame, _size: usize) {}
+    /// Parsing a frame failed, incomplete input isn't reported
+    fn parse_error(&self, _error: &ParserError) {}
+}
+
+/// Parse a full AMQP Frame, reporting into the given metrics
+pub fn parse_frame_with_metrics<I: ParsableInput>(
+    i: I,
+    metrics: &dyn Metrics,
+) -> ParserResult<I, AMQPFrame> {
+    report_parsed_frame(i, parse_frame, metrics)
+}
+
+/// Serialize a frame in the given buffer, reporting into the given metrics
+pub fn gen_frame_with_metrics<'a, W: Write + BackToTheBuffer + 'a>(
+    frame: &'a AMQPFrame,
+    metrics: &'a dyn Metrics,
+) -> impl SerializeFn<W> + 'a {
+    report_generated_frame(frame, gen_frame(frame), metrics)
+}
+
+pub(crate) fn report_parsed_frame<I: ParsableInput>(
+    i: I,
+    parser: impl FnOnce(I) -> ParserResult<I, AMQPFrame>,
+    metrics: &dyn Metrics,
+) -> ParserResult<I, AMQPFrame> {
+    let len = i.input_len();
+    let res = parser(i);
+    match &res {
+        Ok((rest, frame)) => metrics.frame_parsed(frame, len - rest.input_len()),
+        Err(nom::Err::Incomplete(_)) => {}
+        Err(error) => metrics.parse_error(error),
+    }
+    res
+}
+
+pub(crate) fn report_generated_frame<'a, W: Write + 'a>(
+    frame: &'a AMQPFrame,
+    gen: impl SerializeFn<W> + 'a,
+    metrics: &'a dyn Metrics,
+) -> impl SerializeFn<W> + 'a {
+    move |x| {
+        let start = x.position;
+        let x = gen(x)?;
+        metrics.frame_generated(frame, (x.position - start) as usize);
+        Ok(x)
+    }
+}
+
+/// A [`Metrics`] implementation emitting a tracing event for each frame
+///
+/// This is what the frame parser and serializer report into when the `tracing` feature is enabled.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingMetrics;
+
+#[cfg(feature = "tracing")]
+impl TracingMetrics {
+    fn event(frame: &AMQPFrame, size: usize, message: &str) {
+        let (channel, class, method) = match frame {
+            AMQPFrame::ProtocolHeader(_) => (0, None, None),
+            AMQPFrame::Method(channel_id, method) => (
+                *channel_id,
+                Some(method.get_amqp_class_id()),
+                Some(method.get_amqp_method_id()),
+            ),
+            AMQPFrame::Header(channel_id, class_id, _) => (*channel_id, Some(*class_id), None),
+            AMQPFrame::Body(channel_id, _) | AMQPFrame::Heartbeat(channel_id) => {
+                (*channel_id, None, None)
+            }
+        };
+        tracing::trace!(
+            channel,
+            frame_type = ?frame.frame_type(),
+            class,
+            method,
+            size,
+            "{}",
+            message
+        );
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Metrics for TracingMetrics {
+    fn frame_parsed(&self, frame: &AMQPFrame, size: usize) {
+        Self::event(frame, size, "Parsed frame.");
+    }
+
+    fn frame_generated(&self, frame: &AMQPFrame, size: usize) {
+        Self::event(frame, size, "Serialized frame.");
+    }
+
+    fn parse_error(&self, error: &ParserError) {
+        tracing::debug!(?error, "Failed to parse frame.");
+    }
+}
+
+/// A snapshot of the statistics collected by [`FrameCounters`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameStats {
+    /// The number of parsed frames, by type
+    pub frames_in: HashMap<AMQPFrameType, u64>,
+    /// The number of serialized frames, by type
+    pub frames_out: HashMap<AMQPFrameType, u64>,
+    /// The number of parsed methods, by (class id, method id)
+    pub methods_in: HashMap<(Identifier, Identifier), u64>,
+    /// The number of serialized methods, by (class id, method id)
+    pub methods_out: HashMap<(Identifier, Identifier), u64>,
+    /// The number of parsed bytes
+    pub bytes_in: u64,
+    /// The number of serialized bytes
+    pub bytes_out: u64,
+    /// The number of parse errors
+    pub parse_errors: u64,
+}
+
+/// A simple [`Metrics`] implementation counting frames and bytes
+#[derive(Debug, Default)]
+pub struct FrameCounters {
+    stats: Mutex<FrameStats>,
+}
+
+impl FrameCounters {
+    /// Create new counters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the current statistics
+    pub fn stats(&self) -> FrameStats {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Get the current statistics and reset the counters
+    pub fn take(&self) -> FrameStats {
+        std::mem::take(&mut *self.stats.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn record<F: FnOnce(&mut FrameStats)>(&self, f: F) {
+        f(&mut self.stats.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+}
+
+impl Metrics for FrameCounters {
+    fn frame_parsed(&self, frame: &AMQPFrame, size: usize) {
+        self.record(|stats| {
+            *stats.frames_in.entry(frame.frame_type()).or_default() += 1;
+            if let AMQPFrame::Method(_, method) = frame {
+                *stats
+                    .methods_in
+                    .entry((method.get_amqp_class_id(), method.get_amqp_method_id()))
+                    .or_default() += 1;
+            }
+            stats.bytes_in += size as u64;
+        });
+    }
+
+    fn frame_generated(&self, frame: &AMQPFrame, size: usize) {
+        self.record(|stats| {
+            *stats.frames_out.entry(frame.frame_type()).or_default() += 1;
+            if let AMQPFrame::Method(_, method) = frame {
+                *stats
+                    .methods_out
+                    .entry((method.get_amqp_class_id(), method.get_amqp_method_id()))
+                    .or_default() += 1;
+            }
+            stats.bytes_out += size as u64;
+        });
+    }
+
+    fn parse_error(&self, _error: &ParserError) {
+        self.record(|stats| stats.parse_errors += 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocol::{basic, AMQPClass};
+
+    #[test]
+    fn test_frame_counters() {
+        let counters = FrameCounters::new();
+        let frame = AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::Qos(basic::Qos::default())),
+        );
+        let buffer =
+            cookie_factory::gen_simple(gen_frame_with_metrics(&frame, &counters), Vec::new())
+                .unwrap();
+        assert_eq!(
+            parse_frame_with_metrics(&buffer[..], &counters),
+            Ok((&[][..], frame))
+        );
+        assert!(parse_frame_with_metrics(&buffer[..4], &counters).is_err());
+        assert!(parse_frame_with_metrics(&[42, 0, 0][..], &counters).is_err());
+
+        let stats = counters.take();
+        assert_eq!(stats.frames_in.get(&AMQPFrameType::Method), Some(&1));
+        assert_eq!(stats.frames_out.get(&AMQPFrameType::Method), Some(&1));
+        assert_eq!(stats.methods_in.get(&(60, 10)), Some(&1));
+        assert_eq!(stats.methods_out.get(&(60, 10)), Some(&1));
+        assert_eq!(stats.bytes_in, buffer.len() as u64);
+        assert_eq!(stats.bytes_out, buffer.len() as u64);
+        assert_eq!(stats.parse_errors, 1);
+        assert_eq!(counters.stats(), FrameStats::default());
+    }
+}
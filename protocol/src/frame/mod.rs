@@ -1,14 +1,59 @@
+mod accumulator;
+#[cfg(feature = "arena")]
+mod arena;
+mod demux;
+mod fair_queue;
+mod metrics;
 mod structs;
+mod throttle;
 
-pub use self::{generation::gen_frame, parsing::parse_frame, structs::*};
+pub use self::{
+    accumulator::FrameAccumulator,
+    demux::FrameDemux,
+    fair_queue::FrameQueue,
+    generation::{frame_size, gen_frame, gen_frame_with_frame_max, split_content, VectoredFrames},
+    metrics::*,
+    parsing::{parse_frame, parse_frame_from_buf, parse_frame_with},
+    structs::*,
+    throttle::FrameThrottle,
+};
+
+#[cfg(feature = "arena")]
+pub use self::arena::{parse_content_header_in, parse_frame_in, ArenaContentHeader, ArenaFrame};
 
 pub use crate::types::{
     generation::{BackToTheBuffer, GenError, GenResult, SerializeFn},
     parsing::{ParserError, ParserResult},
 };
+pub use bytes::Bytes;
 pub use cookie_factory::WriteContext;
 
+use crate::types::codec::{decode_with, encode_with, Decode, DecodeError, Encode, EncodeError};
+
 /// Serialization utils
 pub mod generation;
 /// Parsing utils
 pub mod parsing;
+
+impl Decode for AMQPFrame {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+        decode_with(bytes, parse_frame)
+    }
+}
+
+impl Encode for AMQPFrame {
+    fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+        encode_with(buffer, gen_frame(self))
+    }
+}
+
+#[cfg(feature = "test-utils")]
+impl crate::types::roundtrip::Roundtrip for AMQPFrame {
+    fn generate(&self) -> Result<Vec<u8>, GenError> {
+        cookie_factory::gen_simple(gen_frame(self), Vec::new())
+    }
+
+    fn parse(i: &[u8]) -> ParserResult<&[u8], Self> {
+        parse_frame(i)
+    }
+}
@@ -2,17 +2,22 @@
 pub use crate::types::parsing::traits;
 use crate::{
     frame::*,
-    protocol::{basic::parse_properties, *},
+    protocol::{basic::parse_properties_with, *},
     types::parsing::*,
 };
+use bytes::Buf;
 use nom::{
     bytes::streaming::{tag, take},
     combinator::{all_consuming, flat_map, map, map_opt, map_res},
-    error::context,
+    error::{context, make_error, ErrorKind},
     Parser,
 };
+use std::io::IoSlice;
 use traits::ParsableInput;
 
+/* frame type + channel id + payload size */
+const FRAME_HEADER_SIZE: usize = 7;
+
 /// Parse a channel id
 pub fn parse_channel<I: ParsableInput>(i: I) -> ParserResult<I, AMQPChannel> {
     context("parse_channel", map(parse_id, From::from)).parse(i)
@@ -58,6 +63,74 @@ pub fn parse_frame_type<I: ParsableInput>(i: I) -> ParserResult<I, AMQPFrameType
 
 /// Parse a full AMQP Frame (with contents)
 pub fn parse_frame<I: ParsableInput>(i: I) -> ParserResult<I, AMQPFrame> {
+    parse_frame_with(i, ParsingOptions::default())
+}
+
+/// Parse a full AMQP Frame (with contents), parsing its field tables with the given options
+pub fn parse_frame_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, AMQPFrame> {
+    #[cfg(feature = "tracing")]
+    let res = metrics::report_parsed_frame(i, |i| parse_full_frame(i, options), &TracingMetrics);
+    #[cfg(not(feature = "tracing"))]
+    let res = parse_full_frame(i, options);
+    res
+}
+
+/// Parse a full AMQP Frame from a buffer, advancing it past the frame
+///
+/// Returns None, leaving the buffer untouched, if it does not hold a full frame yet.
+/// The frame is parsed in place when it lies in the first chunk of the buffer, which is always
+/// the case with `BytesMut`. Otherwise only this frame is copied, using the chunks exposed by
+/// `chunks_vectored` to find its size.
+pub fn parse_frame_from_buf<B: Buf>(buf: &mut B) -> Result<Option<AMQPFrame>, ParserError> {
+    let chunk = buf.chunk();
+    match parse_frame(chunk) {
+        Ok((rest, frame)) => {
+            let consumed = chunk.len() - rest.len();
+            buf.advance(consumed);
+            return Ok(Some(frame));
+        }
+        Err(nom::Err::Incomplete(_)) if chunk.len() < buf.remaining() => {}
+        Err(nom::Err::Incomplete(_)) => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    if buf.remaining() < FRAME_HEADER_SIZE {
+        return Ok(None);
+    }
+    let mut header = [0; FRAME_HEADER_SIZE];
+    if !peek(buf, &mut header) {
+        return Err(nom::Err::Failure(make_error(&header[..], ErrorKind::Fail)));
+    }
+    let frame_len = if header[0] == metadata::NAME.as_bytes()[0] {
+        8
+    } else {
+        let payload_size = u32::from_be_bytes([header[3], header[4], header[5], header[6]]);
+        FRAME_HEADER_SIZE + payload_size as usize + 1
+    };
+    if buf.remaining() < frame_len {
+        return Ok(None);
+    }
+    let bytes = buf.copy_to_bytes(frame_len);
+    let (_, frame) = all_consuming(parse_frame).parse(&bytes[..])?;
+    Ok(Some(frame))
+}
+
+fn peek<B: Buf>(buf: &B, dst: &mut [u8]) -> bool {
+    /* Every chunk holds at least one byte */
+    let mut slices = [IoSlice::new(&[]); FRAME_HEADER_SIZE];
+    let count = buf.chunks_vectored(&mut slices);
+    let mut filled = 0;
+    for slice in &slices[..count] {
+        let len = slice.len().min(dst.len() - filled);
+        dst[filled..filled + len].copy_from_slice(&slice[..len]);
+        filled += len;
+    }
+    filled == dst.len()
+}
+
+fn parse_full_frame<I: ParsableInput>(i: I, options: ParsingOptions) -> ParserResult<I, AMQPFrame> {
     context(
         "parse_frame",
         flat_map(parse_frame_type, move |frame_type| {
@@ -76,15 +149,19 @@ pub fn parse_frame<I: ParsableInput>(i: I) -> ParserResult<I, AMQPFrame> {
                         AMQPFrameType::ProtocolHeader => {
                             Ok(AMQPFrame::ProtocolHeader(ProtocolVersion::amqp_0_9_1()))
                         }
-                        AMQPFrameType::Method => all_consuming(parse_class)
+                        AMQPFrameType::Method => all_consuming(|i: I| parse_class_with(i, options))
                             .parse(payload)
                             .map(|(_, m)| AMQPFrame::Method(channel_id, m)),
-                        AMQPFrameType::Header => all_consuming(parse_content_header)
-                            .parse(payload)
-                            .map(|(_, h)| AMQPFrame::Header(channel_id, h.class_id, Box::new(h))),
+                        AMQPFrameType::Header => {
+                            all_consuming(|i: I| parse_content_header_with(i, options))
+                                .parse(payload)
+                                .map(|(_, h)| {
+                                    AMQPFrame::Header(channel_id, h.class_id, Box::new(h))
+                                })
+                        }
                         AMQPFrameType::Body => Ok(AMQPFrame::Body(
                             channel_id,
-                            payload.iter_elements().collect(),
+                            payload.iter_elements().collect::<Vec<u8>>().into(),
                         )),
                         AMQPFrameType::Heartbeat => Ok(AMQPFrame::Heartbeat(channel_id)),
                     },
@@ -96,6 +173,56 @@ pub fn parse_frame<I: ParsableInput>(i: I) -> ParserResult<I, AMQPFrame> {
     .parse(i)
 }
 
+/// Parse a content-carrying method frame, its content header frame and skip its body frames,
+/// exposing the ranges of the input holding the body instead of copying it
+///
+/// The frames of the message must be contiguous in the input.
+pub fn parse_message_head(i: &[u8]) -> ParserResult<&[u8], MessageHead> {
+    let fail = |i| nom::Err::Error(make_error(i, ErrorKind::Verify));
+    let (rest, (channel_id, method)) = context(
+        "parse_message_head",
+        map_res(parse_frame, |frame| match frame {
+            AMQPFrame::Method(channel_id, method) if method.is_content_carrying() => {
+                Ok((channel_id, method))
+            }
+            _ => Err(()),
+        }),
+    )
+    .parse(i)?;
+    let (mut rest, header) = context(
+        "parse_message_head",
+        map_res(parse_frame, |frame| match frame {
+            AMQPFrame::Header(channel, _, header) if channel == channel_id => Ok(*header),
+            _ => Err(()),
+        }),
+    )
+    .parse(rest)?;
+    let mut body = Vec::new();
+    let mut remaining = header.body_size;
+    while remaining > 0 {
+        let start = i.len() - rest.len() + FRAME_HEADER_SIZE;
+        let (next, frame) = flat_map(parse_frame_type, parse_raw_frame).parse(rest)?;
+        let len = frame.payload.len();
+        if frame.frame_type != AMQPFrameType::Body || frame.channel_id != channel_id {
+            return Err(fail(rest));
+        }
+        remaining = remaining
+            .checked_sub(len as u64)
+            .ok_or_else(|| fail(rest))?;
+        body.push(start..start + len);
+        rest = next;
+    }
+    Ok((
+        rest,
+        MessageHead {
+            channel_id,
+            method,
+            header,
+            body,
+        },
+    ))
+}
+
 /// Parse a raw AMQP frame
 pub fn parse_raw_frame<I: ParsableInput>(
     frame_type: AMQPFrameType,
@@ -120,6 +247,14 @@ pub fn parse_raw_frame<I: ParsableInput>(
 
 /// Parse a content header frame
 pub fn parse_content_header<I: ParsableInput>(i: I) -> ParserResult<I, AMQPContentHeader> {
+    parse_content_header_with(i, ParsingOptions::default())
+}
+
+/// Parse a content header frame, parsing the field tables of its properties with the given options
+pub fn parse_content_header_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, AMQPContentHeader> {
     context(
         "parse_content_header",
         map(
@@ -127,7 +262,7 @@ pub fn parse_content_header<I: ParsableInput>(i: I) -> ParserResult<I, AMQPConte
                 parse_id,
                 parse_short_uint,
                 parse_long_long_uint,
-                context("parse_properties", parse_properties),
+                context("parse_properties", |i: I| parse_properties_with(i, options)),
             ),
             // FIXME: should we validate that weight is 0?
             |(class_id, _weight, body_size, properties)| AMQPContentHeader {
@@ -161,9 +296,25 @@ mod test {
             parse_frame(&[8, 0, 1, 0, 0, 0, 0, 206][..]),
             Ok((&[][..], AMQPFrame::Heartbeat(1)))
         );
+        assert_eq!(
+            parse_frame(&AMQPFrame::HEARTBEAT_BYTES[..]),
+            Ok((&[][..], AMQPFrame::Heartbeat(0)))
+        );
+        assert!(AMQPFrame::is_heartbeat_bytes(&AMQPFrame::HEARTBEAT_BYTES));
+        assert!(AMQPFrame::is_heartbeat_bytes(
+            &[8, 0, 1, 0, 0, 0, 0, 206, 1][..]
+        ));
+        assert!(!AMQPFrame::is_heartbeat_bytes(&[8, 0, 0, 0, 0, 0, 0][..]));
+        assert!(!AMQPFrame::is_heartbeat_bytes(
+            &[8, 0, 0, 0, 0, 0, 1, 0, 206][..]
+        ));
+        assert!(!AMQPFrame::is_heartbeat_bytes(
+            &[3, 0, 0, 0, 0, 0, 0, 206][..]
+        ));
     }
 
     #[test]
+    #[cfg(feature = "class-queue")]
     fn test_parse_declare_queue_frame() {
         let frame = AMQPFrame::Method(
             1,
@@ -183,4 +334,127 @@ mod test {
         assert!(gen_frame(&frame)(buffer.as_mut_slice().into()).is_ok());
         assert_eq!(parse_frame(buffer.as_slice()), Ok((&[][..], frame)));
     }
+
+    #[test]
+    fn test_parse_message_head() {
+        let publish = AMQPClass::Basic(basic::AMQPMethod::Publish(basic::Publish {
+            routing_key: "key".into(),
+            ..basic::Publish::default()
+        }));
+        let header = AMQPContentHeader {
+            class_id: 60,
+            body_size: 11,
+            properties: basic::AMQPProperties::default().with_message_id("42".into()),
+        };
+        let frames = [
+            AMQPFrame::Method(1, publish.clone()),
+            AMQPFrame::Header(1, 60, Box::new(header.clone())),
+            AMQPFrame::Body(1, Bytes::from_static(b"hello ")),
+            AMQPFrame::Body(1, Bytes::from_static(b"world")),
+            AMQPFrame::Heartbeat(0),
+        ];
+        let bytes = frames.iter().fold(Vec::new(), |buffer, frame| {
+            cookie_factory::gen_simple(gen_frame(frame), buffer).unwrap()
+        });
+        let (rest, head) = parse_message_head(&bytes).unwrap();
+        assert_eq!(rest, &[8, 0, 0, 0, 0, 0, 0, 206][..]);
+        assert_eq!(head.channel_id, 1);
+        assert_eq!(head.method, publish);
+        assert_eq!(head.header, header);
+        assert_eq!(
+            head.body
+                .iter()
+                .flat_map(|range| bytes[range.clone()].iter().copied())
+                .collect::<Vec<u8>>(),
+            b"hello world"
+        );
+
+        assert!(matches!(
+            parse_message_head(&bytes[..bytes.len() - 10]),
+            Err(nom::Err::Incomplete(_))
+        ));
+        assert!(parse_message_head(&bytes[bytes.len() - 8..]).is_err());
+    }
+
+    #[test]
+    fn test_parse_frame_with() {
+        use crate::types::{AMQPValue, FieldTable, Utf8Policy};
+
+        fn table(value: &[u8]) -> FieldTable {
+            let mut table = FieldTable::default();
+            table.insert("k".into(), AMQPValue::LongString(value.to_vec().into()));
+            table
+        }
+        let options = |utf8| ParsingOptions {
+            utf8,
+            ..ParsingOptions::default()
+        };
+        let frames: [fn(&[u8]) -> AMQPFrame; 2] = [
+            |value| {
+                AMQPFrame::Header(
+                    1,
+                    60,
+                    Box::new(AMQPContentHeader {
+                        class_id: 60,
+                        body_size: 0,
+                        properties: basic::AMQPProperties::default().with_headers(table(value)),
+                    }),
+                )
+            },
+            |value| {
+                AMQPFrame::Method(
+                    1,
+                    AMQPClass::Basic(basic::AMQPMethod::Consume(basic::Consume {
+                        arguments: table(value),
+                        ..basic::Consume::default()
+                    })),
+                )
+            },
+        ];
+        for frame in frames {
+            let bytes =
+                cookie_factory::gen_simple(gen_frame(&frame(b"v\xff")), Vec::new()).unwrap();
+            assert_eq!(
+                parse_frame_with(&bytes[..], options(Utf8Policy::Raw)),
+                Ok((&[][..], frame(b"v\xff")))
+            );
+            assert!(parse_frame_with(&bytes[..], options(Utf8Policy::Strict)).is_err());
+            assert_eq!(
+                parse_frame_with(&bytes[..], options(Utf8Policy::Lossy)),
+                Ok((&[][..], frame("v\u{fffd}".as_bytes())))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_frame_from_buf() {
+        let body = AMQPFrame::Body(1, Bytes::from_static(b"hello"));
+        let frames = [
+            AMQPFrame::ProtocolHeader(ProtocolVersion::amqp_0_9_1()),
+            AMQPFrame::Heartbeat(0),
+            body.clone(),
+        ];
+        let bytes = frames
+            .iter()
+            .flat_map(|frame| cookie_factory::gen_simple(gen_frame(frame), Vec::new()).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut buf = bytes::BytesMut::from(&bytes[..bytes.len() - 1]);
+        assert_eq!(parse_frame_from_buf(&mut buf), Ok(Some(frames[0].clone())));
+        assert_eq!(parse_frame_from_buf(&mut buf), Ok(Some(frames[1].clone())));
+        assert_eq!(parse_frame_from_buf(&mut buf), Ok(None));
+        assert_eq!(buf.len(), 12);
+
+        // Split in the middle of the frame header, then of the payload
+        for split in [18, 25] {
+            let mut buf = Bytes::copy_from_slice(&bytes[..split])
+                .chain(Bytes::copy_from_slice(&bytes[split..]));
+            buf.advance(16);
+            assert_eq!(parse_frame_from_buf(&mut buf), Ok(Some(body.clone())));
+            assert!(!buf.has_remaining());
+        }
+
+        let mut buf = &[42; 8][..];
+        assert!(parse_frame_from_buf(&mut buf).is_err());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{frame::parsing::traits::ParsableInput, protocol::*, types::*};
-use std::fmt;
+use bytes::Bytes;
+use std::{fmt, ops::Range};
 
 /// Enum representing an AMQP channel
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -30,7 +31,7 @@ impl From<ChannelId> for AMQPChannel {
 }
 
 /// The type of AMQP Frame
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AMQPFrameType {
     /// The Protocol Header,
     ProtocolHeader,
@@ -53,17 +54,61 @@ pub enum AMQPFrame {
     Method(ChannelId, AMQPClass),
     /// Content header
     Header(ChannelId, Identifier, Box<AMQPContentHeader>),
-    /// Content body
-    Body(ChannelId, Vec<u8>),
+    /// Content body, the payload can be shared without copying it
+    Body(ChannelId, Bytes),
     /// Heartbeat frame
     Heartbeat(ChannelId),
 }
 
 impl AMQPFrame {
+    /// The encoded AMQPFrame::Heartbeat(0), to send heartbeats without going through gen_frame
+    pub const HEARTBEAT_BYTES: [u8; 8] = [
+        constants::FRAME_HEARTBEAT,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        constants::FRAME_END,
+    ];
+
+    /// Check whether the bytes start with a heartbeat frame, on any channel, without parsing them
+    ///
+    /// The heartbeat frame is HEARTBEAT_BYTES.len() bytes long, it can be skipped from the
+    /// buffer when this returns true.
+    pub fn is_heartbeat_bytes(bytes: &[u8]) -> bool {
+        matches!(
+            bytes,
+            [
+                constants::FRAME_HEARTBEAT,
+                _,
+                _,
+                0,
+                0,
+                0,
+                0,
+                constants::FRAME_END,
+                ..
+            ]
+        )
+    }
+
     /// Return whether this frame is an AMQPFrame::Header or not
     pub fn is_header(&self) -> bool {
         matches!(self, AMQPFrame::Header(..))
     }
+
+    /// Get the type of this frame
+    pub fn frame_type(&self) -> AMQPFrameType {
+        match self {
+            AMQPFrame::ProtocolHeader(_) => AMQPFrameType::ProtocolHeader,
+            AMQPFrame::Method(..) => AMQPFrameType::Method,
+            AMQPFrame::Header(..) => AMQPFrameType::Header,
+            AMQPFrame::Body(..) => AMQPFrameType::Body,
+            AMQPFrame::Heartbeat(_) => AMQPFrameType::Heartbeat,
+        }
+    }
 }
 
 impl fmt::Display for AMQPFrame {
@@ -102,6 +147,26 @@ impl ProtocolVersion {
             revision: metadata::REVISION,
         }
     }
+
+    /// Get the version from the 8 bytes of a protocol header
+    ///
+    /// Besides the "AMQP" 0 major minor revision layout of AMQP 0-9-1 and 1.0, this understands
+    /// the "AMQP" 1 1 major minor layout of the older versions, such as 0-9 and 0-8.
+    pub fn from_header(header: &[u8]) -> Option<Self> {
+        match header {
+            [b'A', b'M', b'Q', b'P', 1, 1, major, minor, ..] => Some(Self {
+                major: *major,
+                minor: *minor,
+                revision: 0,
+            }),
+            [b'A', b'M', b'Q', b'P', _, major, minor, revision, ..] => Some(Self {
+                major: *major,
+                minor: *minor,
+                revision: *revision,
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for ProtocolVersion {
@@ -134,3 +199,16 @@ pub struct AMQPContentHeader {
     /// The AMQP properties associated with the content
     pub properties: basic::AMQPProperties,
 }
+
+/// A content-carrying method and its content header, decoded without touching the body
+#[derive(Clone, Debug, PartialEq)]
+pub struct MessageHead {
+    /// The channel the message was sent on
+    pub channel_id: ChannelId,
+    /// The method carrying the content, such as basic.publish or basic.deliver
+    pub method: AMQPClass,
+    /// The content header, holding the properties and the size of the body
+    pub header: AMQPContentHeader,
+    /// The ranges of the input holding the body, one per body frame
+    pub body: Vec<Range<usize>>,
+}
@@ -0,0 +1,171 @@
+use crate::frame::{gen_frame, AMQPFrame};
+use std::{
+    io::Write,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Rate limiting of outgoing frames using token buckets
+///
+/// Frames per second and bytes per second can be limited independently, each bucket holding up to
+/// one second worth of tokens to allow for bursts. A frame larger than a full bucket is still sent
+/// once the bucket is full, the following frames then wait for the debt to be paid back.
+/// Heartbeats are never delayed so that the connection stays alive.
+#[derive(Debug)]
+pub struct FrameThrottle<W> {
+    writer: W,
+    frames: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+}
+
+impl<W: Write> FrameThrottle<W> {
+    /// Wrap a writer without any limit
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            frames: None,
+            bytes: None,
+        }
+    }
+
+    /// Limit the number of frames sent per second
+    pub fn with_frames_per_second(mut self, frames: u32) -> Self {
+        self.frames = Some(TokenBucket::new(frames.into(), Instant::now()));
+        self
+    }
+
+    /// Limit the number of bytes sent per second
+    pub fn with_bytes_per_second(mut self, bytes: u64) -> Self {
+        self.bytes = Some(TokenBucket::new(bytes as f64, Instant::now()));
+        self
+    }
+
+    /// Serialize and send a frame, sleeping first if a limit has been reached
+    pub fn send(&mut self, frame: &AMQPFrame) -> Result<(), String> {
+        let buffer = cookie_factory::gen_simple(gen_frame(frame), Vec::new())
+            .map_err(|e| format!("Failed to serialize {:?}: {}", frame, e))?;
+        if !matches!(frame, AMQPFrame::Heartbeat(_)) {
+            let delay = self.reserve(buffer.len());
+            if !delay.is_zero() {
+                thread::sleep(delay);
+            }
+        }
+        self.writer
+            .write_all(&buffer)
+            .and_then(|()| self.writer.flush())
+            .map_err(|e| format!("Failed to send {:?}: {}", frame, e))
+    }
+
+    /// Take the tokens for a frame of the given size, returning how long to wait before sending it
+    ///
+    /// This is meant for callers handling the waiting themselves, `send` does it for you.
+    pub fn reserve(&mut self, size: usize) -> Duration {
+        self.reserve_at(size, Instant::now())
+    }
+
+    fn reserve_at(&mut self, size: usize, now: Instant) -> Duration {
+        let frames = self
+            .frames
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.reserve(1.0, now));
+        let bytes = self
+            .bytes
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.reserve(size as f64, now));
+        frames.max(bytes)
+    }
+
+    /// Get a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Get back the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: rate,
+            last: now,
+        }
+    }
+
+    fn reserve(&mut self, amount: f64, now: Instant) -> Duration {
+        if self.rate <= 0.0 {
+            return Duration::ZERO;
+        }
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last = now;
+        let missing = amount.min(self.rate) - self.tokens;
+        self.tokens -= amount;
+        if missing > 0.0 {
+            Duration::from_secs_f64(missing / self.rate)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::Bytes;
+
+    #[test]
+    fn test_frames_per_second() {
+        let start = Instant::now();
+        let mut throttle = FrameThrottle::new(Vec::new()).with_frames_per_second(2);
+        throttle.frames = Some(TokenBucket::new(2.0, start));
+        assert_eq!(throttle.reserve_at(10, start), Duration::ZERO);
+        assert_eq!(throttle.reserve_at(10, start), Duration::ZERO);
+        assert_eq!(throttle.reserve_at(10, start), Duration::from_millis(500));
+        assert_eq!(
+            throttle.reserve_at(10, start + Duration::from_secs(2)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_bytes_per_second() {
+        let start = Instant::now();
+        let mut throttle = FrameThrottle::new(Vec::new()).with_bytes_per_second(1000);
+        throttle.bytes = Some(TokenBucket::new(1000.0, start));
+        assert_eq!(throttle.reserve_at(4000, start), Duration::ZERO);
+        assert_eq!(throttle.reserve_at(500, start), Duration::from_millis(3500));
+        assert_eq!(
+            throttle.reserve_at(500, start + Duration::from_secs(4)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_send() {
+        let mut throttle = FrameThrottle::new(Vec::new()).with_frames_per_second(1000);
+        throttle.send(&AMQPFrame::Heartbeat(0)).unwrap();
+        throttle
+            .send(&AMQPFrame::Body(1, Bytes::from_static(b"data")))
+            .unwrap();
+        assert_eq!(
+            throttle.into_inner(),
+            vec![8, 0, 0, 0, 0, 0, 0, 206, 3, 0, 1, 0, 0, 0, 4, b'd', b'a', b't', b'a', 206]
+        );
+    }
+}
@@ -209,29 +209,175 @@ impl fmt::Display for AMQPHardError {
     }
 }
 
-use self::access::parse_access;
-use self::basic::parse_basic;
-use self::channel::parse_channel;
-use self::confirm::parse_confirm;
-use self::connection::parse_connection;
-use self::exchange::parse_exchange;
-use self::queue::parse_queue;
-use self::tx::parse_tx;
+/// exchange-type (Generated)
+///
+/// Values are compared through their string representation, so Other holding a well-known value
+/// equals the matching variant (Generated)
+#[derive(Clone, Debug)]
+pub enum ExchangeType {
+    /// direct (Generated)
+    Direct,
+    /// fanout (Generated)
+    Fanout,
+    /// topic (Generated)
+    Topic,
+    /// headers (Generated)
+    Headers,
+    /// Any other value (Generated)
+    Other(String),
+}
+
+impl ExchangeType {
+    /// Get the string representation (Generated)
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExchangeType::Direct => "direct",
+            ExchangeType::Fanout => "fanout",
+            ExchangeType::Topic => "topic",
+            ExchangeType::Headers => "headers",
+            ExchangeType::Other(value) => value,
+        }
+    }
+}
+
+impl PartialEq for ExchangeType {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for ExchangeType {}
+
+impl Hash for ExchangeType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl From<&str> for ExchangeType {
+    fn from(s: &str) -> Self {
+        match s {
+            "direct" => ExchangeType::Direct,
+            "fanout" => ExchangeType::Fanout,
+            "topic" => ExchangeType::Topic,
+            "headers" => ExchangeType::Headers,
+            s => ExchangeType::Other(s.to_string()),
+        }
+    }
+}
+
+impl From<String> for ExchangeType {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+impl FromStr for ExchangeType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+impl From<ExchangeType> for ShortString {
+    fn from(value: ExchangeType) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl fmt::Display for ExchangeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "class-exchange")]
+impl exchange::Declare {
+    /// Get type as a ExchangeType (Generated)
+    pub fn exchange_type(&self) -> ExchangeType {
+        self.kind.as_str().into()
+    }
+
+    /// Set type from a ExchangeType (Generated)
+    pub fn with_exchange_type(mut self, value: ExchangeType) -> Self {
+        self.kind = value.into();
+        self
+    }
+}
+
+#[cfg(feature = "class-access")]
+use self::access::parse_access_with;
+use self::basic::parse_basic_with;
+use self::channel::parse_channel_with;
+#[cfg(feature = "class-confirm")]
+use self::confirm::parse_confirm_with;
+use self::connection::parse_connection_with;
+#[cfg(feature = "class-exchange")]
+use self::exchange::parse_exchange_with;
+#[cfg(feature = "class-queue")]
+use self::queue::parse_queue_with;
+#[cfg(feature = "class-tx")]
+use self::tx::parse_tx_with;
 /// Parse an AMQP class
 pub fn parse_class<I: ParsableInput>(i: I) -> ParserResult<I, AMQPClass> {
+    parse_class_with(i, ParsingOptions::default())
+}
+
+/// Parse an AMQP class, parsing its field tables with the given options
+pub fn parse_class_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, AMQPClass> {
     context(
         "parse_class",
         map_opt(
-            flat_map(parse_id, |id| {
+            flat_map(parse_id, move |id| {
                 move |i| match id {
-                    60 => map(map(parse_basic, AMQPClass::Basic), Some).parse(i),
-                    10 => map(map(parse_connection, AMQPClass::Connection), Some).parse(i),
-                    20 => map(map(parse_channel, AMQPClass::Channel), Some).parse(i),
-                    30 => map(map(parse_access, AMQPClass::Access), Some).parse(i),
-                    40 => map(map(parse_exchange, AMQPClass::Exchange), Some).parse(i),
-                    50 => map(map(parse_queue, AMQPClass::Queue), Some).parse(i),
-                    90 => map(map(parse_tx, AMQPClass::Tx), Some).parse(i),
-                    85 => map(map(parse_confirm, AMQPClass::Confirm), Some).parse(i),
+                    60 => map(
+                        map(|i: I| parse_basic_with(i, options), AMQPClass::Basic),
+                        Some,
+                    )
+                    .parse(i),
+                    10 => map(
+                        map(
+                            |i: I| parse_connection_with(i, options),
+                            AMQPClass::Connection,
+                        ),
+                        Some,
+                    )
+                    .parse(i),
+                    20 => map(
+                        map(|i: I| parse_channel_with(i, options), AMQPClass::Channel),
+                        Some,
+                    )
+                    .parse(i),
+                    #[cfg(feature = "class-access")]
+                    30 => map(
+                        map(|i: I| parse_access_with(i, options), AMQPClass::Access),
+                        Some,
+                    )
+                    .parse(i),
+                    #[cfg(feature = "class-exchange")]
+                    40 => map(
+                        map(|i: I| parse_exchange_with(i, options), AMQPClass::Exchange),
+                        Some,
+                    )
+                    .parse(i),
+                    #[cfg(feature = "class-queue")]
+                    50 => map(
+                        map(|i: I| parse_queue_with(i, options), AMQPClass::Queue),
+                        Some,
+                    )
+                    .parse(i),
+                    #[cfg(feature = "class-tx")]
+                    90 => map(map(|i: I| parse_tx_with(i, options), AMQPClass::Tx), Some).parse(i),
+                    #[cfg(feature = "class-confirm")]
+                    85 => map(
+                        map(|i: I| parse_confirm_with(i, options), AMQPClass::Confirm),
+                        Some,
+                    )
+                    .parse(i),
                     _ => Ok((i, None)),
                 }
             }),
@@ -249,14 +395,31 @@ pub fn gen_class<'a, W: Write + BackToTheBuffer + 'a>(
         AMQPClass::Basic(ref basic) => basic::gen_basic(basic)(input),
         AMQPClass::Connection(ref connection) => connection::gen_connection(connection)(input),
         AMQPClass::Channel(ref channel) => channel::gen_channel(channel)(input),
+        #[cfg(feature = "class-access")]
         AMQPClass::Access(ref access) => access::gen_access(access)(input),
+        #[cfg(feature = "class-exchange")]
         AMQPClass::Exchange(ref exchange) => exchange::gen_exchange(exchange)(input),
+        #[cfg(feature = "class-queue")]
         AMQPClass::Queue(ref queue) => queue::gen_queue(queue)(input),
+        #[cfg(feature = "class-tx")]
         AMQPClass::Tx(ref tx) => tx::gen_tx(tx)(input),
+        #[cfg(feature = "class-confirm")]
         AMQPClass::Confirm(ref confirm) => confirm::gen_confirm(confirm)(input),
     }
 }
 
+impl Decode for AMQPClass {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+        decode_with(bytes, parse_class)
+    }
+}
+
+impl Encode for AMQPClass {
+    fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+        encode_with(buffer, gen_class(self))
+    }
+}
+
 /// The available AMQP classes
 #[derive(Clone, Debug, PartialEq)]
 pub enum AMQPClass {
@@ -267,14 +430,19 @@ pub enum AMQPClass {
     /// channel (Generated)
     Channel(channel::AMQPMethod),
     /// access (Generated)
+    #[cfg(feature = "class-access")]
     Access(access::AMQPMethod),
     /// exchange (Generated)
+    #[cfg(feature = "class-exchange")]
     Exchange(exchange::AMQPMethod),
     /// queue (Generated)
+    #[cfg(feature = "class-queue")]
     Queue(queue::AMQPMethod),
     /// tx (Generated)
+    #[cfg(feature = "class-tx")]
     Tx(tx::AMQPMethod),
     /// confirm (Generated)
+    #[cfg(feature = "class-confirm")]
     Confirm(confirm::AMQPMethod),
 }
 
@@ -285,10 +453,15 @@ impl AMQPClass {
             AMQPClass::Basic(_) => 60,
             AMQPClass::Connection(_) => 10,
             AMQPClass::Channel(_) => 20,
+            #[cfg(feature = "class-access")]
             AMQPClass::Access(_) => 30,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(_) => 40,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(_) => 50,
+            #[cfg(feature = "class-tx")]
             AMQPClass::Tx(_) => 90,
+            #[cfg(feature = "class-confirm")]
             AMQPClass::Confirm(_) => 85,
         }
     }
@@ -334,36 +507,1353 @@ impl AMQPClass {
             AMQPClass::Channel(channel::AMQPMethod::FlowOk(_)) => 21,
             AMQPClass::Channel(channel::AMQPMethod::Close(_)) => 40,
             AMQPClass::Channel(channel::AMQPMethod::CloseOk(_)) => 41,
+            #[cfg(feature = "class-access")]
             AMQPClass::Access(access::AMQPMethod::Request(_)) => 10,
+            #[cfg(feature = "class-access")]
             AMQPClass::Access(access::AMQPMethod::RequestOk(_)) => 11,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::Declare(_)) => 10,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::DeclareOk(_)) => 11,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::Delete(_)) => 20,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::DeleteOk(_)) => 21,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::Bind(_)) => 30,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::BindOk(_)) => 31,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::Unbind(_)) => 40,
+            #[cfg(feature = "class-exchange")]
             AMQPClass::Exchange(exchange::AMQPMethod::UnbindOk(_)) => 51,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::Declare(_)) => 10,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::DeclareOk(_)) => 11,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::Bind(_)) => 20,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::BindOk(_)) => 21,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::Purge(_)) => 30,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::PurgeOk(_)) => 31,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::Delete(_)) => 40,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::DeleteOk(_)) => 41,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::Unbind(_)) => 50,
+            #[cfg(feature = "class-queue")]
             AMQPClass::Queue(queue::AMQPMethod::UnbindOk(_)) => 51,
+            #[cfg(feature = "class-tx")]
             AMQPClass::Tx(tx::AMQPMethod::Select(_)) => 10,
+            #[cfg(feature = "class-tx")]
             AMQPClass::Tx(tx::AMQPMethod::SelectOk(_)) => 11,
+            #[cfg(feature = "class-tx")]
             AMQPClass::Tx(tx::AMQPMethod::Commit(_)) => 20,
+            #[cfg(feature = "class-tx")]
             AMQPClass::Tx(tx::AMQPMethod::CommitOk(_)) => 21,
+            #[cfg(feature = "class-tx")]
             AMQPClass::Tx(tx::AMQPMethod::Rollback(_)) => 30,
+            #[cfg(feature = "class-tx")]
             AMQPClass::Tx(tx::AMQPMethod::RollbackOk(_)) => 31,
+            #[cfg(feature = "class-confirm")]
             AMQPClass::Confirm(confirm::AMQPMethod::Select(_)) => 10,
+            #[cfg(feature = "class-confirm")]
             AMQPClass::Confirm(confirm::AMQPMethod::SelectOk(_)) => 11,
         }
     }
+
+    /// Get the compact identifier of the method (Generated)
+    pub fn id(&self) -> AMQPMethodId {
+        match self {
+            AMQPClass::Basic(basic::AMQPMethod::Qos(_)) => AMQPMethodId::BasicQos,
+            AMQPClass::Basic(basic::AMQPMethod::QosOk(_)) => AMQPMethodId::BasicQosOk,
+            AMQPClass::Basic(basic::AMQPMethod::Consume(_)) => AMQPMethodId::BasicConsume,
+            AMQPClass::Basic(basic::AMQPMethod::ConsumeOk(_)) => AMQPMethodId::BasicConsumeOk,
+            AMQPClass::Basic(basic::AMQPMethod::Cancel(_)) => AMQPMethodId::BasicCancel,
+            AMQPClass::Basic(basic::AMQPMethod::CancelOk(_)) => AMQPMethodId::BasicCancelOk,
+            AMQPClass::Basic(basic::AMQPMethod::Publish(_)) => AMQPMethodId::BasicPublish,
+            AMQPClass::Basic(basic::AMQPMethod::Return(_)) => AMQPMethodId::BasicReturn,
+            AMQPClass::Basic(basic::AMQPMethod::Deliver(_)) => AMQPMethodId::BasicDeliver,
+            AMQPClass::Basic(basic::AMQPMethod::Get(_)) => AMQPMethodId::BasicGet,
+            AMQPClass::Basic(basic::AMQPMethod::GetOk(_)) => AMQPMethodId::BasicGetOk,
+            AMQPClass::Basic(basic::AMQPMethod::GetEmpty(_)) => AMQPMethodId::BasicGetEmpty,
+            AMQPClass::Basic(basic::AMQPMethod::Ack(_)) => AMQPMethodId::BasicAck,
+            AMQPClass::Basic(basic::AMQPMethod::Reject(_)) => AMQPMethodId::BasicReject,
+            AMQPClass::Basic(basic::AMQPMethod::RecoverAsync(_)) => AMQPMethodId::BasicRecoverAsync,
+            AMQPClass::Basic(basic::AMQPMethod::Recover(_)) => AMQPMethodId::BasicRecover,
+            AMQPClass::Basic(basic::AMQPMethod::RecoverOk(_)) => AMQPMethodId::BasicRecoverOk,
+            AMQPClass::Basic(basic::AMQPMethod::Nack(_)) => AMQPMethodId::BasicNack,
+            AMQPClass::Connection(connection::AMQPMethod::Start(_)) => {
+                AMQPMethodId::ConnectionStart
+            }
+            AMQPClass::Connection(connection::AMQPMethod::StartOk(_)) => {
+                AMQPMethodId::ConnectionStartOk
+            }
+            AMQPClass::Connection(connection::AMQPMethod::Secure(_)) => {
+                AMQPMethodId::ConnectionSecure
+            }
+            AMQPClass::Connection(connection::AMQPMethod::SecureOk(_)) => {
+                AMQPMethodId::ConnectionSecureOk
+            }
+            AMQPClass::Connection(connection::AMQPMethod::Tune(_)) => AMQPMethodId::ConnectionTune,
+            AMQPClass::Connection(connection::AMQPMethod::TuneOk(_)) => {
+                AMQPMethodId::ConnectionTuneOk
+            }
+            AMQPClass::Connection(connection::AMQPMethod::Open(_)) => AMQPMethodId::ConnectionOpen,
+            AMQPClass::Connection(connection::AMQPMethod::OpenOk(_)) => {
+                AMQPMethodId::ConnectionOpenOk
+            }
+            AMQPClass::Connection(connection::AMQPMethod::Close(_)) => {
+                AMQPMethodId::ConnectionClose
+            }
+            AMQPClass::Connection(connection::AMQPMethod::CloseOk(_)) => {
+                AMQPMethodId::ConnectionCloseOk
+            }
+            AMQPClass::Connection(connection::AMQPMethod::Blocked(_)) => {
+                AMQPMethodId::ConnectionBlocked
+            }
+            AMQPClass::Connection(connection::AMQPMethod::Unblocked(_)) => {
+                AMQPMethodId::ConnectionUnblocked
+            }
+            AMQPClass::Connection(connection::AMQPMethod::UpdateSecret(_)) => {
+                AMQPMethodId::ConnectionUpdateSecret
+            }
+            AMQPClass::Connection(connection::AMQPMethod::UpdateSecretOk(_)) => {
+                AMQPMethodId::ConnectionUpdateSecretOk
+            }
+            AMQPClass::Channel(channel::AMQPMethod::Open(_)) => AMQPMethodId::ChannelOpen,
+            AMQPClass::Channel(channel::AMQPMethod::OpenOk(_)) => AMQPMethodId::ChannelOpenOk,
+            AMQPClass::Channel(channel::AMQPMethod::Flow(_)) => AMQPMethodId::ChannelFlow,
+            AMQPClass::Channel(channel::AMQPMethod::FlowOk(_)) => AMQPMethodId::ChannelFlowOk,
+            AMQPClass::Channel(channel::AMQPMethod::Close(_)) => AMQPMethodId::ChannelClose,
+            AMQPClass::Channel(channel::AMQPMethod::CloseOk(_)) => AMQPMethodId::ChannelCloseOk,
+            #[cfg(feature = "class-access")]
+            AMQPClass::Access(access::AMQPMethod::Request(_)) => AMQPMethodId::AccessRequest,
+            #[cfg(feature = "class-access")]
+            AMQPClass::Access(access::AMQPMethod::RequestOk(_)) => AMQPMethodId::AccessRequestOk,
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Declare(_)) => AMQPMethodId::ExchangeDeclare,
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::DeclareOk(_)) => {
+                AMQPMethodId::ExchangeDeclareOk
+            }
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Delete(_)) => AMQPMethodId::ExchangeDelete,
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::DeleteOk(_)) => {
+                AMQPMethodId::ExchangeDeleteOk
+            }
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Bind(_)) => AMQPMethodId::ExchangeBind,
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::BindOk(_)) => AMQPMethodId::ExchangeBindOk,
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Unbind(_)) => AMQPMethodId::ExchangeUnbind,
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::UnbindOk(_)) => {
+                AMQPMethodId::ExchangeUnbindOk
+            }
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Declare(_)) => AMQPMethodId::QueueDeclare,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::DeclareOk(_)) => AMQPMethodId::QueueDeclareOk,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Bind(_)) => AMQPMethodId::QueueBind,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::BindOk(_)) => AMQPMethodId::QueueBindOk,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Purge(_)) => AMQPMethodId::QueuePurge,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::PurgeOk(_)) => AMQPMethodId::QueuePurgeOk,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Delete(_)) => AMQPMethodId::QueueDelete,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::DeleteOk(_)) => AMQPMethodId::QueueDeleteOk,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Unbind(_)) => AMQPMethodId::QueueUnbind,
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::UnbindOk(_)) => AMQPMethodId::QueueUnbindOk,
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::Select(_)) => AMQPMethodId::TxSelect,
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::SelectOk(_)) => AMQPMethodId::TxSelectOk,
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::Commit(_)) => AMQPMethodId::TxCommit,
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::CommitOk(_)) => AMQPMethodId::TxCommitOk,
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::Rollback(_)) => AMQPMethodId::TxRollback,
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::RollbackOk(_)) => AMQPMethodId::TxRollbackOk,
+            #[cfg(feature = "class-confirm")]
+            AMQPClass::Confirm(confirm::AMQPMethod::Select(_)) => AMQPMethodId::ConfirmSelect,
+            #[cfg(feature = "class-confirm")]
+            AMQPClass::Confirm(confirm::AMQPMethod::SelectOk(_)) => AMQPMethodId::ConfirmSelectOk,
+        }
+    }
+
+    /// Whether this method is followed by a content header and body frames (Generated)
+    pub fn is_content_carrying(&self) -> bool {
+        is_content_carrying(self.get_amqp_class_id(), self.get_amqp_method_id())
+    }
+
+    /// Build a method from its arguments and the names of its class and its own, as in the
+    /// specification (Generated)
+    pub fn from_arguments(
+        class: &str,
+        method: &str,
+        arguments: &FieldTable,
+    ) -> Result<Self, String> {
+        match (class, method) {
+            ("basic", "qos") => basic::Qos::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Qos(method))),
+            ("basic", "qos-ok") => basic::QosOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::QosOk(method))),
+            ("basic", "consume") => basic::Consume::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Consume(method))),
+            ("basic", "consume-ok") => basic::ConsumeOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::ConsumeOk(method))),
+            ("basic", "cancel") => basic::Cancel::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Cancel(method))),
+            ("basic", "cancel-ok") => basic::CancelOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::CancelOk(method))),
+            ("basic", "publish") => basic::Publish::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Publish(method))),
+            ("basic", "return") => basic::Return::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Return(method))),
+            ("basic", "deliver") => basic::Deliver::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Deliver(method))),
+            ("basic", "get") => basic::Get::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Get(method))),
+            ("basic", "get-ok") => basic::GetOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::GetOk(method))),
+            ("basic", "get-empty") => basic::GetEmpty::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::GetEmpty(method))),
+            ("basic", "ack") => basic::Ack::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Ack(method))),
+            ("basic", "reject") => basic::Reject::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Reject(method))),
+            ("basic", "recover-async") => basic::RecoverAsync::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::RecoverAsync(method))),
+            ("basic", "recover") => basic::Recover::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Recover(method))),
+            ("basic", "recover-ok") => basic::RecoverOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::RecoverOk(method))),
+            ("basic", "nack") => basic::Nack::from_arguments(arguments)
+                .map(|method| AMQPClass::Basic(basic::AMQPMethod::Nack(method))),
+            ("connection", "start") => connection::Start::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::Start(method))),
+            ("connection", "start-ok") => connection::StartOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::StartOk(method))),
+            ("connection", "secure") => connection::Secure::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::Secure(method))),
+            ("connection", "secure-ok") => connection::SecureOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::SecureOk(method))),
+            ("connection", "tune") => connection::Tune::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::Tune(method))),
+            ("connection", "tune-ok") => connection::TuneOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::TuneOk(method))),
+            ("connection", "open") => connection::Open::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::Open(method))),
+            ("connection", "open-ok") => connection::OpenOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::OpenOk(method))),
+            ("connection", "close") => connection::Close::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::Close(method))),
+            ("connection", "close-ok") => connection::CloseOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::CloseOk(method))),
+            ("connection", "blocked") => connection::Blocked::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::Blocked(method))),
+            ("connection", "unblocked") => connection::Unblocked::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::Unblocked(method))),
+            ("connection", "update-secret") => connection::UpdateSecret::from_arguments(arguments)
+                .map(|method| AMQPClass::Connection(connection::AMQPMethod::UpdateSecret(method))),
+            ("connection", "update-secret-ok") => {
+                connection::UpdateSecretOk::from_arguments(arguments).map(|method| {
+                    AMQPClass::Connection(connection::AMQPMethod::UpdateSecretOk(method))
+                })
+            }
+            ("channel", "open") => channel::Open::from_arguments(arguments)
+                .map(|method| AMQPClass::Channel(channel::AMQPMethod::Open(method))),
+            ("channel", "open-ok") => channel::OpenOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Channel(channel::AMQPMethod::OpenOk(method))),
+            ("channel", "flow") => channel::Flow::from_arguments(arguments)
+                .map(|method| AMQPClass::Channel(channel::AMQPMethod::Flow(method))),
+            ("channel", "flow-ok") => channel::FlowOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Channel(channel::AMQPMethod::FlowOk(method))),
+            ("channel", "close") => channel::Close::from_arguments(arguments)
+                .map(|method| AMQPClass::Channel(channel::AMQPMethod::Close(method))),
+            ("channel", "close-ok") => channel::CloseOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Channel(channel::AMQPMethod::CloseOk(method))),
+            #[cfg(feature = "class-access")]
+            ("access", "request") => access::Request::from_arguments(arguments)
+                .map(|method| AMQPClass::Access(access::AMQPMethod::Request(method))),
+            #[cfg(feature = "class-access")]
+            ("access", "request-ok") => access::RequestOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Access(access::AMQPMethod::RequestOk(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "declare") => exchange::Declare::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::Declare(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "declare-ok") => exchange::DeclareOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::DeclareOk(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "delete") => exchange::Delete::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::Delete(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "delete-ok") => exchange::DeleteOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::DeleteOk(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "bind") => exchange::Bind::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::Bind(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "bind-ok") => exchange::BindOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::BindOk(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "unbind") => exchange::Unbind::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::Unbind(method))),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "unbind-ok") => exchange::UnbindOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Exchange(exchange::AMQPMethod::UnbindOk(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "declare") => queue::Declare::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::Declare(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "declare-ok") => queue::DeclareOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::DeclareOk(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "bind") => queue::Bind::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::Bind(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "bind-ok") => queue::BindOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::BindOk(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "purge") => queue::Purge::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::Purge(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "purge-ok") => queue::PurgeOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::PurgeOk(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "delete") => queue::Delete::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::Delete(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "delete-ok") => queue::DeleteOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::DeleteOk(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "unbind") => queue::Unbind::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::Unbind(method))),
+            #[cfg(feature = "class-queue")]
+            ("queue", "unbind-ok") => queue::UnbindOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Queue(queue::AMQPMethod::UnbindOk(method))),
+            #[cfg(feature = "class-tx")]
+            ("tx", "select") => tx::Select::from_arguments(arguments)
+                .map(|method| AMQPClass::Tx(tx::AMQPMethod::Select(method))),
+            #[cfg(feature = "class-tx")]
+            ("tx", "select-ok") => tx::SelectOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Tx(tx::AMQPMethod::SelectOk(method))),
+            #[cfg(feature = "class-tx")]
+            ("tx", "commit") => tx::Commit::from_arguments(arguments)
+                .map(|method| AMQPClass::Tx(tx::AMQPMethod::Commit(method))),
+            #[cfg(feature = "class-tx")]
+            ("tx", "commit-ok") => tx::CommitOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Tx(tx::AMQPMethod::CommitOk(method))),
+            #[cfg(feature = "class-tx")]
+            ("tx", "rollback") => tx::Rollback::from_arguments(arguments)
+                .map(|method| AMQPClass::Tx(tx::AMQPMethod::Rollback(method))),
+            #[cfg(feature = "class-tx")]
+            ("tx", "rollback-ok") => tx::RollbackOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Tx(tx::AMQPMethod::RollbackOk(method))),
+            #[cfg(feature = "class-confirm")]
+            ("confirm", "select") => confirm::Select::from_arguments(arguments)
+                .map(|method| AMQPClass::Confirm(confirm::AMQPMethod::Select(method))),
+            #[cfg(feature = "class-confirm")]
+            ("confirm", "select-ok") => confirm::SelectOk::from_arguments(arguments)
+                .map(|method| AMQPClass::Confirm(confirm::AMQPMethod::SelectOk(method))),
+            _ => Err(format!("Unknown method {}.{}", class, method)),
+        }
+    }
+
+    /// Get the arguments of a method from the names of its class and its own, as in the
+    /// specification (Generated)
+    pub fn arguments(class: &str, method: &str) -> Option<&'static [ArgumentDescriptor]> {
+        match (class, method) {
+            ("basic", "qos") => Some(basic::Qos::ARGUMENTS),
+            ("basic", "qos-ok") => Some(basic::QosOk::ARGUMENTS),
+            ("basic", "consume") => Some(basic::Consume::ARGUMENTS),
+            ("basic", "consume-ok") => Some(basic::ConsumeOk::ARGUMENTS),
+            ("basic", "cancel") => Some(basic::Cancel::ARGUMENTS),
+            ("basic", "cancel-ok") => Some(basic::CancelOk::ARGUMENTS),
+            ("basic", "publish") => Some(basic::Publish::ARGUMENTS),
+            ("basic", "return") => Some(basic::Return::ARGUMENTS),
+            ("basic", "deliver") => Some(basic::Deliver::ARGUMENTS),
+            ("basic", "get") => Some(basic::Get::ARGUMENTS),
+            ("basic", "get-ok") => Some(basic::GetOk::ARGUMENTS),
+            ("basic", "get-empty") => Some(basic::GetEmpty::ARGUMENTS),
+            ("basic", "ack") => Some(basic::Ack::ARGUMENTS),
+            ("basic", "reject") => Some(basic::Reject::ARGUMENTS),
+            ("basic", "recover-async") => Some(basic::RecoverAsync::ARGUMENTS),
+            ("basic", "recover") => Some(basic::Recover::ARGUMENTS),
+            ("basic", "recover-ok") => Some(basic::RecoverOk::ARGUMENTS),
+            ("basic", "nack") => Some(basic::Nack::ARGUMENTS),
+            ("connection", "start") => Some(connection::Start::ARGUMENTS),
+            ("connection", "start-ok") => Some(connection::StartOk::ARGUMENTS),
+            ("connection", "secure") => Some(connection::Secure::ARGUMENTS),
+            ("connection", "secure-ok") => Some(connection::SecureOk::ARGUMENTS),
+            ("connection", "tune") => Some(connection::Tune::ARGUMENTS),
+            ("connection", "tune-ok") => Some(connection::TuneOk::ARGUMENTS),
+            ("connection", "open") => Some(connection::Open::ARGUMENTS),
+            ("connection", "open-ok") => Some(connection::OpenOk::ARGUMENTS),
+            ("connection", "close") => Some(connection::Close::ARGUMENTS),
+            ("connection", "close-ok") => Some(connection::CloseOk::ARGUMENTS),
+            ("connection", "blocked") => Some(connection::Blocked::ARGUMENTS),
+            ("connection", "unblocked") => Some(connection::Unblocked::ARGUMENTS),
+            ("connection", "update-secret") => Some(connection::UpdateSecret::ARGUMENTS),
+            ("connection", "update-secret-ok") => Some(connection::UpdateSecretOk::ARGUMENTS),
+            ("channel", "open") => Some(channel::Open::ARGUMENTS),
+            ("channel", "open-ok") => Some(channel::OpenOk::ARGUMENTS),
+            ("channel", "flow") => Some(channel::Flow::ARGUMENTS),
+            ("channel", "flow-ok") => Some(channel::FlowOk::ARGUMENTS),
+            ("channel", "close") => Some(channel::Close::ARGUMENTS),
+            ("channel", "close-ok") => Some(channel::CloseOk::ARGUMENTS),
+            #[cfg(feature = "class-access")]
+            ("access", "request") => Some(access::Request::ARGUMENTS),
+            #[cfg(feature = "class-access")]
+            ("access", "request-ok") => Some(access::RequestOk::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "declare") => Some(exchange::Declare::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "declare-ok") => Some(exchange::DeclareOk::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "delete") => Some(exchange::Delete::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "delete-ok") => Some(exchange::DeleteOk::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "bind") => Some(exchange::Bind::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "bind-ok") => Some(exchange::BindOk::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "unbind") => Some(exchange::Unbind::ARGUMENTS),
+            #[cfg(feature = "class-exchange")]
+            ("exchange", "unbind-ok") => Some(exchange::UnbindOk::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "declare") => Some(queue::Declare::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "declare-ok") => Some(queue::DeclareOk::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "bind") => Some(queue::Bind::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "bind-ok") => Some(queue::BindOk::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "purge") => Some(queue::Purge::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "purge-ok") => Some(queue::PurgeOk::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "delete") => Some(queue::Delete::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "delete-ok") => Some(queue::DeleteOk::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "unbind") => Some(queue::Unbind::ARGUMENTS),
+            #[cfg(feature = "class-queue")]
+            ("queue", "unbind-ok") => Some(queue::UnbindOk::ARGUMENTS),
+            #[cfg(feature = "class-tx")]
+            ("tx", "select") => Some(tx::Select::ARGUMENTS),
+            #[cfg(feature = "class-tx")]
+            ("tx", "select-ok") => Some(tx::SelectOk::ARGUMENTS),
+            #[cfg(feature = "class-tx")]
+            ("tx", "commit") => Some(tx::Commit::ARGUMENTS),
+            #[cfg(feature = "class-tx")]
+            ("tx", "commit-ok") => Some(tx::CommitOk::ARGUMENTS),
+            #[cfg(feature = "class-tx")]
+            ("tx", "rollback") => Some(tx::Rollback::ARGUMENTS),
+            #[cfg(feature = "class-tx")]
+            ("tx", "rollback-ok") => Some(tx::RollbackOk::ARGUMENTS),
+            #[cfg(feature = "class-confirm")]
+            ("confirm", "select") => Some(confirm::Select::ARGUMENTS),
+            #[cfg(feature = "class-confirm")]
+            ("confirm", "select-ok") => Some(confirm::SelectOk::ARGUMENTS),
+            _ => None,
+        }
+    }
+}
+
+/// The compact identifier of an AMQP method, a (class id, method id) pair which fits in a byte
+/// (Generated)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AMQPMethodId {
+    /// basic.qos (Generated)
+    BasicQos,
+    /// basic.qos-ok (Generated)
+    BasicQosOk,
+    /// basic.consume (Generated)
+    BasicConsume,
+    /// basic.consume-ok (Generated)
+    BasicConsumeOk,
+    /// basic.cancel (Generated)
+    BasicCancel,
+    /// basic.cancel-ok (Generated)
+    BasicCancelOk,
+    /// basic.publish (Generated)
+    BasicPublish,
+    /// basic.return (Generated)
+    BasicReturn,
+    /// basic.deliver (Generated)
+    BasicDeliver,
+    /// basic.get (Generated)
+    BasicGet,
+    /// basic.get-ok (Generated)
+    BasicGetOk,
+    /// basic.get-empty (Generated)
+    BasicGetEmpty,
+    /// basic.ack (Generated)
+    BasicAck,
+    /// basic.reject (Generated)
+    BasicReject,
+    /// basic.recover-async (Generated)
+    BasicRecoverAsync,
+    /// basic.recover (Generated)
+    BasicRecover,
+    /// basic.recover-ok (Generated)
+    BasicRecoverOk,
+    /// basic.nack (Generated)
+    BasicNack,
+    /// connection.start (Generated)
+    ConnectionStart,
+    /// connection.start-ok (Generated)
+    ConnectionStartOk,
+    /// connection.secure (Generated)
+    ConnectionSecure,
+    /// connection.secure-ok (Generated)
+    ConnectionSecureOk,
+    /// connection.tune (Generated)
+    ConnectionTune,
+    /// connection.tune-ok (Generated)
+    ConnectionTuneOk,
+    /// connection.open (Generated)
+    ConnectionOpen,
+    /// connection.open-ok (Generated)
+    ConnectionOpenOk,
+    /// connection.close (Generated)
+    ConnectionClose,
+    /// connection.close-ok (Generated)
+    ConnectionCloseOk,
+    /// connection.blocked (Generated)
+    ConnectionBlocked,
+    /// connection.unblocked (Generated)
+    ConnectionUnblocked,
+    /// connection.update-secret (Generated)
+    ConnectionUpdateSecret,
+    /// connection.update-secret-ok (Generated)
+    ConnectionUpdateSecretOk,
+    /// channel.open (Generated)
+    ChannelOpen,
+    /// channel.open-ok (Generated)
+    ChannelOpenOk,
+    /// channel.flow (Generated)
+    ChannelFlow,
+    /// channel.flow-ok (Generated)
+    ChannelFlowOk,
+    /// channel.close (Generated)
+    ChannelClose,
+    /// channel.close-ok (Generated)
+    ChannelCloseOk,
+    /// access.request (Generated)
+    #[cfg(feature = "class-access")]
+    AccessRequest,
+    /// access.request-ok (Generated)
+    #[cfg(feature = "class-access")]
+    AccessRequestOk,
+    /// exchange.declare (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeDeclare,
+    /// exchange.declare-ok (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeDeclareOk,
+    /// exchange.delete (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeDelete,
+    /// exchange.delete-ok (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeDeleteOk,
+    /// exchange.bind (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeBind,
+    /// exchange.bind-ok (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeBindOk,
+    /// exchange.unbind (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeUnbind,
+    /// exchange.unbind-ok (Generated)
+    #[cfg(feature = "class-exchange")]
+    ExchangeUnbindOk,
+    /// queue.declare (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueDeclare,
+    /// queue.declare-ok (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueDeclareOk,
+    /// queue.bind (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueBind,
+    /// queue.bind-ok (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueBindOk,
+    /// queue.purge (Generated)
+    #[cfg(feature = "class-queue")]
+    QueuePurge,
+    /// queue.purge-ok (Generated)
+    #[cfg(feature = "class-queue")]
+    QueuePurgeOk,
+    /// queue.delete (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueDelete,
+    /// queue.delete-ok (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueDeleteOk,
+    /// queue.unbind (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueUnbind,
+    /// queue.unbind-ok (Generated)
+    #[cfg(feature = "class-queue")]
+    QueueUnbindOk,
+    /// tx.select (Generated)
+    #[cfg(feature = "class-tx")]
+    TxSelect,
+    /// tx.select-ok (Generated)
+    #[cfg(feature = "class-tx")]
+    TxSelectOk,
+    /// tx.commit (Generated)
+    #[cfg(feature = "class-tx")]
+    TxCommit,
+    /// tx.commit-ok (Generated)
+    #[cfg(feature = "class-tx")]
+    TxCommitOk,
+    /// tx.rollback (Generated)
+    #[cfg(feature = "class-tx")]
+    TxRollback,
+    /// tx.rollback-ok (Generated)
+    #[cfg(feature = "class-tx")]
+    TxRollbackOk,
+    /// confirm.select (Generated)
+    #[cfg(feature = "class-confirm")]
+    ConfirmSelect,
+    /// confirm.select-ok (Generated)
+    #[cfg(feature = "class-confirm")]
+    ConfirmSelectOk,
+}
+
+impl AMQPMethodId {
+    /// Get the identifier of the method with the given class and method ids (Generated)
+    pub fn from_ids(class_id: Identifier, method_id: Identifier) -> Option<Self> {
+        match (class_id, method_id) {
+            (60, 10) => Some(AMQPMethodId::BasicQos),
+            (60, 11) => Some(AMQPMethodId::BasicQosOk),
+            (60, 20) => Some(AMQPMethodId::BasicConsume),
+            (60, 21) => Some(AMQPMethodId::BasicConsumeOk),
+            (60, 30) => Some(AMQPMethodId::BasicCancel),
+            (60, 31) => Some(AMQPMethodId::BasicCancelOk),
+            (60, 40) => Some(AMQPMethodId::BasicPublish),
+            (60, 50) => Some(AMQPMethodId::BasicReturn),
+            (60, 60) => Some(AMQPMethodId::BasicDeliver),
+            (60, 70) => Some(AMQPMethodId::BasicGet),
+            (60, 71) => Some(AMQPMethodId::BasicGetOk),
+            (60, 72) => Some(AMQPMethodId::BasicGetEmpty),
+            (60, 80) => Some(AMQPMethodId::BasicAck),
+            (60, 90) => Some(AMQPMethodId::BasicReject),
+            (60, 100) => Some(AMQPMethodId::BasicRecoverAsync),
+            (60, 110) => Some(AMQPMethodId::BasicRecover),
+            (60, 111) => Some(AMQPMethodId::BasicRecoverOk),
+            (60, 120) => Some(AMQPMethodId::BasicNack),
+            (10, 10) => Some(AMQPMethodId::ConnectionStart),
+            (10, 11) => Some(AMQPMethodId::ConnectionStartOk),
+            (10, 20) => Some(AMQPMethodId::ConnectionSecure),
+            (10, 21) => Some(AMQPMethodId::ConnectionSecureOk),
+            (10, 30) => Some(AMQPMethodId::ConnectionTune),
+            (10, 31) => Some(AMQPMethodId::ConnectionTuneOk),
+            (10, 40) => Some(AMQPMethodId::ConnectionOpen),
+            (10, 41) => Some(AMQPMethodId::ConnectionOpenOk),
+            (10, 50) => Some(AMQPMethodId::ConnectionClose),
+            (10, 51) => Some(AMQPMethodId::ConnectionCloseOk),
+            (10, 60) => Some(AMQPMethodId::ConnectionBlocked),
+            (10, 61) => Some(AMQPMethodId::ConnectionUnblocked),
+            (10, 70) => Some(AMQPMethodId::ConnectionUpdateSecret),
+            (10, 71) => Some(AMQPMethodId::ConnectionUpdateSecretOk),
+            (20, 10) => Some(AMQPMethodId::ChannelOpen),
+            (20, 11) => Some(AMQPMethodId::ChannelOpenOk),
+            (20, 20) => Some(AMQPMethodId::ChannelFlow),
+            (20, 21) => Some(AMQPMethodId::ChannelFlowOk),
+            (20, 40) => Some(AMQPMethodId::ChannelClose),
+            (20, 41) => Some(AMQPMethodId::ChannelCloseOk),
+            #[cfg(feature = "class-access")]
+            (30, 10) => Some(AMQPMethodId::AccessRequest),
+            #[cfg(feature = "class-access")]
+            (30, 11) => Some(AMQPMethodId::AccessRequestOk),
+            #[cfg(feature = "class-exchange")]
+            (40, 10) => Some(AMQPMethodId::ExchangeDeclare),
+            #[cfg(feature = "class-exchange")]
+            (40, 11) => Some(AMQPMethodId::ExchangeDeclareOk),
+            #[cfg(feature = "class-exchange")]
+            (40, 20) => Some(AMQPMethodId::ExchangeDelete),
+            #[cfg(feature = "class-exchange")]
+            (40, 21) => Some(AMQPMethodId::ExchangeDeleteOk),
+            #[cfg(feature = "class-exchange")]
+            (40, 30) => Some(AMQPMethodId::ExchangeBind),
+            #[cfg(feature = "class-exchange")]
+            (40, 31) => Some(AMQPMethodId::ExchangeBindOk),
+            #[cfg(feature = "class-exchange")]
+            (40, 40) => Some(AMQPMethodId::ExchangeUnbind),
+            #[cfg(feature = "class-exchange")]
+            (40, 51) => Some(AMQPMethodId::ExchangeUnbindOk),
+            #[cfg(feature = "class-queue")]
+            (50, 10) => Some(AMQPMethodId::QueueDeclare),
+            #[cfg(feature = "class-queue")]
+            (50, 11) => Some(AMQPMethodId::QueueDeclareOk),
+            #[cfg(feature = "class-queue")]
+            (50, 20) => Some(AMQPMethodId::QueueBind),
+            #[cfg(feature = "class-queue")]
+            (50, 21) => Some(AMQPMethodId::QueueBindOk),
+            #[cfg(feature = "class-queue")]
+            (50, 30) => Some(AMQPMethodId::QueuePurge),
+            #[cfg(feature = "class-queue")]
+            (50, 31) => Some(AMQPMethodId::QueuePurgeOk),
+            #[cfg(feature = "class-queue")]
+            (50, 40) => Some(AMQPMethodId::QueueDelete),
+            #[cfg(feature = "class-queue")]
+            (50, 41) => Some(AMQPMethodId::QueueDeleteOk),
+            #[cfg(feature = "class-queue")]
+            (50, 50) => Some(AMQPMethodId::QueueUnbind),
+            #[cfg(feature = "class-queue")]
+            (50, 51) => Some(AMQPMethodId::QueueUnbindOk),
+            #[cfg(feature = "class-tx")]
+            (90, 10) => Some(AMQPMethodId::TxSelect),
+            #[cfg(feature = "class-tx")]
+            (90, 11) => Some(AMQPMethodId::TxSelectOk),
+            #[cfg(feature = "class-tx")]
+            (90, 20) => Some(AMQPMethodId::TxCommit),
+            #[cfg(feature = "class-tx")]
+            (90, 21) => Some(AMQPMethodId::TxCommitOk),
+            #[cfg(feature = "class-tx")]
+            (90, 30) => Some(AMQPMethodId::TxRollback),
+            #[cfg(feature = "class-tx")]
+            (90, 31) => Some(AMQPMethodId::TxRollbackOk),
+            #[cfg(feature = "class-confirm")]
+            (85, 10) => Some(AMQPMethodId::ConfirmSelect),
+            #[cfg(feature = "class-confirm")]
+            (85, 11) => Some(AMQPMethodId::ConfirmSelectOk),
+            _ => None,
+        }
+    }
+
+    /// Get the (class id, method id) pair of the method (Generated)
+    pub fn ids(self) -> (Identifier, Identifier) {
+        match self {
+            AMQPMethodId::BasicQos => (60, 10),
+            AMQPMethodId::BasicQosOk => (60, 11),
+            AMQPMethodId::BasicConsume => (60, 20),
+            AMQPMethodId::BasicConsumeOk => (60, 21),
+            AMQPMethodId::BasicCancel => (60, 30),
+            AMQPMethodId::BasicCancelOk => (60, 31),
+            AMQPMethodId::BasicPublish => (60, 40),
+            AMQPMethodId::BasicReturn => (60, 50),
+            AMQPMethodId::BasicDeliver => (60, 60),
+            AMQPMethodId::BasicGet => (60, 70),
+            AMQPMethodId::BasicGetOk => (60, 71),
+            AMQPMethodId::BasicGetEmpty => (60, 72),
+            AMQPMethodId::BasicAck => (60, 80),
+            AMQPMethodId::BasicReject => (60, 90),
+            AMQPMethodId::BasicRecoverAsync => (60, 100),
+            AMQPMethodId::BasicRecover => (60, 110),
+            AMQPMethodId::BasicRecoverOk => (60, 111),
+            AMQPMethodId::BasicNack => (60, 120),
+            AMQPMethodId::ConnectionStart => (10, 10),
+            AMQPMethodId::ConnectionStartOk => (10, 11),
+            AMQPMethodId::ConnectionSecure => (10, 20),
+            AMQPMethodId::ConnectionSecureOk => (10, 21),
+            AMQPMethodId::ConnectionTune => (10, 30),
+            AMQPMethodId::ConnectionTuneOk => (10, 31),
+            AMQPMethodId::ConnectionOpen => (10, 40),
+            AMQPMethodId::ConnectionOpenOk => (10, 41),
+            AMQPMethodId::ConnectionClose => (10, 50),
+            AMQPMethodId::ConnectionCloseOk => (10, 51),
+            AMQPMethodId::ConnectionBlocked => (10, 60),
+            AMQPMethodId::ConnectionUnblocked => (10, 61),
+            AMQPMethodId::ConnectionUpdateSecret => (10, 70),
+            AMQPMethodId::ConnectionUpdateSecretOk => (10, 71),
+            AMQPMethodId::ChannelOpen => (20, 10),
+            AMQPMethodId::ChannelOpenOk => (20, 11),
+            AMQPMethodId::ChannelFlow => (20, 20),
+            AMQPMethodId::ChannelFlowOk => (20, 21),
+            AMQPMethodId::ChannelClose => (20, 40),
+            AMQPMethodId::ChannelCloseOk => (20, 41),
+            #[cfg(feature = "class-access")]
+            AMQPMethodId::AccessRequest => (30, 10),
+            #[cfg(feature = "class-access")]
+            AMQPMethodId::AccessRequestOk => (30, 11),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDeclare => (40, 10),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDeclareOk => (40, 11),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDelete => (40, 20),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDeleteOk => (40, 21),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeBind => (40, 30),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeBindOk => (40, 31),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeUnbind => (40, 40),
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeUnbindOk => (40, 51),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDeclare => (50, 10),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDeclareOk => (50, 11),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueBind => (50, 20),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueBindOk => (50, 21),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueuePurge => (50, 30),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueuePurgeOk => (50, 31),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDelete => (50, 40),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDeleteOk => (50, 41),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueUnbind => (50, 50),
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueUnbindOk => (50, 51),
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxSelect => (90, 10),
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxSelectOk => (90, 11),
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxCommit => (90, 20),
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxCommitOk => (90, 21),
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxRollback => (90, 30),
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxRollbackOk => (90, 31),
+            #[cfg(feature = "class-confirm")]
+            AMQPMethodId::ConfirmSelect => (85, 10),
+            #[cfg(feature = "class-confirm")]
+            AMQPMethodId::ConfirmSelectOk => (85, 11),
+        }
+    }
+
+    /// Get the AMQP class id (Generated)
+    pub fn class_id(self) -> Identifier {
+        self.ids().0
+    }
+
+    /// Get the AMQP method id (Generated)
+    pub fn method_id(self) -> Identifier {
+        self.ids().1
+    }
+
+    /// Get the name of the method, as in the specification (Generated)
+    pub fn name(self) -> &'static str {
+        match self {
+            AMQPMethodId::BasicQos => "basic.qos",
+            AMQPMethodId::BasicQosOk => "basic.qos-ok",
+            AMQPMethodId::BasicConsume => "basic.consume",
+            AMQPMethodId::BasicConsumeOk => "basic.consume-ok",
+            AMQPMethodId::BasicCancel => "basic.cancel",
+            AMQPMethodId::BasicCancelOk => "basic.cancel-ok",
+            AMQPMethodId::BasicPublish => "basic.publish",
+            AMQPMethodId::BasicReturn => "basic.return",
+            AMQPMethodId::BasicDeliver => "basic.deliver",
+            AMQPMethodId::BasicGet => "basic.get",
+            AMQPMethodId::BasicGetOk => "basic.get-ok",
+            AMQPMethodId::BasicGetEmpty => "basic.get-empty",
+            AMQPMethodId::BasicAck => "basic.ack",
+            AMQPMethodId::BasicReject => "basic.reject",
+            AMQPMethodId::BasicRecoverAsync => "basic.recover-async",
+            AMQPMethodId::BasicRecover => "basic.recover",
+            AMQPMethodId::BasicRecoverOk => "basic.recover-ok",
+            AMQPMethodId::BasicNack => "basic.nack",
+            AMQPMethodId::ConnectionStart => "connection.start",
+            AMQPMethodId::ConnectionStartOk => "connection.start-ok",
+            AMQPMethodId::ConnectionSecure => "connection.secure",
+            AMQPMethodId::ConnectionSecureOk => "connection.secure-ok",
+            AMQPMethodId::ConnectionTune => "connection.tune",
+            AMQPMethodId::ConnectionTuneOk => "connection.tune-ok",
+            AMQPMethodId::ConnectionOpen => "connection.open",
+            AMQPMethodId::ConnectionOpenOk => "connection.open-ok",
+            AMQPMethodId::ConnectionClose => "connection.close",
+            AMQPMethodId::ConnectionCloseOk => "connection.close-ok",
+            AMQPMethodId::ConnectionBlocked => "connection.blocked",
+            AMQPMethodId::ConnectionUnblocked => "connection.unblocked",
+            AMQPMethodId::ConnectionUpdateSecret => "connection.update-secret",
+            AMQPMethodId::ConnectionUpdateSecretOk => "connection.update-secret-ok",
+            AMQPMethodId::ChannelOpen => "channel.open",
+            AMQPMethodId::ChannelOpenOk => "channel.open-ok",
+            AMQPMethodId::ChannelFlow => "channel.flow",
+            AMQPMethodId::ChannelFlowOk => "channel.flow-ok",
+            AMQPMethodId::ChannelClose => "channel.close",
+            AMQPMethodId::ChannelCloseOk => "channel.close-ok",
+            #[cfg(feature = "class-access")]
+            AMQPMethodId::AccessRequest => "access.request",
+            #[cfg(feature = "class-access")]
+            AMQPMethodId::AccessRequestOk => "access.request-ok",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDeclare => "exchange.declare",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDeclareOk => "exchange.declare-ok",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDelete => "exchange.delete",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeDeleteOk => "exchange.delete-ok",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeBind => "exchange.bind",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeBindOk => "exchange.bind-ok",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeUnbind => "exchange.unbind",
+            #[cfg(feature = "class-exchange")]
+            AMQPMethodId::ExchangeUnbindOk => "exchange.unbind-ok",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDeclare => "queue.declare",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDeclareOk => "queue.declare-ok",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueBind => "queue.bind",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueBindOk => "queue.bind-ok",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueuePurge => "queue.purge",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueuePurgeOk => "queue.purge-ok",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDelete => "queue.delete",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueDeleteOk => "queue.delete-ok",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueUnbind => "queue.unbind",
+            #[cfg(feature = "class-queue")]
+            AMQPMethodId::QueueUnbindOk => "queue.unbind-ok",
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxSelect => "tx.select",
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxSelectOk => "tx.select-ok",
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxCommit => "tx.commit",
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxCommitOk => "tx.commit-ok",
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxRollback => "tx.rollback",
+            #[cfg(feature = "class-tx")]
+            AMQPMethodId::TxRollbackOk => "tx.rollback-ok",
+            #[cfg(feature = "class-confirm")]
+            AMQPMethodId::ConfirmSelect => "confirm.select",
+            #[cfg(feature = "class-confirm")]
+            AMQPMethodId::ConfirmSelectOk => "confirm.select-ok",
+        }
+    }
+}
+
+impl fmt::Display for AMQPMethodId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The (class id, method id) of the methods followed by a content header and body frames (Generated)
+pub const CONTENT_METHODS: &[(Identifier, Identifier)] = &[(60, 40), (60, 50), (60, 60), (60, 71)];
+
+/// Whether the given method is followed by a content header and body frames (Generated)
+pub fn is_content_carrying(class_id: Identifier, method_id: Identifier) -> bool {
+    CONTENT_METHODS.contains(&(class_id, method_id))
+}
+
+/// Helpers for the server side of the protocol (Generated)
+#[cfg(feature = "server")]
+pub mod server {
+    use super::*;
+
+    /// The channel_max proposed in connection.tune (Generated)
+    pub const DEFAULT_CHANNEL_MAX: ShortUInt = 2047;
+    /// The frame_max proposed in connection.tune (Generated)
+    pub const DEFAULT_FRAME_MAX: LongUInt = 131072;
+    /// The heartbeat proposed in connection.tune (Generated)
+    pub const DEFAULT_HEARTBEAT: ShortUInt = 60;
+
+    /// The (class id, method id) of the methods the server sends (Generated)
+    pub const SENT_METHODS: &[(Identifier, Identifier)] = &[
+        (60, 11),
+        (60, 21),
+        (60, 30),
+        (60, 31),
+        (60, 50),
+        (60, 60),
+        (60, 71),
+        (60, 72),
+        (60, 80),
+        (60, 111),
+        (60, 120),
+        (10, 10),
+        (10, 20),
+        (10, 30),
+        (10, 41),
+        (10, 50),
+        (10, 51),
+        (10, 60),
+        (10, 61),
+        (10, 71),
+        (20, 11),
+        (20, 20),
+        (20, 21),
+        (20, 40),
+        (20, 41),
+        (30, 11),
+        (40, 11),
+        (40, 21),
+        (40, 31),
+        (40, 51),
+        (50, 11),
+        (50, 21),
+        (50, 31),
+        (50, 41),
+        (50, 51),
+        (90, 11),
+        (90, 21),
+        (90, 31),
+        (85, 11),
+    ];
+
+    /// The (class id, method id) of the methods the server receives (Generated)
+    pub const RECEIVED_METHODS: &[(Identifier, Identifier)] = &[
+        (60, 10),
+        (60, 20),
+        (60, 30),
+        (60, 31),
+        (60, 40),
+        (60, 70),
+        (60, 80),
+        (60, 90),
+        (60, 100),
+        (60, 110),
+        (60, 120),
+        (10, 11),
+        (10, 21),
+        (10, 31),
+        (10, 40),
+        (10, 50),
+        (10, 51),
+        (10, 60),
+        (10, 61),
+        (10, 70),
+        (20, 10),
+        (20, 20),
+        (20, 21),
+        (20, 40),
+        (20, 41),
+        (30, 10),
+        (40, 10),
+        (40, 20),
+        (40, 30),
+        (40, 40),
+        (50, 10),
+        (50, 20),
+        (50, 30),
+        (50, 40),
+        (50, 50),
+        (90, 10),
+        (90, 20),
+        (90, 30),
+        (85, 10),
+    ];
+
+    /// The connection.tune proposing the default values (Generated)
+    pub fn default_tune() -> connection::Tune {
+        connection::Tune {
+            channel_max: DEFAULT_CHANNEL_MAX,
+            frame_max: DEFAULT_FRAME_MAX,
+            heartbeat: DEFAULT_HEARTBEAT,
+        }
+    }
+
+    /// Whether the server sends this method (Generated)
+    pub fn sends(method: &AMQPClass) -> bool {
+        SENT_METHODS.contains(&(method.get_amqp_class_id(), method.get_amqp_method_id()))
+    }
+
+    /// Whether the server receives this method (Generated)
+    pub fn receives(method: &AMQPClass) -> bool {
+        RECEIVED_METHODS.contains(&(method.get_amqp_class_id(), method.get_amqp_method_id()))
+    }
+
+    /// The reply the server sends to this method, if any, with the fields shared with the
+    /// request copied and the other ones to fill in (Generated)
+    pub fn reply(method: &AMQPClass) -> Option<AMQPClass> {
+        match method {
+            AMQPClass::Basic(basic::AMQPMethod::Qos(request)) => {
+                Some(AMQPClass::Basic(basic::AMQPMethod::QosOk(request.into())))
+            }
+            AMQPClass::Basic(basic::AMQPMethod::Consume(request)) => Some(AMQPClass::Basic(
+                basic::AMQPMethod::ConsumeOk(request.into()),
+            )),
+            AMQPClass::Basic(basic::AMQPMethod::Cancel(request)) => Some(AMQPClass::Basic(
+                basic::AMQPMethod::CancelOk(request.into()),
+            )),
+            AMQPClass::Basic(basic::AMQPMethod::Get(request)) => {
+                Some(AMQPClass::Basic(basic::AMQPMethod::GetOk(request.into())))
+            }
+            AMQPClass::Basic(basic::AMQPMethod::Recover(request)) => Some(AMQPClass::Basic(
+                basic::AMQPMethod::RecoverOk(request.into()),
+            )),
+            AMQPClass::Connection(connection::AMQPMethod::Open(request)) => Some(
+                AMQPClass::Connection(connection::AMQPMethod::OpenOk(request.into())),
+            ),
+            AMQPClass::Connection(connection::AMQPMethod::Close(request)) => Some(
+                AMQPClass::Connection(connection::AMQPMethod::CloseOk(request.into())),
+            ),
+            AMQPClass::Connection(connection::AMQPMethod::UpdateSecret(request)) => Some(
+                AMQPClass::Connection(connection::AMQPMethod::UpdateSecretOk(request.into())),
+            ),
+            AMQPClass::Channel(channel::AMQPMethod::Open(request)) => Some(AMQPClass::Channel(
+                channel::AMQPMethod::OpenOk(request.into()),
+            )),
+            AMQPClass::Channel(channel::AMQPMethod::Flow(request)) => Some(AMQPClass::Channel(
+                channel::AMQPMethod::FlowOk(request.into()),
+            )),
+            AMQPClass::Channel(channel::AMQPMethod::Close(request)) => Some(AMQPClass::Channel(
+                channel::AMQPMethod::CloseOk(request.into()),
+            )),
+            #[cfg(feature = "class-access")]
+            AMQPClass::Access(access::AMQPMethod::Request(request)) => Some(AMQPClass::Access(
+                access::AMQPMethod::RequestOk(request.into()),
+            )),
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Declare(request)) => Some(
+                AMQPClass::Exchange(exchange::AMQPMethod::DeclareOk(request.into())),
+            ),
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Delete(request)) => Some(
+                AMQPClass::Exchange(exchange::AMQPMethod::DeleteOk(request.into())),
+            ),
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Bind(request)) => Some(AMQPClass::Exchange(
+                exchange::AMQPMethod::BindOk(request.into()),
+            )),
+            #[cfg(feature = "class-exchange")]
+            AMQPClass::Exchange(exchange::AMQPMethod::Unbind(request)) => Some(
+                AMQPClass::Exchange(exchange::AMQPMethod::UnbindOk(request.into())),
+            ),
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Declare(request)) => Some(AMQPClass::Queue(
+                queue::AMQPMethod::DeclareOk(request.into()),
+            )),
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Bind(request)) => {
+                Some(AMQPClass::Queue(queue::AMQPMethod::BindOk(request.into())))
+            }
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Purge(request)) => {
+                Some(AMQPClass::Queue(queue::AMQPMethod::PurgeOk(request.into())))
+            }
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Delete(request)) => Some(AMQPClass::Queue(
+                queue::AMQPMethod::DeleteOk(request.into()),
+            )),
+            #[cfg(feature = "class-queue")]
+            AMQPClass::Queue(queue::AMQPMethod::Unbind(request)) => Some(AMQPClass::Queue(
+                queue::AMQPMethod::UnbindOk(request.into()),
+            )),
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::Select(request)) => {
+                Some(AMQPClass::Tx(tx::AMQPMethod::SelectOk(request.into())))
+            }
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::Commit(request)) => {
+                Some(AMQPClass::Tx(tx::AMQPMethod::CommitOk(request.into())))
+            }
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(tx::AMQPMethod::Rollback(request)) => {
+                Some(AMQPClass::Tx(tx::AMQPMethod::RollbackOk(request.into())))
+            }
+            #[cfg(feature = "class-confirm")]
+            AMQPClass::Confirm(confirm::AMQPMethod::Select(request)) => Some(AMQPClass::Confirm(
+                confirm::AMQPMethod::SelectOk(request.into()),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Fuzz targets for the parsers, to be called from cargo-fuzz targets (Generated)
+///
+/// Each target parses arbitrary bytes and checks that whatever it accepts serializes and parses
+/// back.
+#[cfg(feature = "fuzzing")]
+pub mod fuzz {
+    use super::*;
+    use crate::frame::{gen_frame, parse_frame};
+
+    /// A fuzz target, taking arbitrary bytes (Generated)
+    pub type FuzzTarget = fn(&[u8]);
+
+    /// All the targets, by name (Generated)
+    pub const TARGETS: &[(&str, FuzzTarget)] = &[
+        ("frame", fuzz_frame),
+        ("basic", fuzz_basic),
+        ("connection", fuzz_connection),
+        ("channel", fuzz_channel),
+        #[cfg(feature = "class-access")]
+        ("access", fuzz_access),
+        #[cfg(feature = "class-exchange")]
+        ("exchange", fuzz_exchange),
+        #[cfg(feature = "class-queue")]
+        ("queue", fuzz_queue),
+        #[cfg(feature = "class-tx")]
+        ("tx", fuzz_tx),
+        #[cfg(feature = "class-confirm")]
+        ("confirm", fuzz_confirm),
+    ];
+
+    /// Fuzz the target picked by the first byte with the remaining ones (Generated)
+    pub fn fuzz_any(data: &[u8]) {
+        if let Some((target, data)) = data.split_first() {
+            (TARGETS[usize::from(*target) % TARGETS.len()].1)(data)
+        }
+    }
+
+    /// Fuzz the frame parser (Generated)
+    pub fn fuzz_frame(data: &[u8]) {
+        if let Ok((_, parsed)) = parse_frame(data) {
+            let bytes = cookie_factory::gen_simple(gen_frame(&parsed), Vec::new())
+                .expect("failed to serialize a parsed frame");
+            let (_, reparsed) =
+                parse_frame(bytes.as_slice()).expect("failed to parse a serialized frame");
+            /* Compare the serialized forms, as a NaN float makes a value differ from itself */
+            let reserialized = cookie_factory::gen_simple(gen_frame(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed frame");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the basic class, starting after the class id (Generated)
+    pub fn fuzz_basic(data: &[u8]) {
+        if let Ok((_, method)) = basic::parse_basic(data) {
+            let parsed = AMQPClass::Basic(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed basic method");
+            let (_, reparsed) =
+                parse_class(bytes.as_slice()).expect("failed to parse a serialized basic method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed basic method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the connection class, starting after the class id (Generated)
+    pub fn fuzz_connection(data: &[u8]) {
+        if let Ok((_, method)) = connection::parse_connection(data) {
+            let parsed = AMQPClass::Connection(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed connection method");
+            let (_, reparsed) = parse_class(bytes.as_slice())
+                .expect("failed to parse a serialized connection method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed connection method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the channel class, starting after the class id (Generated)
+    pub fn fuzz_channel(data: &[u8]) {
+        if let Ok((_, method)) = channel::parse_channel(data) {
+            let parsed = AMQPClass::Channel(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed channel method");
+            let (_, reparsed) =
+                parse_class(bytes.as_slice()).expect("failed to parse a serialized channel method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed channel method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the access class, starting after the class id (Generated)
+    #[cfg(feature = "class-access")]
+    pub fn fuzz_access(data: &[u8]) {
+        if let Ok((_, method)) = access::parse_access(data) {
+            let parsed = AMQPClass::Access(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed access method");
+            let (_, reparsed) =
+                parse_class(bytes.as_slice()).expect("failed to parse a serialized access method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed access method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the exchange class, starting after the class id (Generated)
+    #[cfg(feature = "class-exchange")]
+    pub fn fuzz_exchange(data: &[u8]) {
+        if let Ok((_, method)) = exchange::parse_exchange(data) {
+            let parsed = AMQPClass::Exchange(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed exchange method");
+            let (_, reparsed) = parse_class(bytes.as_slice())
+                .expect("failed to parse a serialized exchange method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed exchange method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the queue class, starting after the class id (Generated)
+    #[cfg(feature = "class-queue")]
+    pub fn fuzz_queue(data: &[u8]) {
+        if let Ok((_, method)) = queue::parse_queue(data) {
+            let parsed = AMQPClass::Queue(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed queue method");
+            let (_, reparsed) =
+                parse_class(bytes.as_slice()).expect("failed to parse a serialized queue method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed queue method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the tx class, starting after the class id (Generated)
+    #[cfg(feature = "class-tx")]
+    pub fn fuzz_tx(data: &[u8]) {
+        if let Ok((_, method)) = tx::parse_tx(data) {
+            let parsed = AMQPClass::Tx(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed tx method");
+            let (_, reparsed) =
+                parse_class(bytes.as_slice()).expect("failed to parse a serialized tx method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed tx method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+
+    /// Fuzz the parser of the methods of the confirm class, starting after the class id (Generated)
+    #[cfg(feature = "class-confirm")]
+    pub fn fuzz_confirm(data: &[u8]) {
+        if let Ok((_, method)) = confirm::parse_confirm(data) {
+            let parsed = AMQPClass::Confirm(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed confirm method");
+            let (_, reparsed) =
+                parse_class(bytes.as_slice()).expect("failed to parse a serialized confirm method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed confirm method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
 }
 
 /// basic (generated)
@@ -372,89 +1862,178 @@ pub mod basic {
 
     /// Parse basic (Generated)
     pub fn parse_basic<I: ParsableInput>(i: I) -> ParserResult<I, basic::AMQPMethod> {
+        parse_basic_with(i, ParsingOptions::default())
+    }
+
+    /// Parse basic, parsing its field tables with the given options (Generated)
+    pub fn parse_basic_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, basic::AMQPMethod> {
         context(
             "parse_basic",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
-                        10 => context("parse_qos", map(map(parse_qos, AMQPMethod::Qos), Some))
-                            .parse(i),
+                        10 => context(
+                            "parse_qos",
+                            map(
+                                map(|i: I| parse_qos_with(i, options), AMQPMethod::Qos),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
                         11 => context(
                             "parse_qos_ok",
-                            map(map(parse_qos_ok, AMQPMethod::QosOk), Some),
+                            map(
+                                map(|i: I| parse_qos_ok_with(i, options), AMQPMethod::QosOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         20 => context(
                             "parse_consume",
-                            map(map(parse_consume, AMQPMethod::Consume), Some),
+                            map(
+                                map(|i: I| parse_consume_with(i, options), AMQPMethod::Consume),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         21 => context(
                             "parse_consume_ok",
-                            map(map(parse_consume_ok, AMQPMethod::ConsumeOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_consume_ok_with(i, options),
+                                    AMQPMethod::ConsumeOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         30 => context(
                             "parse_cancel",
-                            map(map(parse_cancel, AMQPMethod::Cancel), Some),
+                            map(
+                                map(|i: I| parse_cancel_with(i, options), AMQPMethod::Cancel),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         31 => context(
                             "parse_cancel_ok",
-                            map(map(parse_cancel_ok, AMQPMethod::CancelOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_cancel_ok_with(i, options),
+                                    AMQPMethod::CancelOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         40 => context(
                             "parse_publish",
-                            map(map(parse_publish, AMQPMethod::Publish), Some),
+                            map(
+                                map(|i: I| parse_publish_with(i, options), AMQPMethod::Publish),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         50 => context(
                             "parse_return",
-                            map(map(parse_return, AMQPMethod::Return), Some),
+                            map(
+                                map(|i: I| parse_return_with(i, options), AMQPMethod::Return),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         60 => context(
                             "parse_deliver",
-                            map(map(parse_deliver, AMQPMethod::Deliver), Some),
+                            map(
+                                map(|i: I| parse_deliver_with(i, options), AMQPMethod::Deliver),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        70 => context(
+                            "parse_get",
+                            map(
+                                map(|i: I| parse_get_with(i, options), AMQPMethod::Get),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        70 => context("parse_get", map(map(parse_get, AMQPMethod::Get), Some))
-                            .parse(i),
                         71 => context(
                             "parse_get_ok",
-                            map(map(parse_get_ok, AMQPMethod::GetOk), Some),
+                            map(
+                                map(|i: I| parse_get_ok_with(i, options), AMQPMethod::GetOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         72 => context(
                             "parse_get_empty",
-                            map(map(parse_get_empty, AMQPMethod::GetEmpty), Some),
+                            map(
+                                map(
+                                    |i: I| parse_get_empty_with(i, options),
+                                    AMQPMethod::GetEmpty,
+                                ),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        80 => context(
+                            "parse_ack",
+                            map(
+                                map(|i: I| parse_ack_with(i, options), AMQPMethod::Ack),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        80 => context("parse_ack", map(map(parse_ack, AMQPMethod::Ack), Some))
-                            .parse(i),
                         90 => context(
                             "parse_reject",
-                            map(map(parse_reject, AMQPMethod::Reject), Some),
+                            map(
+                                map(|i: I| parse_reject_with(i, options), AMQPMethod::Reject),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         100 => context(
                             "parse_recover_async",
-                            map(map(parse_recover_async, AMQPMethod::RecoverAsync), Some),
+                            map(
+                                map(
+                                    |i: I| parse_recover_async_with(i, options),
+                                    AMQPMethod::RecoverAsync,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         110 => context(
                             "parse_recover",
-                            map(map(parse_recover, AMQPMethod::Recover), Some),
+                            map(
+                                map(|i: I| parse_recover_with(i, options), AMQPMethod::Recover),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         111 => context(
                             "parse_recover_ok",
-                            map(map(parse_recover_ok, AMQPMethod::RecoverOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_recover_ok_with(i, options),
+                                    AMQPMethod::RecoverOk,
+                                ),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        120 => context(
+                            "parse_nack",
+                            map(
+                                map(|i: I| parse_nack_with(i, options), AMQPMethod::Nack),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        120 => context("parse_nack", map(map(parse_nack, AMQPMethod::Nack), Some))
-                            .parse(i),
                         _ => Ok((i, None)),
                     }
                 }),
@@ -490,6 +2069,24 @@ pub mod basic {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_basic(self))
+        }
+    }
+
     /// The available methods in basic
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -550,10 +2147,58 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether qos is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of qos, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "prefetch_count",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: Some(|| AMQPValue::ShortUInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "global",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build qos with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                prefetch_count: 0,
+                global: false,
+            }
+        }
+
+        /// Build qos from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "prefetch_count" => method.prefetch_count = from_argument(name, value)?,
+                    "global" => method.global = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.qos", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse qos (Generated)
     pub fn parse_qos<I: ParsableInput>(i: I) -> ParserResult<I, Qos> {
+        parse_qos_with(i, ParsingOptions::default())
+    }
+
+    /// Parse qos, parsing its field tables with the given options (Generated)
+    pub fn parse_qos_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Qos> {
         let (i, _) = parse_long_uint.parse(i)?;
         let (i, prefetch_count) = parse_short_uint.parse(i)?;
         let (i, flags) = parse_flags(i, &["global"])?;
@@ -580,6 +2225,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Qos {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Qos(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Qos {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_qos(self)),
+            )
+        }
+    }
     /// qos-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct QosOk {}
@@ -594,10 +2260,46 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether qos-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of qos-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build qos-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build qos-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for basic.qos-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Qos> for QosOk {
+        /// Build the reply to qos, copying the fields they share (Generated)
+        fn from(_: &Qos) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse qos-ok (Generated)
     pub fn parse_qos_ok<I: ParsableInput>(i: I) -> ParserResult<I, QosOk> {
+        parse_qos_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse qos-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_qos_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, QosOk> {
         Ok((i, QosOk {}))
     }
 
@@ -610,6 +2312,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for QosOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::QosOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for QosOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_qos_ok(self)),
+            )
+        }
+    }
     /// consume (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Consume {
@@ -639,15 +2362,98 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             20
         }
+
+        /// Whether consume is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of consume, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "consumer_tag",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "no_local",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "no_ack",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "exclusive",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "arguments",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+        ];
+
+        /// Build consume with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: "".into(),
+                consumer_tag: "".into(),
+                no_local: false,
+                no_ack: false,
+                exclusive: false,
+                nowait: false,
+                arguments: Default::default(),
+            }
+        }
+
+        /// Build consume from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "consumer_tag" => method.consumer_tag = from_argument(name, value)?,
+                    "no_local" => method.no_local = from_argument(name, value)?,
+                    "no_ack" => method.no_ack = from_argument(name, value)?,
+                    "exclusive" => method.exclusive = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    "arguments" => method.arguments = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.consume", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse consume (Generated)
     pub fn parse_consume<I: ParsableInput>(i: I) -> ParserResult<I, Consume> {
+        parse_consume_with(i, ParsingOptions::default())
+    }
+
+    /// Parse consume, parsing its field tables with the given options (Generated)
+    pub fn parse_consume_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Consume> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, consumer_tag) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["no_local", "no_ack", "exclusive", "nowait"])?;
-        let (i, arguments) = parse_field_table.parse(i)?;
+        let (i, arguments) = parse_field_table_with(i, options)?;
         Ok((
             i,
             Consume {
@@ -681,6 +2487,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Consume {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Consume(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Consume {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_consume(self)),
+            )
+        }
+    }
     /// consume-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct ConsumeOk {
@@ -698,10 +2525,58 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             21
         }
+
+        /// Whether consume-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of consume-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "consumer_tag",
+            amqp_type: AMQPType::ShortString,
+            default_value: None,
+        }];
+
+        /// Build consume-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                consumer_tag: Default::default(),
+            }
+        }
+
+        /// Build consume-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "consumer_tag" => method.consumer_tag = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.consume-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Consume> for ConsumeOk {
+        /// Build the reply to consume, copying the fields they share (Generated)
+        fn from(request: &Consume) -> Self {
+            Self {
+                consumer_tag: request.consumer_tag.clone(),
+            }
+        }
     }
 
     /// Parse consume-ok (Generated)
     pub fn parse_consume_ok<I: ParsableInput>(i: I) -> ParserResult<I, ConsumeOk> {
+        parse_consume_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse consume-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_consume_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, ConsumeOk> {
         let (i, consumer_tag) = parse_short_string.parse(i)?;
         Ok((i, ConsumeOk { consumer_tag }))
     }
@@ -716,6 +2591,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for ConsumeOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::ConsumeOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for ConsumeOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_consume_ok(self)),
+            )
+        }
+    }
     /// cancel (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Cancel {
@@ -735,10 +2631,58 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             30
         }
+
+        /// Whether cancel is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of cancel, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "consumer_tag",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build cancel with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                consumer_tag: Default::default(),
+                nowait: false,
+            }
+        }
+
+        /// Build cancel from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "consumer_tag" => method.consumer_tag = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.cancel", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse cancel (Generated)
     pub fn parse_cancel<I: ParsableInput>(i: I) -> ParserResult<I, Cancel> {
+        parse_cancel_with(i, ParsingOptions::default())
+    }
+
+    /// Parse cancel, parsing its field tables with the given options (Generated)
+    pub fn parse_cancel_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Cancel> {
         let (i, consumer_tag) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["nowait"])?;
         Ok((
@@ -763,6 +2707,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Cancel {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Cancel(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Cancel {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_cancel(self)),
+            )
+        }
+    }
     /// cancel-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct CancelOk {
@@ -780,10 +2745,58 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             31
         }
+
+        /// Whether cancel-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of cancel-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "consumer_tag",
+            amqp_type: AMQPType::ShortString,
+            default_value: None,
+        }];
+
+        /// Build cancel-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                consumer_tag: Default::default(),
+            }
+        }
+
+        /// Build cancel-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "consumer_tag" => method.consumer_tag = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.cancel-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Cancel> for CancelOk {
+        /// Build the reply to cancel, copying the fields they share (Generated)
+        fn from(request: &Cancel) -> Self {
+            Self {
+                consumer_tag: request.consumer_tag.clone(),
+            }
+        }
     }
 
     /// Parse cancel-ok (Generated)
     pub fn parse_cancel_ok<I: ParsableInput>(i: I) -> ParserResult<I, CancelOk> {
+        parse_cancel_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse cancel-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_cancel_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, CancelOk> {
         let (i, consumer_tag) = parse_short_string.parse(i)?;
         Ok((i, CancelOk { consumer_tag }))
     }
@@ -798,6 +2811,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for CancelOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::CancelOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for CancelOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_cancel_ok(self)),
+            )
+        }
+    }
     /// publish (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Publish {
@@ -821,10 +2855,72 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             40
         }
+
+        /// Whether publish is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            true
+        }
+
+        /// The arguments of publish, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "mandatory",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "immediate",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build publish with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                exchange: "".into(),
+                routing_key: "".into(),
+                mandatory: false,
+                immediate: false,
+            }
+        }
+
+        /// Build publish from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    "mandatory" => method.mandatory = from_argument(name, value)?,
+                    "immediate" => method.immediate = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.publish", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse publish (Generated)
     pub fn parse_publish<I: ParsableInput>(i: I) -> ParserResult<I, Publish> {
+        parse_publish_with(i, ParsingOptions::default())
+    }
+
+    /// Parse publish, parsing its field tables with the given options (Generated)
+    pub fn parse_publish_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Publish> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, exchange) = parse_short_string.parse(i)?;
         let (i, routing_key) = parse_short_string.parse(i)?;
@@ -856,6 +2952,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Publish {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Publish(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Publish {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_publish(self)),
+            )
+        }
+    }
     /// return (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Return {
@@ -879,10 +2996,72 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             50
         }
+
+        /// Whether return is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            true
+        }
+
+        /// The arguments of return, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "reply_code",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "reply_text",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+        ];
+
+        /// Build return with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                reply_code: Default::default(),
+                reply_text: "".into(),
+                exchange: Default::default(),
+                routing_key: Default::default(),
+            }
+        }
+
+        /// Build return from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "reply_code" => method.reply_code = from_argument(name, value)?,
+                    "reply_text" => method.reply_text = from_argument(name, value)?,
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.return", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse return (Generated)
     pub fn parse_return<I: ParsableInput>(i: I) -> ParserResult<I, Return> {
+        parse_return_with(i, ParsingOptions::default())
+    }
+
+    /// Parse return, parsing its field tables with the given options (Generated)
+    pub fn parse_return_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Return> {
         let (i, reply_code) = parse_short_uint.parse(i)?;
         let (i, reply_text) = parse_short_string.parse(i)?;
         let (i, exchange) = parse_short_string.parse(i)?;
@@ -911,6 +3090,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Return {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Return(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Return {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_return(self)),
+            )
+        }
+    }
     /// deliver (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Deliver {
@@ -936,10 +3136,79 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             60
         }
+
+        /// Whether deliver is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            true
+        }
+
+        /// The arguments of deliver, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "consumer_tag",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "delivery_tag",
+                amqp_type: AMQPType::LongLongUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "redelivered",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+        ];
+
+        /// Build deliver with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                consumer_tag: Default::default(),
+                delivery_tag: Default::default(),
+                redelivered: false,
+                exchange: Default::default(),
+                routing_key: Default::default(),
+            }
+        }
+
+        /// Build deliver from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "consumer_tag" => method.consumer_tag = from_argument(name, value)?,
+                    "delivery_tag" => method.delivery_tag = from_argument(name, value)?,
+                    "redelivered" => method.redelivered = from_argument(name, value)?,
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.deliver", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse deliver (Generated)
     pub fn parse_deliver<I: ParsableInput>(i: I) -> ParserResult<I, Deliver> {
+        parse_deliver_with(i, ParsingOptions::default())
+    }
+
+    /// Parse deliver, parsing its field tables with the given options (Generated)
+    pub fn parse_deliver_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Deliver> {
         let (i, consumer_tag) = parse_short_string.parse(i)?;
         let (i, delivery_tag) = parse_long_long_uint.parse(i)?;
         let (i, flags) = parse_flags(i, &["redelivered"])?;
@@ -973,6 +3242,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Deliver {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Deliver(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Deliver {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_deliver(self)),
+            )
+        }
+    }
     /// get (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Get {
@@ -992,10 +3282,58 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             70
         }
+
+        /// Whether get is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of get, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "no_ack",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build get with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: "".into(),
+                no_ack: false,
+            }
+        }
+
+        /// Build get from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "no_ack" => method.no_ack = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.get", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse get (Generated)
     pub fn parse_get<I: ParsableInput>(i: I) -> ParserResult<I, Get> {
+        parse_get_with(i, ParsingOptions::default())
+    }
+
+    /// Parse get, parsing its field tables with the given options (Generated)
+    pub fn parse_get_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Get> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["no_ack"])?;
@@ -1022,6 +3360,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Get {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Get(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Get {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_get(self)),
+            )
+        }
+    }
     /// get-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct GetOk {
@@ -1047,10 +3406,86 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             71
         }
+
+        /// Whether get-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            true
+        }
+
+        /// The arguments of get-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "delivery_tag",
+                amqp_type: AMQPType::LongLongUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "redelivered",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "message_count",
+                amqp_type: AMQPType::LongUInt,
+                default_value: None,
+            },
+        ];
+
+        /// Build get-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                delivery_tag: Default::default(),
+                redelivered: false,
+                exchange: Default::default(),
+                routing_key: Default::default(),
+                message_count: Default::default(),
+            }
+        }
+
+        /// Build get-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "delivery_tag" => method.delivery_tag = from_argument(name, value)?,
+                    "redelivered" => method.redelivered = from_argument(name, value)?,
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    "message_count" => method.message_count = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.get-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Get> for GetOk {
+        /// Build the reply to get, copying the fields they share (Generated)
+        fn from(_: &Get) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse get-ok (Generated)
     pub fn parse_get_ok<I: ParsableInput>(i: I) -> ParserResult<I, GetOk> {
+        parse_get_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse get-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_get_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, GetOk> {
         let (i, delivery_tag) = parse_long_long_uint.parse(i)?;
         let (i, flags) = parse_flags(i, &["redelivered"])?;
         let (i, exchange) = parse_short_string.parse(i)?;
@@ -1084,6 +3519,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for GetOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::GetOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for GetOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_get_ok(self)),
+            )
+        }
+    }
     /// get-empty (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct GetEmpty {}
@@ -1098,10 +3554,39 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             72
         }
+
+        /// Whether get-empty is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of get-empty, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build get-empty with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build get-empty from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for basic.get-empty", name));
+            }
+            Ok(Self::spec_default())
+        }
     }
 
     /// Parse get-empty (Generated)
     pub fn parse_get_empty<I: ParsableInput>(i: I) -> ParserResult<I, GetEmpty> {
+        parse_get_empty_with(i, ParsingOptions::default())
+    }
+
+    /// Parse get-empty, parsing its field tables with the given options (Generated)
+    pub fn parse_get_empty_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, GetEmpty> {
         let (i, _) = parse_short_string.parse(i)?;
         Ok((i, GetEmpty {}))
     }
@@ -1116,6 +3601,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for GetEmpty {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::GetEmpty(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for GetEmpty {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_get_empty(self)),
+            )
+        }
+    }
     /// ack (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Ack {
@@ -1135,10 +3641,58 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             80
         }
+
+        /// Whether ack is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of ack, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "delivery_tag",
+                amqp_type: AMQPType::LongLongUInt,
+                default_value: Some(|| AMQPValue::LongLongInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "multiple",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build ack with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                delivery_tag: 0,
+                multiple: false,
+            }
+        }
+
+        /// Build ack from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "delivery_tag" => method.delivery_tag = from_argument(name, value)?,
+                    "multiple" => method.multiple = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.ack", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse ack (Generated)
     pub fn parse_ack<I: ParsableInput>(i: I) -> ParserResult<I, Ack> {
+        parse_ack_with(i, ParsingOptions::default())
+    }
+
+    /// Parse ack, parsing its field tables with the given options (Generated)
+    pub fn parse_ack_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Ack> {
         let (i, delivery_tag) = parse_long_long_uint.parse(i)?;
         let (i, flags) = parse_flags(i, &["multiple"])?;
         Ok((
@@ -1163,6 +3717,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Ack {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Ack(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Ack {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_ack(self)),
+            )
+        }
+    }
     /// reject (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Reject {
@@ -1182,17 +3757,65 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             90
         }
+
+        /// Whether reject is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of reject, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "delivery_tag",
+                amqp_type: AMQPType::LongLongUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "requeue",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(true)),
+            },
+        ];
+
+        /// Build reject with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                delivery_tag: Default::default(),
+                requeue: true,
+            }
+        }
+
+        /// Build reject from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "delivery_tag" => method.delivery_tag = from_argument(name, value)?,
+                    "requeue" => method.requeue = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.reject", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse reject (Generated)
     pub fn parse_reject<I: ParsableInput>(i: I) -> ParserResult<I, Reject> {
+        parse_reject_with(i, ParsingOptions::default())
+    }
+
+    /// Parse reject, parsing its field tables with the given options (Generated)
+    pub fn parse_reject_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Reject> {
         let (i, delivery_tag) = parse_long_long_uint.parse(i)?;
         let (i, flags) = parse_flags(i, &["requeue"])?;
         Ok((
             i,
             Reject {
                 delivery_tag,
-                requeue: flags.get_flag("requeue").unwrap_or(false),
+                requeue: flags.get_flag("requeue").unwrap_or(true),
             },
         ))
     }
@@ -1210,6 +3833,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Reject {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Reject(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Reject {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_reject(self)),
+            )
+        }
+    }
     /// recover-async (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct RecoverAsync {
@@ -1227,10 +3871,47 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             100
         }
+
+        /// Whether recover-async is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of recover-async, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "requeue",
+            amqp_type: AMQPType::Boolean,
+            default_value: Some(|| AMQPValue::Boolean(false)),
+        }];
+
+        /// Build recover-async with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self { requeue: false }
+        }
+
+        /// Build recover-async from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "requeue" => method.requeue = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.recover-async", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse recover-async (Generated)
     pub fn parse_recover_async<I: ParsableInput>(i: I) -> ParserResult<I, RecoverAsync> {
+        parse_recover_async_with(i, ParsingOptions::default())
+    }
+
+    /// Parse recover-async, parsing its field tables with the given options (Generated)
+    pub fn parse_recover_async_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, RecoverAsync> {
         let (i, flags) = parse_flags(i, &["requeue"])?;
         Ok((
             i,
@@ -1252,6 +3933,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for RecoverAsync {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::RecoverAsync(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for RecoverAsync {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_recover_async(self)),
+            )
+        }
+    }
     /// recover (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Recover {
@@ -1269,10 +3971,47 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             110
         }
+
+        /// Whether recover is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of recover, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "requeue",
+            amqp_type: AMQPType::Boolean,
+            default_value: Some(|| AMQPValue::Boolean(false)),
+        }];
+
+        /// Build recover with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self { requeue: false }
+        }
+
+        /// Build recover from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "requeue" => method.requeue = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.recover", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse recover (Generated)
     pub fn parse_recover<I: ParsableInput>(i: I) -> ParserResult<I, Recover> {
+        parse_recover_with(i, ParsingOptions::default())
+    }
+
+    /// Parse recover, parsing its field tables with the given options (Generated)
+    pub fn parse_recover_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Recover> {
         let (i, flags) = parse_flags(i, &["requeue"])?;
         Ok((
             i,
@@ -1294,6 +4033,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Recover {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Recover(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Recover {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_recover(self)),
+            )
+        }
+    }
     /// recover-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct RecoverOk {}
@@ -1308,10 +4068,46 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             111
         }
+
+        /// Whether recover-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of recover-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build recover-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build recover-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for basic.recover-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Recover> for RecoverOk {
+        /// Build the reply to recover, copying the fields they share (Generated)
+        fn from(_: &Recover) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse recover-ok (Generated)
     pub fn parse_recover_ok<I: ParsableInput>(i: I) -> ParserResult<I, RecoverOk> {
+        parse_recover_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse recover-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_recover_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, RecoverOk> {
         Ok((i, RecoverOk {}))
     }
 
@@ -1324,6 +4120,27 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for RecoverOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::RecoverOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for RecoverOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_recover_ok(self)),
+            )
+        }
+    }
     /// nack (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Nack {
@@ -1345,10 +4162,65 @@ pub mod basic {
         pub fn get_amqp_method_id(&self) -> Identifier {
             120
         }
+
+        /// Whether nack is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of nack, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "delivery_tag",
+                amqp_type: AMQPType::LongLongUInt,
+                default_value: Some(|| AMQPValue::LongLongInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "multiple",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "requeue",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(true)),
+            },
+        ];
+
+        /// Build nack with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                delivery_tag: 0,
+                multiple: false,
+                requeue: true,
+            }
+        }
+
+        /// Build nack from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "delivery_tag" => method.delivery_tag = from_argument(name, value)?,
+                    "multiple" => method.multiple = from_argument(name, value)?,
+                    "requeue" => method.requeue = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for basic.nack", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse nack (Generated)
     pub fn parse_nack<I: ParsableInput>(i: I) -> ParserResult<I, Nack> {
+        parse_nack_with(i, ParsingOptions::default())
+    }
+
+    /// Parse nack, parsing its field tables with the given options (Generated)
+    pub fn parse_nack_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Nack> {
         let (i, delivery_tag) = parse_long_long_uint.parse(i)?;
         let (i, flags) = parse_flags(i, &["multiple", "requeue"])?;
         Ok((
@@ -1356,7 +4228,7 @@ pub mod basic {
             Nack {
                 delivery_tag,
                 multiple: flags.get_flag("multiple").unwrap_or(false),
-                requeue: flags.get_flag("requeue").unwrap_or(false),
+                requeue: flags.get_flag("requeue").unwrap_or(true),
             },
         ))
     }
@@ -1375,8 +4247,30 @@ pub mod basic {
             Ok(input)
         }
     }
+
+    impl Decode for Nack {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Basic(AMQPMethod::Nack(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Nack {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(60), gen_nack(self)),
+            )
+        }
+    }
     /// basic properties (Generated)
-    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct AMQPProperties {
         content_type: Option<ShortString>,
         content_encoding: Option<ShortString>,
@@ -1521,72 +4415,145 @@ pub mod basic {
         pub fn cluster_id(&self) -> &Option<ShortString> {
             &self.cluster_id
         }
-        /// Get the bitmask for serialization (Generated)
-        #[allow(clippy::identity_op)]
+        /// Get a mutable reference to content-type (Generated)
+        pub fn content_type_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.content_type
+        }
+        /// Get a mutable reference to content-encoding (Generated)
+        pub fn content_encoding_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.content_encoding
+        }
+        /// Get a mutable reference to headers (Generated)
+        pub fn headers_mut(&mut self) -> &mut Option<FieldTable> {
+            &mut self.headers
+        }
+        /// Get a mutable reference to delivery-mode (Generated)
+        pub fn delivery_mode_mut(&mut self) -> &mut Option<ShortShortUInt> {
+            &mut self.delivery_mode
+        }
+        /// Get a mutable reference to priority (Generated)
+        pub fn priority_mut(&mut self) -> &mut Option<ShortShortUInt> {
+            &mut self.priority
+        }
+        /// Get a mutable reference to correlation-id (Generated)
+        pub fn correlation_id_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.correlation_id
+        }
+        /// Get a mutable reference to reply-to (Generated)
+        pub fn reply_to_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.reply_to
+        }
+        /// Get a mutable reference to expiration (Generated)
+        pub fn expiration_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.expiration
+        }
+        /// Get a mutable reference to message-id (Generated)
+        pub fn message_id_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.message_id
+        }
+        /// Get a mutable reference to timestamp (Generated)
+        pub fn timestamp_mut(&mut self) -> &mut Option<Timestamp> {
+            &mut self.timestamp
+        }
+        /// Get a mutable reference to type (Generated)
+        pub fn type_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.kind
+        }
+        /// Get a mutable reference to user-id (Generated)
+        pub fn user_id_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.user_id
+        }
+        /// Get a mutable reference to app-id (Generated)
+        pub fn app_id_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.app_id
+        }
+        /// Get a mutable reference to cluster-id (Generated)
+        pub fn cluster_id_mut(&mut self) -> &mut Option<ShortString> {
+            &mut self.cluster_id
+        }
+        /// Get the id of the class the properties belong to (Generated)
+        pub fn class_id(&self) -> Identifier {
+            60
+        }
+
+        /// Get the bitmask for serialization, same as [flags](#method.flags) (Generated)
         pub fn bitmask(&self) -> ShortUInt {
-            (if self.content_type.is_some() {
-                1 << (15 - 0)
-            } else {
-                0
-            }) + (if self.content_encoding.is_some() {
-                1 << (15 - 1)
-            } else {
-                0
-            }) + (if self.headers.is_some() {
-                1 << (15 - 2)
-            } else {
-                0
-            }) + (if self.delivery_mode.is_some() {
-                1 << (15 - 3)
-            } else {
-                0
-            }) + (if self.priority.is_some() {
-                1 << (15 - 4)
-            } else {
-                0
-            }) + (if self.correlation_id.is_some() {
-                1 << (15 - 5)
-            } else {
-                0
-            }) + (if self.reply_to.is_some() {
-                1 << (15 - 6)
-            } else {
-                0
-            }) + (if self.expiration.is_some() {
-                1 << (15 - 7)
-            } else {
-                0
-            }) + (if self.message_id.is_some() {
-                1 << (15 - 8)
-            } else {
-                0
-            }) + (if self.timestamp.is_some() {
-                1 << (15 - 9)
-            } else {
-                0
-            }) + (if self.kind.is_some() {
-                1 << (15 - 10)
-            } else {
-                0
-            }) + (if self.user_id.is_some() {
-                1 << (15 - 11)
-            } else {
-                0
-            }) + (if self.app_id.is_some() {
-                1 << (15 - 12)
-            } else {
-                0
-            }) + (if self.cluster_id.is_some() {
-                1 << (15 - 13)
-            } else {
-                0
-            })
+            self.flags()
+        }
+
+        /// Get the property flags of the content header, telling which properties are set (Generated)
+        pub fn flags(&self) -> ShortUInt {
+            PROPERTY_FLAGS
+                .iter()
+                .zip([
+                    self.content_type.is_some(),
+                    self.content_encoding.is_some(),
+                    self.headers.is_some(),
+                    self.delivery_mode.is_some(),
+                    self.priority.is_some(),
+                    self.correlation_id.is_some(),
+                    self.reply_to.is_some(),
+                    self.expiration.is_some(),
+                    self.message_id.is_some(),
+                    self.timestamp.is_some(),
+                    self.kind.is_some(),
+                    self.user_id.is_some(),
+                    self.app_id.is_some(),
+                    self.cluster_id.is_some(),
+                ])
+                .filter(|(_, is_set)| *is_set)
+                .fold(0, |flags, ((_, flag), _)| flags | flag)
+        }
+
+        /// Get the names of the properties set according to the property flags, in serialization order (Generated)
+        pub fn from_flags(flags: ShortUInt) -> Result<Vec<&'static str>, String> {
+            let known = PROPERTY_FLAGS
+                .iter()
+                .fold(0, |known, (_, flag)| known | flag);
+            if flags & !known != 0 {
+                return Err(format!(
+                    "Unknown basic property flags: {:#018b}",
+                    flags & !known
+                ));
+            }
+            Ok(PROPERTY_FLAGS
+                .iter()
+                .filter(|(_, flag)| flags & flag != 0)
+                .map(|(name, _)| *name)
+                .collect())
         }
     }
 
-    /// Parse basic properties (Generated)
+    /// The name and flag of each basic property, in serialization order (Generated)
     #[allow(clippy::identity_op)]
+    pub const PROPERTY_FLAGS: &[(&str, ShortUInt)] = &[
+        ("content_type", 1 << (15 - 0)),
+        ("content_encoding", 1 << (15 - 1)),
+        ("headers", 1 << (15 - 2)),
+        ("delivery_mode", 1 << (15 - 3)),
+        ("priority", 1 << (15 - 4)),
+        ("correlation_id", 1 << (15 - 5)),
+        ("reply_to", 1 << (15 - 6)),
+        ("expiration", 1 << (15 - 7)),
+        ("message_id", 1 << (15 - 8)),
+        ("timestamp", 1 << (15 - 9)),
+        ("kind", 1 << (15 - 10)),
+        ("user_id", 1 << (15 - 11)),
+        ("app_id", 1 << (15 - 12)),
+        ("cluster_id", 1 << (15 - 13)),
+    ];
+
+    /// Parse basic properties (Generated)
     pub fn parse_properties<I: ParsableInput>(i: I) -> ParserResult<I, AMQPProperties> {
+        parse_properties_with(i, ParsingOptions::default())
+    }
+
+    /// Parse basic properties, parsing their field tables with the given options (Generated)
+    #[allow(clippy::identity_op)]
+    pub fn parse_properties_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, AMQPProperties> {
         let (i, flags) = parse_short_uint(i)?;
         let (i, content_type) = if flags & (1 << (15 - 0)) != 0 {
             map(parse_short_string, Some).parse(i)?
@@ -1599,7 +4566,7 @@ pub mod basic {
             (i, None)
         };
         let (i, headers) = if flags & (1 << (15 - 2)) != 0 {
-            map(parse_field_table, Some).parse(i)?
+            map(|i: I| parse_field_table_with(i, options), Some).parse(i)?
         } else {
             (i, None)
         };
@@ -1729,6 +4696,295 @@ pub mod basic {
             Ok(input)
         })
     }
+
+    impl Decode for AMQPProperties {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, parse_properties)
+        }
+    }
+
+    impl Encode for AMQPProperties {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_properties(self))
+        }
+    }
+
+    /// basic properties whose field tables are kept encoded, to decode them on demand (Generated)
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct LazyProperties {
+        /// The properties, without their field tables (Generated)
+        pub properties: AMQPProperties,
+        /// headers, kept encoded (Generated)
+        pub headers: Option<LazyFieldTable>,
+    }
+
+    impl LazyProperties {
+        /// Decode the field tables, making regular properties (Generated)
+        pub fn decode(self) -> Result<AMQPProperties, String> {
+            let mut properties = self.properties;
+            properties.headers = self.headers.map(|table| table.decode()).transpose()?;
+            Ok(properties)
+        }
+    }
+
+    /// Parse basic properties, keeping their field tables encoded (Generated)
+    #[allow(clippy::identity_op)]
+    pub fn parse_lazy_properties<I: ParsableInput>(i: I) -> ParserResult<I, LazyProperties> {
+        let (i, flags) = parse_short_uint(i)?;
+        let (i, content_type) = if flags & (1 << (15 - 0)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, content_encoding) = if flags & (1 << (15 - 1)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, headers) = if flags & (1 << (15 - 2)) != 0 {
+            map(parse_lazy_field_table, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, delivery_mode) = if flags & (1 << (15 - 3)) != 0 {
+            map(parse_short_short_uint, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, priority) = if flags & (1 << (15 - 4)) != 0 {
+            map(parse_short_short_uint, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, correlation_id) = if flags & (1 << (15 - 5)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, reply_to) = if flags & (1 << (15 - 6)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, expiration) = if flags & (1 << (15 - 7)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, message_id) = if flags & (1 << (15 - 8)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, timestamp) = if flags & (1 << (15 - 9)) != 0 {
+            map(parse_timestamp, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, kind) = if flags & (1 << (15 - 10)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, user_id) = if flags & (1 << (15 - 11)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, app_id) = if flags & (1 << (15 - 12)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, cluster_id) = if flags & (1 << (15 - 13)) != 0 {
+            map(parse_short_string, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        Ok((
+            i,
+            LazyProperties {
+                properties: AMQPProperties {
+                    content_type,
+                    content_encoding,
+                    headers: None,
+                    delivery_mode,
+                    priority,
+                    correlation_id,
+                    reply_to,
+                    expiration,
+                    message_id,
+                    timestamp,
+                    kind,
+                    user_id,
+                    app_id,
+                    cluster_id,
+                },
+                headers,
+            },
+        ))
+    }
+
+    /// basic properties whose strings and field tables are allocated in an arena (Generated)
+    #[cfg(feature = "arena")]
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub struct ArenaProperties<'a> {
+        /// content-type (Generated)
+        pub content_type: Option<&'a str>,
+        /// content-encoding (Generated)
+        pub content_encoding: Option<&'a str>,
+        /// headers (Generated)
+        pub headers: Option<ArenaTable<'a>>,
+        /// delivery-mode (Generated)
+        pub delivery_mode: Option<ShortShortUInt>,
+        /// priority (Generated)
+        pub priority: Option<ShortShortUInt>,
+        /// correlation-id (Generated)
+        pub correlation_id: Option<&'a str>,
+        /// reply-to (Generated)
+        pub reply_to: Option<&'a str>,
+        /// expiration (Generated)
+        pub expiration: Option<&'a str>,
+        /// message-id (Generated)
+        pub message_id: Option<&'a str>,
+        /// timestamp (Generated)
+        pub timestamp: Option<Timestamp>,
+        /// type (Generated)
+        pub kind: Option<&'a str>,
+        /// user-id (Generated)
+        pub user_id: Option<&'a str>,
+        /// app-id (Generated)
+        pub app_id: Option<&'a str>,
+        /// cluster-id (Generated)
+        pub cluster_id: Option<&'a str>,
+    }
+
+    #[cfg(feature = "arena")]
+    impl ArenaProperties<'_> {
+        /// Copy the properties out of the arena (Generated)
+        pub fn to_properties(&self) -> AMQPProperties {
+            AMQPProperties {
+                content_type: self.content_type.map(Into::into),
+                content_encoding: self.content_encoding.map(Into::into),
+                headers: self.headers.map(|table| table.to_field_table()),
+                delivery_mode: self.delivery_mode,
+                priority: self.priority,
+                correlation_id: self.correlation_id.map(Into::into),
+                reply_to: self.reply_to.map(Into::into),
+                expiration: self.expiration.map(Into::into),
+                message_id: self.message_id.map(Into::into),
+                timestamp: self.timestamp,
+                kind: self.kind.map(Into::into),
+                user_id: self.user_id.map(Into::into),
+                app_id: self.app_id.map(Into::into),
+                cluster_id: self.cluster_id.map(Into::into),
+            }
+        }
+    }
+
+    /// Parse basic properties, allocating their strings and field tables in the given arena (Generated)
+    #[cfg(feature = "arena")]
+    #[allow(clippy::identity_op)]
+    pub fn parse_properties_in<'a, 'i>(
+        arena: &'a Bump,
+        i: &'i [u8],
+        options: ParsingOptions,
+    ) -> ParserResult<&'i [u8], ArenaProperties<'a>> {
+        let (i, flags) = parse_short_uint(i)?;
+        let (i, content_type) = if flags & (1 << (15 - 0)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, content_encoding) = if flags & (1 << (15 - 1)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, headers) = if flags & (1 << (15 - 2)) != 0 {
+            map(
+                |i: &'i [u8]| parse_field_table_in_with(arena, i, options),
+                Some,
+            )
+            .parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, delivery_mode) = if flags & (1 << (15 - 3)) != 0 {
+            map(parse_short_short_uint, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, priority) = if flags & (1 << (15 - 4)) != 0 {
+            map(parse_short_short_uint, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, correlation_id) = if flags & (1 << (15 - 5)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, reply_to) = if flags & (1 << (15 - 6)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, expiration) = if flags & (1 << (15 - 7)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, message_id) = if flags & (1 << (15 - 8)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, timestamp) = if flags & (1 << (15 - 9)) != 0 {
+            map(parse_timestamp, Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, kind) = if flags & (1 << (15 - 10)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, user_id) = if flags & (1 << (15 - 11)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, app_id) = if flags & (1 << (15 - 12)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        let (i, cluster_id) = if flags & (1 << (15 - 13)) != 0 {
+            map(|i: &'i [u8]| parse_short_string_in(arena, i), Some).parse(i)?
+        } else {
+            (i, None)
+        };
+        Ok((
+            i,
+            ArenaProperties {
+                content_type,
+                content_encoding,
+                headers,
+                delivery_mode,
+                priority,
+                correlation_id,
+                reply_to,
+                expiration,
+                message_id,
+                timestamp,
+                kind,
+                user_id,
+                app_id,
+                cluster_id,
+            },
+        ))
+    }
 }
 /// connection (generated)
 pub mod connection {
@@ -1736,68 +4992,130 @@ pub mod connection {
 
     /// Parse connection (Generated)
     pub fn parse_connection<I: ParsableInput>(i: I) -> ParserResult<I, connection::AMQPMethod> {
+        parse_connection_with(i, ParsingOptions::default())
+    }
+
+    /// Parse connection, parsing its field tables with the given options (Generated)
+    pub fn parse_connection_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, connection::AMQPMethod> {
         context(
             "parse_connection",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
                         10 => context(
                             "parse_start",
-                            map(map(parse_start, AMQPMethod::Start), Some),
+                            map(
+                                map(|i: I| parse_start_with(i, options), AMQPMethod::Start),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         11 => context(
                             "parse_start_ok",
-                            map(map(parse_start_ok, AMQPMethod::StartOk), Some),
+                            map(
+                                map(|i: I| parse_start_ok_with(i, options), AMQPMethod::StartOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         20 => context(
                             "parse_secure",
-                            map(map(parse_secure, AMQPMethod::Secure), Some),
+                            map(
+                                map(|i: I| parse_secure_with(i, options), AMQPMethod::Secure),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         21 => context(
                             "parse_secure_ok",
-                            map(map(parse_secure_ok, AMQPMethod::SecureOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_secure_ok_with(i, options),
+                                    AMQPMethod::SecureOk,
+                                ),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        30 => context(
+                            "parse_tune",
+                            map(
+                                map(|i: I| parse_tune_with(i, options), AMQPMethod::Tune),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        30 => context("parse_tune", map(map(parse_tune, AMQPMethod::Tune), Some))
-                            .parse(i),
                         31 => context(
                             "parse_tune_ok",
-                            map(map(parse_tune_ok, AMQPMethod::TuneOk), Some),
+                            map(
+                                map(|i: I| parse_tune_ok_with(i, options), AMQPMethod::TuneOk),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        40 => context(
+                            "parse_open",
+                            map(
+                                map(|i: I| parse_open_with(i, options), AMQPMethod::Open),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        40 => context("parse_open", map(map(parse_open, AMQPMethod::Open), Some))
-                            .parse(i),
                         41 => context(
                             "parse_open_ok",
-                            map(map(parse_open_ok, AMQPMethod::OpenOk), Some),
+                            map(
+                                map(|i: I| parse_open_ok_with(i, options), AMQPMethod::OpenOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         50 => context(
                             "parse_close",
-                            map(map(parse_close, AMQPMethod::Close), Some),
+                            map(
+                                map(|i: I| parse_close_with(i, options), AMQPMethod::Close),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         51 => context(
                             "parse_close_ok",
-                            map(map(parse_close_ok, AMQPMethod::CloseOk), Some),
+                            map(
+                                map(|i: I| parse_close_ok_with(i, options), AMQPMethod::CloseOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         60 => context(
                             "parse_blocked",
-                            map(map(parse_blocked, AMQPMethod::Blocked), Some),
+                            map(
+                                map(|i: I| parse_blocked_with(i, options), AMQPMethod::Blocked),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         61 => context(
                             "parse_unblocked",
-                            map(map(parse_unblocked, AMQPMethod::Unblocked), Some),
+                            map(
+                                map(
+                                    |i: I| parse_unblocked_with(i, options),
+                                    AMQPMethod::Unblocked,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         70 => context(
                             "parse_update_secret",
-                            map(map(parse_update_secret, AMQPMethod::UpdateSecret), Some),
+                            map(
+                                map(
+                                    |i: I| parse_update_secret_with(i, options),
+                                    AMQPMethod::UpdateSecret,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         71 => context(
@@ -1841,6 +5159,24 @@ pub mod connection {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_connection(self))
+        }
+    }
+
     /// The available methods in connection
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -1899,13 +5235,82 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether start is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of start, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "version_major",
+                amqp_type: AMQPType::ShortShortUInt,
+                default_value: Some(|| AMQPValue::ShortShortUInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "version_minor",
+                amqp_type: AMQPType::ShortShortUInt,
+                default_value: Some(|| AMQPValue::ShortShortUInt(9)),
+            },
+            ArgumentDescriptor {
+                name: "server_properties",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "mechanisms",
+                amqp_type: AMQPType::LongString,
+                default_value: Some(|| AMQPValue::LongString("PLAIN".into())),
+            },
+            ArgumentDescriptor {
+                name: "locales",
+                amqp_type: AMQPType::LongString,
+                default_value: Some(|| AMQPValue::LongString("en_US".into())),
+            },
+        ];
+
+        /// Build start with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                version_major: 0,
+                version_minor: 9,
+                server_properties: Default::default(),
+                mechanisms: "PLAIN".into(),
+                locales: "en_US".into(),
+            }
+        }
+
+        /// Build start from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "version_major" => method.version_major = from_argument(name, value)?,
+                    "version_minor" => method.version_minor = from_argument(name, value)?,
+                    "server_properties" => method.server_properties = from_argument(name, value)?,
+                    "mechanisms" => method.mechanisms = from_argument(name, value)?,
+                    "locales" => method.locales = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.start", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse start (Generated)
     pub fn parse_start<I: ParsableInput>(i: I) -> ParserResult<I, Start> {
+        parse_start_with(i, ParsingOptions::default())
+    }
+
+    /// Parse start, parsing its field tables with the given options (Generated)
+    pub fn parse_start_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Start> {
         let (i, version_major) = parse_short_short_uint.parse(i)?;
         let (i, version_minor) = parse_short_short_uint.parse(i)?;
-        let (i, server_properties) = parse_field_table.parse(i)?;
+        let (i, server_properties) = parse_field_table_with(i, options)?;
         let (i, mechanisms) = parse_long_string.parse(i)?;
         let (i, locales) = parse_long_string.parse(i)?;
         Ok((
@@ -1934,6 +5339,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for Start {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::Start(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Start {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_start(self)),
+            )
+        }
+    }
     /// start-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct StartOk {
@@ -1957,11 +5383,80 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether start-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of start-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "client_properties",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "mechanism",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("PLAIN".into())),
+            },
+            ArgumentDescriptor {
+                name: "response",
+                amqp_type: AMQPType::LongString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "locale",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("en_US".into())),
+            },
+        ];
+
+        /// Build start-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                client_properties: Default::default(),
+                mechanism: "PLAIN".into(),
+                response: Default::default(),
+                locale: "en_US".into(),
+            }
+        }
+
+        /// Build start-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "client_properties" => method.client_properties = from_argument(name, value)?,
+                    "mechanism" => method.mechanism = from_argument(name, value)?,
+                    "response" => method.response = from_argument(name, value)?,
+                    "locale" => method.locale = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.start-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Start> for StartOk {
+        /// Build the reply to start, copying the fields they share (Generated)
+        fn from(_: &Start) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse start-ok (Generated)
     pub fn parse_start_ok<I: ParsableInput>(i: I) -> ParserResult<I, StartOk> {
-        let (i, client_properties) = parse_field_table.parse(i)?;
+        parse_start_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse start-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_start_ok_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, StartOk> {
+        let (i, client_properties) = parse_field_table_with(i, options)?;
         let (i, mechanism) = parse_short_string.parse(i)?;
         let (i, response) = parse_long_string.parse(i)?;
         let (i, locale) = parse_short_string.parse(i)?;
@@ -1989,6 +5484,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for StartOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::StartOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for StartOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_start_ok(self)),
+            )
+        }
+    }
     /// secure (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Secure {
@@ -2006,10 +5522,49 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             20
         }
+
+        /// Whether secure is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of secure, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "challenge",
+            amqp_type: AMQPType::LongString,
+            default_value: None,
+        }];
+
+        /// Build secure with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                challenge: Default::default(),
+            }
+        }
+
+        /// Build secure from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "challenge" => method.challenge = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.secure", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse secure (Generated)
     pub fn parse_secure<I: ParsableInput>(i: I) -> ParserResult<I, Secure> {
+        parse_secure_with(i, ParsingOptions::default())
+    }
+
+    /// Parse secure, parsing its field tables with the given options (Generated)
+    pub fn parse_secure_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Secure> {
         let (i, challenge) = parse_long_string.parse(i)?;
         Ok((i, Secure { challenge }))
     }
@@ -2024,6 +5579,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for Secure {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::Secure(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Secure {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_secure(self)),
+            )
+        }
+    }
     /// secure-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct SecureOk {
@@ -2041,10 +5617,61 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             21
         }
+
+        /// Whether secure-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of secure-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "response",
+            amqp_type: AMQPType::LongString,
+            default_value: None,
+        }];
+
+        /// Build secure-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                response: Default::default(),
+            }
+        }
+
+        /// Build secure-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "response" => method.response = from_argument(name, value)?,
+                    _ => {
+                        return Err(format!(
+                            "Unknown argument {} for connection.secure-ok",
+                            name
+                        ))
+                    }
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Secure> for SecureOk {
+        /// Build the reply to secure, copying the fields they share (Generated)
+        fn from(_: &Secure) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse secure-ok (Generated)
     pub fn parse_secure_ok<I: ParsableInput>(i: I) -> ParserResult<I, SecureOk> {
+        parse_secure_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse secure-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_secure_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, SecureOk> {
         let (i, response) = parse_long_string.parse(i)?;
         Ok((i, SecureOk { response }))
     }
@@ -2059,6 +5686,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for SecureOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::SecureOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for SecureOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_secure_ok(self)),
+            )
+        }
+    }
     /// tune (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Tune {
@@ -2080,10 +5728,65 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             30
         }
+
+        /// Whether tune is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of tune, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "channel_max",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: Some(|| AMQPValue::ShortUInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "frame_max",
+                amqp_type: AMQPType::LongUInt,
+                default_value: Some(|| AMQPValue::LongUInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "heartbeat",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: Some(|| AMQPValue::ShortUInt(0)),
+            },
+        ];
+
+        /// Build tune with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                channel_max: 0,
+                frame_max: 0,
+                heartbeat: 0,
+            }
+        }
+
+        /// Build tune from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "channel_max" => method.channel_max = from_argument(name, value)?,
+                    "frame_max" => method.frame_max = from_argument(name, value)?,
+                    "heartbeat" => method.heartbeat = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.tune", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse tune (Generated)
     pub fn parse_tune<I: ParsableInput>(i: I) -> ParserResult<I, Tune> {
+        parse_tune_with(i, ParsingOptions::default())
+    }
+
+    /// Parse tune, parsing its field tables with the given options (Generated)
+    pub fn parse_tune_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Tune> {
         let (i, channel_max) = parse_short_uint.parse(i)?;
         let (i, frame_max) = parse_long_uint.parse(i)?;
         let (i, heartbeat) = parse_short_uint.parse(i)?;
@@ -2109,6 +5812,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for Tune {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::Tune(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Tune {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_tune(self)),
+            )
+        }
+    }
     /// tune-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct TuneOk {
@@ -2130,10 +5854,76 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             31
         }
+
+        /// Whether tune-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of tune-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "channel_max",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: Some(|| AMQPValue::ShortUInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "frame_max",
+                amqp_type: AMQPType::LongUInt,
+                default_value: Some(|| AMQPValue::LongUInt(0)),
+            },
+            ArgumentDescriptor {
+                name: "heartbeat",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: Some(|| AMQPValue::ShortUInt(0)),
+            },
+        ];
+
+        /// Build tune-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                channel_max: 0,
+                frame_max: 0,
+                heartbeat: 0,
+            }
+        }
+
+        /// Build tune-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "channel_max" => method.channel_max = from_argument(name, value)?,
+                    "frame_max" => method.frame_max = from_argument(name, value)?,
+                    "heartbeat" => method.heartbeat = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.tune-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Tune> for TuneOk {
+        /// Build the reply to tune, copying the fields they share (Generated)
+        fn from(request: &Tune) -> Self {
+            Self {
+                channel_max: request.channel_max,
+                frame_max: request.frame_max,
+                heartbeat: request.heartbeat,
+            }
+        }
     }
 
     /// Parse tune-ok (Generated)
     pub fn parse_tune_ok<I: ParsableInput>(i: I) -> ParserResult<I, TuneOk> {
+        parse_tune_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse tune-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_tune_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, TuneOk> {
         let (i, channel_max) = parse_short_uint.parse(i)?;
         let (i, frame_max) = parse_long_uint.parse(i)?;
         let (i, heartbeat) = parse_short_uint.parse(i)?;
@@ -2159,6 +5949,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for TuneOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::TuneOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for TuneOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_tune_ok(self)),
+            )
+        }
+    }
     /// open (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Open {
@@ -2176,10 +5987,49 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             40
         }
+
+        /// Whether open is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of open, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "virtual_host",
+            amqp_type: AMQPType::ShortString,
+            default_value: Some(|| AMQPValue::ShortString("/".into())),
+        }];
+
+        /// Build open with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                virtual_host: "/".into(),
+            }
+        }
+
+        /// Build open from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "virtual_host" => method.virtual_host = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.open", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse open (Generated)
     pub fn parse_open<I: ParsableInput>(i: I) -> ParserResult<I, Open> {
+        parse_open_with(i, ParsingOptions::default())
+    }
+
+    /// Parse open, parsing its field tables with the given options (Generated)
+    pub fn parse_open_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Open> {
         let (i, virtual_host) = parse_short_string.parse(i)?;
         let (i, _) = parse_short_string.parse(i)?;
         let (i, _) = parse_flags(i, &["insist"])?;
@@ -2200,6 +6050,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for Open {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::Open(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Open {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_open(self)),
+            )
+        }
+    }
     /// open-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct OpenOk {}
@@ -2210,14 +6081,50 @@ pub mod connection {
             10
         }
 
-        /// Get the AMQP method id for open-ok (Generated)
-        pub fn get_amqp_method_id(&self) -> Identifier {
-            41
+        /// Get the AMQP method id for open-ok (Generated)
+        pub fn get_amqp_method_id(&self) -> Identifier {
+            41
+        }
+
+        /// Whether open-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of open-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build open-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build open-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for connection.open-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Open> for OpenOk {
+        /// Build the reply to open, copying the fields they share (Generated)
+        fn from(_: &Open) -> Self {
+            Self::default()
         }
     }
 
     /// Parse open-ok (Generated)
     pub fn parse_open_ok<I: ParsableInput>(i: I) -> ParserResult<I, OpenOk> {
+        parse_open_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse open-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_open_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, OpenOk> {
         let (i, _) = parse_short_string.parse(i)?;
         Ok((i, OpenOk {}))
     }
@@ -2232,6 +6139,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for OpenOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::OpenOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for OpenOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_open_ok(self)),
+            )
+        }
+    }
     /// close (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Close {
@@ -2255,10 +6183,72 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             50
         }
+
+        /// Whether close is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of close, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "reply_code",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "reply_text",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "class_id",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "method_id",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: None,
+            },
+        ];
+
+        /// Build close with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                reply_code: Default::default(),
+                reply_text: "".into(),
+                class_id: Default::default(),
+                method_id: Default::default(),
+            }
+        }
+
+        /// Build close from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "reply_code" => method.reply_code = from_argument(name, value)?,
+                    "reply_text" => method.reply_text = from_argument(name, value)?,
+                    "class_id" => method.class_id = from_argument(name, value)?,
+                    "method_id" => method.method_id = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.close", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse close (Generated)
     pub fn parse_close<I: ParsableInput>(i: I) -> ParserResult<I, Close> {
+        parse_close_with(i, ParsingOptions::default())
+    }
+
+    /// Parse close, parsing its field tables with the given options (Generated)
+    pub fn parse_close_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Close> {
         let (i, reply_code) = parse_short_uint.parse(i)?;
         let (i, reply_text) = parse_short_string.parse(i)?;
         let (i, class_id) = parse_short_uint.parse(i)?;
@@ -2287,6 +6277,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for Close {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::Close(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Close {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_close(self)),
+            )
+        }
+    }
     /// close-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct CloseOk {}
@@ -2301,10 +6312,46 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             51
         }
+
+        /// Whether close-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of close-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build close-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build close-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for connection.close-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Close> for CloseOk {
+        /// Build the reply to close, copying the fields they share (Generated)
+        fn from(_: &Close) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse close-ok (Generated)
     pub fn parse_close_ok<I: ParsableInput>(i: I) -> ParserResult<I, CloseOk> {
+        parse_close_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse close-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_close_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, CloseOk> {
         Ok((i, CloseOk {}))
     }
 
@@ -2317,6 +6364,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for CloseOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::CloseOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for CloseOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_close_ok(self)),
+            )
+        }
+    }
     /// blocked (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Blocked {
@@ -2334,10 +6402,47 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             60
         }
+
+        /// Whether blocked is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of blocked, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "reason",
+            amqp_type: AMQPType::ShortString,
+            default_value: Some(|| AMQPValue::ShortString("".into())),
+        }];
+
+        /// Build blocked with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self { reason: "".into() }
+        }
+
+        /// Build blocked from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "reason" => method.reason = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for connection.blocked", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse blocked (Generated)
     pub fn parse_blocked<I: ParsableInput>(i: I) -> ParserResult<I, Blocked> {
+        parse_blocked_with(i, ParsingOptions::default())
+    }
+
+    /// Parse blocked, parsing its field tables with the given options (Generated)
+    pub fn parse_blocked_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Blocked> {
         let (i, reason) = parse_short_string.parse(i)?;
         Ok((i, Blocked { reason }))
     }
@@ -2352,6 +6457,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for Blocked {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::Blocked(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Blocked {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_blocked(self)),
+            )
+        }
+    }
     /// unblocked (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Unblocked {}
@@ -2366,10 +6492,42 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             61
         }
+
+        /// Whether unblocked is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of unblocked, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build unblocked with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build unblocked from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!(
+                    "Unknown argument {} for connection.unblocked",
+                    name
+                ));
+            }
+            Ok(Self::spec_default())
+        }
     }
 
     /// Parse unblocked (Generated)
     pub fn parse_unblocked<I: ParsableInput>(i: I) -> ParserResult<I, Unblocked> {
+        parse_unblocked_with(i, ParsingOptions::default())
+    }
+
+    /// Parse unblocked, parsing its field tables with the given options (Generated)
+    pub fn parse_unblocked_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Unblocked> {
         Ok((i, Unblocked {}))
     }
 
@@ -2382,6 +6540,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for Unblocked {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::Unblocked(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Unblocked {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_unblocked(self)),
+            )
+        }
+    }
     /// update-secret (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct UpdateSecret {
@@ -2401,10 +6580,63 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             70
         }
+
+        /// Whether update-secret is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of update-secret, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "new_secret",
+                amqp_type: AMQPType::LongString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "reason",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+        ];
+
+        /// Build update-secret with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                new_secret: Default::default(),
+                reason: Default::default(),
+            }
+        }
+
+        /// Build update-secret from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "new_secret" => method.new_secret = from_argument(name, value)?,
+                    "reason" => method.reason = from_argument(name, value)?,
+                    _ => {
+                        return Err(format!(
+                            "Unknown argument {} for connection.update-secret",
+                            name
+                        ))
+                    }
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse update-secret (Generated)
     pub fn parse_update_secret<I: ParsableInput>(i: I) -> ParserResult<I, UpdateSecret> {
+        parse_update_secret_with(i, ParsingOptions::default())
+    }
+
+    /// Parse update-secret, parsing its field tables with the given options (Generated)
+    pub fn parse_update_secret_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, UpdateSecret> {
         let (i, new_secret) = parse_long_string.parse(i)?;
         let (i, reason) = parse_short_string.parse(i)?;
         Ok((i, UpdateSecret { new_secret, reason }))
@@ -2421,6 +6653,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for UpdateSecret {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::UpdateSecret(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for UpdateSecret {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_update_secret(self)),
+            )
+        }
+    }
     /// update-secret-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct UpdateSecretOk {}
@@ -2435,10 +6688,49 @@ pub mod connection {
         pub fn get_amqp_method_id(&self) -> Identifier {
             71
         }
+
+        /// Whether update-secret-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of update-secret-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build update-secret-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build update-secret-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!(
+                    "Unknown argument {} for connection.update-secret-ok",
+                    name
+                ));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&UpdateSecret> for UpdateSecretOk {
+        /// Build the reply to update-secret, copying the fields they share (Generated)
+        fn from(_: &UpdateSecret) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse update-secret-ok (Generated)
     pub fn parse_update_secret_ok<I: ParsableInput>(i: I) -> ParserResult<I, UpdateSecretOk> {
+        parse_update_secret_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse update-secret-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_update_secret_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, UpdateSecretOk> {
         Ok((i, UpdateSecretOk {}))
     }
 
@@ -2451,6 +6743,27 @@ pub mod connection {
             Ok(input)
         }
     }
+
+    impl Decode for UpdateSecretOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Connection(AMQPMethod::UpdateSecretOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for UpdateSecretOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(10), gen_update_secret_ok(self)),
+            )
+        }
+    }
 }
 /// channel (generated)
 pub mod channel {
@@ -2458,33 +6771,65 @@ pub mod channel {
 
     /// Parse channel (Generated)
     pub fn parse_channel<I: ParsableInput>(i: I) -> ParserResult<I, channel::AMQPMethod> {
+        parse_channel_with(i, ParsingOptions::default())
+    }
+
+    /// Parse channel, parsing its field tables with the given options (Generated)
+    pub fn parse_channel_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, channel::AMQPMethod> {
         context(
             "parse_channel",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
-                        10 => context("parse_open", map(map(parse_open, AMQPMethod::Open), Some))
-                            .parse(i),
+                        10 => context(
+                            "parse_open",
+                            map(
+                                map(|i: I| parse_open_with(i, options), AMQPMethod::Open),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
                         11 => context(
                             "parse_open_ok",
-                            map(map(parse_open_ok, AMQPMethod::OpenOk), Some),
+                            map(
+                                map(|i: I| parse_open_ok_with(i, options), AMQPMethod::OpenOk),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        20 => context(
+                            "parse_flow",
+                            map(
+                                map(|i: I| parse_flow_with(i, options), AMQPMethod::Flow),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        20 => context("parse_flow", map(map(parse_flow, AMQPMethod::Flow), Some))
-                            .parse(i),
                         21 => context(
                             "parse_flow_ok",
-                            map(map(parse_flow_ok, AMQPMethod::FlowOk), Some),
+                            map(
+                                map(|i: I| parse_flow_ok_with(i, options), AMQPMethod::FlowOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         40 => context(
                             "parse_close",
-                            map(map(parse_close, AMQPMethod::Close), Some),
+                            map(
+                                map(|i: I| parse_close_with(i, options), AMQPMethod::Close),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         41 => context(
                             "parse_close_ok",
-                            map(map(parse_close_ok, AMQPMethod::CloseOk), Some),
+                            map(
+                                map(|i: I| parse_close_ok_with(i, options), AMQPMethod::CloseOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         _ => Ok((i, None)),
@@ -2510,6 +6855,24 @@ pub mod channel {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Channel(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_channel(self))
+        }
+    }
+
     /// The available methods in channel
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -2541,10 +6904,39 @@ pub mod channel {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether open is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of open, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build open with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build open from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for channel.open", name));
+            }
+            Ok(Self::spec_default())
+        }
     }
 
     /// Parse open (Generated)
     pub fn parse_open<I: ParsableInput>(i: I) -> ParserResult<I, Open> {
+        parse_open_with(i, ParsingOptions::default())
+    }
+
+    /// Parse open, parsing its field tables with the given options (Generated)
+    pub fn parse_open_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Open> {
         let (i, _) = parse_short_string.parse(i)?;
         Ok((i, Open {}))
     }
@@ -2559,6 +6951,27 @@ pub mod channel {
             Ok(input)
         }
     }
+
+    impl Decode for Open {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Channel(AMQPMethod::Open(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Open {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(20), gen_open(self)),
+            )
+        }
+    }
     /// open-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct OpenOk {}
@@ -2573,10 +6986,46 @@ pub mod channel {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether open-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of open-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build open-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build open-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for channel.open-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Open> for OpenOk {
+        /// Build the reply to open, copying the fields they share (Generated)
+        fn from(_: &Open) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse open-ok (Generated)
     pub fn parse_open_ok<I: ParsableInput>(i: I) -> ParserResult<I, OpenOk> {
+        parse_open_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse open-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_open_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, OpenOk> {
         let (i, _) = parse_long_string.parse(i)?;
         Ok((i, OpenOk {}))
     }
@@ -2591,6 +7040,27 @@ pub mod channel {
             Ok(input)
         }
     }
+
+    impl Decode for OpenOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Channel(AMQPMethod::OpenOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for OpenOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(20), gen_open_ok(self)),
+            )
+        }
+    }
     /// flow (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Flow {
@@ -2608,10 +7078,47 @@ pub mod channel {
         pub fn get_amqp_method_id(&self) -> Identifier {
             20
         }
+
+        /// Whether flow is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of flow, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "active",
+            amqp_type: AMQPType::Boolean,
+            default_value: Some(|| AMQPValue::Boolean(false)),
+        }];
+
+        /// Build flow with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self { active: false }
+        }
+
+        /// Build flow from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "active" => method.active = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for channel.flow", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse flow (Generated)
     pub fn parse_flow<I: ParsableInput>(i: I) -> ParserResult<I, Flow> {
+        parse_flow_with(i, ParsingOptions::default())
+    }
+
+    /// Parse flow, parsing its field tables with the given options (Generated)
+    pub fn parse_flow_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Flow> {
         let (i, flags) = parse_flags(i, &["active"])?;
         Ok((
             i,
@@ -2633,6 +7140,27 @@ pub mod channel {
             Ok(input)
         }
     }
+
+    impl Decode for Flow {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Channel(AMQPMethod::Flow(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Flow {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(20), gen_flow(self)),
+            )
+        }
+    }
     /// flow-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct FlowOk {
@@ -2650,10 +7178,56 @@ pub mod channel {
         pub fn get_amqp_method_id(&self) -> Identifier {
             21
         }
+
+        /// Whether flow-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of flow-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "active",
+            amqp_type: AMQPType::Boolean,
+            default_value: Some(|| AMQPValue::Boolean(false)),
+        }];
+
+        /// Build flow-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self { active: false }
+        }
+
+        /// Build flow-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "active" => method.active = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for channel.flow-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Flow> for FlowOk {
+        /// Build the reply to flow, copying the fields they share (Generated)
+        fn from(request: &Flow) -> Self {
+            Self {
+                active: request.active,
+            }
+        }
     }
 
     /// Parse flow-ok (Generated)
     pub fn parse_flow_ok<I: ParsableInput>(i: I) -> ParserResult<I, FlowOk> {
+        parse_flow_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse flow-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_flow_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, FlowOk> {
         let (i, flags) = parse_flags(i, &["active"])?;
         Ok((
             i,
@@ -2675,6 +7249,27 @@ pub mod channel {
             Ok(input)
         }
     }
+
+    impl Decode for FlowOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Channel(AMQPMethod::FlowOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for FlowOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(20), gen_flow_ok(self)),
+            )
+        }
+    }
     /// close (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Close {
@@ -2694,14 +7289,76 @@ pub mod channel {
             20
         }
 
-        /// Get the AMQP method id for close (Generated)
-        pub fn get_amqp_method_id(&self) -> Identifier {
-            40
+        /// Get the AMQP method id for close (Generated)
+        pub fn get_amqp_method_id(&self) -> Identifier {
+            40
+        }
+
+        /// Whether close is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of close, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "reply_code",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "reply_text",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "class_id",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "method_id",
+                amqp_type: AMQPType::ShortUInt,
+                default_value: None,
+            },
+        ];
+
+        /// Build close with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                reply_code: Default::default(),
+                reply_text: "".into(),
+                class_id: Default::default(),
+                method_id: Default::default(),
+            }
+        }
+
+        /// Build close from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "reply_code" => method.reply_code = from_argument(name, value)?,
+                    "reply_text" => method.reply_text = from_argument(name, value)?,
+                    "class_id" => method.class_id = from_argument(name, value)?,
+                    "method_id" => method.method_id = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for channel.close", name)),
+                }
+            }
+            Ok(method)
         }
     }
 
     /// Parse close (Generated)
     pub fn parse_close<I: ParsableInput>(i: I) -> ParserResult<I, Close> {
+        parse_close_with(i, ParsingOptions::default())
+    }
+
+    /// Parse close, parsing its field tables with the given options (Generated)
+    pub fn parse_close_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Close> {
         let (i, reply_code) = parse_short_uint.parse(i)?;
         let (i, reply_text) = parse_short_string.parse(i)?;
         let (i, class_id) = parse_short_uint.parse(i)?;
@@ -2730,6 +7387,27 @@ pub mod channel {
             Ok(input)
         }
     }
+
+    impl Decode for Close {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Channel(AMQPMethod::Close(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Close {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(20), gen_close(self)),
+            )
+        }
+    }
     /// close-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct CloseOk {}
@@ -2744,10 +7422,46 @@ pub mod channel {
         pub fn get_amqp_method_id(&self) -> Identifier {
             41
         }
+
+        /// Whether close-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of close-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build close-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build close-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for channel.close-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Close> for CloseOk {
+        /// Build the reply to close, copying the fields they share (Generated)
+        fn from(_: &Close) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse close-ok (Generated)
     pub fn parse_close_ok<I: ParsableInput>(i: I) -> ParserResult<I, CloseOk> {
+        parse_close_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse close-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_close_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, CloseOk> {
         Ok((i, CloseOk {}))
     }
 
@@ -2760,26 +7474,65 @@ pub mod channel {
             Ok(input)
         }
     }
+
+    impl Decode for CloseOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Channel(AMQPMethod::CloseOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for CloseOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(20), gen_close_ok(self)),
+            )
+        }
+    }
 }
 /// access (generated)
+#[cfg(feature = "class-access")]
 pub mod access {
     use super::*;
 
     /// Parse access (Generated)
     pub fn parse_access<I: ParsableInput>(i: I) -> ParserResult<I, access::AMQPMethod> {
+        parse_access_with(i, ParsingOptions::default())
+    }
+
+    /// Parse access, parsing its field tables with the given options (Generated)
+    pub fn parse_access_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, access::AMQPMethod> {
         context(
             "parse_access",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
                         10 => context(
                             "parse_request",
-                            map(map(parse_request, AMQPMethod::Request), Some),
+                            map(
+                                map(|i: I| parse_request_with(i, options), AMQPMethod::Request),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         11 => context(
                             "parse_request_ok",
-                            map(map(parse_request_ok, AMQPMethod::RequestOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_request_ok_with(i, options),
+                                    AMQPMethod::RequestOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         _ => Ok((i, None)),
@@ -2801,6 +7554,24 @@ pub mod access {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Access(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_access(self))
+        }
+    }
+
     /// The available methods in access
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -2837,10 +7608,86 @@ pub mod access {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether request is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of request, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "realm",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("/data".into())),
+            },
+            ArgumentDescriptor {
+                name: "exclusive",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "passive",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(true)),
+            },
+            ArgumentDescriptor {
+                name: "active",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(true)),
+            },
+            ArgumentDescriptor {
+                name: "write",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(true)),
+            },
+            ArgumentDescriptor {
+                name: "read",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(true)),
+            },
+        ];
+
+        /// Build request with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                realm: "/data".into(),
+                exclusive: false,
+                passive: true,
+                active: true,
+                write: true,
+                read: true,
+            }
+        }
+
+        /// Build request from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "realm" => method.realm = from_argument(name, value)?,
+                    "exclusive" => method.exclusive = from_argument(name, value)?,
+                    "passive" => method.passive = from_argument(name, value)?,
+                    "active" => method.active = from_argument(name, value)?,
+                    "write" => method.write = from_argument(name, value)?,
+                    "read" => method.read = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for access.request", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse request (Generated)
     pub fn parse_request<I: ParsableInput>(i: I) -> ParserResult<I, Request> {
+        parse_request_with(i, ParsingOptions::default())
+    }
+
+    /// Parse request, parsing its field tables with the given options (Generated)
+    pub fn parse_request_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Request> {
         let (i, realm) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["exclusive", "passive", "active", "write", "read"])?;
         Ok((
@@ -2848,10 +7695,10 @@ pub mod access {
             Request {
                 realm,
                 exclusive: flags.get_flag("exclusive").unwrap_or(false),
-                passive: flags.get_flag("passive").unwrap_or(false),
-                active: flags.get_flag("active").unwrap_or(false),
-                write: flags.get_flag("write").unwrap_or(false),
-                read: flags.get_flag("read").unwrap_or(false),
+                passive: flags.get_flag("passive").unwrap_or(true),
+                active: flags.get_flag("active").unwrap_or(true),
+                write: flags.get_flag("write").unwrap_or(true),
+                read: flags.get_flag("read").unwrap_or(true),
             },
         ))
     }
@@ -2873,6 +7720,27 @@ pub mod access {
             Ok(input)
         }
     }
+
+    impl Decode for Request {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Access(AMQPMethod::Request(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Request {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(30), gen_request(self)),
+            )
+        }
+    }
     /// request-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct RequestOk {}
@@ -2887,10 +7755,46 @@ pub mod access {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether request-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of request-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build request-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build request-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for access.request-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Request> for RequestOk {
+        /// Build the reply to request, copying the fields they share (Generated)
+        fn from(_: &Request) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse request-ok (Generated)
     pub fn parse_request_ok<I: ParsableInput>(i: I) -> ParserResult<I, RequestOk> {
+        parse_request_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse request-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_request_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, RequestOk> {
         let (i, _) = parse_short_uint.parse(i)?;
         Ok((i, RequestOk {}))
     }
@@ -2905,53 +7809,119 @@ pub mod access {
             Ok(input)
         }
     }
+
+    impl Decode for RequestOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Access(AMQPMethod::RequestOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for RequestOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(30), gen_request_ok(self)),
+            )
+        }
+    }
 }
 /// exchange (generated)
+#[cfg(feature = "class-exchange")]
 pub mod exchange {
     use super::*;
 
     /// Parse exchange (Generated)
     pub fn parse_exchange<I: ParsableInput>(i: I) -> ParserResult<I, exchange::AMQPMethod> {
+        parse_exchange_with(i, ParsingOptions::default())
+    }
+
+    /// Parse exchange, parsing its field tables with the given options (Generated)
+    pub fn parse_exchange_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, exchange::AMQPMethod> {
         context(
             "parse_exchange",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
                         10 => context(
                             "parse_declare",
-                            map(map(parse_declare, AMQPMethod::Declare), Some),
+                            map(
+                                map(|i: I| parse_declare_with(i, options), AMQPMethod::Declare),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         11 => context(
                             "parse_declare_ok",
-                            map(map(parse_declare_ok, AMQPMethod::DeclareOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_declare_ok_with(i, options),
+                                    AMQPMethod::DeclareOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         20 => context(
                             "parse_delete",
-                            map(map(parse_delete, AMQPMethod::Delete), Some),
+                            map(
+                                map(|i: I| parse_delete_with(i, options), AMQPMethod::Delete),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         21 => context(
                             "parse_delete_ok",
-                            map(map(parse_delete_ok, AMQPMethod::DeleteOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_delete_ok_with(i, options),
+                                    AMQPMethod::DeleteOk,
+                                ),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        30 => context(
+                            "parse_bind",
+                            map(
+                                map(|i: I| parse_bind_with(i, options), AMQPMethod::Bind),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        30 => context("parse_bind", map(map(parse_bind, AMQPMethod::Bind), Some))
-                            .parse(i),
                         31 => context(
                             "parse_bind_ok",
-                            map(map(parse_bind_ok, AMQPMethod::BindOk), Some),
+                            map(
+                                map(|i: I| parse_bind_ok_with(i, options), AMQPMethod::BindOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         40 => context(
                             "parse_unbind",
-                            map(map(parse_unbind, AMQPMethod::Unbind), Some),
+                            map(
+                                map(|i: I| parse_unbind_with(i, options), AMQPMethod::Unbind),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         51 => context(
                             "parse_unbind_ok",
-                            map(map(parse_unbind_ok, AMQPMethod::UnbindOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_unbind_ok_with(i, options),
+                                    AMQPMethod::UnbindOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         _ => Ok((i, None)),
@@ -2979,6 +7949,24 @@ pub mod exchange {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_exchange(self))
+        }
+    }
+
     /// The available methods in exchange
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -3031,10 +8019,100 @@ pub mod exchange {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether declare is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of declare, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "kind",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("direct".into())),
+            },
+            ArgumentDescriptor {
+                name: "passive",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "durable",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "auto_delete",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "internal",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "arguments",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+        ];
+
+        /// Build declare with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                exchange: Default::default(),
+                kind: "direct".into(),
+                passive: false,
+                durable: false,
+                auto_delete: false,
+                internal: false,
+                nowait: false,
+                arguments: Default::default(),
+            }
+        }
+
+        /// Build declare from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "kind" => method.kind = from_argument(name, value)?,
+                    "passive" => method.passive = from_argument(name, value)?,
+                    "durable" => method.durable = from_argument(name, value)?,
+                    "auto_delete" => method.auto_delete = from_argument(name, value)?,
+                    "internal" => method.internal = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    "arguments" => method.arguments = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for exchange.declare", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse declare (Generated)
     pub fn parse_declare<I: ParsableInput>(i: I) -> ParserResult<I, Declare> {
+        parse_declare_with(i, ParsingOptions::default())
+    }
+
+    /// Parse declare, parsing its field tables with the given options (Generated)
+    pub fn parse_declare_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Declare> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, exchange) = parse_short_string.parse(i)?;
         let (i, kind) = parse_short_string.parse(i)?;
@@ -3042,7 +8120,7 @@ pub mod exchange {
             i,
             &["passive", "durable", "auto_delete", "internal", "nowait"],
         )?;
-        let (i, arguments) = parse_field_table.parse(i)?;
+        let (i, arguments) = parse_field_table_with(i, options)?;
         Ok((
             i,
             Declare {
@@ -3078,6 +8156,27 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for Declare {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::Declare(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Declare {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_declare(self)),
+            )
+        }
+    }
     /// declare-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct DeclareOk {}
@@ -3092,10 +8191,46 @@ pub mod exchange {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether declare-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of declare-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build declare-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build declare-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for exchange.declare-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Declare> for DeclareOk {
+        /// Build the reply to declare, copying the fields they share (Generated)
+        fn from(_: &Declare) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse declare-ok (Generated)
     pub fn parse_declare_ok<I: ParsableInput>(i: I) -> ParserResult<I, DeclareOk> {
+        parse_declare_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse declare-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_declare_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, DeclareOk> {
         Ok((i, DeclareOk {}))
     }
 
@@ -3108,6 +8243,27 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for DeclareOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::DeclareOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for DeclareOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_declare_ok(self)),
+            )
+        }
+    }
     /// delete (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Delete {
@@ -3129,10 +8285,65 @@ pub mod exchange {
         pub fn get_amqp_method_id(&self) -> Identifier {
             20
         }
+
+        /// Whether delete is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of delete, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "if_unused",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build delete with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                exchange: Default::default(),
+                if_unused: false,
+                nowait: false,
+            }
+        }
+
+        /// Build delete from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "if_unused" => method.if_unused = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for exchange.delete", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse delete (Generated)
     pub fn parse_delete<I: ParsableInput>(i: I) -> ParserResult<I, Delete> {
+        parse_delete_with(i, ParsingOptions::default())
+    }
+
+    /// Parse delete, parsing its field tables with the given options (Generated)
+    pub fn parse_delete_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Delete> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, exchange) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["if_unused", "nowait"])?;
@@ -3161,6 +8372,27 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for Delete {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::Delete(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Delete {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_delete(self)),
+            )
+        }
+    }
     /// delete-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct DeleteOk {}
@@ -3175,10 +8407,46 @@ pub mod exchange {
         pub fn get_amqp_method_id(&self) -> Identifier {
             21
         }
+
+        /// Whether delete-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of delete-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build delete-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build delete-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for exchange.delete-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Delete> for DeleteOk {
+        /// Build the reply to delete, copying the fields they share (Generated)
+        fn from(_: &Delete) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse delete-ok (Generated)
     pub fn parse_delete_ok<I: ParsableInput>(i: I) -> ParserResult<I, DeleteOk> {
+        parse_delete_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse delete-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_delete_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, DeleteOk> {
         Ok((i, DeleteOk {}))
     }
 
@@ -3191,6 +8459,27 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for DeleteOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::DeleteOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for DeleteOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_delete_ok(self)),
+            )
+        }
+    }
     /// bind (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Bind {
@@ -3216,16 +8505,85 @@ pub mod exchange {
         pub fn get_amqp_method_id(&self) -> Identifier {
             30
         }
+
+        /// Whether bind is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of bind, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "destination",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "source",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "arguments",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+        ];
+
+        /// Build bind with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                destination: Default::default(),
+                source: Default::default(),
+                routing_key: "".into(),
+                nowait: false,
+                arguments: Default::default(),
+            }
+        }
+
+        /// Build bind from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "destination" => method.destination = from_argument(name, value)?,
+                    "source" => method.source = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    "arguments" => method.arguments = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for exchange.bind", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse bind (Generated)
     pub fn parse_bind<I: ParsableInput>(i: I) -> ParserResult<I, Bind> {
+        parse_bind_with(i, ParsingOptions::default())
+    }
+
+    /// Parse bind, parsing its field tables with the given options (Generated)
+    pub fn parse_bind_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Bind> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, destination) = parse_short_string.parse(i)?;
         let (i, source) = parse_short_string.parse(i)?;
         let (i, routing_key) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["nowait"])?;
-        let (i, arguments) = parse_field_table.parse(i)?;
+        let (i, arguments) = parse_field_table_with(i, options)?;
         Ok((
             i,
             Bind {
@@ -3255,6 +8613,27 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for Bind {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::Bind(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Bind {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_bind(self)),
+            )
+        }
+    }
     /// bind-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct BindOk {}
@@ -3265,14 +8644,50 @@ pub mod exchange {
             40
         }
 
-        /// Get the AMQP method id for bind-ok (Generated)
-        pub fn get_amqp_method_id(&self) -> Identifier {
-            31
+        /// Get the AMQP method id for bind-ok (Generated)
+        pub fn get_amqp_method_id(&self) -> Identifier {
+            31
+        }
+
+        /// Whether bind-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of bind-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build bind-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build bind-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for exchange.bind-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Bind> for BindOk {
+        /// Build the reply to bind, copying the fields they share (Generated)
+        fn from(_: &Bind) -> Self {
+            Self::default()
         }
     }
 
     /// Parse bind-ok (Generated)
     pub fn parse_bind_ok<I: ParsableInput>(i: I) -> ParserResult<I, BindOk> {
+        parse_bind_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse bind-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_bind_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, BindOk> {
         Ok((i, BindOk {}))
     }
 
@@ -3285,6 +8700,27 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for BindOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::BindOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for BindOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_bind_ok(self)),
+            )
+        }
+    }
     /// unbind (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Unbind {
@@ -3310,16 +8746,85 @@ pub mod exchange {
         pub fn get_amqp_method_id(&self) -> Identifier {
             40
         }
+
+        /// Whether unbind is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of unbind, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "destination",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "source",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "arguments",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+        ];
+
+        /// Build unbind with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                destination: Default::default(),
+                source: Default::default(),
+                routing_key: "".into(),
+                nowait: false,
+                arguments: Default::default(),
+            }
+        }
+
+        /// Build unbind from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "destination" => method.destination = from_argument(name, value)?,
+                    "source" => method.source = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    "arguments" => method.arguments = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for exchange.unbind", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse unbind (Generated)
     pub fn parse_unbind<I: ParsableInput>(i: I) -> ParserResult<I, Unbind> {
+        parse_unbind_with(i, ParsingOptions::default())
+    }
+
+    /// Parse unbind, parsing its field tables with the given options (Generated)
+    pub fn parse_unbind_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Unbind> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, destination) = parse_short_string.parse(i)?;
         let (i, source) = parse_short_string.parse(i)?;
         let (i, routing_key) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["nowait"])?;
-        let (i, arguments) = parse_field_table.parse(i)?;
+        let (i, arguments) = parse_field_table_with(i, options)?;
         Ok((
             i,
             Unbind {
@@ -3349,6 +8854,27 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for Unbind {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::Unbind(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Unbind {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_unbind(self)),
+            )
+        }
+    }
     /// unbind-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct UnbindOk {}
@@ -3363,10 +8889,46 @@ pub mod exchange {
         pub fn get_amqp_method_id(&self) -> Identifier {
             51
         }
+
+        /// Whether unbind-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of unbind-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build unbind-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build unbind-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for exchange.unbind-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Unbind> for UnbindOk {
+        /// Build the reply to unbind, copying the fields they share (Generated)
+        fn from(_: &Unbind) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse unbind-ok (Generated)
     pub fn parse_unbind_ok<I: ParsableInput>(i: I) -> ParserResult<I, UnbindOk> {
+        parse_unbind_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse unbind-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_unbind_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, UnbindOk> {
         Ok((i, UnbindOk {}))
     }
 
@@ -3379,63 +8941,135 @@ pub mod exchange {
             Ok(input)
         }
     }
+
+    impl Decode for UnbindOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Exchange(AMQPMethod::UnbindOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for UnbindOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(40), gen_unbind_ok(self)),
+            )
+        }
+    }
 }
 /// queue (generated)
+#[cfg(feature = "class-queue")]
 pub mod queue {
     use super::*;
 
     /// Parse queue (Generated)
     pub fn parse_queue<I: ParsableInput>(i: I) -> ParserResult<I, queue::AMQPMethod> {
+        parse_queue_with(i, ParsingOptions::default())
+    }
+
+    /// Parse queue, parsing its field tables with the given options (Generated)
+    pub fn parse_queue_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, queue::AMQPMethod> {
         context(
             "parse_queue",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
                         10 => context(
                             "parse_declare",
-                            map(map(parse_declare, AMQPMethod::Declare), Some),
+                            map(
+                                map(|i: I| parse_declare_with(i, options), AMQPMethod::Declare),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         11 => context(
                             "parse_declare_ok",
-                            map(map(parse_declare_ok, AMQPMethod::DeclareOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_declare_ok_with(i, options),
+                                    AMQPMethod::DeclareOk,
+                                ),
+                                Some,
+                            ),
+                        )
+                        .parse(i),
+                        20 => context(
+                            "parse_bind",
+                            map(
+                                map(|i: I| parse_bind_with(i, options), AMQPMethod::Bind),
+                                Some,
+                            ),
                         )
                         .parse(i),
-                        20 => context("parse_bind", map(map(parse_bind, AMQPMethod::Bind), Some))
-                            .parse(i),
                         21 => context(
                             "parse_bind_ok",
-                            map(map(parse_bind_ok, AMQPMethod::BindOk), Some),
+                            map(
+                                map(|i: I| parse_bind_ok_with(i, options), AMQPMethod::BindOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         30 => context(
                             "parse_purge",
-                            map(map(parse_purge, AMQPMethod::Purge), Some),
+                            map(
+                                map(|i: I| parse_purge_with(i, options), AMQPMethod::Purge),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         31 => context(
                             "parse_purge_ok",
-                            map(map(parse_purge_ok, AMQPMethod::PurgeOk), Some),
+                            map(
+                                map(|i: I| parse_purge_ok_with(i, options), AMQPMethod::PurgeOk),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         40 => context(
                             "parse_delete",
-                            map(map(parse_delete, AMQPMethod::Delete), Some),
+                            map(
+                                map(|i: I| parse_delete_with(i, options), AMQPMethod::Delete),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         41 => context(
                             "parse_delete_ok",
-                            map(map(parse_delete_ok, AMQPMethod::DeleteOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_delete_ok_with(i, options),
+                                    AMQPMethod::DeleteOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         50 => context(
                             "parse_unbind",
-                            map(map(parse_unbind, AMQPMethod::Unbind), Some),
+                            map(
+                                map(|i: I| parse_unbind_with(i, options), AMQPMethod::Unbind),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         51 => context(
                             "parse_unbind_ok",
-                            map(map(parse_unbind_ok, AMQPMethod::UnbindOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_unbind_ok_with(i, options),
+                                    AMQPMethod::UnbindOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         _ => Ok((i, None)),
@@ -3465,6 +9099,24 @@ pub mod queue {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_queue(self))
+        }
+    }
+
     /// The available methods in queue
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -3519,17 +9171,100 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether declare is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of declare, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "passive",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "durable",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "exclusive",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "auto_delete",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "arguments",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+        ];
+
+        /// Build declare with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: "".into(),
+                passive: false,
+                durable: false,
+                exclusive: false,
+                auto_delete: false,
+                nowait: false,
+                arguments: Default::default(),
+            }
+        }
+
+        /// Build declare from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "passive" => method.passive = from_argument(name, value)?,
+                    "durable" => method.durable = from_argument(name, value)?,
+                    "exclusive" => method.exclusive = from_argument(name, value)?,
+                    "auto_delete" => method.auto_delete = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    "arguments" => method.arguments = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.declare", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse declare (Generated)
     pub fn parse_declare<I: ParsableInput>(i: I) -> ParserResult<I, Declare> {
+        parse_declare_with(i, ParsingOptions::default())
+    }
+
+    /// Parse declare, parsing its field tables with the given options (Generated)
+    pub fn parse_declare_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Declare> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(
             i,
             &["passive", "durable", "exclusive", "auto_delete", "nowait"],
         )?;
-        let (i, arguments) = parse_field_table.parse(i)?;
+        let (i, arguments) = parse_field_table_with(i, options)?;
         Ok((
             i,
             Declare {
@@ -3563,6 +9298,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for Declare {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::Declare(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Declare {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_declare(self)),
+            )
+        }
+    }
     /// declare-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct DeclareOk {
@@ -3584,10 +9340,75 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether declare-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of declare-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "message_count",
+                amqp_type: AMQPType::LongUInt,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "consumer_count",
+                amqp_type: AMQPType::LongUInt,
+                default_value: None,
+            },
+        ];
+
+        /// Build declare-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: Default::default(),
+                message_count: Default::default(),
+                consumer_count: Default::default(),
+            }
+        }
+
+        /// Build declare-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "message_count" => method.message_count = from_argument(name, value)?,
+                    "consumer_count" => method.consumer_count = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.declare-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Declare> for DeclareOk {
+        /// Build the reply to declare, copying the fields they share (Generated)
+        fn from(request: &Declare) -> Self {
+            Self {
+                queue: request.queue.clone(),
+                ..Default::default()
+            }
+        }
     }
 
     /// Parse declare-ok (Generated)
     pub fn parse_declare_ok<I: ParsableInput>(i: I) -> ParserResult<I, DeclareOk> {
+        parse_declare_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse declare-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_declare_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, DeclareOk> {
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, message_count) = parse_long_uint.parse(i)?;
         let (i, consumer_count) = parse_long_uint.parse(i)?;
@@ -3613,6 +9434,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for DeclareOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::DeclareOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for DeclareOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_declare_ok(self)),
+            )
+        }
+    }
     /// bind (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Bind {
@@ -3638,16 +9480,85 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             20
         }
+
+        /// Whether bind is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of bind, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "arguments",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+        ];
+
+        /// Build bind with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: "".into(),
+                exchange: Default::default(),
+                routing_key: "".into(),
+                nowait: false,
+                arguments: Default::default(),
+            }
+        }
+
+        /// Build bind from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    "arguments" => method.arguments = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.bind", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse bind (Generated)
     pub fn parse_bind<I: ParsableInput>(i: I) -> ParserResult<I, Bind> {
+        parse_bind_with(i, ParsingOptions::default())
+    }
+
+    /// Parse bind, parsing its field tables with the given options (Generated)
+    pub fn parse_bind_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Bind> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, exchange) = parse_short_string.parse(i)?;
         let (i, routing_key) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["nowait"])?;
-        let (i, arguments) = parse_field_table.parse(i)?;
+        let (i, arguments) = parse_field_table_with(i, options)?;
         Ok((
             i,
             Bind {
@@ -3677,6 +9588,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for Bind {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::Bind(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Bind {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_bind(self)),
+            )
+        }
+    }
     /// bind-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct BindOk {}
@@ -3691,10 +9623,46 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             21
         }
+
+        /// Whether bind-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of bind-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build bind-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build bind-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for queue.bind-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Bind> for BindOk {
+        /// Build the reply to bind, copying the fields they share (Generated)
+        fn from(_: &Bind) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse bind-ok (Generated)
     pub fn parse_bind_ok<I: ParsableInput>(i: I) -> ParserResult<I, BindOk> {
+        parse_bind_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse bind-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_bind_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, BindOk> {
         Ok((i, BindOk {}))
     }
 
@@ -3707,6 +9675,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for BindOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::BindOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for BindOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_bind_ok(self)),
+            )
+        }
+    }
     /// purge (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Purge {
@@ -3726,10 +9715,58 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             30
         }
+
+        /// Whether purge is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of purge, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build purge with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: "".into(),
+                nowait: false,
+            }
+        }
+
+        /// Build purge from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.purge", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse purge (Generated)
     pub fn parse_purge<I: ParsableInput>(i: I) -> ParserResult<I, Purge> {
+        parse_purge_with(i, ParsingOptions::default())
+    }
+
+    /// Parse purge, parsing its field tables with the given options (Generated)
+    pub fn parse_purge_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Purge> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["nowait"])?;
@@ -3756,6 +9793,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for Purge {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::Purge(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Purge {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_purge(self)),
+            )
+        }
+    }
     /// purge-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct PurgeOk {
@@ -3773,10 +9831,56 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             31
         }
+
+        /// Whether purge-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of purge-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "message_count",
+            amqp_type: AMQPType::LongUInt,
+            default_value: None,
+        }];
+
+        /// Build purge-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                message_count: Default::default(),
+            }
+        }
+
+        /// Build purge-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "message_count" => method.message_count = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.purge-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Purge> for PurgeOk {
+        /// Build the reply to purge, copying the fields they share (Generated)
+        fn from(_: &Purge) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse purge-ok (Generated)
     pub fn parse_purge_ok<I: ParsableInput>(i: I) -> ParserResult<I, PurgeOk> {
+        parse_purge_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse purge-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_purge_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, PurgeOk> {
         let (i, message_count) = parse_long_uint.parse(i)?;
         Ok((i, PurgeOk { message_count }))
     }
@@ -3791,6 +9895,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for PurgeOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::PurgeOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for PurgeOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_purge_ok(self)),
+            )
+        }
+    }
     /// delete (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Delete {
@@ -3814,10 +9939,72 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             40
         }
+
+        /// Whether delete is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of delete, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "if_unused",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "if_empty",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+            ArgumentDescriptor {
+                name: "nowait",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean(false)),
+            },
+        ];
+
+        /// Build delete with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: "".into(),
+                if_unused: false,
+                if_empty: false,
+                nowait: false,
+            }
+        }
+
+        /// Build delete from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "if_unused" => method.if_unused = from_argument(name, value)?,
+                    "if_empty" => method.if_empty = from_argument(name, value)?,
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.delete", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse delete (Generated)
     pub fn parse_delete<I: ParsableInput>(i: I) -> ParserResult<I, Delete> {
+        parse_delete_with(i, ParsingOptions::default())
+    }
+
+    /// Parse delete, parsing its field tables with the given options (Generated)
+    pub fn parse_delete_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Delete> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, flags) = parse_flags(i, &["if_unused", "if_empty", "nowait"])?;
@@ -3848,6 +10035,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for Delete {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::Delete(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Delete {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_delete(self)),
+            )
+        }
+    }
     /// delete-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct DeleteOk {
@@ -3865,10 +10073,56 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             41
         }
+
+        /// Whether delete-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of delete-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "message_count",
+            amqp_type: AMQPType::LongUInt,
+            default_value: None,
+        }];
+
+        /// Build delete-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                message_count: Default::default(),
+            }
+        }
+
+        /// Build delete-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "message_count" => method.message_count = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.delete-ok", name)),
+                }
+            }
+            Ok(method)
+        }
+    }
+
+    impl From<&Delete> for DeleteOk {
+        /// Build the reply to delete, copying the fields they share (Generated)
+        fn from(_: &Delete) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse delete-ok (Generated)
     pub fn parse_delete_ok<I: ParsableInput>(i: I) -> ParserResult<I, DeleteOk> {
+        parse_delete_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse delete-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_delete_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, DeleteOk> {
         let (i, message_count) = parse_long_uint.parse(i)?;
         Ok((i, DeleteOk { message_count }))
     }
@@ -3883,6 +10137,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for DeleteOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::DeleteOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for DeleteOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_delete_ok(self)),
+            )
+        }
+    }
     /// unbind (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Unbind {
@@ -3906,15 +10181,77 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             50
         }
+
+        /// Whether unbind is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of unbind, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            ArgumentDescriptor {
+                name: "queue",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "exchange",
+                amqp_type: AMQPType::ShortString,
+                default_value: None,
+            },
+            ArgumentDescriptor {
+                name: "routing_key",
+                amqp_type: AMQPType::ShortString,
+                default_value: Some(|| AMQPValue::ShortString("".into())),
+            },
+            ArgumentDescriptor {
+                name: "arguments",
+                amqp_type: AMQPType::FieldTable,
+                default_value: None,
+            },
+        ];
+
+        /// Build unbind with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                queue: "".into(),
+                exchange: Default::default(),
+                routing_key: "".into(),
+                arguments: Default::default(),
+            }
+        }
+
+        /// Build unbind from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "queue" => method.queue = from_argument(name, value)?,
+                    "exchange" => method.exchange = from_argument(name, value)?,
+                    "routing_key" => method.routing_key = from_argument(name, value)?,
+                    "arguments" => method.arguments = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for queue.unbind", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse unbind (Generated)
     pub fn parse_unbind<I: ParsableInput>(i: I) -> ParserResult<I, Unbind> {
+        parse_unbind_with(i, ParsingOptions::default())
+    }
+
+    /// Parse unbind, parsing its field tables with the given options (Generated)
+    pub fn parse_unbind_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, Unbind> {
         let (i, _) = parse_short_uint.parse(i)?;
         let (i, queue) = parse_short_string.parse(i)?;
         let (i, exchange) = parse_short_string.parse(i)?;
         let (i, routing_key) = parse_short_string.parse(i)?;
-        let (i, arguments) = parse_field_table.parse(i)?;
+        let (i, arguments) = parse_field_table_with(i, options)?;
         Ok((
             i,
             Unbind {
@@ -3940,6 +10277,27 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for Unbind {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::Unbind(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Unbind {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_unbind(self)),
+            )
+        }
+    }
     /// unbind-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct UnbindOk {}
@@ -3954,10 +10312,46 @@ pub mod queue {
         pub fn get_amqp_method_id(&self) -> Identifier {
             51
         }
+
+        /// Whether unbind-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of unbind-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build unbind-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build unbind-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for queue.unbind-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Unbind> for UnbindOk {
+        /// Build the reply to unbind, copying the fields they share (Generated)
+        fn from(_: &Unbind) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse unbind-ok (Generated)
     pub fn parse_unbind_ok<I: ParsableInput>(i: I) -> ParserResult<I, UnbindOk> {
+        parse_unbind_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse unbind-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_unbind_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, UnbindOk> {
         Ok((i, UnbindOk {}))
     }
 
@@ -3970,46 +10364,103 @@ pub mod queue {
             Ok(input)
         }
     }
+
+    impl Decode for UnbindOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Queue(AMQPMethod::UnbindOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for UnbindOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(50), gen_unbind_ok(self)),
+            )
+        }
+    }
 }
 /// tx (generated)
+#[cfg(feature = "class-tx")]
 pub mod tx {
     use super::*;
 
     /// Parse tx (Generated)
     pub fn parse_tx<I: ParsableInput>(i: I) -> ParserResult<I, tx::AMQPMethod> {
+        parse_tx_with(i, ParsingOptions::default())
+    }
+
+    /// Parse tx, parsing its field tables with the given options (Generated)
+    pub fn parse_tx_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, tx::AMQPMethod> {
         context(
             "parse_tx",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
                         10 => context(
                             "parse_select",
-                            map(map(parse_select, AMQPMethod::Select), Some),
+                            map(
+                                map(|i: I| parse_select_with(i, options), AMQPMethod::Select),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         11 => context(
                             "parse_select_ok",
-                            map(map(parse_select_ok, AMQPMethod::SelectOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_select_ok_with(i, options),
+                                    AMQPMethod::SelectOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         20 => context(
                             "parse_commit",
-                            map(map(parse_commit, AMQPMethod::Commit), Some),
+                            map(
+                                map(|i: I| parse_commit_with(i, options), AMQPMethod::Commit),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         21 => context(
                             "parse_commit_ok",
-                            map(map(parse_commit_ok, AMQPMethod::CommitOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_commit_ok_with(i, options),
+                                    AMQPMethod::CommitOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         30 => context(
                             "parse_rollback",
-                            map(map(parse_rollback, AMQPMethod::Rollback), Some),
+                            map(
+                                map(|i: I| parse_rollback_with(i, options), AMQPMethod::Rollback),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         31 => context(
                             "parse_rollback_ok",
-                            map(map(parse_rollback_ok, AMQPMethod::RollbackOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_rollback_ok_with(i, options),
+                                    AMQPMethod::RollbackOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         _ => Ok((i, None)),
@@ -4035,6 +10486,24 @@ pub mod tx {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Tx(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_tx(self))
+        }
+    }
+
     /// The available methods in tx
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -4066,10 +10535,39 @@ pub mod tx {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether select is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of select, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build select with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build select from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for tx.select", name));
+            }
+            Ok(Self::spec_default())
+        }
     }
 
     /// Parse select (Generated)
     pub fn parse_select<I: ParsableInput>(i: I) -> ParserResult<I, Select> {
+        parse_select_with(i, ParsingOptions::default())
+    }
+
+    /// Parse select, parsing its field tables with the given options (Generated)
+    pub fn parse_select_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Select> {
         Ok((i, Select {}))
     }
 
@@ -4082,6 +10580,27 @@ pub mod tx {
             Ok(input)
         }
     }
+
+    impl Decode for Select {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Tx(AMQPMethod::Select(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Select {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(90), gen_select(self)),
+            )
+        }
+    }
     /// select-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct SelectOk {}
@@ -4096,10 +10615,46 @@ pub mod tx {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether select-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of select-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build select-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build select-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for tx.select-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Select> for SelectOk {
+        /// Build the reply to select, copying the fields they share (Generated)
+        fn from(_: &Select) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse select-ok (Generated)
     pub fn parse_select_ok<I: ParsableInput>(i: I) -> ParserResult<I, SelectOk> {
+        parse_select_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse select-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_select_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, SelectOk> {
         Ok((i, SelectOk {}))
     }
 
@@ -4112,6 +10667,27 @@ pub mod tx {
             Ok(input)
         }
     }
+
+    impl Decode for SelectOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Tx(AMQPMethod::SelectOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for SelectOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(90), gen_select_ok(self)),
+            )
+        }
+    }
     /// commit (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Commit {}
@@ -4126,10 +10702,39 @@ pub mod tx {
         pub fn get_amqp_method_id(&self) -> Identifier {
             20
         }
+
+        /// Whether commit is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of commit, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build commit with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build commit from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for tx.commit", name));
+            }
+            Ok(Self::spec_default())
+        }
     }
 
     /// Parse commit (Generated)
     pub fn parse_commit<I: ParsableInput>(i: I) -> ParserResult<I, Commit> {
+        parse_commit_with(i, ParsingOptions::default())
+    }
+
+    /// Parse commit, parsing its field tables with the given options (Generated)
+    pub fn parse_commit_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Commit> {
         Ok((i, Commit {}))
     }
 
@@ -4142,6 +10747,27 @@ pub mod tx {
             Ok(input)
         }
     }
+
+    impl Decode for Commit {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Tx(AMQPMethod::Commit(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Commit {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(90), gen_commit(self)),
+            )
+        }
+    }
     /// commit-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct CommitOk {}
@@ -4156,10 +10782,46 @@ pub mod tx {
         pub fn get_amqp_method_id(&self) -> Identifier {
             21
         }
+
+        /// Whether commit-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of commit-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build commit-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build commit-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for tx.commit-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Commit> for CommitOk {
+        /// Build the reply to commit, copying the fields they share (Generated)
+        fn from(_: &Commit) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse commit-ok (Generated)
     pub fn parse_commit_ok<I: ParsableInput>(i: I) -> ParserResult<I, CommitOk> {
+        parse_commit_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse commit-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_commit_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, CommitOk> {
         Ok((i, CommitOk {}))
     }
 
@@ -4172,6 +10834,27 @@ pub mod tx {
             Ok(input)
         }
     }
+
+    impl Decode for CommitOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Tx(AMQPMethod::CommitOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for CommitOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(90), gen_commit_ok(self)),
+            )
+        }
+    }
     /// rollback (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct Rollback {}
@@ -4186,10 +10869,39 @@ pub mod tx {
         pub fn get_amqp_method_id(&self) -> Identifier {
             30
         }
+
+        /// Whether rollback is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of rollback, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build rollback with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build rollback from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for tx.rollback", name));
+            }
+            Ok(Self::spec_default())
+        }
     }
 
     /// Parse rollback (Generated)
     pub fn parse_rollback<I: ParsableInput>(i: I) -> ParserResult<I, Rollback> {
+        parse_rollback_with(i, ParsingOptions::default())
+    }
+
+    /// Parse rollback, parsing its field tables with the given options (Generated)
+    pub fn parse_rollback_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Rollback> {
         Ok((i, Rollback {}))
     }
 
@@ -4202,6 +10914,27 @@ pub mod tx {
             Ok(input)
         }
     }
+
+    impl Decode for Rollback {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Tx(AMQPMethod::Rollback(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Rollback {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(90), gen_rollback(self)),
+            )
+        }
+    }
     /// rollback-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct RollbackOk {}
@@ -4216,10 +10949,46 @@ pub mod tx {
         pub fn get_amqp_method_id(&self) -> Identifier {
             31
         }
+
+        /// Whether rollback-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of rollback-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build rollback-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build rollback-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for tx.rollback-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Rollback> for RollbackOk {
+        /// Build the reply to rollback, copying the fields they share (Generated)
+        fn from(_: &Rollback) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse rollback-ok (Generated)
     pub fn parse_rollback_ok<I: ParsableInput>(i: I) -> ParserResult<I, RollbackOk> {
+        parse_rollback_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse rollback-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_rollback_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, RollbackOk> {
         Ok((i, RollbackOk {}))
     }
 
@@ -4232,26 +11001,65 @@ pub mod tx {
             Ok(input)
         }
     }
+
+    impl Decode for RollbackOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Tx(AMQPMethod::RollbackOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for RollbackOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(90), gen_rollback_ok(self)),
+            )
+        }
+    }
 }
 /// confirm (generated)
+#[cfg(feature = "class-confirm")]
 pub mod confirm {
     use super::*;
 
     /// Parse confirm (Generated)
     pub fn parse_confirm<I: ParsableInput>(i: I) -> ParserResult<I, confirm::AMQPMethod> {
+        parse_confirm_with(i, ParsingOptions::default())
+    }
+
+    /// Parse confirm, parsing its field tables with the given options (Generated)
+    pub fn parse_confirm_with<I: ParsableInput>(
+        i: I,
+        options: ParsingOptions,
+    ) -> ParserResult<I, confirm::AMQPMethod> {
         context(
             "parse_confirm",
             map_opt(
-                flat_map(parse_id, |id| {
+                flat_map(parse_id, move |id| {
                     move |i| match id {
                         10 => context(
                             "parse_select",
-                            map(map(parse_select, AMQPMethod::Select), Some),
+                            map(
+                                map(|i: I| parse_select_with(i, options), AMQPMethod::Select),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         11 => context(
                             "parse_select_ok",
-                            map(map(parse_select_ok, AMQPMethod::SelectOk), Some),
+                            map(
+                                map(
+                                    |i: I| parse_select_ok_with(i, options),
+                                    AMQPMethod::SelectOk,
+                                ),
+                                Some,
+                            ),
                         )
                         .parse(i),
                         _ => Ok((i, None)),
@@ -4273,6 +11081,24 @@ pub mod confirm {
         })
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Confirm(method) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_confirm(self))
+        }
+    }
+
     /// The available methods in confirm
     #[derive(Clone, Debug, PartialEq)]
     pub enum AMQPMethod {
@@ -4299,10 +11125,47 @@ pub mod confirm {
         pub fn get_amqp_method_id(&self) -> Identifier {
             10
         }
+
+        /// Whether select is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of select, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[ArgumentDescriptor {
+            name: "nowait",
+            amqp_type: AMQPType::Boolean,
+            default_value: Some(|| AMQPValue::Boolean(false)),
+        }];
+
+        /// Build select with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self { nowait: false }
+        }
+
+        /// Build select from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    "nowait" => method.nowait = from_argument(name, value)?,
+                    _ => return Err(format!("Unknown argument {} for confirm.select", name)),
+                }
+            }
+            Ok(method)
+        }
     }
 
     /// Parse select (Generated)
     pub fn parse_select<I: ParsableInput>(i: I) -> ParserResult<I, Select> {
+        parse_select_with(i, ParsingOptions::default())
+    }
+
+    /// Parse select, parsing its field tables with the given options (Generated)
+    pub fn parse_select_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, Select> {
         let (i, flags) = parse_flags(i, &["nowait"])?;
         Ok((
             i,
@@ -4324,6 +11187,27 @@ pub mod confirm {
             Ok(input)
         }
     }
+
+    impl Decode for Select {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Confirm(AMQPMethod::Select(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for Select {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(85), gen_select(self)),
+            )
+        }
+    }
     /// select-ok (Generated)
     #[derive(Clone, Debug, Default, PartialEq)]
     pub struct SelectOk {}
@@ -4338,10 +11222,46 @@ pub mod confirm {
         pub fn get_amqp_method_id(&self) -> Identifier {
             11
         }
+
+        /// Whether select-ok is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            false
+        }
+
+        /// The arguments of select-ok, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[];
+
+        /// Build select-ok with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {}
+        }
+
+        /// Build select-ok from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for confirm.select-ok", name));
+            }
+            Ok(Self::spec_default())
+        }
+    }
+
+    impl From<&Select> for SelectOk {
+        /// Build the reply to select, copying the fields they share (Generated)
+        fn from(_: &Select) -> Self {
+            Self::default()
+        }
     }
 
     /// Parse select-ok (Generated)
     pub fn parse_select_ok<I: ParsableInput>(i: I) -> ParserResult<I, SelectOk> {
+        parse_select_ok_with(i, ParsingOptions::default())
+    }
+
+    /// Parse select-ok, parsing its field tables with the given options (Generated)
+    pub fn parse_select_ok_with<I: ParsableInput>(
+        i: I,
+        _options: ParsingOptions,
+    ) -> ParserResult<I, SelectOk> {
         Ok((i, SelectOk {}))
     }
 
@@ -4354,4 +11274,25 @@ pub mod confirm {
             Ok(input)
         }
     }
+
+    impl Decode for SelectOk {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| {
+                map_opt(parse_class, |class| match class {
+                    AMQPClass::Confirm(AMQPMethod::SelectOk(method)) => Some(method),
+                    _ => None,
+                })
+                .parse(i)
+            })
+        }
+    }
+
+    impl Encode for SelectOk {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(
+                buffer,
+                cookie_factory::sequence::pair(gen_id(85), gen_select_ok(self)),
+            )
+        }
+    }
 }
@@ -0,0 +1,537 @@
+use crate::{
+    auth::{parse_mechanisms, Mechanism},
+    error::ProtocolError,
+    frame::ProtocolVersion,
+    protocol::connection,
+    types::{AMQPValue, ChannelId, FieldTable, FrameSize, Heartbeat, LongString, ShortString},
+    uri::{AMQPQueryString, AMQPUri},
+};
+
+/// The outcome of a connection handshake
+///
+/// This gathers what the server advertised in connection.start and the tuning values agreed
+/// on in connection.tune-ok.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NegotiatedLimits {
+    /// The highest channel number that can be used, 0 means no limit
+    pub channel_max: ChannelId,
+    /// The largest frame size that can be sent, 0 means no limit
+    pub frame_max: FrameSize,
+    /// The heartbeat interval in seconds, 0 means heartbeats are disabled
+    pub heartbeat: Heartbeat,
+    /// The properties the server sent in connection.start
+    pub server_properties: FieldTable,
+    /// The SASL mechanisms supported by the server
+    pub mechanisms: Vec<Mechanism>,
+    /// The message locales supported by the server
+    pub locales: Vec<String>,
+}
+
+impl NegotiatedLimits {
+    /// Gather the limits from the connection.start we received and the connection.tune-ok we sent
+    pub fn new(start: connection::Start, tune_ok: &connection::TuneOk) -> Self {
+        Self {
+            channel_max: tune_ok.channel_max,
+            frame_max: tune_ok.frame_max,
+            heartbeat: tune_ok.heartbeat,
+            server_properties: start.server_properties,
+            mechanisms: parse_mechanisms(&start.mechanisms),
+            locales: parse_locales(&start.locales),
+        }
+    }
+
+    /// The capabilities advertised by the server
+    pub fn capabilities(&self) -> ServerCapabilities {
+        ServerCapabilities::from_server_properties(&self.server_properties)
+    }
+
+    /// The maximum size of the payload of a body frame, 0 meaning no limit
+    pub fn max_body_frame_size(&self) -> usize {
+        /* 7 bytes of header and the frame end octet */
+        (self.frame_max as usize).saturating_sub(8)
+    }
+}
+
+/// The known RabbitMQ capabilities, from the capabilities table of the server properties
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ServerCapabilities {
+    /// confirm.select is supported
+    pub publisher_confirms: bool,
+    /// exchange.bind and exchange.unbind are supported
+    pub exchange_exchange_bindings: bool,
+    /// basic.nack is supported
+    pub basic_nack: bool,
+    /// The server sends basic.cancel when a queue being consumed is deleted
+    pub consumer_cancel_notify: bool,
+    /// The server sends connection.blocked and connection.unblocked
+    pub connection_blocked: bool,
+    /// The x-priority consumer argument is supported
+    pub consumer_priorities: bool,
+    /// The server sends connection.close on authentication failures
+    pub authentication_failure_close: bool,
+    /// basic.qos with global set to false applies to each consumer
+    pub per_consumer_qos: bool,
+    /// The amq.rabbitmq.reply-to pseudo queue is supported
+    pub direct_reply_to: bool,
+}
+
+impl ServerCapabilities {
+    /// Parse the capabilities table of the properties sent in connection.start
+    pub fn from_server_properties(server_properties: &FieldTable) -> Self {
+        match server_properties.inner().get("capabilities") {
+            Some(AMQPValue::FieldTable(capabilities)) => Self::from_table(capabilities),
+            _ => Self::default(),
+        }
+    }
+
+    /// Parse a capabilities table, unknown capabilities are ignored
+    pub fn from_table(capabilities: &FieldTable) -> Self {
+        let enabled = |name: &str| {
+            matches!(
+                capabilities.inner().get(name),
+                Some(AMQPValue::Boolean(true))
+            )
+        };
+        Self {
+            publisher_confirms: enabled("publisher_confirms"),
+            exchange_exchange_bindings: enabled("exchange_exchange_bindings"),
+            basic_nack: enabled("basic.nack"),
+            consumer_cancel_notify: enabled("consumer_cancel_notify"),
+            connection_blocked: enabled("connection.blocked"),
+            consumer_priorities: enabled("consumer_priorities"),
+            authentication_failure_close: enabled("authentication_failure_close"),
+            per_consumer_qos: enabled("per_consumer_qos"),
+            direct_reply_to: enabled("direct_reply_to"),
+        }
+    }
+}
+
+/// The client properties sent in connection.start-ok
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientProperties {
+    product: Option<String>,
+    version: Option<String>,
+    platform: Option<String>,
+    information: Option<String>,
+    connection_name: Option<String>,
+    capabilities: FieldTable,
+    extra: FieldTable,
+}
+
+impl ClientProperties {
+    /// Start from the conventional properties: the Rust platform and the capabilities most
+    /// clients support (publisher_confirms, exchange_exchange_bindings, basic.nack,
+    /// consumer_cancel_notify, connection.blocked and authentication_failure_close)
+    pub fn builder() -> Self {
+        let mut capabilities = FieldTable::default();
+        for capability in [
+            "publisher_confirms",
+            "exchange_exchange_bindings",
+            "basic.nack",
+            "consumer_cancel_notify",
+            "connection.blocked",
+            "authentication_failure_close",
+        ] {
+            capabilities.insert(capability.into(), AMQPValue::Boolean(true));
+        }
+        Self {
+            product: None,
+            version: None,
+            platform: Some("Rust".to_string()),
+            information: None,
+            connection_name: None,
+            capabilities,
+            extra: FieldTable::default(),
+        }
+    }
+
+    /// Start from the conventional properties, naming the connection after the connection_name
+    /// (or name) query parameter of the uri, if any
+    pub fn from_uri(uri: &AMQPUri) -> Self {
+        Self {
+            connection_name: uri.query.connection_name.clone(),
+            ..Self::builder()
+        }
+    }
+
+    /// The name of the client library or application
+    pub fn with_product(mut self, product: &str) -> Self {
+        self.product = Some(product.to_string());
+        self
+    }
+
+    /// The version of the client library or application
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// The platform the client runs on
+    pub fn with_platform(mut self, platform: &str) -> Self {
+        self.platform = Some(platform.to_string());
+        self
+    }
+
+    /// Free-form information, usually a link to the client documentation
+    pub fn with_information(mut self, information: &str) -> Self {
+        self.information = Some(information.to_string());
+        self
+    }
+
+    /// The name the broker displays for this connection
+    pub fn with_connection_name(mut self, connection_name: &str) -> Self {
+        self.connection_name = Some(connection_name.to_string());
+        self
+    }
+
+    /// Advertise whether the client supports the given capability
+    pub fn with_capability(mut self, capability: &str, enabled: bool) -> Self {
+        self.capabilities
+            .insert(capability.into(), AMQPValue::Boolean(enabled));
+        self
+    }
+
+    /// Add a custom property
+    pub fn with_property(mut self, key: &str, value: AMQPValue) -> Self {
+        self.extra.insert(key.into(), value);
+        self
+    }
+
+    /// Build the client properties table
+    pub fn build(self) -> FieldTable {
+        let mut properties = self.extra;
+        for (key, value) in [
+            ("product", self.product),
+            ("version", self.version),
+            ("platform", self.platform),
+            ("information", self.information),
+            ("connection_name", self.connection_name),
+        ] {
+            if let Some(value) = value {
+                properties.insert(key.into(), AMQPValue::LongString(value.into()));
+            }
+        }
+        properties.insert(
+            "capabilities".into(),
+            AMQPValue::FieldTable(self.capabilities),
+        );
+        properties
+    }
+}
+
+impl Default for ClientProperties {
+    fn default() -> Self {
+        Self::builder()
+    }
+}
+
+impl From<ClientProperties> for FieldTable {
+    fn from(properties: ClientProperties) -> Self {
+        properties.build()
+    }
+}
+
+/// Negotiate one of the values of connection.tune, where 0 means no limit
+///
+/// The lowest value wins, unless one side has no limit in which case the other one wins.
+/// This is the rule for channel_max, frame_max and heartbeat alike.
+pub fn negotiate<T: Copy + Default + Ord>(client_value: T, server_value: T) -> T {
+    if client_value == T::default() || server_value == T::default() {
+        client_value.max(server_value)
+    } else {
+        client_value.min(server_value)
+    }
+}
+
+/// Build the connection.tune-ok answering the server's connection.tune
+///
+/// Each value is negotiated with the one from the uri query, the server's being accepted as is
+/// when the uri does not specify it.
+pub fn negotiate_tune(tune: &connection::Tune, query: &AMQPQueryString) -> connection::TuneOk {
+    fn negotiate_with<T: Copy + Default + Ord>(client_value: Option<T>, server_value: T) -> T {
+        client_value.map_or(server_value, |client_value| {
+            negotiate(client_value, server_value)
+        })
+    }
+    connection::TuneOk {
+        channel_max: negotiate_with(query.channel_max, tune.channel_max),
+        frame_max: negotiate_with(query.frame_max, tune.frame_max),
+        heartbeat: negotiate_with(query.heartbeat, tune.heartbeat),
+    }
+}
+
+/// Check the first bytes the peer sent in response to our protocol header
+///
+/// A server which doesn't speak the version we asked for answers with the protocol header of
+/// the one it speaks before closing the connection. The response needs to hold at least 8
+/// bytes to tell, anything else than a protocol header is accepted.
+pub fn check_protocol_response(
+    ours: ProtocolVersion,
+    response: &[u8],
+) -> Result<(), ProtocolError> {
+    if !response.starts_with(b"AMQP") {
+        return Ok(());
+    }
+    match ProtocolVersion::from_header(response) {
+        Some(theirs) => Err(ProtocolError::VersionMismatch { ours, theirs }),
+        None => Err(ProtocolError::Protocol(format!(
+            "Truncated protocol header: {:?}",
+            response
+        ))),
+    }
+}
+
+/// The locale every server must support according to the specification
+pub const DEFAULT_LOCALE: &str = "en_US";
+
+/// Pick the locale to send in connection.start-ok
+///
+/// The client preferences are tried in order, first for an exact match (ignoring case and
+/// treating "-" as "_"), then for a locale of the same language. The spelling of the server is
+/// kept. If nothing matches, fall back to en_US when the server offers it.
+pub fn negotiate_locale(server_locales: &LongString, preferences: &[&str]) -> Option<ShortString> {
+    let offered = parse_locales(server_locales);
+    let normalize = |locale: &str| locale.replace('-', "_").to_lowercase();
+    let language = |locale: &str| {
+        normalize(locale)
+            .split('_')
+            .next()
+            .unwrap_or("")
+            .to_string()
+    };
+    let exact = preferences.iter().find_map(|preference| {
+        offered
+            .iter()
+            .find(|locale| normalize(locale) == normalize(preference))
+    });
+    let same_language = || {
+        preferences.iter().find_map(|preference| {
+            offered
+                .iter()
+                .find(|locale| language(locale) == language(preference))
+        })
+    };
+    let default = || offered.iter().find(|locale| *locale == DEFAULT_LOCALE);
+    exact
+        .or_else(same_language)
+        .or_else(default)
+        .map(|locale| locale.as_str().into())
+}
+
+/// Split the space-separated locales list of connection.start
+pub fn parse_locales(locales: &LongString) -> Vec<String> {
+    locales
+        .to_str_lossy()
+        .split_whitespace()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Render a locales list the way connection.start carries it
+pub fn render_locales<S: AsRef<str>>(locales: &[S]) -> LongString {
+    locales
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::auth::SASLMechanism;
+
+    #[test]
+    fn test_negotiated_limits() {
+        let limits = NegotiatedLimits::new(
+            connection::Start {
+                version_major: 0,
+                version_minor: 9,
+                server_properties: FieldTable::default(),
+                mechanisms: "PLAIN AMQPLAIN ".into(),
+                locales: "en_US".into(),
+            },
+            &connection::TuneOk {
+                channel_max: 2047,
+                frame_max: 131_072,
+                heartbeat: 60,
+            },
+        );
+        assert_eq!(limits.channel_max, 2047);
+        assert_eq!(limits.max_body_frame_size(), 131_064);
+        assert_eq!(
+            limits.mechanisms,
+            vec![
+                Mechanism::Supported(SASLMechanism::Plain),
+                Mechanism::Supported(SASLMechanism::AMQPlain)
+            ]
+        );
+        assert_eq!(limits.locales, vec!["en_US"]);
+    }
+
+    #[test]
+    fn test_negotiate() {
+        for client_value in 0..=4u16 {
+            for server_value in 0..=4u16 {
+                let value = negotiate(client_value, server_value);
+                assert_eq!(value, negotiate(server_value, client_value));
+                match (client_value, server_value) {
+                    (0, 0) => assert_eq!(value, 0),
+                    (0, limit) | (limit, 0) => assert_eq!(value, limit),
+                    (client_value, server_value) => {
+                        assert_eq!(value, client_value.min(server_value))
+                    }
+                }
+            }
+        }
+        assert_eq!(negotiate(u32::MAX, 4096), 4096);
+        assert_eq!(negotiate(0, u32::MAX), u32::MAX);
+        assert_eq!(negotiate(u16::MAX, 0), u16::MAX);
+    }
+
+    #[test]
+    fn test_negotiate_tune() {
+        let tune = connection::Tune {
+            channel_max: 2047,
+            frame_max: 131_072,
+            heartbeat: 0,
+        };
+        let query = AMQPQueryString {
+            channel_max: Some(0),
+            frame_max: Some(4096),
+            heartbeat: Some(30),
+            ..AMQPQueryString::default()
+        };
+        assert_eq!(
+            negotiate_tune(&tune, &query),
+            connection::TuneOk {
+                channel_max: 2047,
+                frame_max: 4096,
+                heartbeat: 30,
+            }
+        );
+        assert_eq!(
+            negotiate_tune(&tune, &AMQPQueryString::default()),
+            connection::TuneOk::from(&tune)
+        );
+    }
+
+    #[test]
+    fn test_server_capabilities() {
+        let mut capabilities = FieldTable::default();
+        capabilities.insert("publisher_confirms".into(), AMQPValue::Boolean(true));
+        capabilities.insert("basic.nack".into(), AMQPValue::Boolean(true));
+        capabilities.insert("connection.blocked".into(), AMQPValue::Boolean(false));
+        capabilities.insert(
+            "direct_reply_to".into(),
+            AMQPValue::LongString("yes".into()),
+        );
+        let mut server_properties = FieldTable::default();
+        server_properties.insert("capabilities".into(), AMQPValue::FieldTable(capabilities));
+        assert_eq!(
+            ServerCapabilities::from_server_properties(&server_properties),
+            ServerCapabilities {
+                publisher_confirms: true,
+                basic_nack: true,
+                ..ServerCapabilities::default()
+            }
+        );
+        assert_eq!(
+            ServerCapabilities::from_server_properties(&FieldTable::default()),
+            ServerCapabilities::default()
+        );
+    }
+
+    #[test]
+    fn test_client_properties() {
+        let properties = ClientProperties::builder()
+            .with_product("test")
+            .with_version("1.0.0")
+            .with_connection_name("worker-1")
+            .with_capability("connection.blocked", false)
+            .with_property("custom", AMQPValue::LongLongInt(42))
+            .build();
+        let inner = properties.inner();
+        assert_eq!(
+            inner.get("product"),
+            Some(&AMQPValue::LongString("test".into()))
+        );
+        assert_eq!(
+            inner.get("platform"),
+            Some(&AMQPValue::LongString("Rust".into()))
+        );
+        assert_eq!(
+            inner.get("connection_name"),
+            Some(&AMQPValue::LongString("worker-1".into()))
+        );
+        assert_eq!(inner.get("information"), None);
+        assert_eq!(inner.get("custom"), Some(&AMQPValue::LongLongInt(42)));
+        let Some(AMQPValue::FieldTable(capabilities)) = inner.get("capabilities") else {
+            panic!("missing capabilities");
+        };
+        assert_eq!(
+            capabilities.inner().get("basic.nack"),
+            Some(&AMQPValue::Boolean(true))
+        );
+        assert_eq!(
+            capabilities.inner().get("connection.blocked"),
+            Some(&AMQPValue::Boolean(false))
+        );
+
+        let uri: AMQPUri = "amqp://localhost/%2f?name=worker-2".parse().unwrap();
+        assert_eq!(
+            ClientProperties::from_uri(&uri)
+                .build()
+                .inner()
+                .get("connection_name"),
+            Some(&AMQPValue::LongString("worker-2".into()))
+        );
+        assert_eq!(
+            ClientProperties::from_uri(&AMQPUri::default()),
+            ClientProperties::builder()
+        );
+    }
+
+    #[test]
+    fn test_check_protocol_response() {
+        let ours = ProtocolVersion::amqp_0_9_1();
+        assert!(check_protocol_response(ours, &[1, 0, 0, 0, 0, 0, 0xCE]).is_ok());
+        let mismatch = |response: &[u8]| match check_protocol_response(ours, response) {
+            Err(ProtocolError::VersionMismatch { theirs, .. }) => Some(theirs.to_string()),
+            _ => None,
+        };
+        assert_eq!(mismatch(b"AMQP\x00\x01\x00\x00"), Some("1.0.0".into()));
+        assert_eq!(mismatch(b"AMQP\x01\x01\x00\x09"), Some("0.9.0".into()));
+        assert_eq!(mismatch(b"AMQP\x01\x01\x08\x00"), Some("8.0.0".into()));
+        assert!(matches!(
+            check_protocol_response(ours, b"AMQP\x00"),
+            Err(ProtocolError::Protocol(_))
+        ));
+    }
+
+    #[test]
+    fn test_locales() {
+        let locales = parse_locales(&" en_US\tfr_FR ".into());
+        assert_eq!(locales, vec!["en_US", "fr_FR"]);
+        assert_eq!(render_locales(&locales), "en_US fr_FR".into());
+        assert_eq!(render_locales::<&str>(&[]), "".into());
+    }
+
+    #[test]
+    fn test_negotiate_locale() {
+        let offered = LongString::from("en_US fr_FR de_DE");
+        assert_eq!(
+            negotiate_locale(&offered, &["fr-fr", "de_DE"]),
+            Some("fr_FR".into())
+        );
+        assert_eq!(
+            negotiate_locale(&offered, &["de_AT", "fr_FR"]),
+            Some("fr_FR".into())
+        );
+        assert_eq!(negotiate_locale(&offered, &["de_AT"]), Some("de_DE".into()));
+        assert_eq!(negotiate_locale(&offered, &["ja_JP"]), Some("en_US".into()));
+        assert_eq!(negotiate_locale(&offered, &[]), Some("en_US".into()));
+        assert_eq!(negotiate_locale(&"fr_FR".into(), &["ja_JP"]), None);
+    }
+}
@@ -5,16 +5,64 @@
 //! amq-protocol is a library aiming at providing tools to help
 //! implementing software using AMQP
 
+/// Reexport of amq_protocol_codegen
+#[cfg(feature = "codegen-lib")]
+pub use amq_protocol_codegen as codegen;
 /// Reexport of amq_protocol_tcp
+#[cfg(feature = "tcp")]
 pub use amq_protocol_tcp as tcp;
 /// Reexport of amq_protocol_types
 pub use amq_protocol_types as types;
 /// Reexport of amq_protocol_uri
 pub use amq_protocol_uri as uri;
 
+/// Typed builders for the x-arguments tables
+pub mod arguments;
 /// Utility to handle SASL authentication with AMQP server
 pub mod auth;
+/// Representative frame corpora for benchmarks
+#[cfg(feature = "bench-data")]
+pub mod bench_data;
+/// Transparent compression of message bodies
+#[cfg(feature = "compression")]
+pub mod compression;
+/// Scripted exchange of frames with a live broker to check our encoders
+#[cfg(feature = "test-utils")]
+pub mod conformance;
+/// Reconstruct the dead-letter routing chain of messages
+pub mod dead_letter;
+/// Serde types for the definitions.json exported by RabbitMQ
+#[cfg(feature = "definitions")]
+pub mod definitions;
+/// Check our codec against the encodings produced by other clients
+#[cfg(feature = "test-utils")]
+pub mod differential;
+/// Decode the frame dumps found in bug reports and craft new ones
+pub mod dump;
+/// A single error type for all the amq-protocol crates
+pub mod error;
 /// AMQP Frame handling utils
 pub mod frame;
+/// Helpers for the connection handshake
+pub mod handshake;
+/// Messages reassembled from their method, content header and body frames
+pub mod message;
+/// A minimal in-process broker for tests
+#[cfg(feature = "test-utils")]
+pub mod mock;
+/// The commonly used types and traits, re-exported from a single place which stays stable when
+/// the modules they come from get reorganized
+pub mod prelude;
 /// The AMQ Protocol implementation (Generated)
 pub mod protocol;
+/// Building blocks for AMQP-aware proxies
+pub mod proxy;
+/// Helpers to settle deliveries according to a retry policy
+pub mod retry;
+/// Helpers for the RPC over AMQP conventions
+pub mod rpc;
+/// Sign messages and verify their signature
+#[cfg(feature = "signing")]
+pub mod signing;
+/// Helpers to track the state of a connection
+pub mod state;
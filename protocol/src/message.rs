@@ -0,0 +1,551 @@
+use crate::{
+    frame::{AMQPFrame, Bytes},
+    protocol::{basic, AMQPClass, AMQPError, BasicProperties},
+    types::{ChannelId, DeliveryTag, LongUInt, PayloadSize, ReplyCode, ShortString},
+};
+use std::collections::HashMap;
+
+const MAX_PREALLOCATION: PayloadSize = 1024 * 1024;
+
+/// A message delivered to a consumer, or fetched with basic.get
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delivery {
+    channel_id: ChannelId,
+    delivery_tag: DeliveryTag,
+    redelivered: bool,
+    exchange: ShortString,
+    routing_key: ShortString,
+    consumer_tag: Option<ShortString>,
+    message_count: Option<LongUInt>,
+    properties: BasicProperties,
+    body: Bytes,
+}
+
+impl Delivery {
+    fn from_deliver(channel_id: ChannelId, deliver: basic::Deliver) -> Self {
+        Self {
+            channel_id,
+            delivery_tag: deliver.delivery_tag,
+            redelivered: deliver.redelivered,
+            exchange: deliver.exchange,
+            routing_key: deliver.routing_key,
+            consumer_tag: Some(deliver.consumer_tag),
+            message_count: None,
+            properties: BasicProperties::default(),
+            body: Bytes::new(),
+        }
+    }
+
+    fn from_get_ok(channel_id: ChannelId, get_ok: basic::GetOk) -> Self {
+        Self {
+            channel_id,
+            delivery_tag: get_ok.delivery_tag,
+            redelivered: get_ok.redelivered,
+            exchange: get_ok.exchange,
+            routing_key: get_ok.routing_key,
+            consumer_tag: None,
+            message_count: Some(get_ok.message_count),
+            properties: BasicProperties::default(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// The channel the message was delivered on
+    pub fn channel_id(&self) -> ChannelId {
+        self.channel_id
+    }
+
+    /// The tag to acknowledge the message with
+    pub fn delivery_tag(&self) -> DeliveryTag {
+        self.delivery_tag
+    }
+
+    /// Whether the message has already been delivered before
+    pub fn redelivered(&self) -> bool {
+        self.redelivered
+    }
+
+    /// The exchange the message was published to
+    pub fn exchange(&self) -> &ShortString {
+        &self.exchange
+    }
+
+    /// The routing key the message was published with
+    pub fn routing_key(&self) -> &ShortString {
+        &self.routing_key
+    }
+
+    /// The consumer the message was delivered to, None if it was fetched with basic.get
+    pub fn consumer_tag(&self) -> Option<&ShortString> {
+        self.consumer_tag.as_ref()
+    }
+
+    /// The number of messages left in the queue, only known when fetched with basic.get
+    pub fn message_count(&self) -> Option<LongUInt> {
+        self.message_count
+    }
+
+    /// The properties of the message
+    pub fn properties(&self) -> &BasicProperties {
+        &self.properties
+    }
+
+    /// The payload of the message
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Get back the payload of the message
+    pub fn into_body(self) -> Bytes {
+        self.body
+    }
+
+    /// The frame settling this delivery with the given outcome
+    pub fn settle(&self, outcome: AckOutcome) -> AMQPFrame {
+        outcome.to_frame(self.channel_id, self.delivery_tag)
+    }
+}
+
+/// How a consumer settles a delivery
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AckOutcome {
+    /// The message was processed, with basic.ack
+    Ack,
+    /// The message was not processed and must be delivered again, with basic.nack
+    NackRequeue,
+    /// The message was not processed and must be dropped or dead-lettered, with basic.nack
+    NackDiscard,
+    /// The message was not processed and must be delivered again, with basic.reject
+    RejectRequeue,
+    /// The message was not processed and must be dropped or dead-lettered, with basic.reject
+    RejectDiscard,
+}
+
+impl AckOutcome {
+    /// Whether the message goes back to its queue
+    pub fn requeue(self) -> bool {
+        matches!(self, AckOutcome::NackRequeue | AckOutcome::RejectRequeue)
+    }
+
+    /// The method settling the delivery with the given tag
+    pub fn to_method(self, delivery_tag: DeliveryTag) -> basic::AMQPMethod {
+        match self {
+            AckOutcome::Ack => basic::AMQPMethod::Ack(basic::Ack {
+                delivery_tag,
+                multiple: false,
+            }),
+            AckOutcome::NackRequeue | AckOutcome::NackDiscard => {
+                basic::AMQPMethod::Nack(basic::Nack {
+                    delivery_tag,
+                    multiple: false,
+                    requeue: self.requeue(),
+                })
+            }
+            AckOutcome::RejectRequeue | AckOutcome::RejectDiscard => {
+                basic::AMQPMethod::Reject(basic::Reject {
+                    delivery_tag,
+                    requeue: self.requeue(),
+                })
+            }
+        }
+    }
+
+    /// The method settling all the unsettled deliveries up to the given tag included,
+    /// None for the reject outcomes as basic.reject settles a single delivery
+    pub fn to_method_multiple(self, delivery_tag: DeliveryTag) -> Option<basic::AMQPMethod> {
+        match self.to_method(delivery_tag) {
+            basic::AMQPMethod::Ack(ack) => Some(basic::AMQPMethod::Ack(basic::Ack {
+                multiple: true,
+                ..ack
+            })),
+            basic::AMQPMethod::Nack(nack) => Some(basic::AMQPMethod::Nack(basic::Nack {
+                multiple: true,
+                ..nack
+            })),
+            _ => None,
+        }
+    }
+
+    /// The frame settling the delivery with the given tag on the given channel
+    pub fn to_frame(self, channel_id: ChannelId, delivery_tag: DeliveryTag) -> AMQPFrame {
+        AMQPFrame::Method(channel_id, AMQPClass::Basic(self.to_method(delivery_tag)))
+    }
+}
+
+/// A message the server could not route, sent back with basic.return
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReturnedMessage {
+    channel_id: ChannelId,
+    reply_code: ReplyCode,
+    reply_text: ShortString,
+    exchange: ShortString,
+    routing_key: ShortString,
+    properties: BasicProperties,
+    body: Bytes,
+}
+
+impl ReturnedMessage {
+    fn from_return(channel_id: ChannelId, method: basic::Return) -> Self {
+        Self {
+            channel_id,
+            reply_code: method.reply_code,
+            reply_text: method.reply_text,
+            exchange: method.exchange,
+            routing_key: method.routing_key,
+            properties: BasicProperties::default(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// The channel the message was published on
+    pub fn channel_id(&self) -> ChannelId {
+        self.channel_id
+    }
+
+    /// The reply code explaining why the message was returned
+    pub fn reply_code(&self) -> ReplyCode {
+        self.reply_code
+    }
+
+    /// The reply text explaining why the message was returned
+    pub fn reply_text(&self) -> &ShortString {
+        &self.reply_text
+    }
+
+    /// The reply code and text as an AMQPError, None if the reply code is unknown
+    pub fn error(&self) -> Option<AMQPError> {
+        AMQPError::from_id(self.reply_code, self.reply_text.clone())
+    }
+
+    /// The exchange the message was published to
+    pub fn exchange(&self) -> &ShortString {
+        &self.exchange
+    }
+
+    /// The routing key the message was published with
+    pub fn routing_key(&self) -> &ShortString {
+        &self.routing_key
+    }
+
+    /// The properties of the message
+    pub fn properties(&self) -> &BasicProperties {
+        &self.properties
+    }
+
+    /// The payload of the message
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Get back the payload of the message
+    pub fn into_body(self) -> Bytes {
+        self.body
+    }
+}
+
+/// A message reassembled from its frames
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    /// A basic.deliver or basic.get-ok, with its content
+    Delivery(Delivery),
+    /// A basic.return, with its content
+    Returned(ReturnedMessage),
+}
+
+impl Message {
+    fn set_content(&mut self, properties: BasicProperties, body: Bytes) {
+        match self {
+            Message::Delivery(delivery) => {
+                delivery.properties = properties;
+                delivery.body = body;
+            }
+            Message::Returned(returned) => {
+                returned.properties = properties;
+                returned.body = body;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PendingMessage {
+    message: Message,
+    header: Option<(BasicProperties, PayloadSize)>,
+    body: Vec<u8>,
+}
+
+/// Reassemble the messages from the method, content header and body frames received
+///
+/// The content frames of a message must follow its method on the same channel without any other
+/// method in between, but the frames of different channels can be interleaved.
+#[derive(Debug, Default)]
+pub struct MessageAssembler {
+    pending: HashMap<ChannelId, PendingMessage>,
+}
+
+impl MessageAssembler {
+    /// Create an assembler with no pending message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a message is being reassembled on the channel
+    pub fn is_pending(&self, channel_id: ChannelId) -> bool {
+        self.pending.contains_key(&channel_id)
+    }
+
+    /// Handle a received frame, returning the message it completes, if any
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> Result<Option<Message>, String> {
+        match frame {
+            AMQPFrame::Method(channel_id, method) => {
+                if self.is_pending(*channel_id) {
+                    return Err(format!(
+                        "Received a method on channel {} before the end of the content",
+                        channel_id
+                    ));
+                }
+                let message = match method {
+                    AMQPClass::Basic(basic::AMQPMethod::Deliver(deliver)) => {
+                        Message::Delivery(Delivery::from_deliver(*channel_id, deliver.clone()))
+                    }
+                    AMQPClass::Basic(basic::AMQPMethod::GetOk(get_ok)) => {
+                        Message::Delivery(Delivery::from_get_ok(*channel_id, get_ok.clone()))
+                    }
+                    AMQPClass::Basic(basic::AMQPMethod::Return(method)) => {
+                        Message::Returned(ReturnedMessage::from_return(*channel_id, method.clone()))
+                    }
+                    _ => return Ok(None),
+                };
+                self.pending.insert(
+                    *channel_id,
+                    PendingMessage {
+                        message,
+                        header: None,
+                        body: Vec::new(),
+                    },
+                );
+                Ok(None)
+            }
+            AMQPFrame::Header(channel_id, _, header) => {
+                let pending = match self.pending.get_mut(channel_id) {
+                    Some(pending) if pending.header.is_none() => pending,
+                    _ => {
+                        return Err(format!(
+                            "Received an unexpected content header on channel {}",
+                            channel_id
+                        ))
+                    }
+                };
+                pending.header = Some((header.properties.clone(), header.body_size));
+                /* Don't trust the announced size blindly for the preallocation */
+                pending
+                    .body
+                    .reserve(header.body_size.min(MAX_PREALLOCATION) as usize);
+                Ok(self.complete(*channel_id))
+            }
+            AMQPFrame::Body(channel_id, chunk) => {
+                let pending = match self.pending.get_mut(channel_id) {
+                    Some(pending) if pending.header.is_some() => pending,
+                    _ => {
+                        return Err(format!(
+                            "Received an unexpected content body on channel {}",
+                            channel_id
+                        ))
+                    }
+                };
+                pending.body.extend_from_slice(chunk);
+                let body_size = pending.header.as_ref().map_or(0, |(_, size)| *size);
+                if pending.body.len() as PayloadSize > body_size {
+                    self.pending.remove(channel_id);
+                    return Err(format!(
+                        "Received more content than announced on channel {}",
+                        channel_id
+                    ));
+                }
+                Ok(self.complete(*channel_id))
+            }
+            AMQPFrame::ProtocolHeader(_) | AMQPFrame::Heartbeat(_) => Ok(None),
+        }
+    }
+
+    /// Drop the message being reassembled on a closed channel
+    pub fn remove(&mut self, channel_id: ChannelId) {
+        self.pending.remove(&channel_id);
+    }
+
+    fn complete(&mut self, channel_id: ChannelId) -> Option<Message> {
+        let done = self.pending.get(&channel_id).is_some_and(|pending| {
+            pending
+                .header
+                .as_ref()
+                .is_some_and(|(_, size)| pending.body.len() as PayloadSize == *size)
+        });
+        if !done {
+            return None;
+        }
+        let pending = self.pending.remove(&channel_id)?;
+        let (properties, _) = pending.header?;
+        let mut message = pending.message;
+        message.set_content(properties, pending.body.into());
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::AMQPContentHeader;
+
+    fn header(channel_id: ChannelId, body_size: PayloadSize) -> AMQPFrame {
+        AMQPFrame::Header(
+            channel_id,
+            60,
+            Box::new(AMQPContentHeader {
+                class_id: 60,
+                body_size,
+                properties: BasicProperties::default().with_content_type("text/plain".into()),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_assemble_deliveries() {
+        let mut assembler = MessageAssembler::new();
+        let deliver = basic::Deliver {
+            consumer_tag: "ctag".into(),
+            delivery_tag: 1,
+            redelivered: false,
+            exchange: "amq.direct".into(),
+            routing_key: "key".into(),
+        };
+        let frames = [
+            AMQPFrame::Method(1, AMQPClass::Basic(basic::AMQPMethod::Deliver(deliver))),
+            AMQPFrame::Method(
+                2,
+                AMQPClass::Basic(basic::AMQPMethod::GetOk(basic::GetOk {
+                    delivery_tag: 7,
+                    message_count: 3,
+                    ..basic::GetOk::default()
+                })),
+            ),
+            header(1, 5),
+            header(2, 0),
+            AMQPFrame::Body(1, Bytes::from_static(b"hel")),
+            AMQPFrame::Heartbeat(0),
+            AMQPFrame::Body(1, Bytes::from_static(b"lo")),
+        ];
+        let messages = frames
+            .iter()
+            .filter_map(|frame| assembler.handle_frame(frame).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2);
+        let Message::Delivery(get_ok) = &messages[0] else {
+            panic!("expected a delivery");
+        };
+        assert_eq!(get_ok.channel_id(), 2);
+        assert_eq!(get_ok.delivery_tag(), 7);
+        assert_eq!(get_ok.message_count(), Some(3));
+        assert_eq!(get_ok.consumer_tag(), None);
+        assert!(get_ok.body().is_empty());
+        let Message::Delivery(delivery) = &messages[1] else {
+            panic!("expected a delivery");
+        };
+        assert_eq!(
+            delivery.consumer_tag().map(ShortString::as_str),
+            Some("ctag")
+        );
+        assert_eq!(delivery.exchange().as_str(), "amq.direct");
+        assert_eq!(delivery.routing_key().as_str(), "key");
+        assert_eq!(
+            delivery
+                .properties()
+                .content_type()
+                .as_ref()
+                .map(ShortString::as_str),
+            Some("text/plain")
+        );
+        assert_eq!(delivery.body(), b"hello");
+        assert!(!assembler.is_pending(1));
+    }
+
+    #[test]
+    fn test_invalid_sequences() {
+        let mut assembler = MessageAssembler::new();
+        assert!(assembler.handle_frame(&header(1, 5)).is_err());
+        assert!(assembler
+            .handle_frame(&AMQPFrame::Body(1, Bytes::from_static(b"data")))
+            .is_err());
+        let get_ok = AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::GetOk(basic::GetOk::default())),
+        );
+        assembler.handle_frame(&get_ok).unwrap();
+        assert!(assembler.handle_frame(&get_ok).is_err());
+        assembler.handle_frame(&header(1, 2)).unwrap();
+        assert!(assembler
+            .handle_frame(&AMQPFrame::Body(1, Bytes::from_static(b"data")))
+            .is_err());
+        assert!(!assembler.is_pending(1));
+    }
+
+    #[test]
+    fn test_assemble_returned_message() {
+        use crate::protocol::{AMQPErrorKind, AMQPSoftError};
+
+        let mut assembler = MessageAssembler::new();
+        let method = AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::Return(basic::Return {
+                reply_code: 312,
+                reply_text: "NO_ROUTE".into(),
+                exchange: "amq.direct".into(),
+                routing_key: "nowhere".into(),
+            })),
+        );
+        assert_eq!(assembler.handle_frame(&method), Ok(None));
+        assert_eq!(assembler.handle_frame(&header(1, 4)), Ok(None));
+        let Some(Message::Returned(returned)) = assembler
+            .handle_frame(&AMQPFrame::Body(1, Bytes::from_static(b"lost")))
+            .unwrap()
+        else {
+            panic!("expected a returned message");
+        };
+        assert_eq!(returned.reply_code(), 312);
+        assert_eq!(
+            returned.error().map(|error| error.kind().clone()),
+            Some(AMQPErrorKind::Soft(AMQPSoftError::NOROUTE))
+        );
+        assert_eq!(returned.routing_key().as_str(), "nowhere");
+        assert_eq!(returned.into_body(), Bytes::from_static(b"lost"));
+    }
+
+    #[test]
+    fn test_ack_outcome() {
+        assert_eq!(
+            AckOutcome::Ack.to_frame(2, 5),
+            AMQPFrame::Method(
+                2,
+                AMQPClass::Basic(basic::AMQPMethod::Ack(basic::Ack {
+                    delivery_tag: 5,
+                    multiple: false,
+                }))
+            )
+        );
+        assert_eq!(
+            AckOutcome::NackDiscard.to_method_multiple(5),
+            Some(basic::AMQPMethod::Nack(basic::Nack {
+                delivery_tag: 5,
+                multiple: true,
+                requeue: false,
+            }))
+        );
+        assert_eq!(
+            AckOutcome::RejectRequeue.to_method(5),
+            basic::AMQPMethod::Reject(basic::Reject {
+                delivery_tag: 5,
+                requeue: true,
+            })
+        );
+        assert_eq!(AckOutcome::RejectDiscard.to_method_multiple(5), None);
+    }
+}
@@ -0,0 +1,12 @@
+pub use crate::{
+    error::ProtocolError,
+    frame::{AMQPContentHeader, AMQPFrame, FrameAccumulator, ProtocolVersion},
+    message::{Delivery, Message, MessageAssembler},
+    protocol::{AMQPClass, AMQPError, AMQPErrorKind, AMQPMethodId, BasicProperties},
+    types::{
+        codec::{Decode, DecodeError, Encode, EncodeError},
+        AMQPType, AMQPValue, ByteArray, ChannelId, CoerceValue, DeliveryTag, FieldArray,
+        FieldTable, LongString, ShortString,
+    },
+    uri::AMQPUri,
+};
@@ -1,4 +1,7 @@
+#[cfg(feature = "arena")]
+use crate::types::arena::*;
 use crate::types::{
+    codec::*,
     flags::*,
     generation::*,
     parsing::{traits::ParsableInput, *},
@@ -9,17 +12,406 @@ use nom::{
     error::context,
     Parser,
 };
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, error, fmt, io::Write};
+use std::{
+    convert::{Infallible, TryFrom},
+    error, fmt,
+    hash::{Hash, Hasher},
+    io::Write,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 #[cfg(feature = "codegen")]
 include!(concat!(env!("OUT_DIR"), "/protocol.rs"));
 #[cfg(not(feature = "codegen"))]
 include!("generated.rs");
 
+/// The description of an argument of a method, letting generic tooling build methods at runtime
+#[derive(Clone, Copy, Debug)]
+pub struct ArgumentDescriptor {
+    /// The name of the argument, as the field of the method
+    pub name: &'static str,
+    /// The type of the argument
+    pub amqp_type: AMQPType,
+    default_value: Option<fn() -> AMQPValue>,
+}
+
+impl ArgumentDescriptor {
+    /// The value used when the argument is not set, if the specification defines one
+    pub fn default_value(&self) -> Option<AMQPValue> {
+        self.default_value.map(|default_value| default_value())
+    }
+}
+
+/* The conversions from the values given to the generated from_arguments */
+trait FromAMQPValue: Sized {
+    fn from_amqp_value(value: &AMQPValue) -> Option<Self>;
+}
+
+impl FromAMQPValue for Boolean {
+    fn from_amqp_value(value: &AMQPValue) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+macro_rules! integer_from_amqp_value {
+    ($($t:ty),*) => {
+        $(
+            impl FromAMQPValue for $t {
+                fn from_amqp_value(value: &AMQPValue) -> Option<Self> {
+                    let integer = match *value {
+                        AMQPValue::ShortShortInt(v) => i128::from(v),
+                        AMQPValue::ShortShortUInt(v) => i128::from(v),
+                        AMQPValue::ShortInt(v) => i128::from(v),
+                        AMQPValue::ShortUInt(v) => i128::from(v),
+                        AMQPValue::LongInt(v) => i128::from(v),
+                        AMQPValue::LongUInt(v) => i128::from(v),
+                        AMQPValue::LongLongInt(v) => i128::from(v),
+                        AMQPValue::Timestamp(v) => i128::from(v),
+                        _ => return None,
+                    };
+                    Self::try_from(integer).ok()
+                }
+            }
+        )*
+    };
+}
+
+integer_from_amqp_value!(ShortShortUInt, ShortUInt, LongUInt, LongLongUInt);
+
+impl FromAMQPValue for ShortString {
+    fn from_amqp_value(value: &AMQPValue) -> Option<Self> {
+        value.as_short_string().cloned()
+    }
+}
+
+impl FromAMQPValue for LongString {
+    fn from_amqp_value(value: &AMQPValue) -> Option<Self> {
+        match value {
+            AMQPValue::ShortString(v) => Some(v.as_str().into()),
+            AMQPValue::LongString(v) => Some(v.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromAMQPValue for FieldTable {
+    fn from_amqp_value(value: &AMQPValue) -> Option<Self> {
+        value.as_field_table().cloned()
+    }
+}
+
+fn from_argument<T: FromAMQPValue>(name: &ShortString, value: &AMQPValue) -> Result<T, String> {
+    T::from_amqp_value(value).ok_or_else(|| format!("Invalid value for {}: {:?}", name, value))
+}
+
 /// Type alias for AMQP BasicProperties
 pub type BasicProperties = basic::AMQPProperties;
 
+/// The delivery mode of a message
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DeliveryMode {
+    /// The message may be lost if the server restarts
+    #[default]
+    Transient,
+    /// The message is persisted to disk if it goes to a durable queue
+    Persistent,
+}
+
+impl From<DeliveryMode> for ShortShortUInt {
+    fn from(mode: DeliveryMode) -> Self {
+        match mode {
+            DeliveryMode::Transient => 1,
+            DeliveryMode::Persistent => 2,
+        }
+    }
+}
+
+impl TryFrom<ShortShortUInt> for DeliveryMode {
+    type Error = String;
+
+    fn try_from(mode: ShortShortUInt) -> Result<Self, Self::Error> {
+        match mode {
+            1 => Ok(DeliveryMode::Transient),
+            2 => Ok(DeliveryMode::Persistent),
+            mode => Err(format!("Invalid delivery mode: {}", mode)),
+        }
+    }
+}
+
+/// The priority of a message, only honoured by queues declared with x-max-priority
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority(pub ShortShortUInt);
+
+impl Priority {
+    /// Get the raw priority
+    pub fn get(self) -> ShortShortUInt {
+        self.0
+    }
+}
+
+impl From<ShortShortUInt> for Priority {
+    fn from(priority: ShortShortUInt) -> Self {
+        Self(priority)
+    }
+}
+
+impl From<Priority> for ShortShortUInt {
+    fn from(priority: Priority) -> Self {
+        priority.0
+    }
+}
+
+/// Parse the wire form of the expiration property: a number of milliseconds, without sign,
+/// whitespace or fractional part
+pub fn parse_expiration(expiration: &str) -> Result<Duration, String> {
+    if expiration.is_empty() || !expiration.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid expiration: {:?}", expiration));
+    }
+    expiration
+        .parse()
+        .map(Duration::from_millis)
+        .map_err(|e| format!("Invalid expiration {:?}: {}", expiration, e))
+}
+
+impl basic::AMQPProperties {
+    /// Set delivery-mode from a typed DeliveryMode
+    pub fn with_typed_delivery_mode(self, mode: DeliveryMode) -> Self {
+        self.with_delivery_mode(mode.into())
+    }
+
+    /// Get delivery-mode as a typed DeliveryMode, None if unset or invalid
+    pub fn typed_delivery_mode(&self) -> Option<DeliveryMode> {
+        self.delivery_mode()
+            .and_then(|mode| DeliveryMode::try_from(mode).ok())
+    }
+
+    /// Whether the message is marked as persistent or not
+    pub fn is_persistent(&self) -> bool {
+        self.typed_delivery_mode() == Some(DeliveryMode::Persistent)
+    }
+
+    /// Set priority from a typed Priority
+    pub fn with_typed_priority(self, priority: Priority) -> Self {
+        self.with_priority(priority.into())
+    }
+
+    /// Get priority as a typed Priority
+    pub fn typed_priority(&self) -> Option<Priority> {
+        self.priority().map(Priority)
+    }
+
+    /// Set expiration from a Duration, rounded up to milliseconds so that a non-zero one never
+    /// makes the message expire right away
+    pub fn with_typed_expiration(self, ttl: Duration) -> Self {
+        self.with_expiration(ttl.as_nanos().div_ceil(1_000_000).to_string().into())
+    }
+
+    /// Get expiration as a Duration, None if unset or invalid
+    pub fn typed_expiration(&self) -> Option<Duration> {
+        self.expiration()
+            .as_ref()
+            .and_then(|expiration| parse_expiration(expiration.as_str()).ok())
+    }
+
+    /// Set timestamp from a SystemTime, truncated to seconds
+    pub fn with_typed_timestamp(self, time: SystemTime) -> Self {
+        self.with_timestamp(
+            time.duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_secs()),
+        )
+    }
+
+    /// Set timestamp to the current time
+    pub fn with_current_timestamp(self) -> Self {
+        self.with_typed_timestamp(SystemTime::now())
+    }
+
+    /// Get timestamp as a SystemTime
+    pub fn typed_timestamp(&self) -> Option<SystemTime> {
+        self.timestamp()
+            .and_then(|timestamp| UNIX_EPOCH.checked_add(Duration::from_secs(timestamp)))
+    }
+
+    /// Check that timestamp is set and at most allowed_skew away from now, in either direction
+    ///
+    /// As timestamps only have a precision of one second, now is truncated to seconds too.
+    pub fn check_timestamp(&self, now: SystemTime, allowed_skew: Duration) -> Result<(), String> {
+        let timestamp = self
+            .timestamp()
+            .ok_or_else(|| "Missing timestamp".to_string())?;
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        let skew = Duration::from_secs(timestamp.abs_diff(now));
+        if skew <= allowed_skew {
+            Ok(())
+        } else if timestamp > now {
+            Err(format!(
+                "Timestamp {} is {}s in the future, more than the allowed {:?}",
+                timestamp,
+                skew.as_secs(),
+                allowed_skew
+            ))
+        } else {
+            Err(format!(
+                "Timestamp {} is {}s in the past, more than the allowed {:?}",
+                timestamp,
+                skew.as_secs(),
+                allowed_skew
+            ))
+        }
+    }
+
+    /// Get the value of a header
+    pub fn header(&self, name: &str) -> Option<&AMQPValue> {
+        self.headers()
+            .as_ref()
+            .and_then(|headers| headers.inner().get(name))
+    }
+
+    /// Set a header, creating the headers table if needed
+    pub fn with_header(mut self, name: ShortString, value: AMQPValue) -> Self {
+        self.set_header(name, value);
+        self
+    }
+
+    /// Set a header, creating the headers table if needed
+    pub fn set_header(&mut self, name: ShortString, value: AMQPValue) {
+        self.headers_mut()
+            .get_or_insert_with(FieldTable::default)
+            .insert(name, value);
+    }
+
+    /// Remove a header and return its value, dropping the headers table once it is empty
+    pub fn remove_header(&mut self, name: &str) -> Option<AMQPValue> {
+        let headers = self.headers_mut().as_mut()?;
+        let value = headers.remove(name);
+        if headers.inner().is_empty() {
+            *self.headers_mut() = None;
+        }
+        value
+    }
+}
+
+impl basic::LazyProperties {
+    /// Decode the value of a header, leaving the other ones encoded
+    pub fn header(&self, name: &str) -> Result<Option<AMQPValue>, String> {
+        self.headers
+            .as_ref()
+            .map_or(Ok(None), |headers| headers.get(name))
+    }
+}
+
+#[cfg(feature = "class-exchange")]
+impl exchange::Bind {
+    /// Bind the destination exchange to the source one, so that it receives the messages the
+    /// source routes with the given routing key (RabbitMQ extension)
+    pub fn new(
+        destination: impl Into<ShortString>,
+        source: impl Into<ShortString>,
+        routing_key: impl Into<ShortString>,
+    ) -> Self {
+        Self {
+            destination: destination.into(),
+            source: source.into(),
+            routing_key: routing_key.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Use the given binding arguments, such as the ones of a HeadersBindingArguments
+    pub fn with_arguments(mut self, arguments: FieldTable) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Don't wait for a bind-ok
+    pub fn with_nowait(mut self, nowait: Boolean) -> Self {
+        self.nowait = nowait;
+        self
+    }
+
+    /// Check that the server can accept this binding
+    pub fn validate(&self) -> Result<(), String> {
+        validate_exchange_binding(&self.destination, &self.source, &self.arguments)
+    }
+}
+
+#[cfg(feature = "class-exchange")]
+impl exchange::Unbind {
+    /// Remove the binding of the destination exchange to the source one (RabbitMQ extension)
+    pub fn new(
+        destination: impl Into<ShortString>,
+        source: impl Into<ShortString>,
+        routing_key: impl Into<ShortString>,
+    ) -> Self {
+        Self {
+            destination: destination.into(),
+            source: source.into(),
+            routing_key: routing_key.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Use the given binding arguments, which must be the ones the binding was created with
+    pub fn with_arguments(mut self, arguments: FieldTable) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Don't wait for an unbind-ok
+    pub fn with_nowait(mut self, nowait: Boolean) -> Self {
+        self.nowait = nowait;
+        self
+    }
+
+    /// Check that the server can accept this unbinding
+    pub fn validate(&self) -> Result<(), String> {
+        validate_exchange_binding(&self.destination, &self.source, &self.arguments)
+    }
+}
+
+/* Removing a binding requires the exact arguments it was created with */
+#[cfg(feature = "class-exchange")]
+impl From<&exchange::Bind> for exchange::Unbind {
+    fn from(bind: &exchange::Bind) -> Self {
+        Self {
+            destination: bind.destination.clone(),
+            source: bind.source.clone(),
+            routing_key: bind.routing_key.clone(),
+            nowait: bind.nowait,
+            arguments: bind.arguments.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "class-exchange")]
+fn validate_exchange_binding(
+    destination: &ShortString,
+    source: &ShortString,
+    arguments: &FieldTable,
+) -> Result<(), String> {
+    use crate::arguments::XMatch;
+
+    if destination.as_str().is_empty() || source.as_str().is_empty() {
+        return Err("the default exchange cannot be bound to nor from".to_string());
+    }
+    if let Some(x_match) = arguments.inner().get("x-match") {
+        let modes = [XMatch::All, XMatch::Any, XMatch::AllWithX, XMatch::AnyWithX];
+        let x_match_mode = x_match.as_long_string().map(LongString::as_bytes);
+        if !modes
+            .iter()
+            .any(|mode| x_match_mode == Some(mode.as_str().as_bytes()))
+        {
+            return Err(format!("invalid x-match: {}", x_match));
+        }
+    }
+    Ok(())
+}
+
 /// An AMQP Error
 #[derive(Clone, Debug, PartialEq)]
 pub struct AMQPError {
@@ -72,6 +464,15 @@ impl TryFrom<connection::Close> for AMQPError {
     }
 }
 
+impl TryFrom<basic::Return> for AMQPError {
+    type Error = String;
+
+    fn try_from(method: basic::Return) -> Result<Self, Self::Error> {
+        Self::from_id(method.reply_code, method.reply_text.clone())
+            .ok_or_else(|| format!("Couldn't convert method to error: {:?}", method))
+    }
+}
+
 impl fmt::Display for AMQPError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}: {}", self.kind, self.message)
@@ -144,4 +545,498 @@ mod test {
             "AMQP - 0.9.1"
         );
     }
+
+    #[test]
+    fn test_exchange_type() {
+        assert_eq!(ExchangeType::from("topic"), ExchangeType::Topic);
+        assert_eq!(
+            ExchangeType::from("x-delayed-message"),
+            ExchangeType::Other("x-delayed-message".to_string())
+        );
+        assert_eq!(ShortString::from(ExchangeType::Fanout).as_str(), "fanout");
+        assert_eq!(ExchangeType::Headers.to_string(), "headers");
+        assert_eq!(
+            ExchangeType::Other("direct".to_string()),
+            ExchangeType::Direct
+        );
+        assert_eq!("fanout".parse(), Ok(ExchangeType::Fanout));
+        assert_eq!(ExchangeType::from("topic".to_string()), ExchangeType::Topic);
+    }
+
+    #[test]
+    #[cfg(feature = "class-exchange")]
+    fn test_exchange_declare_type() {
+        let declare = exchange::Declare::default().with_exchange_type(ExchangeType::Topic);
+        assert_eq!(declare.kind.as_str(), "topic");
+        assert_eq!(declare.exchange_type(), ExchangeType::Topic);
+    }
+
+    #[test]
+    fn test_typed_properties() {
+        let properties = BasicProperties::default()
+            .with_typed_delivery_mode(DeliveryMode::Persistent)
+            .with_typed_priority(Priority(5));
+        assert_eq!(*properties.delivery_mode(), Some(2));
+        assert!(properties.is_persistent());
+        assert_eq!(properties.typed_priority(), Some(Priority(5)));
+        assert_eq!(
+            BasicProperties::default()
+                .with_delivery_mode(3)
+                .typed_delivery_mode(),
+            None
+        );
+        assert!(DeliveryMode::try_from(0).is_err());
+    }
+
+    #[test]
+    fn test_property_flags() {
+        let properties = BasicProperties::default()
+            .with_content_type("text/plain".into())
+            .with_delivery_mode(2)
+            .with_cluster_id("".into());
+        assert_eq!(properties.flags(), 0b1001_0000_0000_0100);
+        assert_eq!(properties.flags(), properties.bitmask());
+        assert_eq!(
+            BasicProperties::from_flags(properties.flags()),
+            Ok(vec!["content_type", "delivery_mode", "cluster_id"])
+        );
+        assert_eq!(BasicProperties::from_flags(0), Ok(Vec::new()));
+        assert!(BasicProperties::from_flags(0b11).is_err());
+        assert_eq!(basic::PROPERTY_FLAGS.len(), 14);
+    }
+
+    #[test]
+    fn test_expiration() {
+        let properties = BasicProperties::default().with_typed_expiration(Duration::from_secs(60));
+        assert_eq!(properties.expiration(), &Some("60000".into()));
+        assert_eq!(properties.typed_expiration(), Some(Duration::from_secs(60)));
+        assert_eq!(
+            BasicProperties::default()
+                .with_typed_expiration(Duration::from_micros(1500))
+                .expiration(),
+            &Some("2".into())
+        );
+        assert_eq!(
+            BasicProperties::default()
+                .with_typed_expiration(Duration::from_nanos(1))
+                .expiration(),
+            &Some("1".into())
+        );
+        assert_eq!(
+            BasicProperties::default()
+                .with_typed_expiration(Duration::ZERO)
+                .expiration(),
+            &Some("0".into())
+        );
+        assert_eq!(BasicProperties::default().typed_expiration(), None);
+        assert_eq!(
+            BasicProperties::default()
+                .with_expiration("-1".into())
+                .typed_expiration(),
+            None
+        );
+        assert_eq!(parse_expiration("0"), Ok(Duration::ZERO));
+        for invalid in ["", "+5", " 5", "1.5", "5s", "99999999999999999999"] {
+            assert!(parse_expiration(invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let now = UNIX_EPOCH + Duration::from_millis(1_700_000_000_500);
+        let properties = BasicProperties::default().with_typed_timestamp(now);
+        assert_eq!(properties.timestamp(), &Some(1_700_000_000));
+        assert_eq!(
+            properties.typed_timestamp(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+        assert_eq!(properties.check_timestamp(now, Duration::ZERO), Ok(()));
+        let skew = Duration::from_secs(30);
+        assert_eq!(
+            properties.check_timestamp(now + Duration::from_secs(30), skew),
+            Ok(())
+        );
+        assert!(properties
+            .check_timestamp(now + Duration::from_secs(31), skew)
+            .is_err());
+        assert!(properties
+            .check_timestamp(now - Duration::from_secs(31), skew)
+            .is_err());
+        assert!(BasicProperties::default()
+            .check_timestamp(now, skew)
+            .is_err());
+        assert!(BasicProperties::default()
+            .with_current_timestamp()
+            .check_timestamp(SystemTime::now(), Duration::from_secs(5))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_headers() {
+        let mut properties =
+            BasicProperties::default().with_header("x-retries".into(), AMQPValue::LongUInt(1));
+        assert_eq!(
+            properties.header("x-retries"),
+            Some(&AMQPValue::LongUInt(1))
+        );
+        properties.set_header("x-retries".into(), AMQPValue::LongUInt(2));
+        properties.set_header("x-origin".into(), AMQPValue::LongString("test".into()));
+        assert_eq!(
+            properties.header("x-retries"),
+            Some(&AMQPValue::LongUInt(2))
+        );
+        assert_eq!(
+            properties.headers().as_ref().map(|h| h.inner().len()),
+            Some(2)
+        );
+        assert_eq!(properties.remove_header("x-unknown"), None);
+        assert_eq!(
+            properties.remove_header("x-retries"),
+            Some(AMQPValue::LongUInt(2))
+        );
+        assert!(properties.remove_header("x-origin").is_some());
+        assert_eq!(properties.headers(), &None);
+        assert_eq!(properties.header("x-origin"), None);
+        assert_eq!(properties.remove_header("x-origin"), None);
+    }
+
+    #[test]
+    fn test_lazy_properties() {
+        let properties = BasicProperties::default()
+            .with_content_type("text/plain".into())
+            .with_header("x-retries".into(), AMQPValue::LongUInt(1))
+            .with_priority(3);
+        let bytes =
+            cookie_factory::gen_simple(basic::gen_properties(&properties), Vec::new()).unwrap();
+        let (rest, lazy) = basic::parse_lazy_properties(&bytes[..]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(lazy.properties.content_type(), properties.content_type());
+        assert_eq!(lazy.properties.priority(), &Some(3));
+        assert_eq!(lazy.properties.headers(), &None);
+        assert_eq!(lazy.header("x-retries"), Ok(Some(AMQPValue::LongUInt(1))));
+        assert_eq!(lazy.header("x-missing"), Ok(None));
+        assert_eq!(lazy.decode(), Ok(properties));
+        assert_eq!(
+            basic::LazyProperties::default().header("x-retries"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_method_id() {
+        assert_eq!(std::mem::size_of::<Option<AMQPMethodId>>(), 1);
+        let id = AMQPMethodId::from_ids(60, 40).unwrap();
+        assert_eq!(id, AMQPMethodId::BasicPublish);
+        assert_eq!(id.ids(), (60, 40));
+        assert_eq!(id.to_string(), "basic.publish");
+        assert_eq!(AMQPMethodId::from_ids(60, 1), None);
+        let flow = AMQPClass::Channel(channel::AMQPMethod::Flow(channel::Flow { active: true }));
+        assert_eq!(flow.id(), AMQPMethodId::ChannelFlow);
+        assert_eq!(
+            (flow.id().class_id(), flow.id().method_id()),
+            (flow.get_amqp_class_id(), flow.get_amqp_method_id())
+        );
+    }
+
+    #[test]
+    fn test_codec() {
+        let class = AMQPClass::Channel(channel::AMQPMethod::Flow(channel::Flow { active: true }));
+        let bytes = class.to_bytes().unwrap();
+        assert_eq!(AMQPClass::decode(&bytes), Ok((class, bytes.len())));
+        assert_eq!(
+            AMQPClass::decode(&bytes[..2]),
+            Err(DecodeError::Incomplete(Some(2)))
+        );
+
+        let flow = channel::Flow { active: true };
+        assert_eq!(flow.to_bytes().unwrap(), bytes);
+        assert_eq!(
+            channel::Flow::decode(&bytes),
+            Ok((flow.clone(), bytes.len()))
+        );
+        assert!(matches!(
+            channel::FlowOk::decode(&bytes),
+            Err(DecodeError::Invalid(_))
+        ));
+        let method = channel::AMQPMethod::Flow(flow);
+        assert_eq!(method.to_bytes().unwrap(), bytes);
+        assert_eq!(
+            channel::AMQPMethod::decode(&bytes),
+            Ok((method, bytes.len()))
+        );
+        assert!(matches!(
+            basic::AMQPMethod::decode(&bytes),
+            Err(DecodeError::Invalid(_))
+        ));
+
+        let properties = BasicProperties::default().with_priority(5);
+        let bytes = properties.to_bytes().unwrap();
+        assert_eq!(
+            BasicProperties::decode(&bytes),
+            Ok((properties, bytes.len()))
+        );
+
+        let frame = crate::frame::AMQPFrame::Heartbeat(0);
+        let bytes = frame.to_bytes().unwrap();
+        assert_eq!(
+            crate::frame::AMQPFrame::decode(&bytes),
+            Ok((frame, bytes.len()))
+        );
+    }
+
+    #[cfg(feature = "fuzzing")]
+    #[test]
+    fn test_fuzz_targets() {
+        let frame = crate::frame::AMQPFrame::Method(
+            1,
+            AMQPClass::Channel(channel::AMQPMethod::Flow(channel::Flow { active: true })),
+        );
+        let bytes = frame.to_bytes().unwrap();
+        fuzz::fuzz_frame(&bytes);
+        fuzz::fuzz_channel(&bytes[9..bytes.len() - 1]);
+        for target in 0..fuzz::TARGETS.len() as u8 {
+            fuzz::fuzz_any(&[target]);
+            fuzz::fuzz_any(&[&[target][..], &bytes[..]].concat());
+        }
+        fuzz::fuzz_any(&[]);
+    }
+
+    #[cfg(feature = "class-exchange")]
+    #[test]
+    fn test_exchange_binding() {
+        let bind = exchange::Bind::new("destination", "source", "some.key")
+            .with_arguments(
+                crate::arguments::HeadersBindingArguments::any()
+                    .with_header("format", AMQPValue::LongString("pdf".into()))
+                    .build()
+                    .unwrap(),
+            )
+            .with_nowait(true);
+        assert_eq!(bind.validate(), Ok(()));
+        let unbind = exchange::Unbind::from(&bind);
+        assert_eq!(unbind.arguments, bind.arguments);
+        assert_eq!(unbind.validate(), Ok(()));
+        assert_eq!(
+            exchange::Unbind::new("destination", "source", "some.key")
+                .with_arguments(bind.arguments.clone())
+                .with_nowait(true),
+            unbind
+        );
+
+        assert!(exchange::Bind::new("", "source", "").validate().is_err());
+        assert!(exchange::Unbind::new("destination", "", "")
+            .validate()
+            .is_err());
+        let mut arguments = FieldTable::default();
+        arguments.insert("x-match".into(), AMQPValue::LongString("some".into()));
+        assert_eq!(
+            exchange::Bind::new("destination", "source", "")
+                .with_arguments(arguments)
+                .validate(),
+            Err("invalid x-match: \"some\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_arguments() {
+        let arguments = basic::Consume::ARGUMENTS;
+        assert_eq!(
+            arguments
+                .iter()
+                .map(|argument| argument.name)
+                .collect::<Vec<_>>(),
+            vec![
+                "queue",
+                "consumer_tag",
+                "no_local",
+                "no_ack",
+                "exclusive",
+                "nowait",
+                "arguments"
+            ]
+        );
+        assert_eq!(arguments[0].amqp_type, AMQPType::ShortString);
+        assert_eq!(
+            arguments[0].default_value(),
+            Some(AMQPValue::ShortString("".into()))
+        );
+        assert_eq!(
+            arguments[5].default_value(),
+            Some(AMQPValue::Boolean(false))
+        );
+        assert_eq!(
+            connection::Start::ARGUMENTS[3].default_value(),
+            Some(AMQPValue::LongString("PLAIN".into()))
+        );
+        assert_eq!(basic::ConsumeOk::ARGUMENTS[0].default_value(), None);
+        assert!(basic::QosOk::ARGUMENTS.is_empty());
+    }
+
+    #[test]
+    fn test_from_arguments() {
+        let mut arguments = FieldTable::default();
+        arguments.insert("prefetch_count".into(), AMQPValue::LongLongInt(10));
+        arguments.insert("global".into(), AMQPValue::Boolean(true));
+        assert_eq!(
+            AMQPClass::from_arguments("basic", "qos", &arguments),
+            Ok(AMQPClass::Basic(basic::AMQPMethod::Qos(basic::Qos {
+                prefetch_count: 10,
+                global: true,
+            })))
+        );
+        arguments.insert("prefetch_count".into(), AMQPValue::LongLongInt(-1));
+        assert!(basic::Qos::from_arguments(&arguments).is_err());
+        arguments.insert("unknown".into(), AMQPValue::Void);
+        assert!(basic::QosOk::from_arguments(&arguments).is_err());
+        assert_eq!(
+            basic::QosOk::from_arguments(&FieldTable::default()),
+            Ok(basic::QosOk {})
+        );
+        assert!(AMQPClass::from_arguments("basic", "unknown", &FieldTable::default()).is_err());
+        assert!(
+            basic::Reject::from_arguments(&FieldTable::default())
+                .unwrap()
+                .requeue
+        );
+        assert!(
+            basic::Nack::from_arguments(&FieldTable::default())
+                .unwrap()
+                .requeue
+        );
+        assert_eq!(
+            connection::Open::from_arguments(&FieldTable::default())
+                .unwrap()
+                .virtual_host,
+            ShortString::from("/")
+        );
+        #[cfg(feature = "class-exchange")]
+        assert_eq!(
+            exchange::Declare::from_arguments(&FieldTable::default())
+                .unwrap()
+                .kind,
+            ShortString::from("direct")
+        );
+        assert_eq!(
+            AMQPClass::arguments("basic", "consume-ok").map(|arguments| arguments.len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_content_carrying() {
+        assert!(is_content_carrying(60, 40));
+        assert!(is_content_carrying(60, 71));
+        assert!(!is_content_carrying(60, 70));
+        assert!(!is_content_carrying(10, 10));
+        assert!(basic::Deliver::default().is_content_carrying());
+        assert!(!basic::Ack::default().is_content_carrying());
+        assert!(
+            AMQPClass::Basic(basic::AMQPMethod::Return(basic::Return::default()))
+                .is_content_carrying()
+        );
+        assert!(
+            !AMQPClass::Basic(basic::AMQPMethod::Qos(basic::Qos::default())).is_content_carrying()
+        );
+    }
+
+    #[test]
+    fn test_reply_from_request() {
+        let cancel = basic::Cancel {
+            consumer_tag: "ctag".into(),
+            nowait: false,
+        };
+        assert_eq!(basic::CancelOk::from(&cancel).consumer_tag.as_str(), "ctag");
+        let tune = connection::Tune {
+            channel_max: 2047,
+            frame_max: 131072,
+            heartbeat: 60,
+        };
+        assert_eq!(
+            connection::TuneOk::from(&tune),
+            connection::TuneOk {
+                channel_max: 2047,
+                frame_max: 131072,
+                heartbeat: 60,
+            }
+        );
+        assert_eq!(
+            channel::CloseOk::from(&channel::Close::default()),
+            channel::CloseOk {}
+        );
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn test_server_helpers() {
+        let tune = server::default_tune();
+        assert_eq!(tune.frame_max, server::DEFAULT_FRAME_MAX);
+        let tune = AMQPClass::Connection(connection::AMQPMethod::Tune(tune));
+        assert!(server::sends(&tune));
+        assert!(!server::receives(&tune));
+        assert_eq!(server::reply(&tune), None);
+        let flow = AMQPClass::Channel(channel::AMQPMethod::Flow(channel::Flow { active: false }));
+        assert!(server::sends(&flow) && server::receives(&flow));
+        assert_eq!(
+            server::reply(&flow),
+            Some(AMQPClass::Channel(channel::AMQPMethod::FlowOk(
+                channel::FlowOk { active: false }
+            )))
+        );
+    }
+
+    #[cfg(feature = "class-queue")]
+    #[test]
+    fn test_declare_ok_skeleton() {
+        let declare = queue::Declare {
+            queue: "some_queue".into(),
+            durable: true,
+            ..queue::Declare::default()
+        };
+        assert_eq!(
+            queue::DeclareOk::from(&declare),
+            queue::DeclareOk {
+                queue: "some_queue".into(),
+                message_count: 0,
+                consumer_count: 0,
+            }
+        );
+    }
+
+    #[cfg(feature = "codegen-lib")]
+    #[test]
+    fn test_render_without_debug() {
+        use amq_protocol_codegen::{CodeGenerator, HandlebarsAMQPExtension};
+        use std::{fs, path::Path};
+
+        let template = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/templates/protocol.rs"
+        ));
+        let out_dir =
+            std::env::temp_dir().join(format!("amq-protocol-render-{}", std::process::id()));
+        fs::create_dir_all(&out_dir).unwrap();
+        let out_dir = out_dir.to_str().unwrap();
+        let render = |debug: bool| {
+            CodeGenerator::default()
+                .register_amqp_helpers()
+                .try_codegen_with_data(
+                    out_dir,
+                    "protocol",
+                    "protocol",
+                    template,
+                    "protocol",
+                    Some(serde_json::json!({ "server": true, "derives": { "debug": debug } })),
+                )
+                .unwrap();
+            fs::read_to_string(Path::new(out_dir).join("protocol.rs")).unwrap()
+        };
+        let without_debug = render(false);
+        let with_debug = render(true);
+        fs::remove_dir_all(out_dir).unwrap();
+        assert!(without_debug.contains("#[derive(Clone, PartialEq)]\npub enum AMQPClass"));
+        assert!(!without_debug.contains("Debug, Default, PartialEq"));
+        assert!(without_debug.contains("#[derive(Clone)]\npub enum ExchangeType"));
+        assert!(without_debug.contains("#[derive(Clone, PartialEq)]\npub enum AMQPSoftError"));
+        assert!(with_debug.contains("#[derive(Clone, Debug, PartialEq)]\npub enum AMQPClass"));
+    }
 }
@@ -0,0 +1,170 @@
+pub use crate::message::AckOutcome;
+use crate::{
+    dead_letter::DeathChain,
+    types::{FieldTable, LongLongUInt},
+};
+
+/// The metadata of a delivery needed to decide how to acknowledge it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeliveryInfo {
+    /// The delivery tag of the message
+    pub delivery_tag: LongLongUInt,
+    /// Whether the message has already been delivered before or not
+    pub redelivered: bool,
+    /// The number of times the message has been dead-lettered, as reported by x-death
+    pub death_count: LongLongUInt,
+}
+
+impl DeliveryInfo {
+    /// Extract the delivery info from the delivery tag, redelivered flag and message headers
+    pub fn new(
+        delivery_tag: LongLongUInt,
+        redelivered: bool,
+        headers: Option<&FieldTable>,
+    ) -> Self {
+        Self {
+            delivery_tag,
+            redelivered,
+            death_count: headers.map_or(0, x_death_count),
+        }
+    }
+}
+
+/// Get the total number of times a message has been dead-lettered from its x-death header
+///
+/// This is the [DeathChain::total_count] of the headers, entries without a count standing for a
+/// single death. An invalid x-death header counts as no death at all.
+pub fn x_death_count(headers: &FieldTable) -> LongLongUInt {
+    DeathChain::from_table(headers).map_or(0, |chain| chain.total_count())
+}
+
+/// What to do with a failed delivery once it has exhausted its retries
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExhaustedAction {
+    /// Reject the message without requeueing it, so that it gets dead-lettered
+    #[default]
+    Reject,
+    /// Acknowledge the message, discarding it
+    Discard,
+}
+
+/// Policy deciding how to settle failed deliveries
+///
+/// Failed deliveries are negatively acknowledged without requeue, so that they go through the
+/// dead letter exchange of the queue (usually a retry queue with a TTL) which increments their
+/// x-death count. Once this count reaches max_retries, the exhausted action is applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times a message may be dead-lettered for retry
+    pub max_retries: LongLongUInt,
+    /// Requeue the message directly on its first failure instead of dead-lettering it
+    pub requeue_first_failure: bool,
+    /// What to do with a message which exhausted its retries
+    pub exhausted: ExhaustedAction,
+}
+
+impl RetryPolicy {
+    /// Create a new policy allowing max_retries retries
+    pub fn new(max_retries: LongLongUInt) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Get the outcome of a successfully processed delivery
+    pub fn success(&self, _delivery: &DeliveryInfo) -> AckOutcome {
+        AckOutcome::Ack
+    }
+
+    /// Get the outcome of a delivery which failed to be processed
+    ///
+    /// Use [AckOutcome::to_frame] with the delivery tag to get the frame to send.
+    pub fn failure(&self, delivery: &DeliveryInfo) -> AckOutcome {
+        if delivery.death_count >= self.max_retries {
+            match self.exhausted {
+                ExhaustedAction::Reject => AckOutcome::RejectDiscard,
+                ExhaustedAction::Discard => self.success(delivery),
+            }
+        } else if self.requeue_first_failure && !delivery.redelivered {
+            AckOutcome::RejectRequeue
+        } else {
+            AckOutcome::NackDiscard
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        protocol::basic,
+        types::{AMQPValue, FieldArray, LongLongInt},
+    };
+
+    fn headers(counts: &[Option<LongLongInt>]) -> FieldTable {
+        let mut headers = FieldTable::default();
+        let deaths = counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let mut death = FieldTable::default();
+                death.insert(
+                    "queue".into(),
+                    AMQPValue::LongString(format!("queue-{}", i).into()),
+                );
+                death.insert("reason".into(), AMQPValue::LongString("rejected".into()));
+                if let Some(count) = count {
+                    death.insert("count".into(), AMQPValue::LongLongInt(*count));
+                }
+                AMQPValue::FieldTable(death)
+            })
+            .collect::<Vec<_>>();
+        headers.insert(
+            "x-death".into(),
+            AMQPValue::FieldArray(FieldArray::from(deaths)),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_x_death_count() {
+        assert_eq!(x_death_count(&FieldTable::default()), 0);
+        assert_eq!(x_death_count(&headers(&[Some(2), Some(1)])), 3);
+        assert_eq!(x_death_count(&headers(&[Some(2), None])), 3);
+        let x_death = headers(&[None, Some(4), None]);
+        assert_eq!(x_death_count(&x_death), 6);
+        assert_eq!(
+            x_death_count(&x_death),
+            DeathChain::from_table(&x_death).unwrap().total_count()
+        );
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let policy = RetryPolicy {
+            requeue_first_failure: true,
+            ..RetryPolicy::new(2)
+        };
+        let first = DeliveryInfo::new(1, false, None);
+        assert_eq!(policy.failure(&first), AckOutcome::RejectRequeue);
+        let retry = DeliveryInfo::new(2, true, Some(&headers(&[Some(1)])));
+        assert_eq!(policy.failure(&retry), AckOutcome::NackDiscard);
+        assert_eq!(
+            policy.failure(&retry).to_method(retry.delivery_tag),
+            basic::AMQPMethod::Nack(basic::Nack {
+                delivery_tag: 2,
+                multiple: false,
+                requeue: false,
+            })
+        );
+        let exhausted = DeliveryInfo::new(3, true, Some(&headers(&[Some(2)])));
+        assert_eq!(policy.failure(&exhausted), AckOutcome::RejectDiscard);
+        assert!(!policy.failure(&exhausted).requeue());
+        let discard = RetryPolicy {
+            exhausted: ExhaustedAction::Discard,
+            ..policy
+        };
+        assert_eq!(discard.failure(&exhausted), AckOutcome::Ack);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{protocol::BasicProperties, types::ShortString};
+use std::collections::HashMap;
+
+/// The pseudo-queue used for RabbitMQ direct reply-to
+pub const DIRECT_REPLY_TO: &str = "amq.rabbitmq.reply-to";
+
+/// Whether the queue is the direct reply-to pseudo-queue or one of the names the server
+/// substitutes to it in the reply_to property of the published requests
+pub fn is_direct_reply_to(queue: &str) -> bool {
+    queue
+        .strip_prefix(DIRECT_REPLY_TO)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Get the routing key and properties to use to reply to a request, None if the request
+/// didn't specify where to reply
+pub fn reply_properties(request: &BasicProperties) -> Option<(ShortString, BasicProperties)> {
+    let reply_to = request.reply_to().clone()?;
+    let mut properties = BasicProperties::default();
+    if let Some(correlation_id) = request.correlation_id() {
+        properties = properties.with_correlation_id(correlation_id.clone());
+    }
+    Some((reply_to, properties))
+}
+
+/// Track pending RPC requests and match the responses with them using their correlation_id
+#[derive(Clone, Debug)]
+pub struct RpcCorrelator<T> {
+    prefix: String,
+    next_id: u64,
+    pending: HashMap<ShortString, T>,
+}
+
+impl<T> RpcCorrelator<T> {
+    /// Create a new correlator, generating correlation ids starting with the given prefix
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            next_id: 0,
+            pending: HashMap::default(),
+        }
+    }
+
+    /// Stamp a new correlation_id and the given reply_to on the properties of a request,
+    /// registering the value to be returned when the response comes in
+    pub fn prepare(
+        &mut self,
+        properties: BasicProperties,
+        reply_to: &str,
+        value: T,
+    ) -> BasicProperties {
+        let correlation_id = ShortString::from(format!("{}{}", self.prefix, self.next_id));
+        self.next_id += 1;
+        self.pending.insert(correlation_id.clone(), value);
+        properties
+            .with_correlation_id(correlation_id)
+            .with_reply_to(reply_to.into())
+    }
+
+    /// Match a response with its pending request using its correlation_id
+    pub fn resolve(&mut self, properties: &BasicProperties) -> Option<T> {
+        properties
+            .correlation_id()
+            .as_ref()
+            .and_then(|correlation_id| self.pending.remove(correlation_id))
+    }
+
+    /// Stop waiting for the response to the given request
+    pub fn cancel(&mut self, correlation_id: &str) -> Option<T> {
+        self.pending.remove(correlation_id)
+    }
+
+    /// The number of requests waiting for a response
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether there are no requests waiting for a response or not
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_direct_reply_to() {
+        assert!(is_direct_reply_to(DIRECT_REPLY_TO));
+        assert!(is_direct_reply_to(
+            "amq.rabbitmq.reply-to.g1h2AA5yZXBseUAyNjI4NjI0OAAAAAA="
+        ));
+        assert!(!is_direct_reply_to("amq.rabbitmq.reply-tos"));
+        assert!(!is_direct_reply_to("replies"));
+    }
+
+    #[test]
+    fn test_rpc_roundtrip() {
+        let mut correlator = RpcCorrelator::new("req-");
+        let request = correlator.prepare(BasicProperties::default(), DIRECT_REPLY_TO, "first");
+        correlator.prepare(BasicProperties::default(), DIRECT_REPLY_TO, "second");
+        assert_eq!(correlator.len(), 2);
+        assert_eq!(request.correlation_id().as_ref().unwrap().as_str(), "req-0");
+
+        let (routing_key, response) = reply_properties(&request).unwrap();
+        assert_eq!(routing_key.as_str(), DIRECT_REPLY_TO);
+        assert_eq!(correlator.resolve(&response), Some("first"));
+        assert_eq!(correlator.resolve(&response), None);
+        assert_eq!(correlator.cancel("req-1"), Some("second"));
+        assert!(correlator.is_empty());
+        assert_eq!(reply_properties(&BasicProperties::default()), None);
+    }
+}
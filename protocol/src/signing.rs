@@ -0,0 +1,384 @@
+use crate::{
+    protocol::BasicProperties,
+    types::{
+        generation::{gen_field_table_with, GenerationOptions},
+        AMQPValue, FieldTable, ShortString,
+    },
+};
+use ring::{
+    hmac,
+    signature::{self, Ed25519KeyPair, KeyPair, UnparsedPublicKey},
+};
+use std::fmt;
+
+/// The header the signature is stored in, unless told otherwise
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+/// The properties covered by default by a signature
+pub const DEFAULT_SIGNED_PROPERTIES: [&str; 10] = [
+    "content-type",
+    "content-encoding",
+    "correlation-id",
+    "reply-to",
+    "expiration",
+    "message-id",
+    "timestamp",
+    "type",
+    "user-id",
+    "app-id",
+];
+
+/// The properties and headers covered by a signature, on top of the body
+///
+/// Only what is listed here is signed, so that the headers brokers add on the way, such as
+/// x-death, don't break the signature. The signer and the verifier must use the same list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedFields {
+    properties: Vec<ShortString>,
+    headers: Vec<ShortString>,
+}
+
+impl Default for SignedFields {
+    /// The DEFAULT_SIGNED_PROPERTIES and no header
+    fn default() -> Self {
+        Self {
+            properties: DEFAULT_SIGNED_PROPERTIES
+                .iter()
+                .map(|&name| name.into())
+                .collect(),
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl SignedFields {
+    /// Cover neither properties nor headers, only the body
+    pub fn none() -> Self {
+        Self {
+            properties: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Also cover the given property, named as in the specification (e.g. "content-type")
+    pub fn with_property(mut self, name: impl Into<ShortString>) -> Self {
+        self.properties.push(name.into());
+        self
+    }
+
+    /// Also cover the given header
+    pub fn with_header(mut self, name: impl Into<ShortString>) -> Self {
+        self.headers.push(name.into());
+        self
+    }
+}
+
+/// Sign messages, storing the signature in one of their headers
+///
+/// The signature covers the body and the properties and headers listed in the SignedFields,
+/// which default to the DEFAULT_SIGNED_PROPERTIES and no header. They are serialized in the
+/// canonical form of field tables, so that re-encoding by intermediaries doesn't break the
+/// signature.
+pub struct MessageSigner {
+    key: SigningKey,
+    header: ShortString,
+    fields: SignedFields,
+}
+
+enum SigningKey {
+    Hmac(hmac::Key),
+    Ed25519(Ed25519KeyPair),
+}
+
+impl MessageSigner {
+    /// Sign with HMAC-SHA256 using the given shared secret
+    pub fn hmac_sha256(secret: &[u8]) -> Self {
+        Self::new(SigningKey::Hmac(hmac::Key::new(hmac::HMAC_SHA256, secret)))
+    }
+
+    /// Sign with Ed25519 using the given PKCS#8 v2 encoded key pair
+    pub fn ed25519(pkcs8: &[u8]) -> Result<Self, String> {
+        Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map(|key_pair| Self::new(SigningKey::Ed25519(key_pair)))
+            .map_err(|e| format!("Invalid Ed25519 key pair: {}", e))
+    }
+
+    fn new(key: SigningKey) -> Self {
+        Self {
+            key,
+            header: SIGNATURE_HEADER.into(),
+            fields: SignedFields::default(),
+        }
+    }
+
+    /// Store the signature in the given header instead of x-signature
+    pub fn with_header(mut self, header: impl Into<ShortString>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    /// Sign the given properties and headers instead of the default ones
+    pub fn with_fields(mut self, fields: SignedFields) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// The Ed25519 public key to give to the verifiers, None when signing with HMAC
+    pub fn public_key(&self) -> Option<&[u8]> {
+        match &self.key {
+            SigningKey::Hmac(_) => None,
+            SigningKey::Ed25519(key_pair) => Some(key_pair.public_key().as_ref()),
+        }
+    }
+
+    /// Sign the message, replacing any previous signature
+    pub fn sign(&self, properties: &mut BasicProperties, body: &[u8]) -> Result<(), String> {
+        let payload = signed_payload(properties, body, &self.header, &self.fields)?;
+        let signature = match &self.key {
+            SigningKey::Hmac(key) => hmac::sign(key, &payload).as_ref().to_vec(),
+            SigningKey::Ed25519(key_pair) => key_pair.sign(&payload).as_ref().to_vec(),
+        };
+        properties.set_header(self.header.clone(), AMQPValue::LongString(signature.into()));
+        Ok(())
+    }
+}
+
+impl fmt::Debug for MessageSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageSigner")
+            .field("header", &self.header)
+            .field("fields", &self.fields)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Verify the signatures stored by a MessageSigner
+pub struct MessageVerifier {
+    key: VerifyingKey,
+    header: ShortString,
+    fields: SignedFields,
+}
+
+enum VerifyingKey {
+    Hmac(hmac::Key),
+    Ed25519(UnparsedPublicKey<Vec<u8>>),
+}
+
+impl MessageVerifier {
+    /// Verify HMAC-SHA256 signatures made with the given shared secret
+    pub fn hmac_sha256(secret: &[u8]) -> Self {
+        Self::new(VerifyingKey::Hmac(hmac::Key::new(
+            hmac::HMAC_SHA256,
+            secret,
+        )))
+    }
+
+    /// Verify Ed25519 signatures made with the key pair of the given public key
+    pub fn ed25519(public_key: &[u8]) -> Self {
+        Self::new(VerifyingKey::Ed25519(UnparsedPublicKey::new(
+            &signature::ED25519,
+            public_key.to_vec(),
+        )))
+    }
+
+    fn new(key: VerifyingKey) -> Self {
+        Self {
+            key,
+            header: SIGNATURE_HEADER.into(),
+            fields: SignedFields::default(),
+        }
+    }
+
+    /// Read the signature from the given header instead of x-signature
+    pub fn with_header(mut self, header: impl Into<ShortString>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    /// Verify signatures covering the given properties and headers instead of the default ones
+    pub fn with_fields(mut self, fields: SignedFields) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Check the signature of the message
+    pub fn verify(&self, properties: &BasicProperties, body: &[u8]) -> Result<(), String> {
+        let signature = properties
+            .header(self.header.as_str())
+            .ok_or_else(|| format!("Message has no {} header", self.header))?
+            .as_long_string()
+            .ok_or_else(|| format!("The {} header must be a long string", self.header))?;
+        let payload = signed_payload(properties, body, &self.header, &self.fields)?;
+        let verified = match &self.key {
+            VerifyingKey::Hmac(key) => hmac::verify(key, &payload, signature.as_bytes()),
+            VerifyingKey::Ed25519(key) => key.verify(&payload, signature.as_bytes()),
+        };
+        verified.map_err(|_| "Invalid message signature".to_string())
+    }
+}
+
+impl fmt::Debug for MessageVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageVerifier")
+            .field("header", &self.header)
+            .field("fields", &self.fields)
+            .finish_non_exhaustive()
+    }
+}
+
+fn signed_payload(
+    properties: &BasicProperties,
+    body: &[u8],
+    header: &ShortString,
+    fields: &SignedFields,
+) -> Result<Vec<u8>, String> {
+    let mut signed = FieldTable::default();
+    for name in &fields.properties {
+        if let Some(value) = property(properties, name.as_str())? {
+            signed.insert(name.clone(), value);
+        }
+    }
+    let mut headers = FieldTable::default();
+    for name in fields.headers.iter().filter(|name| *name != header) {
+        if let Some(value) = properties.header(name.as_str()) {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+    signed.insert("headers".into(), AMQPValue::FieldTable(headers));
+    signed.insert("body".into(), AMQPValue::LongString(body.into()));
+    cookie_factory::gen_simple(
+        gen_field_table_with(&signed, GenerationOptions::canonical()),
+        Vec::new(),
+    )
+    .map_err(|e| format!("Failed to serialize the signed payload: {:?}", e))
+}
+
+fn property(properties: &BasicProperties, name: &str) -> Result<Option<AMQPValue>, String> {
+    let string = |value: &Option<ShortString>| {
+        value
+            .as_ref()
+            .map(|value| AMQPValue::LongString(value.as_str().into()))
+    };
+    Ok(match name {
+        "content-type" => string(properties.content_type()),
+        "content-encoding" => string(properties.content_encoding()),
+        "delivery-mode" => properties.delivery_mode().map(AMQPValue::ShortShortUInt),
+        "priority" => properties.priority().map(AMQPValue::ShortShortUInt),
+        "correlation-id" => string(properties.correlation_id()),
+        "reply-to" => string(properties.reply_to()),
+        "expiration" => string(properties.expiration()),
+        "message-id" => string(properties.message_id()),
+        "timestamp" => properties.timestamp().map(AMQPValue::Timestamp),
+        "type" => string(properties.kind()),
+        "user-id" => string(properties.user_id()),
+        "app-id" => string(properties.app_id()),
+        "cluster-id" => string(properties.cluster_id()),
+        name => return Err(format!("Cannot sign the unknown property {}", name)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ring::rand::SystemRandom;
+
+    fn properties() -> BasicProperties {
+        BasicProperties::default()
+            .with_content_type("application/json".into())
+            .with_message_id("42".into())
+            .with_header("x-tenant".into(), AMQPValue::ShortString("acme".into()))
+    }
+
+    fn fields() -> SignedFields {
+        SignedFields::default().with_header("x-tenant")
+    }
+
+    #[test]
+    fn test_hmac() {
+        let signer = MessageSigner::hmac_sha256(b"secret").with_fields(fields());
+        let verifier = MessageVerifier::hmac_sha256(b"secret").with_fields(fields());
+        let mut properties = properties();
+        signer.sign(&mut properties, b"{}").unwrap();
+        assert!(properties.header(SIGNATURE_HEADER).is_some());
+        assert_eq!(verifier.verify(&properties, b"{}"), Ok(()));
+        assert!(verifier.verify(&properties, b"{ }").is_err());
+        assert!(MessageVerifier::hmac_sha256(b"other")
+            .with_fields(fields())
+            .verify(&properties, b"{}")
+            .is_err());
+
+        /* Intermediaries may re-encode the headers or change the priority */
+        properties.set_header("x-tenant".into(), AMQPValue::LongString("acme".into()));
+        let properties = properties.with_priority(9);
+        assert_eq!(verifier.verify(&properties, b"{}"), Ok(()));
+        let tampered = properties.clone().with_message_id("43".into());
+        assert!(verifier.verify(&tampered, b"{}").is_err());
+        assert!(verifier.verify(&self::properties(), b"{}").is_err());
+    }
+
+    #[test]
+    fn test_ed25519() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let signer = MessageSigner::ed25519(pkcs8.as_ref())
+            .unwrap()
+            .with_header("x-sig")
+            .with_fields(fields());
+        let verifier = MessageVerifier::ed25519(signer.public_key().unwrap())
+            .with_header("x-sig")
+            .with_fields(fields());
+        let mut properties = properties();
+        signer.sign(&mut properties, b"body").unwrap();
+        assert!(properties.header(SIGNATURE_HEADER).is_none());
+        assert_eq!(verifier.verify(&properties, b"body"), Ok(()));
+        properties.set_header("x-tenant".into(), AMQPValue::LongString("evil".into()));
+        assert_eq!(
+            verifier.verify(&properties, b"body"),
+            Err("Invalid message signature".to_string())
+        );
+        assert!(MessageSigner::ed25519(b"garbage").is_err());
+    }
+    #[test]
+    fn test_signed_fields() {
+        let signer = MessageSigner::hmac_sha256(b"secret").with_fields(fields());
+        let verifier = MessageVerifier::hmac_sha256(b"secret").with_fields(fields());
+        let mut properties = properties().with_header("x-trace".into(), AMQPValue::Boolean(true));
+        signer.sign(&mut properties, b"body").unwrap();
+
+        /* Brokers add x-death when dead-lettering, unlisted headers are not signed */
+        let mut death = FieldTable::default();
+        death.insert("count".into(), AMQPValue::LongLongInt(1));
+        death.insert("queue".into(), AMQPValue::LongString("work".into()));
+        properties.set_header(
+            "x-death".into(),
+            AMQPValue::FieldArray(vec![AMQPValue::FieldTable(death)].into()),
+        );
+        properties.set_header("x-trace".into(), AMQPValue::Boolean(false));
+        assert_eq!(verifier.verify(&properties, b"body"), Ok(()));
+
+        /* Removing a listed header is detected */
+        let mut stripped = properties.clone();
+        stripped.headers_mut().as_mut().unwrap().remove("x-tenant");
+        assert!(verifier.verify(&stripped, b"body").is_err());
+
+        /* The priority is only covered when listed */
+        let priority = SignedFields::none().with_property("priority");
+        let signer = MessageSigner::hmac_sha256(b"secret").with_fields(priority.clone());
+        let verifier = MessageVerifier::hmac_sha256(b"secret").with_fields(priority);
+        let mut properties = properties.with_priority(1);
+        signer.sign(&mut properties, b"body").unwrap();
+        assert_eq!(
+            verifier.verify(&properties.clone().with_message_id("43".into()), b"body"),
+            Ok(())
+        );
+        assert!(verifier
+            .verify(&properties.with_priority(9), b"body")
+            .is_err());
+
+        let unknown = MessageSigner::hmac_sha256(b"secret")
+            .with_fields(SignedFields::none().with_property("x-tenant"));
+        assert_eq!(
+            unknown.sign(&mut BasicProperties::default(), b"body"),
+            Err("Cannot sign the unknown property x-tenant".to_string())
+        );
+    }
+}
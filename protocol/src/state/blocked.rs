@@ -0,0 +1,145 @@
+use crate::{
+    frame::{AMQPFrame, FrameQueue},
+    protocol::{connection, AMQPClass},
+};
+use std::fmt;
+
+type BlockedHook = Box<dyn FnMut(Option<&str>) + Send + 'static>;
+
+/// Track the connection.blocked and connection.unblocked notifications sent by the server
+///
+/// While blocked, the server stops reading from the connection, so publishing should be paused.
+/// Hooks registered with [on_change](#method.on_change) are called on each transition with the
+/// reason of the blocking, or None when unblocked.
+#[derive(Default)]
+pub struct ConnectionBlocked {
+    reason: Option<String>,
+    hooks: Vec<BlockedHook>,
+}
+
+impl ConnectionBlocked {
+    /// Create a new, unblocked, state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the connection is currently blocked or not
+    pub fn is_blocked(&self) -> bool {
+        self.reason.is_some()
+    }
+
+    /// The reason given by the server for blocking the connection, if blocked
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Register a hook to be called each time the connection gets blocked or unblocked
+    pub fn on_change<F: FnMut(Option<&str>) + Send + 'static>(&mut self, hook: F) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    /// Mark the connection as blocked for the given reason
+    pub fn block(&mut self, reason: String) {
+        self.reason = Some(reason);
+        self.notify();
+    }
+
+    /// Mark the connection as unblocked
+    pub fn unblock(&mut self) {
+        if self.reason.take().is_some() {
+            self.notify();
+        }
+    }
+
+    /// Update the state if the method is a connection.blocked or connection.unblocked one.
+    /// Return whether the method was handled or not
+    pub fn handle_method(&mut self, method: &AMQPClass) -> bool {
+        match method {
+            AMQPClass::Connection(connection::AMQPMethod::Blocked(blocked)) => {
+                self.block(blocked.reason.to_string());
+                true
+            }
+            AMQPClass::Connection(connection::AMQPMethod::Unblocked(_)) => {
+                self.unblock();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Update the state if the frame carries a connection.blocked or connection.unblocked method.
+    /// Return whether the frame was handled or not
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> bool {
+        match frame {
+            AMQPFrame::Method(0, method) => self.handle_method(method),
+            _ => false,
+        }
+    }
+
+    /// Pause or resume the emission of regular frames from the queue according to the state
+    pub fn apply(&self, queue: &mut FrameQueue) {
+        if self.is_blocked() {
+            queue.pause();
+        } else {
+            queue.resume();
+        }
+    }
+
+    fn notify(&mut self) {
+        let reason = self.reason.as_deref();
+        for hook in self.hooks.iter_mut() {
+            hook(reason);
+        }
+    }
+}
+
+impl fmt::Debug for ConnectionBlocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionBlocked")
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_blocked_unblocked() {
+        let mut state = ConnectionBlocked::new();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let hook_changes = changes.clone();
+        state.on_change(move |reason| {
+            hook_changes
+                .lock()
+                .unwrap()
+                .push(reason.map(ToOwned::to_owned))
+        });
+        assert!(state.handle_frame(&AMQPFrame::Method(
+            0,
+            AMQPClass::Connection(connection::AMQPMethod::Blocked(connection::Blocked {
+                reason: "low on memory".into(),
+            })),
+        )));
+        assert!(state.is_blocked());
+        assert_eq!(state.reason(), Some("low on memory"));
+        let mut queue = FrameQueue::new();
+        state.apply(&mut queue);
+        assert!(queue.is_paused());
+        assert!(
+            state.handle_method(&AMQPClass::Connection(connection::AMQPMethod::Unblocked(
+                connection::Unblocked {}
+            ),))
+        );
+        assert!(!state.is_blocked());
+        state.apply(&mut queue);
+        assert!(!queue.is_paused());
+        state.unblock();
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![Some("low on memory".to_owned()), None]
+        );
+    }
+}
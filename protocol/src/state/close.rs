@@ -0,0 +1,292 @@
+use crate::{
+    frame::AMQPFrame,
+    protocol::{channel, connection, AMQPClass},
+    types::{ChannelId, Identifier, ReplyCode},
+};
+use std::time::{Duration, Instant};
+
+/// The reason given for closing a channel or a connection
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CloseReason {
+    /// The reply code
+    pub reply_code: ReplyCode,
+    /// The reply text
+    pub reply_text: String,
+    /// The class id of the method which caused the close, if any
+    pub class_id: Identifier,
+    /// The method id of the method which caused the close, if any
+    pub method_id: Identifier,
+}
+
+/// The state of the close handshake
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloseState {
+    /// Not closing
+    Open,
+    /// We sent a Close and are waiting for the CloseOk
+    Closing {
+        /// When we sent the Close
+        since: Instant,
+    },
+    /// The close handshake is over
+    Closed,
+}
+
+/// What to do with a received frame, according to the close handshake
+#[derive(Clone, Debug, PartialEq)]
+pub enum CloseEvent {
+    /// The frame is unrelated to the close handshake and should be handled normally
+    Unhandled,
+    /// The frame must be discarded as we are closing
+    Discarded,
+    /// The peer closed, the CloseOk frame must be sent in reply
+    ClosedByPeer {
+        /// The CloseOk frame to send
+        close_ok: AMQPFrame,
+        /// The reason given by the peer
+        reason: CloseReason,
+    },
+    /// The peer acknowledged our Close
+    Closed,
+}
+
+/// Drive the close handshake of a channel, or of the connection for the channel 0
+///
+/// Once we sent a Close, every frame but Close and CloseOk must be discarded until we receive
+/// the CloseOk. If the peer sends a Close at the same time, we reply with a CloseOk and
+/// consider the handshake over.
+#[derive(Clone, Debug)]
+pub struct CloseSequencer {
+    channel_id: ChannelId,
+    state: CloseState,
+    timeout: Option<Duration>,
+}
+
+impl CloseSequencer {
+    /// Create a new sequencer for the given channel, 0 meaning the connection
+    pub fn new(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            state: CloseState::Open,
+            timeout: None,
+        }
+    }
+
+    /// Give up waiting for the CloseOk after the given timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The current state of the handshake
+    pub fn state(&self) -> CloseState {
+        self.state
+    }
+
+    /// Whether the close handshake is over or not
+    pub fn is_closed(&self) -> bool {
+        self.state == CloseState::Closed
+    }
+
+    /// Start the close handshake, returning the Close frame to send.
+    /// Return None if we're already closing or closed.
+    pub fn close(&mut self, reason: CloseReason) -> Option<AMQPFrame> {
+        if self.state != CloseState::Open {
+            return None;
+        }
+        self.state = CloseState::Closing {
+            since: Instant::now(),
+        };
+        Some(self.close_frame(reason))
+    }
+
+    /// Handle a frame received from the peer
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> CloseEvent {
+        let method = match frame {
+            AMQPFrame::Method(channel_id, method) if *channel_id == self.channel_id => method,
+            AMQPFrame::Heartbeat(_) => return CloseEvent::Unhandled,
+            frame if self.receives(frame) => return self.discard(),
+            _ => return CloseEvent::Unhandled,
+        };
+        match (self.channel_id, method) {
+            (0, AMQPClass::Connection(connection::AMQPMethod::Close(close))) => self
+                .closed_by_peer(CloseReason {
+                    reply_code: close.reply_code,
+                    reply_text: close.reply_text.to_string(),
+                    class_id: close.class_id,
+                    method_id: close.method_id,
+                }),
+            (1.., AMQPClass::Channel(channel::AMQPMethod::Close(close))) => {
+                self.closed_by_peer(CloseReason {
+                    reply_code: close.reply_code,
+                    reply_text: close.reply_text.to_string(),
+                    class_id: close.class_id,
+                    method_id: close.method_id,
+                })
+            }
+            (0, AMQPClass::Connection(connection::AMQPMethod::CloseOk(_)))
+            | (1.., AMQPClass::Channel(channel::AMQPMethod::CloseOk(_)))
+                if matches!(self.state, CloseState::Closing { .. }) =>
+            {
+                self.state = CloseState::Closed;
+                CloseEvent::Closed
+            }
+            _ => self.discard(),
+        }
+    }
+
+    /// When to give up waiting for the CloseOk, if we're waiting for it and have a timeout
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.state {
+            CloseState::Closing { since } => self.timeout.map(|timeout| since + timeout),
+            _ => None,
+        }
+    }
+
+    /// Check whether we waited too long for the CloseOk, in which case the handshake is
+    /// considered over and the underlying transport should be shut down
+    pub fn check_timeout(&mut self, now: Instant) -> bool {
+        if self.deadline().is_some_and(|deadline| now >= deadline) {
+            self.state = CloseState::Closed;
+            return true;
+        }
+        false
+    }
+
+    fn receives(&self, frame: &AMQPFrame) -> bool {
+        match frame {
+            AMQPFrame::Header(channel_id, ..) | AMQPFrame::Body(channel_id, _) => {
+                self.channel_id == 0 || *channel_id == self.channel_id
+            }
+            AMQPFrame::Method(..) => self.channel_id == 0,
+            _ => false,
+        }
+    }
+
+    fn discard(&self) -> CloseEvent {
+        match self.state {
+            CloseState::Open => CloseEvent::Unhandled,
+            _ => CloseEvent::Discarded,
+        }
+    }
+
+    fn closed_by_peer(&mut self, reason: CloseReason) -> CloseEvent {
+        self.state = CloseState::Closed;
+        CloseEvent::ClosedByPeer {
+            close_ok: self.close_ok_frame(),
+            reason,
+        }
+    }
+
+    fn close_frame(&self, reason: CloseReason) -> AMQPFrame {
+        let method = if self.channel_id == 0 {
+            AMQPClass::Connection(connection::AMQPMethod::Close(connection::Close {
+                reply_code: reason.reply_code,
+                reply_text: reason.reply_text.into(),
+                class_id: reason.class_id,
+                method_id: reason.method_id,
+            }))
+        } else {
+            AMQPClass::Channel(channel::AMQPMethod::Close(channel::Close {
+                reply_code: reason.reply_code,
+                reply_text: reason.reply_text.into(),
+                class_id: reason.class_id,
+                method_id: reason.method_id,
+            }))
+        };
+        AMQPFrame::Method(self.channel_id, method)
+    }
+
+    fn close_ok_frame(&self) -> AMQPFrame {
+        let method = if self.channel_id == 0 {
+            AMQPClass::Connection(connection::AMQPMethod::CloseOk(connection::CloseOk {}))
+        } else {
+            AMQPClass::Channel(channel::AMQPMethod::CloseOk(channel::CloseOk {}))
+        };
+        AMQPFrame::Method(self.channel_id, method)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::Bytes;
+
+    fn reason() -> CloseReason {
+        CloseReason {
+            reply_code: 200,
+            reply_text: "bye".into(),
+            ..CloseReason::default()
+        }
+    }
+
+    #[test]
+    fn test_close_handshake() {
+        let mut sequencer = CloseSequencer::new(1);
+        assert!(matches!(
+            sequencer.close(reason()),
+            Some(AMQPFrame::Method(
+                1,
+                AMQPClass::Channel(channel::AMQPMethod::Close(_))
+            ))
+        ));
+        assert_eq!(sequencer.close(reason()), None);
+        assert_eq!(
+            sequencer.handle_frame(&AMQPFrame::Body(1, Bytes::new())),
+            CloseEvent::Discarded
+        );
+        assert_eq!(
+            sequencer.handle_frame(&AMQPFrame::Body(2, Bytes::new())),
+            CloseEvent::Unhandled
+        );
+        assert_eq!(
+            sequencer.handle_frame(&AMQPFrame::Method(
+                1,
+                AMQPClass::Channel(channel::AMQPMethod::CloseOk(channel::CloseOk {}))
+            )),
+            CloseEvent::Closed
+        );
+        assert!(sequencer.is_closed());
+    }
+
+    #[test]
+    fn test_racing_close() {
+        let mut sequencer = CloseSequencer::new(0);
+        sequencer.close(reason());
+        let event = sequencer.handle_frame(&AMQPFrame::Method(
+            0,
+            AMQPClass::Connection(connection::AMQPMethod::Close(connection::Close {
+                reply_code: 320,
+                reply_text: "shutdown".into(),
+                class_id: 0,
+                method_id: 0,
+            })),
+        ));
+        assert_eq!(
+            event,
+            CloseEvent::ClosedByPeer {
+                close_ok: AMQPFrame::Method(
+                    0,
+                    AMQPClass::Connection(connection::AMQPMethod::CloseOk(connection::CloseOk {}))
+                ),
+                reason: CloseReason {
+                    reply_code: 320,
+                    reply_text: "shutdown".into(),
+                    ..CloseReason::default()
+                },
+            }
+        );
+        assert!(sequencer.is_closed());
+    }
+
+    #[test]
+    fn test_close_timeout() {
+        let mut sequencer = CloseSequencer::new(1).with_timeout(Duration::from_secs(5));
+        assert_eq!(sequencer.deadline(), None);
+        sequencer.close(reason());
+        let deadline = sequencer.deadline().unwrap();
+        assert!(!sequencer.check_timeout(deadline - Duration::from_secs(1)));
+        assert!(sequencer.check_timeout(deadline));
+        assert!(sequencer.is_closed());
+    }
+}
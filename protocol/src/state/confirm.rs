@@ -0,0 +1,191 @@
+use super::ChannelModes;
+use crate::{
+    frame::AMQPFrame,
+    protocol::{basic, confirm, AMQPClass},
+    types::{ChannelId, DeliveryTag},
+};
+use std::collections::HashMap;
+
+/// The confirm mode of a channel
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConfirmState {
+    /// confirm.select was never sent
+    #[default]
+    Off,
+    /// We sent confirm.select and are waiting for confirm.select-ok
+    Pending,
+    /// The channel is in confirm mode
+    Selected,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct ChannelConfirm {
+    state: ConfirmState,
+    next_delivery_tag: DeliveryTag,
+}
+
+/// Track the confirm mode of each channel
+///
+/// Outgoing frames go through [before_send](#method.before_send) which rejects selecting the
+/// confirm mode on a transactional channel and numbers the messages published once confirm.select
+/// has been sent, the broker acknowledging them with those delivery tags.
+#[derive(Clone, Debug, Default)]
+pub struct ConfirmMode {
+    channels: HashMap<ChannelId, ChannelConfirm>,
+    modes: ChannelModes,
+}
+
+impl ConfirmMode {
+    /// Create a new tracker where no channel is in confirm mode
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The confirm mode of the channel
+    pub fn state(&self, channel_id: ChannelId) -> ConfirmState {
+        self.channels
+            .get(&channel_id)
+            .map_or(ConfirmState::Off, |channel| channel.state)
+    }
+
+    /// Whether confirm.select has been sent on the channel
+    pub fn is_enabled(&self, channel_id: ChannelId) -> bool {
+        self.state(channel_id) != ConfirmState::Off
+    }
+
+    /// The delivery tag the next message published on the channel will be confirmed with,
+    /// None if the channel is not in confirm mode
+    pub fn next_delivery_tag(&self, channel_id: ChannelId) -> Option<DeliveryTag> {
+        self.channels
+            .get(&channel_id)
+            .filter(|channel| channel.state != ConfirmState::Off)
+            .map(|channel| channel.next_delivery_tag)
+    }
+
+    /// Check that we can send the frame, and update the state accordingly
+    pub fn before_send(&mut self, frame: &AMQPFrame) -> Result<(), String> {
+        let AMQPFrame::Method(channel_id, method) = frame else {
+            return Ok(());
+        };
+        self.modes.before_send(*channel_id, method)?;
+        match method {
+            AMQPClass::Confirm(confirm::AMQPMethod::Select(select)) => {
+                let channel = self.channels.entry(*channel_id).or_default();
+                if channel.state == ConfirmState::Off {
+                    channel.next_delivery_tag = 1;
+                }
+                if select.nowait {
+                    channel.state = ConfirmState::Selected;
+                } else if channel.state == ConfirmState::Off {
+                    channel.state = ConfirmState::Pending;
+                }
+            }
+            AMQPClass::Basic(basic::AMQPMethod::Publish(_)) => {
+                if let Some(channel) = self
+                    .channels
+                    .get_mut(channel_id)
+                    .filter(|channel| channel.state != ConfirmState::Off)
+                {
+                    channel.next_delivery_tag += 1;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle a frame received from the peer
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> Result<(), String> {
+        let AMQPFrame::Method(channel_id, AMQPClass::Confirm(confirm::AMQPMethod::SelectOk(_))) =
+            frame
+        else {
+            return Ok(());
+        };
+        match self.channels.get_mut(channel_id) {
+            Some(channel) if channel.state == ConfirmState::Pending => {
+                channel.state = ConfirmState::Selected;
+                Ok(())
+            }
+            _ => Err(format!(
+                "Received an unexpected confirm.select-ok on channel {}",
+                channel_id
+            )),
+        }
+    }
+
+    /// Forget about a closed channel
+    pub fn remove(&mut self, channel_id: ChannelId) {
+        self.channels.remove(&channel_id);
+        self.modes.remove(channel_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn select(nowait: bool) -> AMQPFrame {
+        AMQPFrame::Method(
+            1,
+            AMQPClass::Confirm(confirm::AMQPMethod::Select(confirm::Select { nowait })),
+        )
+    }
+
+    fn publish() -> AMQPFrame {
+        AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::Publish(basic::Publish::default())),
+        )
+    }
+
+    #[test]
+    fn test_confirm_mode() {
+        let mut mode = ConfirmMode::new();
+        mode.before_send(&publish()).unwrap();
+        assert_eq!(mode.next_delivery_tag(1), None);
+        assert!(mode
+            .handle_frame(&AMQPFrame::Method(
+                1,
+                AMQPClass::Confirm(confirm::AMQPMethod::SelectOk(confirm::SelectOk {}))
+            ))
+            .is_err());
+        mode.before_send(&select(false)).unwrap();
+        assert_eq!(mode.state(1), ConfirmState::Pending);
+        mode.before_send(&publish()).unwrap();
+        mode.handle_frame(&AMQPFrame::Method(
+            1,
+            AMQPClass::Confirm(confirm::AMQPMethod::SelectOk(confirm::SelectOk {})),
+        ))
+        .unwrap();
+        assert_eq!(mode.state(1), ConfirmState::Selected);
+        mode.before_send(&publish()).unwrap();
+        assert_eq!(mode.next_delivery_tag(1), Some(3));
+        assert_eq!(mode.state(2), ConfirmState::Off);
+        mode.remove(1);
+        mode.before_send(&select(true)).unwrap();
+        assert_eq!(mode.state(1), ConfirmState::Selected);
+        assert_eq!(mode.next_delivery_tag(1), Some(1));
+    }
+
+    #[cfg(feature = "class-tx")]
+    #[test]
+    fn test_confirm_and_tx() {
+        use crate::protocol::tx;
+
+        let mut mode = ConfirmMode::new();
+        mode.before_send(&AMQPFrame::Method(
+            1,
+            AMQPClass::Tx(tx::AMQPMethod::Select(tx::Select {})),
+        ))
+        .unwrap();
+        assert!(mode.before_send(&select(false)).is_err());
+        mode.remove(1);
+        mode.before_send(&select(false)).unwrap();
+        assert!(mode
+            .before_send(&AMQPFrame::Method(
+                1,
+                AMQPClass::Tx(tx::AMQPMethod::Select(tx::Select {})),
+            ))
+            .is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use crate::{
+    frame::AMQPFrame,
+    protocol::{basic, AMQPClass},
+    types::{ChannelId, ShortString},
+};
+use std::collections::{HashMap, VecDeque};
+
+/// The state of a consumer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConsumerState {
+    /// The consumer is registered and receiving deliveries
+    Active,
+    /// We sent basic.cancel and are waiting for basic.cancel-ok
+    Cancelling,
+}
+
+/// What happened to a consumer, according to a received frame
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsumerEvent {
+    /// The frame does not concern the consumers
+    Unhandled,
+    /// The server acknowledged a basic.consume
+    Registered {
+        /// The consumer tag, possibly assigned by the server
+        consumer_tag: String,
+    },
+    /// The server acknowledged a basic.cancel
+    Cancelled {
+        /// The consumer tag
+        consumer_tag: String,
+    },
+    /// The server cancelled the consumer, for example because its queue was deleted
+    CancelledByServer {
+        /// The consumer tag
+        consumer_tag: String,
+        /// The CancelOk frame to send in reply, if the server expects one
+        cancel_ok: Option<AMQPFrame>,
+    },
+}
+
+/// Generate consumer tags and track the consumers of each channel
+///
+/// Outgoing frames go through [before_send](#method.before_send) which rejects reusing a tag
+/// already in use on the channel, or cancelling an unknown consumer. When basic.consume is sent
+/// with an empty tag, the tag assigned by the server is registered on basic.consume-ok.
+#[derive(Clone, Debug)]
+pub struct ConsumerTagRegistry {
+    prefix: String,
+    counter: u64,
+    consumers: HashMap<(ChannelId, String), ConsumerState>,
+    pending: HashMap<ChannelId, VecDeque<String>>,
+}
+
+impl Default for ConsumerTagRegistry {
+    fn default() -> Self {
+        Self {
+            prefix: "ctag".to_string(),
+            counter: 0,
+            consumers: HashMap::default(),
+            pending: HashMap::default(),
+        }
+    }
+}
+
+impl ConsumerTagRegistry {
+    /// Create an empty registry generating tags starting with "ctag"
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate tags starting with the given prefix
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Generate a tag not in use on the given channel
+    pub fn generate(&mut self, channel_id: ChannelId) -> ShortString {
+        loop {
+            self.counter += 1;
+            let consumer_tag = format!("{}-{}", self.prefix, self.counter);
+            if !self.is_in_use(channel_id, &consumer_tag) {
+                return consumer_tag.into();
+            }
+        }
+    }
+
+    /// The state of the consumer, None if it is unknown
+    pub fn state(&self, channel_id: ChannelId, consumer_tag: &str) -> Option<ConsumerState> {
+        self.consumers
+            .get(&(channel_id, consumer_tag.to_string()))
+            .copied()
+    }
+
+    /// The tags of the consumers registered on the channel
+    pub fn consumers(&self, channel_id: ChannelId) -> Vec<&str> {
+        let mut consumers = self
+            .consumers
+            .keys()
+            .filter(|(channel, _)| *channel == channel_id)
+            .map(|(_, consumer_tag)| consumer_tag.as_str())
+            .collect::<Vec<_>>();
+        consumers.sort_unstable();
+        consumers
+    }
+
+    /// Check that we can send the frame, and update the state accordingly
+    pub fn before_send(&mut self, frame: &AMQPFrame) -> Result<(), String> {
+        let AMQPFrame::Method(channel_id, AMQPClass::Basic(method)) = frame else {
+            return Ok(());
+        };
+        match method {
+            basic::AMQPMethod::Consume(consume) => {
+                let consumer_tag = consume.consumer_tag.to_string();
+                if consumer_tag.is_empty() && consume.nowait {
+                    return Err(
+                        "Cannot let the server assign the consumer tag with nowait".to_string()
+                    );
+                }
+                if !consumer_tag.is_empty() && self.is_in_use(*channel_id, &consumer_tag) {
+                    return Err(format!(
+                        "Consumer tag {} is already in use on channel {}",
+                        consumer_tag, channel_id
+                    ));
+                }
+                if consume.nowait {
+                    self.consumers
+                        .insert((*channel_id, consumer_tag), ConsumerState::Active);
+                } else {
+                    self.pending
+                        .entry(*channel_id)
+                        .or_default()
+                        .push_back(consumer_tag);
+                }
+            }
+            basic::AMQPMethod::Cancel(cancel) => {
+                let key = (*channel_id, cancel.consumer_tag.to_string());
+                match self.consumers.get(&key) {
+                    Some(ConsumerState::Active) if cancel.nowait => {
+                        self.consumers.remove(&key);
+                    }
+                    Some(ConsumerState::Active) => {
+                        self.consumers.insert(key, ConsumerState::Cancelling);
+                    }
+                    _ => {
+                        return Err(format!(
+                            "Cannot cancel the consumer {} on channel {}, it is not active",
+                            key.1, channel_id
+                        ))
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle a frame received from the server
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> Result<ConsumerEvent, String> {
+        let AMQPFrame::Method(channel_id, AMQPClass::Basic(method)) = frame else {
+            return Ok(ConsumerEvent::Unhandled);
+        };
+        match method {
+            basic::AMQPMethod::ConsumeOk(consume_ok) => {
+                let consumer_tag = consume_ok.consumer_tag.to_string();
+                let requested = self
+                    .pending
+                    .get_mut(channel_id)
+                    .and_then(VecDeque::pop_front)
+                    .ok_or_else(|| {
+                        format!(
+                            "Received an unexpected basic.consume-ok on channel {}",
+                            channel_id
+                        )
+                    })?;
+                if !requested.is_empty() && requested != consumer_tag {
+                    return Err(format!(
+                        "Expected basic.consume-ok for {} on channel {}, got {}",
+                        requested, channel_id, consumer_tag
+                    ));
+                }
+                if self.is_in_use(*channel_id, &consumer_tag) {
+                    return Err(format!(
+                        "The server assigned the consumer tag {} already in use on channel {}",
+                        consumer_tag, channel_id
+                    ));
+                }
+                self.consumers
+                    .insert((*channel_id, consumer_tag.clone()), ConsumerState::Active);
+                Ok(ConsumerEvent::Registered { consumer_tag })
+            }
+            basic::AMQPMethod::CancelOk(cancel_ok) => {
+                let consumer_tag = cancel_ok.consumer_tag.to_string();
+                match self.consumers.remove(&(*channel_id, consumer_tag.clone())) {
+                    Some(ConsumerState::Cancelling) => {
+                        Ok(ConsumerEvent::Cancelled { consumer_tag })
+                    }
+                    state => {
+                        if let Some(state) = state {
+                            self.consumers
+                                .insert((*channel_id, consumer_tag.clone()), state);
+                        }
+                        Err(format!(
+                            "Received an unexpected basic.cancel-ok for {} on channel {}",
+                            consumer_tag, channel_id
+                        ))
+                    }
+                }
+            }
+            basic::AMQPMethod::Cancel(cancel) => {
+                let consumer_tag = cancel.consumer_tag.to_string();
+                self.consumers.remove(&(*channel_id, consumer_tag.clone()));
+                let cancel_ok = (!cancel.nowait).then(|| {
+                    AMQPFrame::Method(
+                        *channel_id,
+                        AMQPClass::Basic(basic::AMQPMethod::CancelOk(basic::CancelOk {
+                            consumer_tag: cancel.consumer_tag.clone(),
+                        })),
+                    )
+                });
+                Ok(ConsumerEvent::CancelledByServer {
+                    consumer_tag,
+                    cancel_ok,
+                })
+            }
+            _ => Ok(ConsumerEvent::Unhandled),
+        }
+    }
+
+    /// Forget about all the consumers of a closed channel
+    pub fn remove_channel(&mut self, channel_id: ChannelId) {
+        self.consumers
+            .retain(|(channel, _), _| *channel != channel_id);
+        self.pending.remove(&channel_id);
+    }
+
+    fn is_in_use(&self, channel_id: ChannelId, consumer_tag: &str) -> bool {
+        self.state(channel_id, consumer_tag).is_some()
+            || self
+                .pending
+                .get(&channel_id)
+                .is_some_and(|pending| pending.iter().any(|tag| tag == consumer_tag))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn consume(consumer_tag: &str) -> AMQPFrame {
+        AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::Consume(basic::Consume {
+                consumer_tag: consumer_tag.into(),
+                ..basic::Consume::default()
+            })),
+        )
+    }
+
+    fn consume_ok(consumer_tag: &str) -> AMQPFrame {
+        AMQPFrame::Method(
+            1,
+            AMQPClass::Basic(basic::AMQPMethod::ConsumeOk(basic::ConsumeOk {
+                consumer_tag: consumer_tag.into(),
+            })),
+        )
+    }
+
+    fn cancel(consumer_tag: &str) -> basic::Cancel {
+        basic::Cancel {
+            consumer_tag: consumer_tag.into(),
+            nowait: false,
+        }
+    }
+
+    #[test]
+    fn test_consumer_lifecycle() {
+        let mut registry = ConsumerTagRegistry::new().with_prefix("test");
+        let consumer_tag = registry.generate(1);
+        assert_eq!(consumer_tag.as_str(), "test-1");
+        registry.before_send(&consume("test-1")).unwrap();
+        assert!(registry.before_send(&consume("test-1")).is_err());
+        assert_eq!(registry.generate(1).as_str(), "test-2");
+        registry.before_send(&consume("")).unwrap();
+        assert_eq!(
+            registry.handle_frame(&consume_ok("test-1")),
+            Ok(ConsumerEvent::Registered {
+                consumer_tag: "test-1".into()
+            })
+        );
+        assert_eq!(
+            registry.handle_frame(&consume_ok("amq.ctag-xyz")),
+            Ok(ConsumerEvent::Registered {
+                consumer_tag: "amq.ctag-xyz".into()
+            })
+        );
+        assert_eq!(registry.consumers(1), vec!["amq.ctag-xyz", "test-1"]);
+        assert!(registry.handle_frame(&consume_ok("other")).is_err());
+
+        registry
+            .before_send(&AMQPFrame::Method(
+                1,
+                AMQPClass::Basic(basic::AMQPMethod::Cancel(cancel("test-1"))),
+            ))
+            .unwrap();
+        assert_eq!(registry.state(1, "test-1"), Some(ConsumerState::Cancelling));
+        assert_eq!(
+            registry.handle_frame(&AMQPFrame::Method(
+                1,
+                AMQPClass::Basic(basic::AMQPMethod::CancelOk(basic::CancelOk {
+                    consumer_tag: "test-1".into(),
+                })),
+            )),
+            Ok(ConsumerEvent::Cancelled {
+                consumer_tag: "test-1".into()
+            })
+        );
+        assert_eq!(registry.state(1, "test-1"), None);
+        assert!(registry
+            .before_send(&AMQPFrame::Method(
+                1,
+                AMQPClass::Basic(basic::AMQPMethod::Cancel(cancel("test-1"))),
+            ))
+            .is_err());
+    }
+
+    #[test]
+    fn test_cancelled_by_server() {
+        let mut registry = ConsumerTagRegistry::new();
+        registry.before_send(&consume("ctag-1")).unwrap();
+        registry.handle_frame(&consume_ok("ctag-1")).unwrap();
+        assert_eq!(
+            registry.handle_frame(&AMQPFrame::Method(
+                1,
+                AMQPClass::Basic(basic::AMQPMethod::Cancel(cancel("ctag-1"))),
+            )),
+            Ok(ConsumerEvent::CancelledByServer {
+                consumer_tag: "ctag-1".into(),
+                cancel_ok: Some(AMQPFrame::Method(
+                    1,
+                    AMQPClass::Basic(basic::AMQPMethod::CancelOk(basic::CancelOk {
+                        consumer_tag: "ctag-1".into(),
+                    })),
+                )),
+            })
+        );
+        assert!(registry.consumers(1).is_empty());
+        assert_eq!(registry.generate(1).as_str(), "ctag-1");
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    frame::AMQPFrame,
+    protocol::{channel, AMQPClass, AMQPError},
+    state::CloseReason,
+    types::ChannelId,
+};
+use std::collections::HashMap;
+
+/// The error which closed a channel
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelError {
+    /// The reason given by the server in channel.close
+    pub reason: CloseReason,
+    /// Whether we replied with channel.close-ok, after which the channel id can be reused
+    pub close_ok_sent: bool,
+}
+
+impl ChannelError {
+    /// The AMQP error matching the reply code, if it is a known one
+    pub fn error(&self) -> Option<AMQPError> {
+        AMQPError::from_id(
+            self.reason.reply_code,
+            self.reason.reply_text.as_str().into(),
+        )
+    }
+}
+
+/// Record the errors which closed channels, to know when their ids can be reused
+///
+/// Received frames go through [handle_frame](#method.handle_frame), which records the
+/// channel.close sent by the server, and outgoing ones through
+/// [before_send](#method.before_send). A channel id is reusable once we replied with
+/// channel.close-ok, and its error is forgotten when it is opened again.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelErrorMap {
+    errors: HashMap<ChannelId, ChannelError>,
+}
+
+impl ChannelErrorMap {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the error if the frame is a channel.close, returning it
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> Option<&ChannelError> {
+        match frame {
+            AMQPFrame::Method(
+                channel_id @ 1..,
+                AMQPClass::Channel(channel::AMQPMethod::Close(close)),
+            ) => {
+                let error = ChannelError {
+                    reason: CloseReason {
+                        reply_code: close.reply_code,
+                        reply_text: close.reply_text.to_string(),
+                        class_id: close.class_id,
+                        method_id: close.method_id,
+                    },
+                    close_ok_sent: false,
+                };
+                self.errors.insert(*channel_id, error);
+                self.errors.get(channel_id)
+            }
+            _ => None,
+        }
+    }
+
+    /// Track a frame we are about to send: channel.close-ok makes the channel id reusable and
+    /// channel.open forgets its error
+    pub fn before_send(&mut self, frame: &AMQPFrame) {
+        match frame {
+            AMQPFrame::Method(channel_id, AMQPClass::Channel(channel::AMQPMethod::CloseOk(_))) => {
+                if let Some(error) = self.errors.get_mut(channel_id) {
+                    error.close_ok_sent = true;
+                }
+            }
+            AMQPFrame::Method(channel_id, AMQPClass::Channel(channel::AMQPMethod::Open(_))) => {
+                self.errors.remove(channel_id);
+            }
+            _ => {}
+        }
+    }
+
+    /// The error which closed the channel, if any
+    pub fn error(&self, channel_id: ChannelId) -> Option<&ChannelError> {
+        self.errors.get(&channel_id)
+    }
+
+    /// Whether the channel id can be opened again, which is the case if it wasn't closed by an
+    /// error or if we acknowledged it
+    pub fn is_reusable(&self, channel_id: ChannelId) -> bool {
+        self.errors
+            .get(&channel_id)
+            .map_or(true, |error| error.close_ok_sent)
+    }
+
+    /// The channels closed by an error, sorted by id
+    pub fn channels(&self) -> Vec<ChannelId> {
+        let mut channels = self.errors.keys().copied().collect::<Vec<_>>();
+        channels.sort_unstable();
+        channels
+    }
+
+    /// Forget the error of a channel
+    pub fn remove(&mut self, channel_id: ChannelId) {
+        self.errors.remove(&channel_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocol::{AMQPErrorKind, AMQPSoftError};
+
+    fn channel_method(channel_id: ChannelId, method: channel::AMQPMethod) -> AMQPFrame {
+        AMQPFrame::Method(channel_id, AMQPClass::Channel(method))
+    }
+
+    #[test]
+    fn test_channel_errors() {
+        let mut errors = ChannelErrorMap::new();
+        let close = channel_method(
+            3,
+            channel::AMQPMethod::Close(channel::Close {
+                reply_code: 404,
+                reply_text: "NOT_FOUND - no queue 'missing'".into(),
+                class_id: 50,
+                method_id: 10,
+            }),
+        );
+        let error = errors.handle_frame(&close).cloned().unwrap();
+        assert_eq!(error.reason.class_id, 50);
+        assert_eq!(error.reason.method_id, 10);
+        assert_eq!(
+            error.error().map(|error| error.kind().clone()),
+            Some(AMQPErrorKind::Soft(AMQPSoftError::NOTFOUND))
+        );
+        assert!(!errors.is_reusable(3));
+        assert!(errors.is_reusable(2));
+        assert_eq!(errors.channels(), vec![3]);
+
+        errors.before_send(&channel_method(
+            3,
+            channel::AMQPMethod::CloseOk(channel::CloseOk {}),
+        ));
+        assert!(errors.is_reusable(3));
+        assert_eq!(
+            errors.error(3).map(|error| error.reason.reply_code),
+            Some(404)
+        );
+
+        errors.before_send(&channel_method(
+            3,
+            channel::AMQPMethod::Open(channel::Open {}),
+        ));
+        assert_eq!(errors.error(3), None);
+        assert_eq!(errors.handle_frame(&AMQPFrame::Heartbeat(0)), None);
+    }
+}
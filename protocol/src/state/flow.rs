@@ -0,0 +1,193 @@
+use crate::{
+    frame::AMQPFrame,
+    protocol::{channel, AMQPClass},
+    types::ChannelId,
+};
+use std::collections::HashMap;
+
+/// What happened on a channel, according to a received frame
+#[derive(Clone, Debug, PartialEq)]
+pub enum FlowEvent {
+    /// The frame is not a channel.flow or channel.flow-ok method
+    Unhandled,
+    /// The peer asked us to stop or resume publishing, the FlowOk frame must be sent in reply
+    Changed {
+        /// Whether publishing is now permitted
+        active: bool,
+        /// The FlowOk frame to send
+        flow_ok: AMQPFrame,
+    },
+    /// The peer acknowledged our request to stop or resume the deliveries
+    Acknowledged {
+        /// Whether the deliveries are now active
+        active: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+struct FlowState {
+    publishing: bool,
+    deliveries: bool,
+    pending: Option<bool>,
+}
+
+impl Default for FlowState {
+    fn default() -> Self {
+        Self {
+            publishing: true,
+            deliveries: true,
+            pending: None,
+        }
+    }
+}
+
+/// Track the channel.flow and channel.flow-ok methods on each channel
+///
+/// The peer can ask us to stop publishing content on a channel, which we must acknowledge, and we
+/// can ask the peer to stop the deliveries, which it acknowledges in turn. Only one request of
+/// ours can be pending on a channel at a time. Channels are active until told otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelFlow {
+    channels: HashMap<ChannelId, FlowState>,
+}
+
+impl ChannelFlow {
+    /// Create a new state where every channel is active
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether publishing content on the channel is currently permitted
+    pub fn can_publish(&self, channel_id: ChannelId) -> bool {
+        self.state(channel_id).publishing
+    }
+
+    /// Whether the peer currently sends us deliveries on the channel, as last acknowledged
+    pub fn deliveries_active(&self, channel_id: ChannelId) -> bool {
+        self.state(channel_id).deliveries
+    }
+
+    /// The state we asked for and which the peer has not acknowledged yet, if any
+    pub fn pending(&self, channel_id: ChannelId) -> Option<bool> {
+        self.state(channel_id).pending
+    }
+
+    /// Ask the peer to stop or resume the deliveries, returning the Flow frame to send
+    pub fn request(&mut self, channel_id: ChannelId, active: bool) -> Result<AMQPFrame, String> {
+        let state = self.channels.entry(channel_id).or_default();
+        if state.pending.is_some() {
+            return Err(format!(
+                "A channel.flow request is already pending on channel {}",
+                channel_id
+            ));
+        }
+        state.pending = Some(active);
+        Ok(AMQPFrame::Method(
+            channel_id,
+            AMQPClass::Channel(channel::AMQPMethod::Flow(channel::Flow { active })),
+        ))
+    }
+
+    /// Handle a frame received from the peer
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> Result<FlowEvent, String> {
+        let AMQPFrame::Method(channel_id, AMQPClass::Channel(method)) = frame else {
+            return Ok(FlowEvent::Unhandled);
+        };
+        match method {
+            channel::AMQPMethod::Flow(flow) => {
+                self.channels.entry(*channel_id).or_default().publishing = flow.active;
+                Ok(FlowEvent::Changed {
+                    active: flow.active,
+                    flow_ok: AMQPFrame::Method(
+                        *channel_id,
+                        AMQPClass::Channel(channel::AMQPMethod::FlowOk(channel::FlowOk {
+                            active: flow.active,
+                        })),
+                    ),
+                })
+            }
+            channel::AMQPMethod::FlowOk(flow_ok) => {
+                let state = self.channels.entry(*channel_id).or_default();
+                if state.pending.take().is_none() {
+                    return Err(format!(
+                        "Received an unexpected channel.flow-ok on channel {}",
+                        channel_id
+                    ));
+                }
+                state.deliveries = flow_ok.active;
+                Ok(FlowEvent::Acknowledged {
+                    active: flow_ok.active,
+                })
+            }
+            _ => Ok(FlowEvent::Unhandled),
+        }
+    }
+
+    /// Forget about a closed channel
+    pub fn remove(&mut self, channel_id: ChannelId) {
+        self.channels.remove(&channel_id);
+    }
+
+    fn state(&self, channel_id: ChannelId) -> FlowState {
+        self.channels.get(&channel_id).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn flow(channel_id: ChannelId, active: bool) -> AMQPFrame {
+        AMQPFrame::Method(
+            channel_id,
+            AMQPClass::Channel(channel::AMQPMethod::Flow(channel::Flow { active })),
+        )
+    }
+
+    fn flow_ok(channel_id: ChannelId, active: bool) -> AMQPFrame {
+        AMQPFrame::Method(
+            channel_id,
+            AMQPClass::Channel(channel::AMQPMethod::FlowOk(channel::FlowOk { active })),
+        )
+    }
+
+    #[test]
+    fn test_flow_from_peer() {
+        let mut flow_state = ChannelFlow::new();
+        assert!(flow_state.can_publish(1));
+        assert_eq!(
+            flow_state.handle_frame(&flow(1, false)),
+            Ok(FlowEvent::Changed {
+                active: false,
+                flow_ok: flow_ok(1, false),
+            })
+        );
+        assert!(!flow_state.can_publish(1));
+        assert!(flow_state.can_publish(2));
+        flow_state.handle_frame(&flow(1, true)).unwrap();
+        assert!(flow_state.can_publish(1));
+        assert_eq!(
+            flow_state.handle_frame(&AMQPFrame::Heartbeat(0)),
+            Ok(FlowEvent::Unhandled)
+        );
+    }
+
+    #[test]
+    fn test_flow_request() {
+        let mut flow_state = ChannelFlow::new();
+        assert_eq!(flow_state.request(1, false), Ok(flow(1, false)));
+        assert!(flow_state.request(1, true).is_err());
+        assert_eq!(flow_state.pending(1), Some(false));
+        assert!(flow_state.deliveries_active(1));
+        assert_eq!(
+            flow_state.handle_frame(&flow_ok(1, false)),
+            Ok(FlowEvent::Acknowledged { active: false })
+        );
+        assert_eq!(flow_state.pending(1), None);
+        assert!(!flow_state.deliveries_active(1));
+        assert!(flow_state.can_publish(1));
+        assert!(flow_state.handle_frame(&flow_ok(1, true)).is_err());
+        flow_state.remove(1);
+        assert!(flow_state.deliveries_active(1));
+    }
+}
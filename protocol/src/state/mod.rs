@@ -0,0 +1,16 @@
+mod blocked;
+mod close;
+#[cfg(feature = "class-confirm")]
+mod confirm;
+mod consumers;
+mod errors;
+mod flow;
+mod modes;
+#[cfg(feature = "class-tx")]
+mod tx;
+
+#[cfg(feature = "class-confirm")]
+pub use self::confirm::*;
+#[cfg(feature = "class-tx")]
+pub use self::tx::*;
+pub use self::{blocked::*, close::*, consumers::*, errors::*, flow::*, modes::*};
@@ -0,0 +1,99 @@
+use crate::{protocol::AMQPClass, types::ChannelId};
+use std::collections::HashMap;
+
+/// The mutually exclusive modes a channel can be put in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelMode {
+    /// tx.select was sent on the channel
+    Transactional,
+    /// confirm.select was sent on the channel
+    Confirm,
+}
+
+impl ChannelMode {
+    /// The mode the method puts the channel in, if any
+    pub fn selected_by(method: &AMQPClass) -> Option<Self> {
+        match method {
+            #[cfg(feature = "class-tx")]
+            AMQPClass::Tx(crate::protocol::tx::AMQPMethod::Select(_)) => Some(Self::Transactional),
+            #[cfg(feature = "class-confirm")]
+            AMQPClass::Confirm(crate::protocol::confirm::AMQPMethod::Select(_)) => {
+                Some(Self::Confirm)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Remember which channels were made transactional or put in confirm mode, refusing to mix both
+#[derive(Clone, Debug, Default)]
+pub struct ChannelModes {
+    channels: HashMap<ChannelId, ChannelMode>,
+}
+
+impl ChannelModes {
+    /// Create a new tracker where no channel has a mode
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The mode the channel is in
+    pub fn mode(&self, channel_id: ChannelId) -> Option<ChannelMode> {
+        self.channels.get(&channel_id).copied()
+    }
+
+    /// Record the mode the method puts the channel in, failing if it is already in the other one
+    pub fn before_send(&mut self, channel_id: ChannelId, method: &AMQPClass) -> Result<(), String> {
+        let Some(mode) = ChannelMode::selected_by(method) else {
+            return Ok(());
+        };
+        match (self.mode(channel_id), mode) {
+            (Some(ChannelMode::Transactional), ChannelMode::Confirm) => Err(format!(
+                "Cannot use confirm mode on the transactional channel {}",
+                channel_id
+            )),
+            (Some(ChannelMode::Confirm), ChannelMode::Transactional) => Err(format!(
+                "Cannot make the channel {} transactional, it is in confirm mode",
+                channel_id
+            )),
+            _ => {
+                self.channels.insert(channel_id, mode);
+                Ok(())
+            }
+        }
+    }
+
+    /// Forget about a closed channel
+    pub fn remove(&mut self, channel_id: ChannelId) {
+        self.channels.remove(&channel_id);
+    }
+}
+
+#[cfg(all(test, feature = "class-tx", feature = "class-confirm"))]
+mod test {
+    use super::*;
+    use crate::protocol::{confirm, tx};
+
+    #[test]
+    fn test_channel_modes() {
+        let tx_select = AMQPClass::Tx(tx::AMQPMethod::Select(tx::Select {}));
+        let confirm_select = AMQPClass::Confirm(confirm::AMQPMethod::Select(confirm::Select {
+            nowait: false,
+        }));
+        let mut modes = ChannelModes::new();
+        modes.before_send(1, &tx_select).unwrap();
+        modes.before_send(1, &tx_select).unwrap();
+        assert_eq!(modes.mode(1), Some(ChannelMode::Transactional));
+        assert_eq!(
+            modes.before_send(1, &confirm_select),
+            Err("Cannot use confirm mode on the transactional channel 1".to_string())
+        );
+        modes.before_send(2, &confirm_select).unwrap();
+        assert_eq!(
+            modes.before_send(2, &tx_select),
+            Err("Cannot make the channel 2 transactional, it is in confirm mode".to_string())
+        );
+        modes.remove(1);
+        assert_eq!(modes.mode(1), None);
+    }
+}
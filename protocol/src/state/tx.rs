@@ -0,0 +1,180 @@
+use super::ChannelModes;
+use crate::{
+    frame::AMQPFrame,
+    protocol::{tx, AMQPClass},
+    types::ChannelId,
+};
+use std::collections::HashMap;
+
+/// The transactional state of a channel
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TxState {
+    /// tx.select was never sent
+    #[default]
+    NonTransactional,
+    /// We sent tx.select and are waiting for tx.select-ok
+    Selecting,
+    /// The channel is transactional and idle
+    Transactional,
+    /// We sent tx.commit and are waiting for tx.commit-ok
+    Committing,
+    /// We sent tx.rollback and are waiting for tx.rollback-ok
+    RollingBack,
+}
+
+/// Track the tx.select, tx.commit and tx.rollback sequencing on each channel
+///
+/// Outgoing frames go through [before_send](#method.before_send) which rejects the illegal ones:
+/// committing or rolling back outside of a transaction, starting a new step while one is pending,
+/// or mixing the transactional and the confirm modes on the same channel.
+#[derive(Clone, Debug, Default)]
+pub struct TxTracker {
+    channels: HashMap<ChannelId, TxState>,
+    modes: ChannelModes,
+}
+
+impl TxTracker {
+    /// Create a new tracker where no channel is transactional
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The transactional state of the channel
+    pub fn state(&self, channel_id: ChannelId) -> TxState {
+        self.channels.get(&channel_id).copied().unwrap_or_default()
+    }
+
+    /// Whether the channel has been made transactional
+    pub fn is_transactional(&self, channel_id: ChannelId) -> bool {
+        !matches!(
+            self.state(channel_id),
+            TxState::NonTransactional | TxState::Selecting
+        )
+    }
+
+    /// Check that we can send the frame, and update the state accordingly
+    pub fn before_send(&mut self, frame: &AMQPFrame) -> Result<(), String> {
+        let AMQPFrame::Method(channel_id, method) = frame else {
+            return Ok(());
+        };
+        let channel_id = *channel_id;
+        self.modes.before_send(channel_id, method)?;
+        let state = self.state(channel_id);
+        let next = match (method, state) {
+            (
+                AMQPClass::Tx(tx::AMQPMethod::Select(_)),
+                TxState::NonTransactional | TxState::Transactional,
+            ) => TxState::Selecting,
+            (AMQPClass::Tx(tx::AMQPMethod::Commit(_)), TxState::Transactional) => {
+                TxState::Committing
+            }
+            (AMQPClass::Tx(tx::AMQPMethod::Rollback(_)), TxState::Transactional) => {
+                TxState::RollingBack
+            }
+            (AMQPClass::Tx(method), state) => {
+                return Err(format!(
+                    "Cannot send {:?} on channel {} while {:?}",
+                    method, channel_id, state
+                ))
+            }
+            _ => return Ok(()),
+        };
+        self.channels.insert(channel_id, next);
+        Ok(())
+    }
+
+    /// Handle a frame received from the peer
+    pub fn handle_frame(&mut self, frame: &AMQPFrame) -> Result<(), String> {
+        let AMQPFrame::Method(channel_id, AMQPClass::Tx(method)) = frame else {
+            return Ok(());
+        };
+        let state = self.state(*channel_id);
+        match (method, state) {
+            (tx::AMQPMethod::SelectOk(_), TxState::Selecting)
+            | (tx::AMQPMethod::CommitOk(_), TxState::Committing)
+            | (tx::AMQPMethod::RollbackOk(_), TxState::RollingBack) => {
+                self.channels.insert(*channel_id, TxState::Transactional);
+                Ok(())
+            }
+            (method, state) => Err(format!(
+                "Received an unexpected {:?} on channel {} while {:?}",
+                method, channel_id, state
+            )),
+        }
+    }
+
+    /// Forget about a closed channel
+    pub fn remove(&mut self, channel_id: ChannelId) {
+        self.channels.remove(&channel_id);
+        self.modes.remove(channel_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame(method: tx::AMQPMethod) -> AMQPFrame {
+        AMQPFrame::Method(1, AMQPClass::Tx(method))
+    }
+
+    #[test]
+    fn test_tx_sequencing() {
+        let mut tracker = TxTracker::new();
+        assert!(tracker
+            .before_send(&frame(tx::AMQPMethod::Commit(tx::Commit {})))
+            .is_err());
+        tracker
+            .before_send(&frame(tx::AMQPMethod::Select(tx::Select {})))
+            .unwrap();
+        assert_eq!(tracker.state(1), TxState::Selecting);
+        assert!(!tracker.is_transactional(1));
+        tracker
+            .handle_frame(&frame(tx::AMQPMethod::SelectOk(tx::SelectOk {})))
+            .unwrap();
+        assert!(tracker.is_transactional(1));
+        tracker
+            .before_send(&frame(tx::AMQPMethod::Commit(tx::Commit {})))
+            .unwrap();
+        assert!(tracker
+            .before_send(&frame(tx::AMQPMethod::Rollback(tx::Rollback {})))
+            .is_err());
+        assert!(tracker
+            .handle_frame(&frame(tx::AMQPMethod::RollbackOk(tx::RollbackOk {})))
+            .is_err());
+        tracker
+            .handle_frame(&frame(tx::AMQPMethod::CommitOk(tx::CommitOk {})))
+            .unwrap();
+        assert_eq!(tracker.state(1), TxState::Transactional);
+        assert_eq!(tracker.state(2), TxState::NonTransactional);
+        tracker.remove(1);
+        assert!(!tracker.is_transactional(1));
+    }
+
+    #[cfg(feature = "class-confirm")]
+    #[test]
+    fn test_tx_and_confirm() {
+        use crate::protocol::confirm;
+
+        let confirm_select = |channel_id| {
+            AMQPFrame::Method(
+                channel_id,
+                AMQPClass::Confirm(confirm::AMQPMethod::Select(confirm::Select {
+                    nowait: false,
+                })),
+            )
+        };
+        let mut tracker = TxTracker::new();
+        tracker.before_send(&confirm_select(1)).unwrap();
+        assert!(tracker
+            .before_send(&frame(tx::AMQPMethod::Select(tx::Select {})))
+            .is_err());
+        tracker
+            .before_send(&AMQPFrame::Method(
+                2,
+                AMQPClass::Tx(tx::AMQPMethod::Select(tx::Select {})),
+            ))
+            .unwrap();
+        assert!(tracker.before_send(&confirm_select(2)).is_err());
+    }
+}
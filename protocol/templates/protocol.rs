@@ -27,7 +27,7 @@ pub mod constants {
 }
 
 /// The available soft AMQP errors
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}}, PartialEq)]
 pub enum AMQPSoftError {
     {{#each protocol.soft_errors as |constant| ~}}
     /// {{{constant.name}}} (Generated)
@@ -67,7 +67,7 @@ impl fmt::Display for AMQPSoftError {
 }
 
 /// The available hard AMQP errors
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}}, PartialEq)]
 pub enum AMQPHardError {
     {{#each protocol.hard_errors as |constant| ~}}
     /// {{{constant.name}}} (Generated)
@@ -106,15 +106,124 @@ impl fmt::Display for AMQPHardError {
     }
 }
 
+{{#each protocol.string_enums as |string_enum| ~}}
+/// {{string_enum.name}} (Generated)
+///
+/// Values are compared through their string representation, so Other holding a well-known value
+/// equals the matching variant (Generated)
+#[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}})]
+pub enum {{camel string_enum.name}} {
+    {{#each string_enum.values as |value| ~}}
+    /// {{value}} (Generated)
+    {{camel value}},
+    {{/each ~}}
+    /// Any other value (Generated)
+    Other(String),
+}
+
+impl {{camel string_enum.name}} {
+    /// Get the string representation (Generated)
+    pub fn as_str(&self) -> &str {
+        match self {
+            {{#each string_enum.values as |value| ~}}
+            {{camel string_enum.name}}::{{camel value}} => "{{value}}",
+            {{/each ~}}
+            {{camel string_enum.name}}::Other(value) => value,
+        }
+    }
+}
+
+impl PartialEq for {{camel string_enum.name}} {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for {{camel string_enum.name}} {}
+
+impl Hash for {{camel string_enum.name}} {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl From<&str> for {{camel string_enum.name}} {
+    fn from(s: &str) -> Self {
+        match s {
+            {{#each string_enum.values as |value| ~}}
+            "{{value}}" => {{camel string_enum.name}}::{{camel value}},
+            {{/each ~}}
+            s => {{camel string_enum.name}}::Other(s.to_string()),
+        }
+    }
+}
+
+impl From<String> for {{camel string_enum.name}} {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+impl FromStr for {{camel string_enum.name}} {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+impl From<{{camel string_enum.name}}> for ShortString {
+    fn from(value: {{camel string_enum.name}}) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl fmt::Display for {{camel string_enum.name}} {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+{{#each string_enum.arguments as |argument| ~}}
+{{#if argument.feature ~}}
+#[cfg(feature = "{{argument.feature}}")]
+{{/if ~}}
+impl {{snake argument.class}}::{{camel argument.method}} {
+    /// Get {{argument.name}} as a {{camel string_enum.name}} (Generated)
+    pub fn {{snake string_enum.name false}}(&self) -> {{camel string_enum.name}} {
+        self.{{snake argument.name}}.as_str().into()
+    }
+
+    /// Set {{argument.name}} from a {{camel string_enum.name}} (Generated)
+    pub fn with_{{snake string_enum.name false}}(mut self, value: {{camel string_enum.name}}) -> Self {
+        self.{{snake argument.name}} = value.into();
+        self
+    }
+}
+
+{{/each ~}}
+{{/each ~}}
+
 {{#each protocol.classes as |class| ~}}
-use self::{{snake class.name}}::parse_{{snake class.name}};
+{{#if class.feature ~}}
+#[cfg(feature = "{{class.feature}}")]
+{{/if ~}}
+use self::{{snake class.name}}::parse_{{snake class.name}}_with;
 {{/each ~}}
 
 /// Parse an AMQP class
 pub fn parse_class<I: ParsableInput>(i: I) -> ParserResult<I, AMQPClass> {
-    context("parse_class", map_opt(flat_map(parse_id, |id| move |i| match id {
+    parse_class_with(i, ParsingOptions::default())
+}
+
+/// Parse an AMQP class, parsing its field tables with the given options
+pub fn parse_class_with<I: ParsableInput>(i: I, options: ParsingOptions) -> ParserResult<I, AMQPClass> {
+    context("parse_class", map_opt(flat_map(parse_id, move |id| move |i| match id {
         {{#each protocol.classes as |class| ~}}
-        {{class.id}} => map(map(parse_{{snake class.name false}}, AMQPClass::{{camel class.name}}), Some).parse(i),
+        {{#if class.feature ~}}
+        #[cfg(feature = "{{class.feature}}")]
+        {{/if ~}}
+        {{class.id}} => map(map(|i: I| parse_{{snake class.name false}}_with(i, options), AMQPClass::{{camel class.name}}), Some).parse(i),
         {{/each ~}}
         _ => Ok((i, None)),
     }), std::convert::identity)).parse(i)
@@ -124,16 +233,34 @@ pub fn parse_class<I: ParsableInput>(i: I) -> ParserResult<I, AMQPClass> {
 pub fn gen_class<'a, W: Write + BackToTheBuffer + 'a>(class: &'a AMQPClass) -> impl SerializeFn<W> + 'a {
     move |input| match *class {
         {{#each protocol.classes as |class| ~}}
+        {{#if class.feature ~}}
+        #[cfg(feature = "{{class.feature}}")]
+        {{/if ~}}
         AMQPClass::{{camel class.name}}(ref {{snake class.name}}) => {{snake class.name}}::gen_{{snake class.name false}}({{snake class.name}})(input),
         {{/each ~}}
     }
 }
 
+impl Decode for AMQPClass {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+        decode_with(bytes, parse_class)
+    }
+}
+
+impl Encode for AMQPClass {
+    fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+        encode_with(buffer, gen_class(self))
+    }
+}
+
 /// The available AMQP classes
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}}, PartialEq)]
 pub enum AMQPClass {
     {{#each protocol.classes as |class| ~}}
     /// {{class.name}} (Generated)
+    {{#if class.feature ~}}
+    #[cfg(feature = "{{class.feature}}")]
+    {{/if ~}}
     {{camel class.name}}({{snake class.name}}::AMQPMethod),
     {{/each ~}}
 }
@@ -143,6 +270,9 @@ impl AMQPClass {
     pub fn get_amqp_class_id(&self) -> Identifier {
         match self {
             {{#each protocol.classes as |class| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
             AMQPClass::{{camel class.name}}(_) => {{class.id}},
             {{/each ~}}
         }
@@ -153,23 +283,315 @@ impl AMQPClass {
         match self {
             {{#each protocol.classes as |class| ~}}
             {{#each class.methods as |method| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
             AMQPClass::{{camel class.name}}({{snake class.name}}::AMQPMethod::{{camel method.name}}(_)) => {{method.id}},
             {{/each ~}}
             {{/each ~}}
         }
     }
+
+    /// Get the compact identifier of the method (Generated)
+    pub fn id(&self) -> AMQPMethodId {
+        match self {
+            {{#each protocol.classes as |class| ~}}
+            {{#each class.methods as |method| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
+            AMQPClass::{{camel class.name}}({{snake class.name}}::AMQPMethod::{{camel method.name}}(_)) => AMQPMethodId::{{camel class.name}}{{camel method.name}},
+            {{/each ~}}
+            {{/each ~}}
+        }
+    }
+
+    /// Whether this method is followed by a content header and body frames (Generated)
+    pub fn is_content_carrying(&self) -> bool {
+        is_content_carrying(self.get_amqp_class_id(), self.get_amqp_method_id())
+    }
+
+    /// Build a method from its arguments and the names of its class and its own, as in the
+    /// specification (Generated)
+    pub fn from_arguments(class: &str, method: &str, arguments: &FieldTable) -> Result<Self, String> {
+        match (class, method) {
+            {{#each protocol.classes as |class| ~}}
+            {{#each class.methods as |method| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
+            ("{{class.name}}", "{{method.name}}") => {{snake class.name}}::{{camel method.name}}::from_arguments(arguments).map(|method| AMQPClass::{{camel class.name}}({{snake class.name}}::AMQPMethod::{{camel method.name}}(method))),
+            {{/each ~}}
+            {{/each ~}}
+            _ => Err(format!("Unknown method {}.{}", class, method)),
+        }
+    }
+
+    /// Get the arguments of a method from the names of its class and its own, as in the
+    /// specification (Generated)
+    pub fn arguments(class: &str, method: &str) -> Option<&'static [ArgumentDescriptor]> {
+        match (class, method) {
+            {{#each protocol.classes as |class| ~}}
+            {{#each class.methods as |method| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
+            ("{{class.name}}", "{{method.name}}") => Some({{snake class.name}}::{{camel method.name}}::ARGUMENTS),
+            {{/each ~}}
+            {{/each ~}}
+            _ => None,
+        }
+    }
+}
+
+/// The compact identifier of an AMQP method, a (class id, method id) pair which fits in a byte
+/// (Generated)
+#[derive(Clone, Copy{{#if @root.protocol.derives.debug}}, Debug{{/if}}, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AMQPMethodId {
+    {{#each protocol.classes as |class| ~}}
+    {{#each class.methods as |method| ~}}
+    /// {{class.name}}.{{method.name}} (Generated)
+    {{#if class.feature ~}}
+    #[cfg(feature = "{{class.feature}}")]
+    {{/if ~}}
+    {{camel class.name}}{{camel method.name}},
+    {{/each ~}}
+    {{/each ~}}
+}
+
+impl AMQPMethodId {
+    /// Get the identifier of the method with the given class and method ids (Generated)
+    pub fn from_ids(class_id: Identifier, method_id: Identifier) -> Option<Self> {
+        match (class_id, method_id) {
+            {{#each protocol.classes as |class| ~}}
+            {{#each class.methods as |method| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
+            ({{class.id}}, {{method.id}}) => Some(AMQPMethodId::{{camel class.name}}{{camel method.name}}),
+            {{/each ~}}
+            {{/each ~}}
+            _ => None,
+        }
+    }
+
+    /// Get the (class id, method id) pair of the method (Generated)
+    pub fn ids(self) -> (Identifier, Identifier) {
+        match self {
+            {{#each protocol.classes as |class| ~}}
+            {{#each class.methods as |method| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
+            AMQPMethodId::{{camel class.name}}{{camel method.name}} => ({{class.id}}, {{method.id}}),
+            {{/each ~}}
+            {{/each ~}}
+        }
+    }
+
+    /// Get the AMQP class id (Generated)
+    pub fn class_id(self) -> Identifier {
+        self.ids().0
+    }
+
+    /// Get the AMQP method id (Generated)
+    pub fn method_id(self) -> Identifier {
+        self.ids().1
+    }
+
+    /// Get the name of the method, as in the specification (Generated)
+    pub fn name(self) -> &'static str {
+        match self {
+            {{#each protocol.classes as |class| ~}}
+            {{#each class.methods as |method| ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
+            AMQPMethodId::{{camel class.name}}{{camel method.name}} => "{{class.name}}.{{method.name}}",
+            {{/each ~}}
+            {{/each ~}}
+        }
+    }
+}
+
+impl fmt::Display for AMQPMethodId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The (class id, method id) of the methods followed by a content header and body frames (Generated)
+pub const CONTENT_METHODS: &[(Identifier, Identifier)] = &[
+    {{#each protocol.classes as |class| ~}}
+    {{#each class.methods as |method| ~}}
+    {{#if method.content ~}}
+    ({{class.id}}, {{method.id}}),
+    {{/if ~}}
+    {{/each ~}}
+    {{/each ~}}
+];
+
+/// Whether the given method is followed by a content header and body frames (Generated)
+pub fn is_content_carrying(class_id: Identifier, method_id: Identifier) -> bool {
+    CONTENT_METHODS.contains(&(class_id, method_id))
+}
+{{#if protocol.server}}
+
+/// Helpers for the server side of the protocol (Generated)
+#[cfg(feature = "server")]
+pub mod server {
+    use super::*;
+
+    /// The channel_max proposed in connection.tune (Generated)
+    pub const DEFAULT_CHANNEL_MAX: ShortUInt = {{protocol.server.channel_max}};
+    /// The frame_max proposed in connection.tune (Generated)
+    pub const DEFAULT_FRAME_MAX: LongUInt = {{protocol.server.frame_max}};
+    /// The heartbeat proposed in connection.tune (Generated)
+    pub const DEFAULT_HEARTBEAT: ShortUInt = {{protocol.server.heartbeat}};
+
+    /// The (class id, method id) of the methods the server sends (Generated)
+    pub const SENT_METHODS: &[(Identifier, Identifier)] = &[
+        {{#each protocol.classes as |class| ~}}
+        {{#each class.methods as |method| ~}}
+        {{#if method.s2c ~}}
+        ({{class.id}}, {{method.id}}),
+        {{/if ~}}
+        {{/each ~}}
+        {{/each ~}}
+    ];
+
+    /// The (class id, method id) of the methods the server receives (Generated)
+    pub const RECEIVED_METHODS: &[(Identifier, Identifier)] = &[
+        {{#each protocol.classes as |class| ~}}
+        {{#each class.methods as |method| ~}}
+        {{#if method.c2s ~}}
+        ({{class.id}}, {{method.id}}),
+        {{/if ~}}
+        {{/each ~}}
+        {{/each ~}}
+    ];
+
+    /// The connection.tune proposing the default values (Generated)
+    pub fn default_tune() -> connection::Tune {
+        connection::Tune {
+            channel_max: DEFAULT_CHANNEL_MAX,
+            frame_max: DEFAULT_FRAME_MAX,
+            heartbeat: DEFAULT_HEARTBEAT,
+        }
+    }
+
+    /// Whether the server sends this method (Generated)
+    pub fn sends(method: &AMQPClass) -> bool {
+        SENT_METHODS.contains(&(method.get_amqp_class_id(), method.get_amqp_method_id()))
+    }
+
+    /// Whether the server receives this method (Generated)
+    pub fn receives(method: &AMQPClass) -> bool {
+        RECEIVED_METHODS.contains(&(method.get_amqp_class_id(), method.get_amqp_method_id()))
+    }
+
+    /// The reply the server sends to this method, if any, with the fields shared with the
+    /// request copied and the other ones to fill in (Generated)
+    pub fn reply(method: &AMQPClass) -> Option<AMQPClass> {
+        match method {
+            {{#each protocol.classes as |class| ~}}
+            {{#each class.methods as |method| ~}}
+            {{#if method.request ~}}
+            {{#if method.s2c ~}}
+            {{#if class.feature ~}}
+            #[cfg(feature = "{{class.feature}}")]
+            {{/if ~}}
+            AMQPClass::{{camel class.name}}({{snake class.name}}::AMQPMethod::{{camel method.request.name}}(request)) => Some(AMQPClass::{{camel class.name}}({{snake class.name}}::AMQPMethod::{{camel method.name}}(request.into()))),
+            {{/if ~}}
+            {{/if ~}}
+            {{/each ~}}
+            {{/each ~}}
+            _ => None,
+        }
+    }
+}
+{{/if}}
+
+/// Fuzz targets for the parsers, to be called from cargo-fuzz targets (Generated)
+///
+/// Each target parses arbitrary bytes and checks that whatever it accepts serializes and parses
+/// back.
+#[cfg(feature = "fuzzing")]
+pub mod fuzz {
+    use super::*;
+    use crate::frame::{gen_frame, parse_frame};
+
+    /// A fuzz target, taking arbitrary bytes (Generated)
+    pub type FuzzTarget = fn(&[u8]);
+
+    /// All the targets, by name (Generated)
+    pub const TARGETS: &[(&str, FuzzTarget)] = &[
+        ("frame", fuzz_frame),
+        {{#each protocol.classes as |class| ~}}
+        {{#if class.feature ~}}
+        #[cfg(feature = "{{class.feature}}")]
+        {{/if ~}}
+        ("{{class.name}}", fuzz_{{snake class.name false}}),
+        {{/each ~}}
+    ];
+
+    /// Fuzz the target picked by the first byte with the remaining ones (Generated)
+    pub fn fuzz_any(data: &[u8]) {
+        if let Some((target, data)) = data.split_first() {
+            (TARGETS[usize::from(*target) % TARGETS.len()].1)(data)
+        }
+    }
+
+    /// Fuzz the frame parser (Generated)
+    pub fn fuzz_frame(data: &[u8]) {
+        if let Ok((_, parsed)) = parse_frame(data) {
+            let bytes = cookie_factory::gen_simple(gen_frame(&parsed), Vec::new())
+                .expect("failed to serialize a parsed frame");
+            let (_, reparsed) = parse_frame(bytes.as_slice()).expect("failed to parse a serialized frame");
+            /* Compare the serialized forms, as a NaN float makes a value differ from itself */
+            let reserialized = cookie_factory::gen_simple(gen_frame(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed frame");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+    {{#each protocol.classes as |class|}}
+
+    /// Fuzz the parser of the methods of the {{class.name}} class, starting after the class id (Generated)
+    {{#if class.feature ~}}
+    #[cfg(feature = "{{class.feature}}")]
+    {{/if ~}}
+    pub fn fuzz_{{snake class.name false}}(data: &[u8]) {
+        if let Ok((_, method)) = {{snake class.name}}::parse_{{snake class.name false}}(data) {
+            let parsed = AMQPClass::{{camel class.name}}(method);
+            let bytes = cookie_factory::gen_simple(gen_class(&parsed), Vec::new())
+                .expect("failed to serialize a parsed {{class.name}} method");
+            let (_, reparsed) = parse_class(bytes.as_slice()).expect("failed to parse a serialized {{class.name}} method");
+            let reserialized = cookie_factory::gen_simple(gen_class(&reparsed), Vec::new())
+                .expect("failed to serialize a reparsed {{class.name}} method");
+            assert_eq!(reserialized, bytes);
+        }
+    }
+    {{/each ~}}
 }
 
 {{#each protocol.classes as |class|}}
 /// {{class.name}} (generated)
+{{#if class.feature ~}}
+#[cfg(feature = "{{class.feature}}")]
+{{/if ~}}
 pub mod {{snake class.name}} {
     use super::*;
 
     /// Parse {{class.name}} (Generated)
     pub fn parse_{{snake class.name false}}<I: ParsableInput>(i: I) -> ParserResult<I, {{snake class.name}}::AMQPMethod> {
-        context("parse_{{snake class.name false}}", map_opt(flat_map(parse_id, |id| move |i| match id {
+        parse_{{snake class.name false}}_with(i, ParsingOptions::default())
+    }
+
+    /// Parse {{class.name}}, parsing its field tables with the given options (Generated)
+    pub fn parse_{{snake class.name false}}_with<I: ParsableInput>(i: I, options: ParsingOptions) -> ParserResult<I, {{snake class.name}}::AMQPMethod> {
+        context("parse_{{snake class.name false}}", map_opt(flat_map(parse_id, move |id| move |i| match id {
             {{#each class.methods as |method| ~}}
-            {{method.id}} => context("parse_{{snake method.name false}}", map(map(parse_{{snake method.name false}}, AMQPMethod::{{camel method.name}}), Some)).parse(i),
+            {{method.id}} => context("parse_{{snake method.name false}}", map(map(|i: I| parse_{{snake method.name false}}_with(i, options), AMQPMethod::{{camel method.name}}), Some)).parse(i),
             {{/each ~}}
             _ => Ok((i, None)),
         }), std::convert::identity)).parse(i)
@@ -189,8 +611,23 @@ pub mod {{snake class.name}} {
         )
     }
 
+    impl Decode for AMQPMethod {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| map_opt(parse_class, |class| match class {
+                AMQPClass::{{camel class.name}}(method) => Some(method),
+                _ => None,
+            }).parse(i))
+        }
+    }
+
+    impl Encode for AMQPMethod {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_{{snake class.name false}}(self))
+        }
+    }
+
     /// The available methods in {{class.name}}
-    #[derive(Clone, Debug, PartialEq)]
+    #[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}}, PartialEq{{#each class.metadata.derives as |derive|}}, {{derive}}{{/each}})]
     pub enum AMQPMethod {
         {{#each class.methods as |method| ~}}
         /// {{method.name}} (Generated)
@@ -199,8 +636,8 @@ pub mod {{snake class.name}} {
     }
 
     {{#each class.methods as |method|}}
-    /// {{method.name}} (Generated)
-    #[derive(Clone, Debug, Default, PartialEq)]
+    {{#> method_struct}}/// {{method.name}} (Generated)
+    #[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}}, Default, PartialEq{{#each method.metadata.derives as |derive|}}, {{derive}}{{/each}})]
     pub struct {{camel method.name}} {
         {{#each_argument method.arguments as |argument| ~}}
         {{#if @argument_is_value ~}}
@@ -219,7 +656,9 @@ pub mod {{snake class.name}} {
         {{/unless ~}}
         {{/if ~}}
         {{/each_argument ~}}
-    }
+    }{{#each method.metadata.extra_impls as |extra_impl|}}
+
+    {{extra_impl}}{{/each}}{{/method_struct}}
 
     impl {{camel method.name}} {
         /// Get the AMQP class id for {{method.name}} (Generated)
@@ -231,13 +670,128 @@ pub mod {{snake class.name}} {
         pub fn get_amqp_method_id(&self) -> Identifier {
             {{method.id}}
         }
+
+        /// Whether {{method.name}} is followed by a content header and body frames (Generated)
+        pub fn is_content_carrying(&self) -> bool {
+            {{method.content}}
+        }
+
+        /// The arguments of {{method.name}}, in serialization order (Generated)
+        pub const ARGUMENTS: &'static [ArgumentDescriptor] = &[
+            {{#each_argument method.arguments as |argument| ~}}
+            {{#if @argument_is_value ~}}
+            {{#unless argument.force_default ~}}
+            ArgumentDescriptor {
+                name: "{{snake argument.name}}",
+                amqp_type: AMQPType::{{argument.type}},
+                default_value: {{#if argument.default_value ~}}Some(|| {{amqp_value_expr argument.default_value}}){{else}}None{{/if ~}},
+            },
+            {{/unless ~}}
+            {{else}}
+            {{#unless argument.ignore_flags ~}}
+            {{#each argument.flags as |flag| ~}}
+            {{#unless flag.force_default ~}}
+            ArgumentDescriptor {
+                name: "{{snake flag.name}}",
+                amqp_type: AMQPType::Boolean,
+                default_value: Some(|| AMQPValue::Boolean({{flag.default_value}})),
+            },
+            {{/unless ~}}
+            {{/each ~}}
+            {{/unless ~}}
+            {{/if ~}}
+            {{/each_argument ~}}
+        ];
+
+        /// Build {{method.name}} with the default values the specification gives its arguments (Generated)
+        pub fn spec_default() -> Self {
+            Self {
+                {{#each_argument method.arguments as |argument| ~}}
+                {{#if @argument_is_value ~}}
+                {{#unless argument.force_default ~}}
+                {{snake argument.name}}: {{#if argument.default_value ~}}{{amqp_field_expr argument.default_value}}{{else}}Default::default(){{/if ~}},
+                {{/unless ~}}
+                {{else}}
+                {{#unless argument.ignore_flags ~}}
+                {{#each argument.flags as |flag| ~}}
+                {{#unless flag.force_default ~}}
+                {{snake flag.name}}: {{flag.default_value}},
+                {{/unless ~}}
+                {{/each ~}}
+                {{/unless ~}}
+                {{/if ~}}
+                {{/each_argument ~}}
+            }
+        }
+
+        /// Build {{method.name}} from its arguments, the missing ones keeping the default value of the specification (Generated)
+        pub fn from_arguments(arguments: &FieldTable) -> Result<Self, String> {
+            {{#if method.ignore_args ~}}
+            if let Some(name) = arguments.inner().keys().next() {
+                return Err(format!("Unknown argument {} for {{class.name}}.{{method.name}}", name));
+            }
+            Ok(Self::spec_default())
+            {{else}}
+            let mut method = Self::spec_default();
+            for (name, value) in arguments.inner() {
+                match name.as_str() {
+                    {{#each_argument method.arguments as |argument| ~}}
+                    {{#if @argument_is_value ~}}
+                    {{#unless argument.force_default ~}}
+                    "{{snake argument.name}}" => method.{{snake argument.name}} = from_argument(name, value)?,
+                    {{/unless ~}}
+                    {{else}}
+                    {{#unless argument.ignore_flags ~}}
+                    {{#each argument.flags as |flag| ~}}
+                    {{#unless flag.force_default ~}}
+                    "{{snake flag.name}}" => method.{{snake flag.name}} = from_argument(name, value)?,
+                    {{/unless ~}}
+                    {{/each ~}}
+                    {{/unless ~}}
+                    {{/if ~}}
+                    {{/each_argument ~}}
+                    _ => return Err(format!("Unknown argument {} for {{class.name}}.{{method.name}}", name)),
+                }
+            }
+            Ok(method)
+            {{/if ~}}
+        }
+    }
+    {{#if method.request}}
+
+    impl From<&{{camel method.request.name}}> for {{camel method.name}} {
+        /// Build the reply to {{method.request.name}}, copying the fields they share (Generated)
+        fn from({{#if method.request.fields ~}}request{{else}}_{{/if ~}}: &{{camel method.request.name}}) -> Self {
+            {{#if method.request.fields ~}}
+            Self {
+                {{#each method.request.fields as |field| ~}}
+                {{snake field.name}}: request.{{snake field.name}}{{#if (pass_by_ref field.type) ~}}.clone(){{/if ~}},
+                {{/each ~}}
+                {{#if method.request.partial ~}}
+                ..Default::default()
+                {{/if ~}}
+            }
+            {{else}}
+            Self::default()
+            {{/if ~}}
+        }
     }
+    {{/if}}
 
     /// Parse {{method.name}} (Generated)
     pub fn parse_{{snake method.name false}}<I: ParsableInput>(i: I) -> ParserResult<I, {{camel method.name}}> {
+        parse_{{snake method.name false}}_with(i, ParsingOptions::default())
+    }
+
+    /// Parse {{method.name}}, parsing its field tables with the given options (Generated)
+    pub fn parse_{{snake method.name false}}_with<I: ParsableInput>(i: I, {{#unless method.has_field_tables ~}}_{{/unless ~}}options: ParsingOptions) -> ParserResult<I, {{camel method.name}}> {
         {{#each_argument method.arguments as |argument| ~}}
         {{#if @argument_is_value ~}}
+        {{#if (eq argument.type "FieldTable") ~}}
+        let (i, {{#if argument.force_default ~}}_{{else}}{{snake argument.name}}{{/if ~}}) = parse_field_table_with(i, options)?;
+        {{else}}
         let (i, {{#if argument.force_default ~}}_{{else}}{{snake argument.name}}{{/if ~}}) = parse_{{snake_type argument.type}}.parse(i)?;
+        {{/if ~}}
         {{else}}
         let (i, {{#if argument.ignore_flags ~}}_{{else}}flags{{/if ~}}) = parse_flags(i, &[
             {{#each argument.flags as |flag| ~}}
@@ -289,10 +843,26 @@ pub mod {{snake class.name}} {
             Ok(input)
         }
     }
+
+    impl Decode for {{camel method.name}} {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, |i| map_opt(parse_class, |class| match class {
+                AMQPClass::{{camel class.name}}(AMQPMethod::{{camel method.name}}(method)) => Some(method),
+                _ => None,
+            }).parse(i))
+        }
+    }
+
+    impl Encode for {{camel method.name}} {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, cookie_factory::sequence::pair(gen_id({{class.id}}), gen_{{snake method.name false}}(self)))
+        }
+    }
     {{/each ~}}
     {{#if class.properties ~}}
     /// {{class.name}} properties (Generated)
-    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}}, Default, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct AMQPProperties {
         {{#each class.properties as |property| ~}}
         {{snake property.name}}: Option<{{property.type}}>,
@@ -315,21 +885,73 @@ pub mod {{snake class.name}} {
         }
         {{/each ~}}
 
-        /// Get the bitmask for serialization (Generated)
-        #[allow(clippy::identity_op)]
+        {{#each class.properties as |property| ~}}
+        /// Get a mutable reference to {{property.name}} (Generated)
+        pub fn {{snake property.name false}}_mut(&mut self) -> &mut Option<{{property.type}}> {
+            &mut self.{{snake property.name}}
+        }
+        {{/each ~}}
+
+        /// Get the id of the class the properties belong to (Generated)
+        pub fn class_id(&self) -> Identifier {
+            {{class.id}}
+        }
+
+        /// Get the bitmask for serialization, same as [flags](#method.flags) (Generated)
         pub fn bitmask(&self) -> ShortUInt {
-            {{#each class.properties as |property| ~}}
-            (if self.{{snake property.name}}.is_some() { 1 << (15 - {{@index}}) } else { 0 }) {{#unless @last ~}} + {{/unless ~}}
-            {{/each ~}}
+            self.flags()
+        }
+
+        /// Get the property flags of the content header, telling which properties are set (Generated)
+        pub fn flags(&self) -> ShortUInt {
+            PROPERTY_FLAGS
+                .iter()
+                .zip([
+                    {{#each class.properties as |property| ~}}
+                    self.{{snake property.name}}.is_some(),
+                    {{/each ~}}
+                ])
+                .filter(|(_, is_set)| *is_set)
+                .fold(0, |flags, ((_, flag), _)| flags | flag)
+        }
+
+        /// Get the names of the properties set according to the property flags, in serialization order (Generated)
+        pub fn from_flags(flags: ShortUInt) -> Result<Vec<&'static str>, String> {
+            let known = PROPERTY_FLAGS.iter().fold(0, |known, (_, flag)| known | flag);
+            if flags & !known != 0 {
+                return Err(format!("Unknown {{class.name}} property flags: {:#018b}", flags & !known));
+            }
+            Ok(PROPERTY_FLAGS
+                .iter()
+                .filter(|(_, flag)| flags & flag != 0)
+                .map(|(name, _)| *name)
+                .collect())
         }
     }
 
-    /// Parse {{class.name}} properties (Generated)
+    /// The name and flag of each {{class.name}} property, in serialization order (Generated)
     #[allow(clippy::identity_op)]
+    pub const PROPERTY_FLAGS: &[(&str, ShortUInt)] = &[
+        {{#each class.properties as |property| ~}}
+        ("{{snake property.name}}", 1 << (15 - {{@index}})),
+        {{/each ~}}
+    ];
+
+    /// Parse {{class.name}} properties (Generated)
     pub fn parse_properties<I: ParsableInput>(i: I) -> ParserResult<I, AMQPProperties> {
+        parse_properties_with(i, ParsingOptions::default())
+    }
+
+    /// Parse {{class.name}} properties, parsing their field tables with the given options (Generated)
+    #[allow(clippy::identity_op)]
+    pub fn parse_properties_with<I: ParsableInput>(i: I, options: ParsingOptions) -> ParserResult<I, AMQPProperties> {
         let (i, flags) = parse_short_uint(i)?;
         {{#each class.properties as |property| ~}}
+        {{#if (eq property.type "FieldTable") ~}}
+        let (i, {{snake property.name}}) = if flags & (1 << (15 - {{@index}})) != 0 { map(|i: I| parse_field_table_with(i, options), Some).parse(i)? } else { (i, None) };
+        {{else}}
         let (i, {{snake property.name}}) = if flags & (1 << (15 - {{@index}})) != 0 { map(parse_{{snake_type property.type}}, Some).parse(i)? } else { (i, None) };
+        {{/if ~}}
         {{/each ~}}
         Ok((i, AMQPProperties {
             {{#each class.properties as |property| ~}}
@@ -352,6 +974,114 @@ pub mod {{snake class.name}} {
             }
         )
     }
+
+    impl Decode for AMQPProperties {
+        fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+            decode_with(bytes, parse_properties)
+        }
+    }
+
+    impl Encode for AMQPProperties {
+        fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+            encode_with(buffer, gen_properties(self))
+        }
+    }
+
+    /// {{class.name}} properties whose field tables are kept encoded, to decode them on demand (Generated)
+    #[derive(Clone{{#if @root.protocol.derives.debug}}, Debug{{/if}}, Default, PartialEq)]
+    pub struct LazyProperties {
+        /// The properties, without their field tables (Generated)
+        pub properties: AMQPProperties,
+        {{#each class.properties as |property| ~}}
+        {{#if (eq property.type "FieldTable") ~}}
+        /// {{property.name}}, kept encoded (Generated)
+        pub {{snake property.name}}: Option<LazyFieldTable>,
+        {{/if ~}}
+        {{/each ~}}
+    }
+
+    impl LazyProperties {
+        /// Decode the field tables, making regular properties (Generated)
+        pub fn decode(self) -> Result<AMQPProperties, String> {
+            let mut properties = self.properties;
+            {{#each class.properties as |property| ~}}
+            {{#if (eq property.type "FieldTable") ~}}
+            properties.{{snake property.name}} = self.{{snake property.name}}.map(|table| table.decode()).transpose()?;
+            {{/if ~}}
+            {{/each ~}}
+            Ok(properties)
+        }
+    }
+
+    /// Parse {{class.name}} properties, keeping their field tables encoded (Generated)
+    #[allow(clippy::identity_op)]
+    pub fn parse_lazy_properties<I: ParsableInput>(i: I) -> ParserResult<I, LazyProperties> {
+        let (i, flags) = parse_short_uint(i)?;
+        {{#each class.properties as |property| ~}}
+        {{#if (eq property.type "FieldTable") ~}}
+        let (i, {{snake property.name}}) = if flags & (1 << (15 - {{@index}})) != 0 { map(parse_lazy_field_table, Some).parse(i)? } else { (i, None) };
+        {{else}}
+        let (i, {{snake property.name}}) = if flags & (1 << (15 - {{@index}})) != 0 { map(parse_{{snake_type property.type}}, Some).parse(i)? } else { (i, None) };
+        {{/if ~}}
+        {{/each ~}}
+        Ok((i, LazyProperties {
+            properties: AMQPProperties {
+                {{#each class.properties as |property| ~}}
+                {{snake property.name}}{{#if (eq property.type "FieldTable")}}: None{{/if}},
+                {{/each ~}}
+            },
+            {{#each class.properties as |property| ~}}
+            {{#if (eq property.type "FieldTable") ~}}
+            {{snake property.name}},
+            {{/if ~}}
+            {{/each ~}}
+        }))
+    }
+
+    /// {{class.name}} properties whose strings and field tables are allocated in an arena (Generated)
+    #[cfg(feature = "arena")]
+    #[derive(Clone, Copy{{#if @root.protocol.derives.debug}}, Debug{{/if}}, Default, PartialEq)]
+    pub struct ArenaProperties<'a> {
+        {{#each class.properties as |property| ~}}
+        /// {{property.name}} (Generated)
+        pub {{snake property.name}}: Option<{{#if (eq property.type "ShortString")}}&'a str{{else}}{{#if (eq property.type "LongString")}}&'a [u8]{{else}}{{#if (eq property.type "FieldTable")}}ArenaTable<'a>{{else}}{{property.type}}{{/if}}{{/if}}{{/if}}>,
+        {{/each ~}}
+    }
+
+    #[cfg(feature = "arena")]
+    impl ArenaProperties<'_> {
+        /// Copy the properties out of the arena (Generated)
+        pub fn to_properties(&self) -> AMQPProperties {
+            AMQPProperties {
+                {{#each class.properties as |property| ~}}
+                {{snake property.name}}: self.{{snake property.name}}{{#if (eq property.type "FieldTable")}}.map(|table| table.to_field_table()){{else}}{{#if (use_str_ref property.type)}}.map(Into::into){{/if}}{{/if}},
+                {{/each ~}}
+            }
+        }
+    }
+
+    /// Parse {{class.name}} properties, allocating their strings and field tables in the given arena (Generated)
+    #[cfg(feature = "arena")]
+    #[allow(clippy::identity_op)]
+    pub fn parse_properties_in<'a, 'i>(arena: &'a Bump, i: &'i [u8], options: ParsingOptions) -> ParserResult<&'i [u8], ArenaProperties<'a>> {
+        let (i, flags) = parse_short_uint(i)?;
+        {{#each class.properties as |property| ~}}
+        {{#if (eq property.type "FieldTable") ~}}
+        let (i, {{snake property.name}}) = if flags & (1 << (15 - {{@index}})) != 0 { map(|i: &'i [u8]| parse_field_table_in_with(arena, i, options), Some).parse(i)? } else { (i, None) };
+        {{else}}
+        {{#if (use_str_ref property.type) ~}}
+        let (i, {{snake property.name}}) = if flags & (1 << (15 - {{@index}})) != 0 { map(|i: &'i [u8]| parse_{{snake_type property.type}}_in(arena, i), Some).parse(i)? } else { (i, None) };
+        {{else}}
+        let (i, {{snake property.name}}) = if flags & (1 << (15 - {{@index}})) != 0 { map(parse_{{snake_type property.type}}, Some).parse(i)? } else { (i, None) };
+        {{/if ~}}
+        {{/if ~}}
+        {{/each ~}}
+        Ok((i, ArenaProperties {
+            {{#each class.properties as |property| ~}}
+            {{snake property.name}},
+            {{/each ~}}
+        }))
+    }
     {{/if ~}}
 }
 {{/each ~}}
@@ -0,0 +1,31 @@
+use amq_protocol::differential::{check_reference_encodings, REFERENCE_ENCODINGS_DIR};
+use std::path::{Path, PathBuf};
+
+/* Check every encoding of the directory, returning how many there were */
+fn check_dir(dir: &Path) -> usize {
+    let report = check_reference_encodings(dir).unwrap();
+    for (name, err) in &report.failed {
+        eprintln!("{}: {}", name, err);
+    }
+    assert!(
+        report.is_success(),
+        "{} reference encodings failed, {} passed",
+        report.failed.len(),
+        report.passed.len()
+    );
+    report.passed.len()
+}
+
+#[test]
+fn test_reference_encodings() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/reference_encodings");
+    assert_ne!(check_dir(&dir), 0, "no reference encodings found");
+}
+
+/* Additional encodings, such as a fresh capture from the Java or Erlang clients */
+#[test]
+fn test_external_reference_encodings() {
+    if let Some(dir) = std::env::var_os(REFERENCE_ENCODINGS_DIR) {
+        check_dir(&PathBuf::from(dir));
+    }
+}
@@ -0,0 +1,126 @@
+use amq_protocol::{
+    frame::{gen_frame, parse_frame, AMQPContentHeader, AMQPFrame, Bytes},
+    protocol::{basic, AMQPClass},
+    types::{AMQPValue, FieldTable, ShortString},
+};
+use proptest::{collection, option, prelude::*};
+
+fn short_string() -> impl Strategy<Value = ShortString> {
+    "[a-zA-Z0-9._-]{0,64}".prop_map(ShortString::from)
+}
+
+fn field_table() -> impl Strategy<Value = FieldTable> {
+    let value = prop_oneof![
+        any::<bool>().prop_map(AMQPValue::Boolean),
+        any::<i32>().prop_map(AMQPValue::LongInt),
+        any::<i64>().prop_map(AMQPValue::LongLongInt),
+        "[ -~]{0,64}".prop_map(|s| AMQPValue::LongString(s.into())),
+    ];
+    collection::vec((short_string(), value), 0..8).prop_map(|entries| {
+        let mut table = FieldTable::default();
+        for (key, value) in entries {
+            table.insert(key, value);
+        }
+        table
+    })
+}
+
+fn properties() -> impl Strategy<Value = basic::AMQPProperties> {
+    (
+        option::of(short_string()),
+        option::of(field_table()),
+        option::of(any::<u8>()),
+        option::of(any::<u8>()),
+        option::of(short_string()),
+        option::of(any::<u64>()),
+    )
+        .prop_map(
+            |(content_type, headers, delivery_mode, priority, message_id, timestamp)| {
+                let mut properties = basic::AMQPProperties::default();
+                *properties.content_type_mut() = content_type;
+                *properties.headers_mut() = headers;
+                *properties.delivery_mode_mut() = delivery_mode;
+                *properties.priority_mut() = priority;
+                *properties.message_id_mut() = message_id;
+                *properties.timestamp_mut() = timestamp;
+                properties
+            },
+        )
+}
+
+fn method() -> impl Strategy<Value = AMQPClass> {
+    prop_oneof![
+        (short_string(), short_string(), any::<bool>(), any::<bool>()).prop_map(
+            |(exchange, routing_key, mandatory, immediate)| {
+                AMQPClass::Basic(basic::AMQPMethod::Publish(basic::Publish {
+                    exchange,
+                    routing_key,
+                    mandatory,
+                    immediate,
+                }))
+            }
+        ),
+        (any::<u16>(), any::<bool>()).prop_map(|(prefetch_count, global)| {
+            AMQPClass::Basic(basic::AMQPMethod::Qos(basic::Qos {
+                prefetch_count,
+                global,
+            }))
+        }),
+        (any::<u64>(), any::<bool>()).prop_map(|(delivery_tag, multiple)| {
+            AMQPClass::Basic(basic::AMQPMethod::Ack(basic::Ack {
+                delivery_tag,
+                multiple,
+            }))
+        }),
+        (
+            short_string(),
+            any::<u64>(),
+            any::<bool>(),
+            short_string(),
+            short_string()
+        )
+            .prop_map(
+                |(consumer_tag, delivery_tag, redelivered, exchange, routing_key)| {
+                    AMQPClass::Basic(basic::AMQPMethod::Deliver(basic::Deliver {
+                        consumer_tag,
+                        delivery_tag,
+                        redelivered,
+                        exchange,
+                        routing_key,
+                    }))
+                }
+            ),
+    ]
+}
+
+fn frame() -> impl Strategy<Value = AMQPFrame> {
+    prop_oneof![
+        (1..=u16::MAX, method())
+            .prop_map(|(channel_id, method)| AMQPFrame::Method(channel_id, method)),
+        (1..=u16::MAX, any::<u64>(), properties()).prop_map(
+            |(channel_id, body_size, properties)| AMQPFrame::Header(
+                channel_id,
+                60,
+                Box::new(AMQPContentHeader {
+                    class_id: 60,
+                    body_size,
+                    properties,
+                })
+            )
+        ),
+        (1..=u16::MAX, collection::vec(any::<u8>(), 0..512))
+            .prop_map(|(channel_id, body)| AMQPFrame::Body(channel_id, Bytes::from(body))),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_frame_roundtrip(frame in frame()) {
+        let bytes = cookie_factory::gen_simple(gen_frame(&frame), Vec::new()).unwrap();
+        let (rest, parsed) = parse_frame(&bytes[..]).unwrap();
+        prop_assert!(rest.is_empty());
+        prop_assert_eq!(&parsed, &frame);
+        let regenerated = cookie_factory::gen_simple(gen_frame(&parsed), Vec::new()).unwrap();
+        prop_assert_eq!(regenerated, bytes);
+    }
+}
@@ -4,7 +4,7 @@ main() {
     export AMQ_PROTOCOL_CODEGEN_DIR="$(dirname "${0}" | xargs realpath)/protocol/src/"
     export AMQ_PROTOCOL_CODEGEN_FILE="generated"
 
-    cargo build --manifest-path protocol/Cargo.toml --features=codegen-internal
+    cargo build --manifest-path protocol/Cargo.toml --features=codegen-internal,server
     rustfmt --edition=2021 "${AMQ_PROTOCOL_CODEGEN_DIR}/${AMQ_PROTOCOL_CODEGEN_FILE}.rs"
 }
 
@@ -0,0 +1,215 @@
+use crate::{HandshakeResult, TcpStream};
+use std::io;
+
+#[cfg(feature = "native-tls")]
+use crate::NativeTlsConnector;
+
+#[cfg(feature = "openssl")]
+use crate::OpenSslConnector;
+
+#[cfg(feature = "rustls-common")]
+use crate::RustlsConnector;
+
+/// The settings of the TLS sessions opened by an AMQPTlsConnector
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AMQPTlsSessionConfig {
+    /// The protocols offered through ALPN, by order of preference. None are offered when empty
+    pub alpn_protocols: Vec<String>,
+    /// The number of sessions kept to resume them when reconnecting with the same connector,
+    /// 0 disabling session resumption. Only rustls resumes sessions, the other backends ignore it
+    pub session_cache_size: usize,
+}
+
+impl Default for AMQPTlsSessionConfig {
+    fn default() -> Self {
+        Self {
+            alpn_protocols: Vec::new(),
+            session_cache_size: 256,
+        }
+    }
+}
+
+impl AMQPTlsSessionConfig {
+    /// The ALPN protocols in the wire format, each of them prefixed with its length
+    pub fn alpn_wire_format(&self) -> io::Result<Vec<u8>> {
+        let mut wire = Vec::new();
+        for protocol in &self.alpn_protocols {
+            let len = u8::try_from(protocol.len())
+                .ok()
+                .filter(|len| *len > 0)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Invalid ALPN protocol length: {}", protocol.len()),
+                    )
+                })?;
+            wire.push(len);
+            wire.extend_from_slice(protocol.as_bytes());
+        }
+        Ok(wire)
+    }
+}
+
+/// A TLS connector, built once by the caller and shared between connections
+///
+/// Reusing the same connector across reconnections lets the TLS backend reuse its session
+/// cache to resume sessions and reduce the handshake latency. Build it with the constructor of
+/// the backend to apply an AMQPTlsSessionConfig, or convert an already configured connector.
+pub enum AMQPTlsConnector {
+    /// A native-tls connector
+    #[cfg(feature = "native-tls")]
+    NativeTls(NativeTlsConnector),
+    /// An openssl connector
+    #[cfg(feature = "openssl")]
+    OpenSsl(OpenSslConnector),
+    /// A rustls connector
+    #[cfg(feature = "rustls-common")]
+    Rustls(RustlsConnector),
+}
+
+impl AMQPTlsConnector {
+    /// Build a rustls connector from the given configuration, applying the ALPN protocols and the
+    /// size of the session cache
+    #[cfg(feature = "rustls-common")]
+    pub fn rustls(mut config: rustls::ClientConfig, session: &AMQPTlsSessionConfig) -> Self {
+        configure_rustls(&mut config, session);
+        AMQPTlsConnector::Rustls(config.into())
+    }
+
+    /// Build an openssl connector from the given builder, applying the ALPN protocols
+    #[cfg(feature = "openssl")]
+    pub fn openssl(
+        mut builder: openssl::ssl::SslConnectorBuilder,
+        session: &AMQPTlsSessionConfig,
+    ) -> io::Result<Self> {
+        if !session.alpn_protocols.is_empty() {
+            builder
+                .set_alpn_protos(&session.alpn_wire_format()?)
+                .map_err(io::Error::other)?;
+        }
+        Ok(AMQPTlsConnector::OpenSsl(builder.build()))
+    }
+
+    /// Build a native-tls connector from the given builder, applying the ALPN protocols
+    #[cfg(feature = "native-tls")]
+    pub fn native_tls(
+        builder: &mut native_tls::TlsConnectorBuilder,
+        session: &AMQPTlsSessionConfig,
+    ) -> io::Result<Self> {
+        let protocols = session
+            .alpn_protocols
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        builder.request_alpns(&protocols);
+        Ok(AMQPTlsConnector::NativeTls(
+            builder.build().map_err(io::Error::other)?,
+        ))
+    }
+
+    /// Enable TLS on the given stream, using domain for SNI and certificate verification
+    #[cfg_attr(
+        not(any(feature = "native-tls", feature = "openssl", feature = "rustls-common")),
+        allow(unused_variables)
+    )]
+    pub fn connect(&self, stream: TcpStream, domain: &str) -> HandshakeResult {
+        match *self {
+            #[cfg(feature = "native-tls")]
+            AMQPTlsConnector::NativeTls(ref connector) => stream.into_native_tls(connector, domain),
+            #[cfg(feature = "openssl")]
+            AMQPTlsConnector::OpenSsl(ref connector) => stream.into_openssl(connector, domain),
+            #[cfg(feature = "rustls-common")]
+            AMQPTlsConnector::Rustls(ref connector) => stream.into_rustls(connector, domain),
+        }
+    }
+}
+
+#[cfg(feature = "rustls-common")]
+fn configure_rustls(config: &mut rustls::ClientConfig, session: &AMQPTlsSessionConfig) {
+    config.alpn_protocols = session
+        .alpn_protocols
+        .iter()
+        .map(|protocol| protocol.as_bytes().to_vec())
+        .collect();
+    config.resumption = match session.session_cache_size {
+        0 => rustls::client::Resumption::disabled(),
+        size => rustls::client::Resumption::in_memory_sessions(size),
+    };
+}
+
+#[cfg(feature = "native-tls")]
+impl From<NativeTlsConnector> for AMQPTlsConnector {
+    fn from(connector: NativeTlsConnector) -> Self {
+        AMQPTlsConnector::NativeTls(connector)
+    }
+}
+
+#[cfg(feature = "openssl")]
+impl From<OpenSslConnector> for AMQPTlsConnector {
+    fn from(connector: OpenSslConnector) -> Self {
+        AMQPTlsConnector::OpenSsl(connector)
+    }
+}
+
+#[cfg(feature = "rustls-common")]
+impl From<RustlsConnector> for AMQPTlsConnector {
+    fn from(connector: RustlsConnector) -> Self {
+        AMQPTlsConnector::Rustls(connector)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alpn_wire_format() {
+        let session = AMQPTlsSessionConfig {
+            alpn_protocols: vec!["amqp".to_string(), "h2".to_string()],
+            ..AMQPTlsSessionConfig::default()
+        };
+        assert_eq!(
+            session.alpn_wire_format().unwrap(),
+            b"\x04amqp\x02h2".to_vec()
+        );
+        let session = AMQPTlsSessionConfig {
+            alpn_protocols: vec![String::new()],
+            ..AMQPTlsSessionConfig::default()
+        };
+        assert_eq!(
+            session.alpn_wire_format().unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        let session = AMQPTlsSessionConfig {
+            alpn_protocols: vec!["a".repeat(256)],
+            ..AMQPTlsSessionConfig::default()
+        };
+        assert!(session.alpn_wire_format().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rustls-common")]
+    fn test_configure_rustls() {
+        let mut config = rustls::ClientConfig::builder()
+            .with_root_certificates(rustls::RootCertStore::empty())
+            .with_no_client_auth();
+        configure_rustls(
+            &mut config,
+            &AMQPTlsSessionConfig {
+                alpn_protocols: vec!["amqp".to_string()],
+                ..AMQPTlsSessionConfig::default()
+            },
+        );
+        assert_eq!(config.alpn_protocols, vec![b"amqp".to_vec()]);
+        assert!(format!("{:?}", config.resumption).contains("ClientSessionMemoryCache"));
+        configure_rustls(
+            &mut config,
+            &AMQPTlsSessionConfig {
+                session_cache_size: 0,
+                ..AMQPTlsSessionConfig::default()
+            },
+        );
+        assert!(config.alpn_protocols.is_empty());
+        assert!(format!("{:?}", config.resumption).contains("NoClientSessionStorage"));
+    }
+}
@@ -5,8 +5,17 @@
 //! amq-protocol-tcp is a library aiming at providing tools to help
 //! connecting to an AMQP URI
 
-use amq_protocol_uri::{AMQPScheme, AMQPUri};
-use std::time::Duration;
+mod connector;
+mod memory;
+mod reconnect;
+mod resolver;
+mod stream;
+mod tls;
+
+pub use crate::{connector::*, memory::*, reconnect::*, resolver::*, stream::*, tls::*};
+
+use amq_protocol_uri::{AMQPScheme, AMQPTlsConfig, AMQPTlsVerify, AMQPUri};
+use std::{fs, io, time::Duration};
 use tracing::trace;
 
 /// Re-export TcpStream
@@ -35,23 +44,187 @@ pub trait AMQPUriTcpExt {
     }
 
     /// connect to a TcpStream with the given configuration
-    fn connect_with_config(&self, config: TLSConfig<'_, '_, '_>) -> HandshakeResult;
+    ///
+    /// For AMQPUri, the TLS settings from the query string are used to fill in the ones which
+    /// are missing from the given configuration.
+    fn connect_with_config(&self, config: TLSConfig<'_, '_, '_>) -> HandshakeResult {
+        self.connect_with_resolver(config, &SystemResolver)
+    }
+
+    /// connect to a TcpStream with the given configuration, using the given resolver to get the
+    /// addresses to connect to.
+    ///
+    /// The resolved addresses are only used to open the TCP connection: for AMQPUri, SNI and the
+    /// verification of the server certificate use the server_name_indication from the query
+    /// string, or the host from the uri when it is not set.
+    fn connect_with_resolver(
+        &self,
+        config: TLSConfig<'_, '_, '_>,
+        resolver: &dyn Resolver,
+    ) -> HandshakeResult;
+
+    /// connect to a TcpStream using the given TLS connector for encrypted connections, which
+    /// can be reused between connections for TLS session resumption.
+    /// The TLS files settings from the uri are ignored as the connector is already configured.
+    fn connect_with_connector(
+        &self,
+        connector: &AMQPTlsConnector,
+        resolver: &dyn Resolver,
+    ) -> HandshakeResult;
 }
 
 impl AMQPUriTcpExt for AMQPUri {
-    fn connect_with_config(&self, config: TLSConfig<'_, '_, '_>) -> HandshakeResult {
-        let uri = format!("{}:{}", self.authority.host, self.authority.port);
-        trace!(uri = %uri, "Connecting.");
-        let stream = if let Some(timeout) = self.query.connection_timeout {
-            TcpStream::connect_timeout(uri, Duration::from_millis(timeout))
+    fn connect_with_resolver(
+        &self,
+        config: TLSConfig<'_, '_, '_>,
+        resolver: &dyn Resolver,
+    ) -> HandshakeResult {
+        let stream = connect_plain(self, resolver)?;
+        let stream = if self.scheme.is_tls() {
+            let uri_config = load_tls_config(&self.query.tls)?;
+            let config = TLSConfig {
+                identity: config
+                    .identity
+                    .or_else(|| uri_config.identity.as_ref().map(OwnedIdentity::as_ref)),
+                cert_chain: config.cert_chain.or(uri_config.cert_chain.as_deref()),
+            };
+            trace!(domain = tls_domain(self), "Starting TLS handshake.");
+            stream
+                .into_tls(tls_domain(self), config)
+                .map_err(|err| classify_handshake_error(tls_domain(self), err))?
         } else {
-            TcpStream::connect(uri)
-        }?;
-        let stream = match self.scheme {
-            AMQPScheme::AMQP => stream,
-            AMQPScheme::AMQPS => stream.into_tls(&self.authority.host, config)?,
+            stream
         };
+        trace!(host = %self.authority.host, "Connected.");
         stream.set_nonblocking(true)?;
         Ok(stream)
     }
+
+    fn connect_with_connector(
+        &self,
+        connector: &AMQPTlsConnector,
+        resolver: &dyn Resolver,
+    ) -> HandshakeResult {
+        let stream = connect_plain(self, resolver)?;
+        let stream = if self.scheme.is_tls() {
+            trace!(
+                domain = tls_domain(self),
+                "Starting TLS handshake with connector."
+            );
+            connector
+                .connect(stream, tls_domain(self))
+                .map_err(|err| classify_handshake_error(tls_domain(self), err))?
+        } else {
+            stream
+        };
+        trace!(host = %self.authority.host, "Connected.");
+        stream.set_nonblocking(true)?;
+        Ok(stream)
+    }
+}
+
+fn connect_plain(uri: &AMQPUri, resolver: &dyn Resolver) -> io::Result<TcpStream> {
+    if !matches!(uri.scheme, AMQPScheme::AMQP | AMQPScheme::AMQPS) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{} is not supported over TCP", uri.scheme),
+        ));
+    }
+    let addrs = resolver.resolve(&uri.authority.host, uri.authority.port)?;
+    trace!(host = %uri.authority.host, port = uri.authority.port, addrs = ?addrs, "Connecting.");
+    if let Some(timeout) = uri.query.connection_timeout {
+        TcpStream::connect_timeout(&addrs[..], Duration::from_millis(timeout))
+    } else {
+        TcpStream::connect(&addrs[..])
+    }
+}
+
+fn tls_domain(uri: &AMQPUri) -> &str {
+    uri.query
+        .tls
+        .server_name_indication
+        .as_deref()
+        .unwrap_or(&uri.authority.host)
+}
+
+/// Load the files referenced by the TLS settings of an AMQPUri
+///
+/// The client identity is either the PEM certificate chain of certfile along with the PKCS#8
+/// PEM key of keyfile, or the PKCS#12 archive of pkcs12file, optionally protected by the
+/// password. Disabling the verification of the server certificate is not supported by the
+/// underlying TLS connectors.
+pub fn load_tls_config(tls: &AMQPTlsConfig) -> io::Result<OwnedTLSConfig> {
+    if tls.verify == Some(AMQPTlsVerify::VerifyNone) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "verify_none is not supported",
+        ));
+    }
+    let cert_chain = tls
+        .cacertfile
+        .as_ref()
+        .map(fs::read_to_string)
+        .transpose()?;
+    let identity = match (&tls.certfile, &tls.keyfile, &tls.pkcs12file) {
+        (None, None, None) => None,
+        (Some(certfile), Some(keyfile), None) => Some(OwnedIdentity::PKCS8 {
+            pem: fs::read(certfile)?,
+            key: fs::read(keyfile)?,
+        }),
+        (None, None, Some(pkcs12file)) => Some(OwnedIdentity::PKCS12 {
+            der: fs::read(pkcs12file)?,
+            password: tls.password.clone().unwrap_or_default(),
+        }),
+        (_, _, Some(_)) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "pkcs12file cannot be used along with certfile or keyfile",
+            ))
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "certfile and keyfile must be used together",
+            ))
+        }
+    };
+    Ok(OwnedTLSConfig {
+        identity,
+        cert_chain,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_tls_config_identity() {
+        let config = |certfile: Option<&str>, keyfile: Option<&str>, pkcs12file: Option<&str>| {
+            load_tls_config(&AMQPTlsConfig {
+                certfile: certfile.map(ToString::to_string),
+                keyfile: keyfile.map(ToString::to_string),
+                pkcs12file: pkcs12file.map(ToString::to_string),
+                ..AMQPTlsConfig::default()
+            })
+            .map_err(|err| err.kind())
+        };
+        assert_eq!(config(None, None, None), Ok(OwnedTLSConfig::default()));
+        assert_eq!(
+            config(Some("client.pem"), None, None),
+            Err(io::ErrorKind::InvalidInput)
+        );
+        assert_eq!(
+            config(None, Some("client.key"), None),
+            Err(io::ErrorKind::InvalidInput)
+        );
+        assert_eq!(
+            config(Some("client.pem"), Some("client.key"), Some("client.p12")),
+            Err(io::ErrorKind::InvalidInput)
+        );
+        assert_eq!(
+            config(Some("/nonexistent/client.pem"), Some("client.key"), None),
+            Err(io::ErrorKind::NotFound)
+        );
+    }
 }
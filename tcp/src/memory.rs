@@ -0,0 +1,214 @@
+use crate::AMQPStream;
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    net::Shutdown,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex, MutexGuard,
+    },
+    time::Duration,
+};
+
+/// One end of an in-memory connection, to test protocol logic without network
+///
+/// Like a TcpStream, reads block until data is available unless the stream is in nonblocking
+/// mode, and return 0 once the other end has been dropped and everything has been read.
+/// It implements AMQPStream so it can be used wherever a connected TcpStream is expected.
+#[derive(Debug)]
+pub struct MemoryStream {
+    incoming: Arc<Pipe>,
+    outgoing: Arc<Pipe>,
+    nonblocking: AtomicBool,
+    read_timeout: Mutex<Option<Duration>>,
+}
+
+#[derive(Debug, Default)]
+struct Pipe {
+    state: Mutex<PipeState>,
+    readable: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct PipeState {
+    data: VecDeque<u8>,
+    closed: bool,
+}
+
+impl Pipe {
+    fn state(&self) -> MutexGuard<'_, PipeState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn close(&self) {
+        self.state().closed = true;
+        self.readable.notify_all();
+    }
+}
+
+impl MemoryStream {
+    /// Create the two connected ends of an in-memory connection
+    pub fn pair() -> (MemoryStream, MemoryStream) {
+        let first = Arc::new(Pipe::default());
+        let second = Arc::new(Pipe::default());
+        (
+            Self::new(first.clone(), second.clone()),
+            Self::new(second, first),
+        )
+    }
+
+    fn new(incoming: Arc<Pipe>, outgoing: Arc<Pipe>) -> Self {
+        Self {
+            incoming,
+            outgoing,
+            nonblocking: AtomicBool::new(false),
+            read_timeout: Mutex::new(None),
+        }
+    }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        *self.read_timeout.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl AMQPStream for MemoryStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.nonblocking.store(nonblocking, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        *self.read_timeout.lock().unwrap_or_else(|e| e.into_inner()) = timeout;
+        Ok(())
+    }
+
+    /// Closing the writing half makes the other end read 0 once it has read everything
+    fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        if how != Shutdown::Write {
+            self.incoming.close();
+        }
+        if how != Shutdown::Read {
+            self.outgoing.close();
+        }
+        Ok(())
+    }
+}
+
+impl Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let read_timeout = self.read_timeout();
+        let mut state = self.incoming.state();
+        while state.data.is_empty() && !state.closed {
+            if self.nonblocking.load(Ordering::Relaxed) {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            state = match read_timeout {
+                Some(timeout) => {
+                    let (state, res) = self
+                        .incoming
+                        .readable
+                        .wait_timeout(state, timeout)
+                        .unwrap_or_else(|e| e.into_inner());
+                    if res.timed_out() && state.data.is_empty() && !state.closed {
+                        return Err(io::ErrorKind::TimedOut.into());
+                    }
+                    state
+                }
+                None => self
+                    .incoming
+                    .readable
+                    .wait(state)
+                    .unwrap_or_else(|e| e.into_inner()),
+            };
+        }
+        let len = buf.len().min(state.data.len());
+        for (dst, src) in buf.iter_mut().zip(state.data.drain(..len)) {
+            *dst = src;
+        }
+        Ok(len)
+    }
+}
+
+impl Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.outgoing.state();
+        if state.closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        state.data.extend(buf);
+        self.outgoing.readable.notify_all();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for MemoryStream {
+    fn drop(&mut self) {
+        self.outgoing.close();
+        self.incoming.close();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_memory_stream() {
+        let (mut client, mut server) = MemoryStream::pair();
+        client.write_all(b"hello").unwrap();
+        let mut buf = [0; 16];
+        assert_eq!(server.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf[..5], b"hello");
+
+        server.set_nonblocking(true).unwrap();
+        assert_eq!(
+            server.read(&mut buf).unwrap_err().kind(),
+            io::ErrorKind::WouldBlock
+        );
+        server.set_nonblocking(false).unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_millis(10)))
+            .unwrap();
+        assert_eq!(
+            server.read(&mut buf).unwrap_err().kind(),
+            io::ErrorKind::TimedOut
+        );
+
+        let writer = std::thread::spawn(move || {
+            client.write_all(b"world").unwrap();
+        });
+        server.set_read_timeout(None).unwrap();
+        let mut received = Vec::new();
+        server.read_to_end(&mut received).unwrap();
+        writer.join().unwrap();
+        assert_eq!(received, b"world");
+        assert_eq!(
+            server.write(b"closed").unwrap_err().kind(),
+            io::ErrorKind::BrokenPipe
+        );
+    }
+
+    fn exchange<S: AMQPStream>(mut client: S, mut server: S) -> Vec<u8> {
+        server
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        client.write_all(b"AMQP\x00\x00\x09\x01").unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+        let mut received = Vec::new();
+        server.read_to_end(&mut received).unwrap();
+        received
+    }
+
+    #[test]
+    fn test_memory_stream_as_amqp_stream() {
+        let (client, server) = MemoryStream::pair();
+        assert_eq!(exchange(client, server), b"AMQP\x00\x00\x09\x01");
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{AMQPUriTcpExt, HandshakeResult};
+use amq_protocol_uri::AMQPUri;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    thread,
+    time::Duration,
+};
+use tracing::trace;
+
+/// Exponential backoff settings
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Backoff {
+    /// The delay before the first retry
+    pub initial: Duration,
+    /// The maximum delay between two attempts
+    pub max: Duration,
+    /// The factor applied to the delay after each failed attempt
+    pub multiplier: f64,
+    /// The fraction (between 0 and 1) of the delay which can be randomly removed
+    pub jitter: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl Backoff {
+    /// Get the delay to wait before the given retry (starting at 1), jitter excluded
+    pub fn delay(&self, retry: u32) -> Duration {
+        if retry == 0 {
+            return Duration::ZERO;
+        }
+        let factor = self
+            .multiplier
+            .powi(retry.saturating_sub(1).try_into().unwrap_or(i32::MAX));
+        Duration::try_from_secs_f64(self.initial.as_secs_f64() * factor)
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+
+    fn jittered(&self, delay: Duration) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 || delay.is_zero() {
+            return delay;
+        }
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        delay.mul_f64(1.0 - jitter * random)
+    }
+}
+
+/// A connection attempt to perform
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReconnectAttempt {
+    /// The number of this attempt, starting at 0
+    pub attempt: u32,
+    /// The delay to wait before attempting to connect
+    pub delay: Duration,
+    /// The uri to connect to
+    pub uri: AMQPUri,
+}
+
+/// Cycle through a list of uris with exponential backoff between connection attempts
+///
+/// This is a pull-based iterator of attempts, letting the caller wait for the delay the
+/// way it sees fit (sleeping or using an async timer) before connecting.
+#[derive(Clone, Debug)]
+pub struct Reconnector {
+    uris: Vec<AMQPUri>,
+    backoff: Backoff,
+    max_attempts: Option<u32>,
+    attempt: u32,
+}
+
+impl Reconnector {
+    /// Create a new Reconnector cycling through the given uris
+    pub fn new(uris: Vec<AMQPUri>) -> Self {
+        Self {
+            uris,
+            backoff: Backoff::default(),
+            max_attempts: None,
+            attempt: 0,
+        }
+    }
+
+    /// Use the given backoff settings
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Stop after the given number of attempts instead of retrying forever
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// The uris we cycle through
+    pub fn uris(&self) -> &[AMQPUri] {
+        &self.uris
+    }
+
+    /// Start over from the first uri without delay, to be called once connected
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Block until we manage to connect to one of the uris, or until we run out of attempts,
+    /// in which case the last error is returned
+    pub fn connect(&mut self) -> Option<HandshakeResult> {
+        let mut last = None;
+        for attempt in self.by_ref() {
+            thread::sleep(attempt.delay);
+            trace!(attempt = attempt.attempt, "Attempting to connect.");
+            let res = attempt.uri.connect();
+            if res.is_ok() {
+                self.reset();
+                return Some(res);
+            }
+            last = Some(res);
+        }
+        last
+    }
+}
+
+impl Iterator for Reconnector {
+    type Item = ReconnectAttempt;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.uris.is_empty() || self.max_attempts.is_some_and(|max| self.attempt >= max) {
+            return None;
+        }
+        let attempt = self.attempt;
+        let uri = self.uris[attempt as usize % self.uris.len()].clone();
+        let delay = self.backoff.jittered(self.backoff.delay(attempt));
+        self.attempt = self.attempt.saturating_add(1);
+        Some(ReconnectAttempt {
+            attempt,
+            delay,
+            uri,
+        })
+    }
+}
+
+impl FromIterator<AMQPUri> for Reconnector {
+    fn from_iter<T: IntoIterator<Item = AMQPUri>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay() {
+        let backoff = Backoff {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(5),
+            ..Backoff::default()
+        };
+        assert_eq!(backoff.delay(0), Duration::ZERO);
+        assert_eq!(backoff.delay(1), Duration::from_secs(1));
+        assert_eq!(backoff.delay(3), Duration::from_secs(4));
+        assert_eq!(backoff.delay(4), Duration::from_secs(5));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_reconnector_cycles_uris() {
+        let uris: Vec<AMQPUri> = ["amqp://host1", "amqp://host2"]
+            .iter()
+            .map(|uri| uri.parse().unwrap())
+            .collect();
+        let attempts = Reconnector::new(uris)
+            .with_backoff(Backoff {
+                jitter: 0.0,
+                ..Backoff::default()
+            })
+            .with_max_attempts(3)
+            .map(|attempt| (attempt.uri.authority.host, attempt.delay.as_millis()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attempts,
+            vec![
+                ("host1".to_string(), 0),
+                ("host2".to_string(), 100),
+                ("host1".to_string(), 200)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jitter() {
+        let backoff = Backoff::default();
+        let delay = backoff.jittered(Duration::from_secs(10));
+        assert!(delay <= Duration::from_secs(10));
+        assert!(delay >= Duration::from_secs(8));
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs},
+};
+
+/// Resolve a host and port into the socket addresses to try connecting to, in order
+pub trait Resolver {
+    /// Resolve the given host and port
+    fn resolve(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>>;
+}
+
+/// The default resolver, using the system resolution through ToSocketAddrs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemResolver;
+
+impl Resolver for SystemResolver {
+    fn resolve(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+        Ok((host, port).to_socket_addrs()?.collect())
+    }
+}
+
+/// Use pre-resolved addresses, ignoring the host and port from the uri
+impl Resolver for [SocketAddr] {
+    fn resolve(&self, _host: &str, _port: u16) -> io::Result<Vec<SocketAddr>> {
+        Ok(self.to_vec())
+    }
+}
+
+/// Use pre-resolved addresses, ignoring the host and port from the uri
+impl Resolver for Vec<SocketAddr> {
+    fn resolve(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+        self.as_slice().resolve(host, port)
+    }
+}
+
+impl<F: Fn(&str, u16) -> io::Result<Vec<SocketAddr>>> Resolver for F {
+    fn resolve(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+        self(host, port)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_custom_resolvers() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 5672);
+        assert_eq!(vec![addr].resolve("rabbitmq", 5671).unwrap(), vec![addr]);
+        let resolver = |host: &str, port| {
+            assert_eq!(host, "rabbitmq.service.consul");
+            Ok(vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port)])
+        };
+        assert_eq!(
+            resolver
+                .resolve("rabbitmq.service.consul", 5672)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            SystemResolver.resolve("127.0.0.1", 5672).unwrap(),
+            vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 5672)]
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::TcpStream;
+use std::{
+    io::{self, Read, Write},
+    net::Shutdown,
+    time::Duration,
+};
+
+/// A connected stream AMQP frames can be exchanged over
+///
+/// Implemented for the TcpStream returned by AMQPUriTcpExt and for MemoryStream, so that code
+/// written against it can be tested without network.
+pub trait AMQPStream: Read + Write + Send {
+    /// Make reads return WouldBlock instead of waiting for data
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()>;
+
+    /// Make blocking reads fail after the given duration
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Shut down the reading half, the writing half or both halves of the stream
+    fn shutdown(&self, how: Shutdown) -> io::Result<()>;
+}
+
+impl AMQPStream for TcpStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        std::net::TcpStream::set_nonblocking(self, nonblocking)
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        std::net::TcpStream::set_read_timeout(self, timeout)
+    }
+
+    fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        std::net::TcpStream::shutdown(self, how)
+    }
+}
@@ -0,0 +1,319 @@
+use crate::HandshakeError;
+use std::{error, fmt, io};
+use tracing::debug;
+
+/// The common causes of TLS handshake failures
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TlsErrorKind {
+    /// The server certificate has expired or is not valid yet
+    ExpiredCertificate,
+    /// The server certificate is not valid for the host we connected to
+    HostnameMismatch,
+    /// The server certificate was not issued by a trusted certificate authority
+    UnknownCertificateAuthority,
+    /// The client and the server have no TLS version in common, or the server doesn't speak TLS
+    ProtocolVersion,
+    /// Any other failure
+    Other,
+}
+
+impl TlsErrorKind {
+    /// A human readable hint on how to fix the failure
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            TlsErrorKind::ExpiredCertificate => Some(
+                "renew the certificate of the server, or check the clock of this host",
+            ),
+            TlsErrorKind::HostnameMismatch => Some(
+                "connect using a host name listed in the certificate, or set server_name_indication in the URI",
+            ),
+            TlsErrorKind::UnknownCertificateAuthority => Some(
+                "pass the certificate of the authority which issued the one of the server with cacertfile in the URI",
+            ),
+            TlsErrorKind::ProtocolVersion => Some(
+                "check that the port is the TLS one (5671 by default) and that the server accepts TLS 1.2 or later",
+            ),
+            TlsErrorKind::Other => None,
+        }
+    }
+
+    /* Look for the error of the TLS backend in the chain of causes */
+    fn classify(source: &io::Error) -> Self {
+        let mut cause = source
+            .get_ref()
+            .map(|err| err as &(dyn error::Error + 'static));
+        while let Some(err) = cause {
+            if let Some(kind) = Self::from_backend_error(err) {
+                return kind;
+            }
+            /* The source of an io::Error is the one of the error it wraps, skipping it */
+            cause = match err.downcast_ref::<io::Error>() {
+                Some(err) => err
+                    .get_ref()
+                    .map(|err| err as &(dyn error::Error + 'static)),
+                None => err.source(),
+            };
+        }
+        TlsErrorKind::Other
+    }
+
+    #[allow(unused_variables)]
+    fn from_backend_error(err: &(dyn error::Error + 'static)) -> Option<Self> {
+        #[cfg(feature = "rustls-common")]
+        if let Some(err) = err.downcast_ref::<rustls::Error>() {
+            return Some(Self::from_rustls(err));
+        }
+        #[cfg(feature = "openssl")]
+        if let Some(err) = err.downcast_ref::<openssl::ssl::Error>() {
+            return err.ssl_error().map(Self::from_openssl);
+        }
+        /* native-tls hides its errors, but exposes the ErrorStack of openssl as their source */
+        #[cfg(feature = "openssl")]
+        if let Some(stack) = err.downcast_ref::<openssl::error::ErrorStack>() {
+            return Some(Self::from_openssl(stack));
+        }
+        None
+    }
+
+    #[cfg(feature = "rustls-common")]
+    fn from_rustls(err: &rustls::Error) -> Self {
+        use rustls::{AlertDescription, CertificateError, Error, InvalidMessage, PeerIncompatible};
+
+        match err {
+            Error::InvalidCertificate(
+                CertificateError::Expired
+                | CertificateError::ExpiredContext { .. }
+                | CertificateError::NotValidYet
+                | CertificateError::NotValidYetContext { .. },
+            ) => TlsErrorKind::ExpiredCertificate,
+            Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            ) => TlsErrorKind::HostnameMismatch,
+            Error::InvalidCertificate(CertificateError::UnknownIssuer) => {
+                TlsErrorKind::UnknownCertificateAuthority
+            }
+            /* A server which doesn't speak TLS answers with records of an invalid type */
+            Error::PeerIncompatible(
+                PeerIncompatible::ServerDoesNotSupportTls12Or13
+                | PeerIncompatible::ServerTlsVersionIsDisabledByOurConfig,
+            )
+            | Error::AlertReceived(AlertDescription::ProtocolVersion)
+            | Error::InvalidMessage(InvalidMessage::InvalidContentType) => {
+                TlsErrorKind::ProtocolVersion
+            }
+            _ => TlsErrorKind::Other,
+        }
+    }
+
+    /* openssl only reports a failed certificate verification, without its cause */
+    #[cfg(feature = "openssl")]
+    fn from_openssl(stack: &openssl::error::ErrorStack) -> Self {
+        use std::ffi::c_int;
+
+        /* From openssl/err.h and openssl/sslerr.h */
+        const ERR_LIB_SSL: c_int = 20;
+        const SSL_R_NO_PROTOCOLS_AVAILABLE: c_int = 191;
+        const SSL_R_UNSUPPORTED_PROTOCOL: c_int = 258;
+        const SSL_R_WRONG_VERSION_NUMBER: c_int = 267;
+        const SSL_R_TLSV1_ALERT_PROTOCOL_VERSION: c_int = 1070;
+
+        let is_protocol_version = stack.errors().iter().any(|err| {
+            err.library_code() == ERR_LIB_SSL
+                && matches!(
+                    err.reason_code(),
+                    SSL_R_NO_PROTOCOLS_AVAILABLE
+                        | SSL_R_UNSUPPORTED_PROTOCOL
+                        | SSL_R_WRONG_VERSION_NUMBER
+                        | SSL_R_TLSV1_ALERT_PROTOCOL_VERSION
+                )
+        });
+        if is_protocol_version {
+            TlsErrorKind::ProtocolVersion
+        } else {
+            TlsErrorKind::Other
+        }
+    }
+}
+
+/// A TLS handshake failure, classified from the error of the TLS backend
+///
+/// The connection methods of AMQPUriTcpExt wrap their TLS failures in it, as the inner error of
+/// the io::Error they return. The original error of the backend is kept as the source.
+#[derive(Debug)]
+pub struct TlsError {
+    kind: TlsErrorKind,
+    domain: String,
+    source: io::Error,
+}
+
+impl TlsError {
+    /// Classify the failure of the TLS handshake with the given domain
+    pub fn classify(domain: &str, source: io::Error) -> Self {
+        Self {
+            kind: TlsErrorKind::classify(&source),
+            domain: domain.to_string(),
+            source,
+        }
+    }
+
+    /// Get the TlsError wrapped in an io::Error, if any
+    pub fn from_io_error(err: &io::Error) -> Option<&Self> {
+        err.get_ref().and_then(|err| err.downcast_ref())
+    }
+
+    /// The cause of the failure
+    pub fn kind(&self) -> TlsErrorKind {
+        self.kind
+    }
+
+    /// A human readable hint on how to fix the failure
+    pub fn hint(&self) -> Option<&'static str> {
+        self.kind.hint()
+    }
+
+    /// The domain used for SNI and the verification of the certificate
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Get back the error of the TLS backend
+    pub fn into_inner(self) -> io::Error {
+        self.source
+    }
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TLS handshake with {} failed: {}",
+            self.domain, self.source
+        )?;
+        if let Some(hint) = self.hint() {
+            write!(f, " (hint: {})", hint)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for TlsError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<TlsError> for io::Error {
+    fn from(err: TlsError) -> Self {
+        io::Error::new(err.source.kind(), err)
+    }
+}
+
+/// Classify the failure of a TLS handshake with the given domain, leaving the handshakes which
+/// would block as is
+///
+/// Use it on the result of MidHandshakeTlsStream::handshake when driving non-blocking
+/// handshakes.
+pub fn classify_handshake_error(domain: &str, err: HandshakeError) -> HandshakeError {
+    match err {
+        HandshakeError::Failure(err) => {
+            let err = TlsError::classify(domain, err);
+            debug!(domain, kind = ?err.kind(), hint = err.hint(), error = %err.source, "TLS handshake failed.");
+            HandshakeError::Failure(err.into())
+        }
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    struct BackendError(&'static str);
+
+    impl fmt::Display for BackendError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl error::Error for BackendError {}
+
+    fn classify<E: error::Error + Send + Sync + 'static>(err: E) -> TlsErrorKind {
+        TlsError::classify(
+            "rabbit.local",
+            io::Error::new(io::ErrorKind::InvalidData, err),
+        )
+        .kind()
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify(BackendError("connection reset by peer")),
+            TlsErrorKind::Other
+        );
+        /* The message of the backend doesn't matter, only its error does */
+        assert_eq!(
+            classify(BackendError("invalid peer certificate: Expired")),
+            TlsErrorKind::Other
+        );
+    }
+
+    #[cfg(feature = "rustls-common")]
+    #[test]
+    fn test_classify_rustls() {
+        use rustls::{CertificateError, Error, InvalidMessage, PeerIncompatible};
+
+        assert_eq!(
+            classify(Error::InvalidCertificate(CertificateError::Expired)),
+            TlsErrorKind::ExpiredCertificate
+        );
+        assert_eq!(
+            classify(Error::InvalidCertificate(CertificateError::NotValidForName)),
+            TlsErrorKind::HostnameMismatch
+        );
+        assert_eq!(
+            classify(Error::InvalidCertificate(CertificateError::UnknownIssuer)),
+            TlsErrorKind::UnknownCertificateAuthority
+        );
+        assert_eq!(
+            classify(Error::InvalidMessage(InvalidMessage::InvalidContentType)),
+            TlsErrorKind::ProtocolVersion
+        );
+        assert_eq!(
+            classify(Error::PeerIncompatible(
+                PeerIncompatible::ServerDoesNotSupportTls12Or13
+            )),
+            TlsErrorKind::ProtocolVersion
+        );
+        assert_eq!(
+            classify(io::Error::other(Error::InvalidCertificate(
+                CertificateError::UnknownIssuer
+            ))),
+            TlsErrorKind::UnknownCertificateAuthority
+        );
+        assert_eq!(classify(Error::DecryptError), TlsErrorKind::Other);
+    }
+
+    #[cfg(feature = "rustls-common")]
+    #[test]
+    fn test_handshake_error() {
+        let err = classify_handshake_error(
+            "rabbit.local",
+            HandshakeError::Failure(io::Error::new(
+                io::ErrorKind::InvalidData,
+                rustls::Error::InvalidCertificate(rustls::CertificateError::UnknownIssuer),
+            )),
+        );
+        let HandshakeError::Failure(err) = err else {
+            panic!("expected a failure");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let tls = TlsError::from_io_error(&err).unwrap();
+        assert_eq!(tls.domain(), "rabbit.local");
+        assert_eq!(tls.kind(), TlsErrorKind::UnknownCertificateAuthority);
+        assert!(err.to_string().contains("hint: pass the certificate"));
+        assert!(TlsError::from_io_error(&io::Error::other("plain")).is_none());
+    }
+}
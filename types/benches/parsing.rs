@@ -0,0 +1,67 @@
+use amq_protocol_types::{
+    generation::gen_field_table,
+    parsing::{parse_field_table, parse_long_long_uint, parse_long_uint, parse_short_uint},
+    AMQPValue, FieldTable,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+/* Run a primitive parser until the input is exhausted */
+fn parse_all<'a, T, E>(bytes: &'a [u8], parser: impl Fn(&'a [u8]) -> Result<(&'a [u8], T), E>) {
+    let mut input = bytes;
+    while !input.is_empty() {
+        let Ok((rest, value)) = parser(input) else {
+            panic!("invalid input");
+        };
+        black_box(value);
+        input = rest;
+    }
+}
+
+fn primitives(c: &mut Criterion) {
+    let bytes = (0..4096u32).flat_map(u32::to_be_bytes).collect::<Vec<_>>();
+    let mut group = c.benchmark_group("primitives");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::new("short_uint", bytes.len()),
+        &bytes,
+        |b, bytes| b.iter(|| parse_all(bytes, parse_short_uint)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("long_uint", bytes.len()),
+        &bytes,
+        |b, bytes| b.iter(|| parse_all(bytes, parse_long_uint)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("long_long_uint", bytes.len()),
+        &bytes,
+        |b, bytes| b.iter(|| parse_all(bytes, parse_long_long_uint)),
+    );
+    group.finish();
+}
+
+fn field_table(c: &mut Criterion) {
+    let mut table = FieldTable::default();
+    for i in 0..64 {
+        let value = match i % 4 {
+            0 => AMQPValue::ShortUInt(i),
+            1 => AMQPValue::LongUInt(i.into()),
+            2 => AMQPValue::LongLongInt(i.into()),
+            _ => AMQPValue::Double(i.into()),
+        };
+        table.insert(format!("key-{}", i).into(), value);
+    }
+    let bytes = cookie_factory::gen_simple(gen_field_table(&table), Vec::new())
+        .expect("failed to serialize");
+    let mut group = c.benchmark_group("field_table");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::new("parse", table.inner().len()),
+        &bytes[..],
+        |b, bytes| b.iter(|| black_box(parse_field_table(bytes).expect("invalid table"))),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, primitives, field_table);
+criterion_main!(benches);
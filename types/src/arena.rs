@@ -0,0 +1,508 @@
+use crate::{parsing::*, types::*, value::AMQPValue};
+use bumpalo::collections::Vec as BumpVec;
+use nom::{
+    bytes::streaming::take,
+    combinator::{complete, flat_map, map, map_res},
+    error::context,
+    sequence::pair,
+    Parser,
+};
+use std::str;
+
+pub use bumpalo::Bump;
+
+/// An AMQPValue whose strings, arrays and tables are allocated in an arena
+///
+/// Parsing a table this way makes a handful of allocations in the arena instead of one per
+/// string, array and table, and everything is freed at once when the arena is reset or dropped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArenaValue<'a> {
+    /// A bool
+    Boolean(Boolean),
+    /// An i8
+    ShortShortInt(ShortShortInt),
+    /// A u8
+    ShortShortUInt(ShortShortUInt),
+    /// An i16
+    ShortInt(ShortInt),
+    /// A u16
+    ShortUInt(ShortUInt),
+    /// An i32
+    LongInt(LongInt),
+    /// A u32
+    LongUInt(LongUInt),
+    /// An i64
+    LongLongInt(LongLongInt),
+    /// An f32
+    Float(Float),
+    /// An f64
+    Double(Double),
+    /// A decimal value
+    DecimalValue(DecimalValue),
+    /// A String (deprecated)
+    ShortString(&'a str),
+    /// A String
+    LongString(&'a [u8]),
+    /// An array of ArenaValue
+    FieldArray(&'a [ArenaValue<'a>]),
+    /// A timestamp (u64)
+    Timestamp(Timestamp),
+    /// A table of ArenaValue
+    FieldTable(ArenaTable<'a>),
+    /// An array of bytes (RabbitMQ specific)
+    ByteArray(&'a [u8]),
+    /// No value
+    Void,
+}
+
+impl ArenaValue<'_> {
+    /// Get the AMQPType of an ArenaValue
+    pub fn get_type(&self) -> AMQPType {
+        match self {
+            ArenaValue::Boolean(_) => AMQPType::Boolean,
+            ArenaValue::ShortShortInt(_) => AMQPType::ShortShortInt,
+            ArenaValue::ShortShortUInt(_) => AMQPType::ShortShortUInt,
+            ArenaValue::ShortInt(_) => AMQPType::ShortInt,
+            ArenaValue::ShortUInt(_) => AMQPType::ShortUInt,
+            ArenaValue::LongInt(_) => AMQPType::LongInt,
+            ArenaValue::LongUInt(_) => AMQPType::LongUInt,
+            ArenaValue::LongLongInt(_) => AMQPType::LongLongInt,
+            ArenaValue::Float(_) => AMQPType::Float,
+            ArenaValue::Double(_) => AMQPType::Double,
+            ArenaValue::DecimalValue(_) => AMQPType::DecimalValue,
+            ArenaValue::ShortString(_) => AMQPType::ShortString,
+            ArenaValue::LongString(_) => AMQPType::LongString,
+            ArenaValue::FieldArray(_) => AMQPType::FieldArray,
+            ArenaValue::Timestamp(_) => AMQPType::Timestamp,
+            ArenaValue::FieldTable(_) => AMQPType::FieldTable,
+            ArenaValue::ByteArray(_) => AMQPType::ByteArray,
+            ArenaValue::Void => AMQPType::Void,
+        }
+    }
+
+    /// Copy the value out of the arena
+    pub fn to_value(&self) -> AMQPValue {
+        match *self {
+            ArenaValue::Boolean(v) => AMQPValue::Boolean(v),
+            ArenaValue::ShortShortInt(v) => AMQPValue::ShortShortInt(v),
+            ArenaValue::ShortShortUInt(v) => AMQPValue::ShortShortUInt(v),
+            ArenaValue::ShortInt(v) => AMQPValue::ShortInt(v),
+            ArenaValue::ShortUInt(v) => AMQPValue::ShortUInt(v),
+            ArenaValue::LongInt(v) => AMQPValue::LongInt(v),
+            ArenaValue::LongUInt(v) => AMQPValue::LongUInt(v),
+            ArenaValue::LongLongInt(v) => AMQPValue::LongLongInt(v),
+            ArenaValue::Float(v) => AMQPValue::Float(v),
+            ArenaValue::Double(v) => AMQPValue::Double(v),
+            ArenaValue::DecimalValue(v) => AMQPValue::DecimalValue(v),
+            ArenaValue::ShortString(v) => AMQPValue::ShortString(v.into()),
+            ArenaValue::LongString(v) => AMQPValue::LongString(v.into()),
+            ArenaValue::FieldArray(v) => AMQPValue::FieldArray(
+                v.iter()
+                    .map(ArenaValue::to_value)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            ArenaValue::Timestamp(v) => AMQPValue::Timestamp(v),
+            ArenaValue::FieldTable(v) => AMQPValue::FieldTable(v.to_field_table()),
+            ArenaValue::ByteArray(v) => AMQPValue::ByteArray(v.into()),
+            ArenaValue::Void => AMQPValue::Void,
+        }
+    }
+}
+
+/// A FieldTable allocated in an arena, keeping its entries in wire order
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ArenaTable<'a> {
+    entries: &'a [(&'a str, ArenaValue<'a>)],
+}
+
+impl<'a> ArenaTable<'a> {
+    /// Get the value of the given key, the last one winning when it is repeated
+    pub fn get(&self, key: &str) -> Option<&ArenaValue<'a>> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Iterate over the entries, in wire order
+    pub fn iter(&self) -> impl Iterator<Item = &(&'a str, ArenaValue<'a>)> {
+        self.entries.iter()
+    }
+
+    /// The number of entries, repeated keys included
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Copy the table out of the arena
+    pub fn to_field_table(&self) -> FieldTable {
+        self.entries
+            .iter()
+            .map(|(k, v)| (ShortString::from(*k), v.to_value()))
+            .collect::<std::collections::BTreeMap<_, _>>()
+            .into()
+    }
+}
+
+/// Parse an ArenaValue, allocating its strings, arrays and tables in the given arena
+pub fn parse_value_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+) -> ParserResult<&'i [u8], ArenaValue<'a>> {
+    parse_value_in_with(arena, i, ParsingOptions::default())
+}
+
+/// Parse an ArenaValue using the given [ParsingOptions](../parsing/struct.ParsingOptions.html),
+/// allocating its strings, arrays and tables in the given arena
+pub fn parse_value_in_with<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+    options: ParsingOptions,
+) -> ParserResult<&'i [u8], ArenaValue<'a>> {
+    let input_len = i.len();
+    context("parse_value", move |i: &'i [u8]| {
+        let (i, amqp_type) = parse_value_type(i)?;
+        parse_raw_value_in_with(arena, amqp_type, i, options)
+    })
+    .parse(i)
+    .map_err(|err| locate_unknown_type(err, input_len))
+}
+
+/// Parse the ArenaValue of the given AMQPType, allocating its strings, arrays and tables in
+/// the given arena
+pub fn parse_raw_value_in<'a, 'i>(
+    arena: &'a Bump,
+    amqp_type: AMQPType,
+    i: &'i [u8],
+) -> ParserResult<&'i [u8], ArenaValue<'a>> {
+    parse_raw_value_in_with(arena, amqp_type, i, ParsingOptions::default())
+}
+
+/// Parse the ArenaValue of the given AMQPType using the given
+/// [ParsingOptions](../parsing/struct.ParsingOptions.html), allocating its strings, arrays and
+/// tables in the given arena
+pub fn parse_raw_value_in_with<'a, 'i>(
+    arena: &'a Bump,
+    amqp_type: AMQPType,
+    i: &'i [u8],
+    options: ParsingOptions,
+) -> ParserResult<&'i [u8], ArenaValue<'a>> {
+    context("parse_raw_value", |i| match amqp_type {
+        AMQPType::Boolean => map(parse_boolean, ArenaValue::Boolean).parse(i),
+        AMQPType::ShortShortInt => map(parse_short_short_int, ArenaValue::ShortShortInt).parse(i),
+        AMQPType::ShortShortUInt => {
+            map(parse_short_short_uint, ArenaValue::ShortShortUInt).parse(i)
+        }
+        AMQPType::ShortInt => map(parse_short_int, ArenaValue::ShortInt).parse(i),
+        AMQPType::ShortUInt => map(parse_short_uint, ArenaValue::ShortUInt).parse(i),
+        AMQPType::LongInt => map(parse_long_int, ArenaValue::LongInt).parse(i),
+        AMQPType::LongUInt => map(parse_long_uint, ArenaValue::LongUInt).parse(i),
+        AMQPType::LongLongInt => map(parse_long_long_int, ArenaValue::LongLongInt).parse(i),
+        /* RabbitMQ treats LongLongUInt as a LongLongInt hence expose it as such */
+        AMQPType::LongLongUInt => map(parse_long_long_int, ArenaValue::LongLongInt).parse(i),
+        AMQPType::Float => map(parse_float, ArenaValue::Float).parse(i),
+        AMQPType::Double => map(parse_double, ArenaValue::Double).parse(i),
+        AMQPType::DecimalValue => map(parse_decimal_value, ArenaValue::DecimalValue).parse(i),
+        AMQPType::ShortString => context(
+            "parse_short_string",
+            map_res(flat_map(parse_short_short_uint, take), |s: &[u8]| {
+                match (str::from_utf8(s), invalid_utf8(options.utf8)) {
+                    (Ok(s), _) => Ok(ArenaValue::ShortString(arena.alloc_str(s))),
+                    (Err(_), InvalidUtf8::Replace) => Ok(ArenaValue::ShortString(
+                        arena.alloc_str(&String::from_utf8_lossy(s)),
+                    )),
+                    (Err(_), InvalidUtf8::Keep) => {
+                        Ok(ArenaValue::LongString(arena.alloc_slice_copy(s)))
+                    }
+                    (Err(err), InvalidUtf8::Reject) => Err(err),
+                }
+            }),
+        )
+        .parse(i),
+        AMQPType::LongString => match invalid_utf8(options.utf8) {
+            InvalidUtf8::Keep => {
+                map(|i| parse_long_string_in(arena, i), ArenaValue::LongString).parse(i)
+            }
+            policy => context(
+                "parse_long_string",
+                map_res(flat_map(parse_long_uint, take), |s: &[u8]| {
+                    match (str::from_utf8(s), &policy) {
+                        (Ok(s), _) => Ok(ArenaValue::LongString(arena.alloc_str(s).as_bytes())),
+                        (Err(_), InvalidUtf8::Replace) => Ok(ArenaValue::LongString(
+                            arena.alloc_str(&String::from_utf8_lossy(s)).as_bytes(),
+                        )),
+                        (Err(err), _) => Err(err),
+                    }
+                }),
+            )
+            .parse(i),
+        },
+        AMQPType::FieldArray => map(
+            |i| parse_field_array_in_with(arena, i, options),
+            ArenaValue::FieldArray,
+        )
+        .parse(i),
+        AMQPType::Timestamp => {
+            map(|i| parse_timestamp_with(i, options), ArenaValue::Timestamp).parse(i)
+        }
+        AMQPType::FieldTable => map(
+            |i| parse_field_table_in_with(arena, i, options),
+            ArenaValue::FieldTable,
+        )
+        .parse(i),
+        AMQPType::ByteArray => {
+            map(|i| parse_long_string_in(arena, i), ArenaValue::ByteArray).parse(i)
+        }
+        AMQPType::Void => Ok((i, ArenaValue::Void)),
+    })
+    .parse(i)
+}
+
+/// Parse a FieldArray, allocating it in the given arena
+pub fn parse_field_array_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+) -> ParserResult<&'i [u8], &'a [ArenaValue<'a>]> {
+    parse_field_array_in_with(arena, i, ParsingOptions::default())
+}
+
+/// Parse a FieldArray using the given [ParsingOptions](../parsing/struct.ParsingOptions.html),
+/// allocating it in the given arena
+pub fn parse_field_array_in_with<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+    options: ParsingOptions,
+) -> ParserResult<&'i [u8], &'a [ArenaValue<'a>]> {
+    let input_len = i.len();
+    context("parse_field_array", move |i: &'i [u8]| {
+        let (i, mut content) = flat_map(parse_long_uint, take).parse(i)?;
+        let mut values = BumpVec::new_in(arena);
+        while !content.is_empty() {
+            let (rest, value) = context(
+                "parse_field_array_entry",
+                complete(|i: &'i [u8]| {
+                    parse_entry(i, options, |i| parse_value_in_with(arena, i, options))
+                }),
+            )
+            .parse(content)
+            .map_err(|err| shift_unknown_type(err, i.len()))?;
+            values.extend(value);
+            content = rest;
+        }
+        Ok((i, values.into_bump_slice()))
+    })
+    .parse(i)
+    .map_err(|err| locate_unknown_type(err, input_len))
+}
+
+/// Parse a FieldTable, allocating it in the given arena
+pub fn parse_field_table_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+) -> ParserResult<&'i [u8], ArenaTable<'a>> {
+    parse_field_table_in_with(arena, i, ParsingOptions::default())
+}
+
+/// Parse a FieldTable using the given [ParsingOptions](../parsing/struct.ParsingOptions.html),
+/// allocating it in the given arena
+pub fn parse_field_table_in_with<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+    options: ParsingOptions,
+) -> ParserResult<&'i [u8], ArenaTable<'a>> {
+    let input_len = i.len();
+    context("parse_field_table", move |i: &'i [u8]| {
+        let (i, mut content) = flat_map(parse_long_uint, take).parse(i)?;
+        let mut entries = BumpVec::new_in(arena);
+        while !content.is_empty() {
+            let (rest, (key, value)) = context(
+                "parse_field_table_entry",
+                complete(pair(
+                    |i| parse_key_in(arena, i, options),
+                    |i: &'i [u8]| {
+                        parse_entry(i, options, |i| parse_value_in_with(arena, i, options))
+                    },
+                )),
+            )
+            .parse(content)
+            .map_err(|err| shift_unknown_type(err, i.len()))?;
+            if let Some(value) = value {
+                entries.push((key, value));
+            }
+            content = rest;
+        }
+        Ok((
+            i,
+            ArenaTable {
+                entries: entries.into_bump_slice(),
+            },
+        ))
+    })
+    .parse(i)
+    .map_err(|err| locate_unknown_type(err, input_len))
+}
+
+/// Parse a ShortString, allocating it in the given arena
+pub fn parse_short_string_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+) -> ParserResult<&'i [u8], &'a str> {
+    parse_key_in(arena, i, ParsingOptions::default())
+}
+
+/// Parse a LongString, allocating its bytes in the given arena
+pub fn parse_long_string_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+) -> ParserResult<&'i [u8], &'a [u8]> {
+    context(
+        "parse_long_string",
+        map(flat_map(parse_long_uint, take), |s: &[u8]| {
+            &*arena.alloc_slice_copy(s)
+        }),
+    )
+    .parse(i)
+}
+
+/* Keys follow the UTF-8 policy but cannot be kept as LongStrings */
+fn parse_key_in<'a, 'i>(
+    arena: &'a Bump,
+    i: &'i [u8],
+    options: ParsingOptions,
+) -> ParserResult<&'i [u8], &'a str> {
+    context(
+        "parse_short_string",
+        map_res(flat_map(parse_short_short_uint, take), |s: &[u8]| {
+            match (str::from_utf8(s), invalid_utf8(options.utf8)) {
+                (Ok(s), _) => Ok(&*arena.alloc_str(s)),
+                (Err(_), InvalidUtf8::Replace) => {
+                    Ok(&*arena.alloc_str(&String::from_utf8_lossy(s)))
+                }
+                (Err(err), _) => Err(err),
+            }
+        }),
+    )
+    .parse(i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generation::gen_field_table;
+
+    fn table() -> FieldTable {
+        let mut nested = FieldTable::default();
+        nested.insert("count".into(), AMQPValue::LongLongInt(3));
+        nested.insert("queue".into(), AMQPValue::LongString("work".into()));
+        let mut table = FieldTable::default();
+        table.insert(
+            "x-death".into(),
+            AMQPValue::FieldArray(vec![AMQPValue::FieldTable(nested)].into()),
+        );
+        table.insert("bytes".into(), AMQPValue::ByteArray(vec![1, 2, 3].into()));
+        table.insert("flag".into(), AMQPValue::Boolean(true));
+        table.insert("time".into(), AMQPValue::Timestamp(1700000000));
+        table
+    }
+
+    #[test]
+    fn test_parse_field_table_in() {
+        let table = table();
+        let bytes = cookie_factory::gen_simple(gen_field_table(&table), Vec::new()).unwrap();
+        let arena = Bump::new();
+        let (rest, parsed) = parse_field_table_in(&arena, &bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(parsed.get("flag"), Some(&ArenaValue::Boolean(true)));
+        assert_eq!(parsed.get("missing"), None);
+        match parsed.get("x-death") {
+            Some(ArenaValue::FieldArray([ArenaValue::FieldTable(death)])) => {
+                assert_eq!(death.get("queue"), Some(&ArenaValue::LongString(b"work")));
+            }
+            other => panic!("unexpected x-death: {:?}", other),
+        }
+        assert_eq!(parsed.to_field_table(), table);
+
+        assert!(matches!(
+            parse_field_table_in(&arena, &bytes[..bytes.len() - 1]),
+            Err(nom::Err::Incomplete(_))
+        ));
+    }
+
+    fn assert_same_as_owned(entries: &[u8], options: ParsingOptions) {
+        let bytes = [&(entries.len() as u32).to_be_bytes()[..], entries].concat();
+        let arena = Bump::new();
+        match (
+            parse_field_table_with(&bytes[..], options),
+            parse_field_table_in_with(&arena, &bytes, options),
+        ) {
+            (Ok((rest, table)), Ok((arena_rest, arena_table))) => {
+                assert_eq!(rest, arena_rest);
+                assert_eq!(table, arena_table.to_field_table(), "{:?}", options);
+            }
+            (Err(nom::Err::Error(err)), Err(nom::Err::Error(arena_err)))
+            | (Err(nom::Err::Failure(err)), Err(nom::Err::Failure(arena_err))) => {
+                assert_eq!(
+                    err.unknown_type(),
+                    arena_err.unknown_type(),
+                    "{:?}",
+                    options
+                );
+            }
+            (owned, in_arena) => panic!("{:?} != {:?} with {:?}", owned, in_arena, options),
+        }
+    }
+
+    #[test]
+    fn test_parse_field_table_in_with() {
+        let invalid_utf8 = [
+            1, b's', b's', 2, b'a', 0xff, 1, b'l', b'S', 0, 0, 0, 2, b'b', 0xff,
+        ];
+        let timestamp32 = [1, b't', b'T', 0x65, 0x53, 0xf1, 0x00];
+        let unknown_type = [
+            2, b'o', b'k', b't', 1, 3, b'b', b'a', b'd', b'?', 0, 1, b'z', b't', 0,
+        ];
+        let unknown_last = [2, b'o', b'k', b't', 1, 1, b'z', b'?'];
+        for timestamp_width in [
+            TimestampWidth::Bits64,
+            TimestampWidth::Bits32,
+            TimestampWidth::Auto,
+        ] {
+            for utf8 in [Utf8Policy::Raw, Utf8Policy::Strict, Utf8Policy::Lossy] {
+                for unknown_types in [UnknownTypePolicy::Strict, UnknownTypePolicy::Skip] {
+                    let options = ParsingOptions {
+                        timestamp_width,
+                        utf8,
+                        unknown_types,
+                    };
+                    for entries in [
+                        &invalid_utf8[..],
+                        &timestamp32,
+                        &unknown_type,
+                        &unknown_last,
+                    ] {
+                        assert_same_as_owned(entries, options);
+                    }
+                }
+            }
+        }
+
+        let arena = Bump::new();
+        let bytes = [&[0, 0, 0, 15][..], &invalid_utf8].concat();
+        let (_, table) = parse_field_table_in(&arena, &bytes).unwrap();
+        assert_eq!(table.get("s"), Some(&ArenaValue::LongString(b"a\xff")));
+        let lossy = ParsingOptions {
+            utf8: Utf8Policy::Lossy,
+            ..ParsingOptions::default()
+        };
+        let (_, table) = parse_field_table_in_with(&arena, &bytes, lossy).unwrap();
+        assert_eq!(table.get("s"), Some(&ArenaValue::ShortString("a\u{fffd}")));
+    }
+}
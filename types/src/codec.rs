@@ -0,0 +1,183 @@
+use crate::{
+    generation::{GenError, SerializeFn},
+    parsing::{ParserError, ParserResult},
+    *,
+};
+use std::{error, fmt};
+
+/// The error returned when decoding fails, independent of the parser backend
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// More bytes are needed, with their number when it is known
+    Incomplete(Option<usize>),
+    /// The bytes are not a valid encoding of the value
+    Invalid(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Incomplete(Some(needed)) => {
+                write!(f, "incomplete input, {} more bytes needed", needed)
+            }
+            DecodeError::Incomplete(None) => f.write_str("incomplete input"),
+            DecodeError::Invalid(err) => write!(f, "invalid input: {}", err),
+        }
+    }
+}
+
+impl error::Error for DecodeError {}
+
+impl From<ParserError> for DecodeError {
+    fn from(err: ParserError) -> Self {
+        match err {
+            nom::Err::Incomplete(nom::Needed::Size(needed)) => Self::Incomplete(Some(needed.get())),
+            nom::Err::Incomplete(nom::Needed::Unknown) => Self::Incomplete(None),
+            nom::Err::Error(err) | nom::Err::Failure(err) => Self::Invalid(err.to_string()),
+        }
+    }
+}
+
+/// The error returned when encoding fails, for example because a value is too large
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncodeError(String);
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to encode: {}", self.0)
+    }
+}
+
+impl error::Error for EncodeError {}
+
+impl From<GenError> for EncodeError {
+    fn from(err: GenError) -> Self {
+        Self(err.to_string())
+    }
+}
+
+/// A value which can be decoded from its wire format
+pub trait Decode: Sized {
+    /// Decode a value from the start of the bytes, returning it with the number of bytes read
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError>;
+}
+
+/// A value which can be encoded in its wire format
+pub trait Encode {
+    /// Append the encoded value to the buffer, which is left untouched on failure
+    fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError>;
+
+    /// Encode the value in a new buffer
+    fn to_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut buffer = Vec::new();
+        self.encode(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/* Not part of the API as they expose the parser and generator backends, only public so that the
+ * generated code of amq-protocol can implement the traits */
+
+/// Implement [Decode] with a parser
+#[doc(hidden)]
+pub fn decode_with<'a, T, P: FnOnce(&'a [u8]) -> ParserResult<&'a [u8], T>>(
+    bytes: &'a [u8],
+    parser: P,
+) -> Result<(T, usize), DecodeError> {
+    let (rest, value) = parser(bytes)?;
+    Ok((value, bytes.len() - rest.len()))
+}
+
+/// Implement [Encode] with a generator
+#[doc(hidden)]
+pub fn encode_with<G: SerializeFn<Vec<u8>>>(
+    buffer: &mut Vec<u8>,
+    gen: G,
+) -> Result<(), EncodeError> {
+    buffer.extend(cookie_factory::gen_simple(gen, Vec::new())?);
+    Ok(())
+}
+
+macro_rules! codec_impl {
+    ($ty:ty, |$v:ident| $gen:expr, $parse:expr) => {
+        impl Decode for $ty {
+            fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError> {
+                decode_with(bytes, $parse)
+            }
+        }
+
+        impl Encode for $ty {
+            fn encode(&self, buffer: &mut Vec<u8>) -> Result<(), EncodeError> {
+                let $v = self;
+                encode_with(buffer, $gen)
+            }
+        }
+    };
+}
+
+codec_impl!(
+    AMQPValue,
+    |v| generation::gen_value(v),
+    parsing::parse_value
+);
+codec_impl!(
+    FieldTable,
+    |v| generation::gen_field_table(v),
+    parsing::parse_field_table
+);
+codec_impl!(
+    FieldArray,
+    |v| generation::gen_field_array(v),
+    parsing::parse_field_array
+);
+codec_impl!(
+    ShortString,
+    |v| generation::gen_short_string(v.as_str()),
+    parsing::parse_short_string
+);
+codec_impl!(
+    LongString,
+    |v| generation::gen_long_string(v.as_bytes()),
+    parsing::parse_long_string
+);
+codec_impl!(
+    DecimalValue,
+    |v| generation::gen_decimal_value(*v),
+    parsing::parse_decimal_value
+);
+codec_impl!(
+    ByteArray,
+    |v| generation::gen_byte_array(v),
+    parsing::parse_byte_array
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_codec() {
+        let mut table = FieldTable::default();
+        table.insert("key".into(), AMQPValue::LongString("value".into()));
+        let bytes = table.to_bytes().unwrap();
+        assert_eq!(FieldTable::decode(&bytes), Ok((table.clone(), bytes.len())));
+
+        let mut buffer = vec![42];
+        AMQPValue::Boolean(true).encode(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![42, b't', 1]);
+        assert_eq!(
+            AMQPValue::decode(&buffer[1..]),
+            Ok((AMQPValue::Boolean(true), 2))
+        );
+
+        assert_eq!(
+            FieldTable::decode(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::Incomplete(Some(1)))
+        );
+        assert!(matches!(
+            AMQPValue::decode(b"?"),
+            Err(DecodeError::Invalid(_))
+        ));
+        assert_eq!(ShortString::decode(b"\x03abcd"), Ok(("abc".into(), 4)));
+    }
+}
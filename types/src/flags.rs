@@ -1,9 +1,11 @@
 use crate::types::Boolean;
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A struct representing AMQP boolean flags for RPC
-#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct AMQPFlags {
     flags: Vec<(String, Boolean)>,
 }
@@ -1,14 +1,14 @@
 /// Serialization types and traits
 pub use cookie_factory::{BackToTheBuffer, GenError, GenResult, SerializeFn};
 
-use crate::{flags::*, types::*, value::*};
+use crate::{flags::*, lazy::LazyFieldTable, types::*, value::*};
 use cookie_factory::{
     bytes::{be_f32, be_f64, be_i16, be_i32, be_i64, be_i8, be_u16, be_u32, be_u64, be_u8},
     combinator::{back_to_the_buffer, slice},
     multi::many_ref,
     sequence::pair,
 };
-use std::io::Write;
+use std::io::{self, Write};
 
 /// Apply a generator and serialize its length at the beginning of buffer
 pub fn gen_with_len<W: Write + BackToTheBuffer, F: SerializeFn<W>>(f: F) -> impl SerializeFn<W> {
@@ -24,9 +24,40 @@ pub fn gen_with_len<W: Write + BackToTheBuffer, F: SerializeFn<W>>(f: F) -> impl
     )
 }
 
+/// Options altering how values are serialized, for compatibility with non-compliant peers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenerationOptions {
+    /// The width of the timestamps generated in field tables and field arrays
+    pub timestamp_width: TimestampWidth,
+    /// Normalize the numeric and string types in field tables and field arrays, so that two
+    /// semantically equal tables produce identical bytes: integers become LongLongInt, floats
+    /// become Double and short strings become LongString. The keys of field tables are always
+    /// generated sorted.
+    pub canonical: bool,
+}
+
+impl GenerationOptions {
+    /// Options generating the canonical form of field tables and field arrays
+    pub fn canonical() -> Self {
+        Self {
+            canonical: true,
+            ..Self::default()
+        }
+    }
+}
+
 /// Generate the [AMQPValue](../type.AMQPValue.html) in the given buffer (x)
 pub fn gen_raw_value<'a, W: Write + BackToTheBuffer + 'a>(
     v: &'a AMQPValue,
+) -> impl SerializeFn<W> + 'a {
+    gen_raw_value_with(v, GenerationOptions::default())
+}
+
+/// Generate the [AMQPValue](../type.AMQPValue.html) in the given buffer (x) using the given
+/// [GenerationOptions](struct.GenerationOptions.html)
+pub fn gen_raw_value_with<'a, W: Write + BackToTheBuffer + 'a>(
+    v: &'a AMQPValue,
+    options: GenerationOptions,
 ) -> impl SerializeFn<W> + 'a {
     move |x| match *v {
         AMQPValue::Boolean(b) => gen_boolean(b)(x),
@@ -42,9 +73,9 @@ pub fn gen_raw_value<'a, W: Write + BackToTheBuffer + 'a>(
         AMQPValue::DecimalValue(d) => gen_decimal_value(d)(x),
         AMQPValue::ShortString(ref s) => gen_short_string(s.as_str())(x),
         AMQPValue::LongString(ref s) => gen_long_string(s.as_bytes())(x),
-        AMQPValue::FieldArray(ref a) => gen_field_array(a)(x),
-        AMQPValue::Timestamp(t) => gen_timestamp(t)(x),
-        AMQPValue::FieldTable(ref t) => gen_field_table(t)(x),
+        AMQPValue::FieldArray(ref a) => gen_field_array_with(a, options)(x),
+        AMQPValue::Timestamp(t) => gen_timestamp_with(t, options)(x),
+        AMQPValue::FieldTable(ref t) => gen_field_table_with(t, options)(x),
         AMQPValue::ByteArray(ref a) => gen_byte_array(a)(x),
         AMQPValue::Void => Ok(x),
     }
@@ -54,7 +85,34 @@ pub fn gen_raw_value<'a, W: Write + BackToTheBuffer + 'a>(
 pub fn gen_value<'a, W: Write + BackToTheBuffer + 'a>(
     v: &'a AMQPValue,
 ) -> impl SerializeFn<W> + 'a {
-    pair(gen_type(v.get_type()), gen_raw_value(v))
+    gen_value_with(v, GenerationOptions::default())
+}
+
+/// Generate the [AMQPValue](../type.AMQPValue.html) preceded with its [AMQPType](../type.AMQPType.html) in the given buffer (x)
+/// using the given [GenerationOptions](struct.GenerationOptions.html)
+pub fn gen_value_with<'a, W: Write + BackToTheBuffer + 'a>(
+    v: &'a AMQPValue,
+    options: GenerationOptions,
+) -> impl SerializeFn<W> + 'a {
+    move |x| match *v {
+        _ if !options.canonical => pair(gen_type(v.get_type()), gen_raw_value_with(v, options))(x),
+        AMQPValue::ShortShortInt(i) => gen_canonical_integer(i.into())(x),
+        AMQPValue::ShortShortUInt(u) => gen_canonical_integer(u.into())(x),
+        AMQPValue::ShortInt(i) => gen_canonical_integer(i.into())(x),
+        AMQPValue::ShortUInt(u) => gen_canonical_integer(u.into())(x),
+        AMQPValue::LongInt(i) => gen_canonical_integer(i.into())(x),
+        AMQPValue::LongUInt(u) => gen_canonical_integer(u.into())(x),
+        AMQPValue::Float(f) => pair(gen_type(AMQPType::Double), gen_double(f.into()))(x),
+        AMQPValue::ShortString(ref s) => pair(
+            gen_type(AMQPType::LongString),
+            gen_long_string(s.as_str().as_bytes()),
+        )(x),
+        _ => pair(gen_type(v.get_type()), gen_raw_value_with(v, options))(x),
+    }
+}
+
+fn gen_canonical_integer<W: Write>(i: LongLongInt) -> impl SerializeFn<W> {
+    pair(gen_type(AMQPType::LongLongInt), gen_long_long_int(i))
 }
 
 /// Generate the [AMQPType](../type.AMQPType.html) in the given buffer (x)
@@ -144,7 +202,18 @@ pub fn gen_long_string<'a, W: Write + 'a>(s: &'a [u8]) -> impl SerializeFn<W> +
 pub fn gen_field_array<'a, W: Write + BackToTheBuffer + 'a>(
     a: &'a FieldArray,
 ) -> impl SerializeFn<W> + 'a {
-    gen_with_len(many_ref(a.as_slice(), move |field| gen_value(field)))
+    gen_field_array_with(a, GenerationOptions::default())
+}
+
+/// Generate the [FieldArray](../type.FieldArray.html) in the given buffer (x) using the given
+/// [GenerationOptions](struct.GenerationOptions.html)
+pub fn gen_field_array_with<'a, W: Write + BackToTheBuffer + 'a>(
+    a: &'a FieldArray,
+    options: GenerationOptions,
+) -> impl SerializeFn<W> + 'a {
+    gen_with_len(many_ref(a.as_slice(), move |field| {
+        gen_value_with(field, options)
+    }))
 }
 
 /// Generate the [Timestamp](../type.Timestamp.html) in the given buffer (x)
@@ -152,17 +221,56 @@ pub fn gen_timestamp<W: Write>(t: Timestamp) -> impl SerializeFn<W> {
     gen_long_long_uint(t)
 }
 
+/// Generate the [Timestamp](../type.Timestamp.html) in the given buffer (x) using the width from
+/// the given [GenerationOptions](struct.GenerationOptions.html).
+/// Fails if the timestamp doesn't fit in 32 bits when generating 32-bit timestamps
+pub fn gen_timestamp_with<W: Write>(
+    t: Timestamp,
+    options: GenerationOptions,
+) -> impl SerializeFn<W> {
+    move |x| match options.timestamp_width {
+        TimestampWidth::Bits32 => {
+            let t = LongUInt::try_from(t).map_err(|_| {
+                GenError::IoError(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Timestamp {} doesn't fit in 32 bits", t),
+                ))
+            })?;
+            gen_long_uint(t)(x)
+        }
+        TimestampWidth::Bits64 | TimestampWidth::Auto => gen_timestamp(t)(x),
+    }
+}
+
 /// Generate the [FieldTable](../type.FieldTable.html) in the given buffer (x)
 pub fn gen_field_table<'a, W: Write + BackToTheBuffer + 'a>(
     t: &'a FieldTable,
 ) -> impl SerializeFn<W> + 'a {
-    gen_with_len(many_ref(t, gen_field_entry))
+    gen_field_table_with(t, GenerationOptions::default())
+}
+
+/// Generate the [FieldTable](../type.FieldTable.html) in the given buffer (x) using the given
+/// [GenerationOptions](struct.GenerationOptions.html)
+pub fn gen_field_table_with<'a, W: Write + BackToTheBuffer + 'a>(
+    t: &'a FieldTable,
+    options: GenerationOptions,
+) -> impl SerializeFn<W> + 'a {
+    gen_with_len(many_ref(t, move |e| gen_field_entry(e, options)))
 }
 
 fn gen_field_entry<'a, W: Write + BackToTheBuffer + 'a>(
     e: (&'a ShortString, &'a AMQPValue),
+    options: GenerationOptions,
 ) -> impl SerializeFn<W> + 'a {
-    pair(gen_short_string(e.0.as_str()), gen_value(e.1))
+    pair(gen_short_string(e.0.as_str()), gen_value_with(e.1, options))
+}
+
+/// Generate the [LazyFieldTable](../struct.LazyFieldTable.html) in the given buffer (x), as is
+pub fn gen_lazy_field_table<'a, W: Write + 'a>(t: &'a LazyFieldTable) -> impl SerializeFn<W> + 'a {
+    pair(
+        gen_long_uint(t.as_bytes().len() as LongUInt),
+        slice(t.as_bytes()),
+    )
 }
 
 /// Generate the [ByteArray](../type.ByteArray.html) in the given buffer (x)
@@ -490,6 +598,69 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_gen_timestamp_width() {
+        let options = GenerationOptions {
+            timestamp_width: TimestampWidth::Bits32,
+            ..GenerationOptions::default()
+        };
+        let mut table = FieldTable::default();
+        table.insert("ts".into(), AMQPValue::Timestamp(1700000000));
+        let mut buf = [0u8; 12];
+        assert_eq!(
+            cf_gen(gen_field_table_with(&table, options), &mut buf[..])
+                .map(|t| t.1)
+                .ok(),
+            Some(12)
+        );
+        assert_eq!(buf, [0, 0, 0, 8, 2, 116, 115, 84, 101, 83, 241, 0]);
+
+        let mut table = FieldTable::default();
+        table.insert("ts".into(), AMQPValue::Timestamp(u64::from(u32::MAX) + 1));
+        let mut buf = [0u8; 12];
+        assert!(cf_gen(gen_field_table_with(&table, options), &mut buf[..]).is_err());
+    }
+
+    #[test]
+    fn test_gen_canonical_field_table() {
+        let gen = |table: &FieldTable| {
+            cookie_factory::gen_simple(
+                gen_field_table_with(table, GenerationOptions::canonical()),
+                Vec::new(),
+            )
+            .unwrap()
+        };
+        let mut nested = FieldTable::default();
+        nested.insert("count".into(), AMQPValue::ShortShortUInt(1));
+        let mut table = FieldTable::default();
+        table.insert("b".into(), AMQPValue::LongUInt(42));
+        table.insert("a".into(), AMQPValue::ShortString("test".into()));
+        table.insert("c".into(), AMQPValue::Float(1.5));
+        table.insert(
+            "d".into(),
+            AMQPValue::FieldArray(vec![AMQPValue::FieldTable(nested)].into()),
+        );
+        let mut nested = FieldTable::default();
+        nested.insert("count".into(), AMQPValue::LongLongInt(1));
+        let mut equivalent = FieldTable::default();
+        equivalent.insert(
+            "d".into(),
+            AMQPValue::FieldArray(vec![AMQPValue::FieldTable(nested)].into()),
+        );
+        equivalent.insert("c".into(), AMQPValue::Double(1.5));
+        equivalent.insert("a".into(), AMQPValue::LongString("test".into()));
+        equivalent.insert("b".into(), AMQPValue::ShortInt(42));
+        assert_ne!(table, equivalent);
+        assert_eq!(gen(&table), gen(&equivalent));
+
+        let mut table = FieldTable::default();
+        table.insert("b".into(), AMQPValue::LongUInt(42));
+        assert_eq!(
+            gen(&table),
+            vec![0, 0, 0, 11, 1, 98, 108, 0, 0, 0, 0, 0, 0, 0, 42]
+        );
+    }
+
     #[test]
     fn test_gen_byte_array() {
         assert_eq!(
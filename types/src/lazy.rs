@@ -0,0 +1,223 @@
+use crate::{parsing::parse_value, types::*, value::AMQPValue};
+use std::{fmt, ops::Range, str, sync::OnceLock};
+
+/// A FieldTable kept in its encoded form, decoding its values on demand
+///
+/// The index of the keys is built on the first access, without decoding any value. Only the
+/// values which are looked up get decoded, which is cheap when large tables are received but
+/// zero or one of their keys is read.
+#[derive(Clone, Default)]
+pub struct LazyFieldTable {
+    bytes: Vec<u8>,
+    index: OnceLock<Result<Vec<LazyEntry>, String>>,
+}
+
+#[derive(Clone, Debug)]
+struct LazyEntry {
+    key: Range<usize>,
+    value: Range<usize>,
+}
+
+impl LazyFieldTable {
+    /// Wrap the encoded entries of a table, without its length prefix
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            index: OnceLock::new(),
+        }
+    }
+
+    /// The encoded entries of the table, without its length prefix
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Decode the value of the given key, the last one winning when it is repeated
+    pub fn get(&self, key: &str) -> Result<Option<AMQPValue>, String> {
+        self.index()?
+            .iter()
+            .rev()
+            .find(|entry| &self.bytes[entry.key.clone()] == key.as_bytes())
+            .map(|entry| self.value(key, entry))
+            .transpose()
+    }
+
+    /// Check whether the table contains the given key
+    pub fn contains_key(&self, key: &str) -> Result<bool, String> {
+        Ok(self
+            .index()?
+            .iter()
+            .any(|entry| &self.bytes[entry.key.clone()] == key.as_bytes()))
+    }
+
+    /// The keys of the table, in wire order
+    pub fn keys(&self) -> Result<impl Iterator<Item = &str>, String> {
+        Ok(self.index()?.iter().map(|entry| self.key(entry)))
+    }
+
+    /// The number of entries, repeated keys included
+    pub fn len(&self) -> Result<usize, String> {
+        self.index().map(Vec::len)
+    }
+
+    /// Check whether the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Decode the whole table in a single pass, the last value winning when a key is repeated
+    pub fn decode(&self) -> Result<FieldTable, String> {
+        let mut table = FieldTable::default();
+        for entry in self.index()? {
+            let key = self.key(entry);
+            table.insert(key.into(), self.value(key, entry)?);
+        }
+        Ok(table)
+    }
+
+    fn key(&self, entry: &LazyEntry) -> &str {
+        /* Keys are checked to be valid UTF-8 when building the index */
+        str::from_utf8(&self.bytes[entry.key.clone()]).unwrap_or_default()
+    }
+
+    fn value(&self, key: &str, entry: &LazyEntry) -> Result<AMQPValue, String> {
+        parse_value(&self.bytes[entry.value.clone()])
+            .map(|(_, value)| value)
+            .map_err(|e| format!("Invalid value for key {}: {}", key, e))
+    }
+
+    fn index(&self) -> Result<&Vec<LazyEntry>, String> {
+        self.index
+            .get_or_init(|| build_index(&self.bytes))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+}
+
+fn build_index(bytes: &[u8]) -> Result<Vec<LazyEntry>, String> {
+    let truncated = || "Truncated field table".to_string();
+    let mut index = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let key_len = usize::from(bytes[offset]);
+        let key = offset + 1..offset + 1 + key_len;
+        let key_bytes = bytes.get(key.clone()).ok_or_else(truncated)?;
+        str::from_utf8(key_bytes).map_err(|e| format!("Invalid field table key: {}", e))?;
+        let type_id = *bytes.get(key.end).ok_or_else(truncated)?;
+        let amqp_type = AMQPType::from_id(type_id as char)
+            .ok_or_else(|| format!("Invalid field table value type: {:?}", type_id as char))?;
+        let len = value_len(amqp_type, &bytes[key.end + 1..]).ok_or_else(truncated)?;
+        let value = key.end..key.end + 1 + len;
+        offset = value.end;
+        index.push(LazyEntry { key, value });
+    }
+    Ok(index)
+}
+
+/* The encoded size of a value, without its type */
+fn value_len(amqp_type: AMQPType, bytes: &[u8]) -> Option<usize> {
+    let prefixed = |prefix: usize| {
+        let len = bytes
+            .get(..prefix)?
+            .iter()
+            .fold(0, |len, b| len << 8 | usize::from(*b));
+        Some(prefix + len)
+    };
+    let len = match amqp_type {
+        AMQPType::Void => 0,
+        AMQPType::Boolean | AMQPType::ShortShortInt | AMQPType::ShortShortUInt => 1,
+        AMQPType::ShortInt | AMQPType::ShortUInt => 2,
+        AMQPType::LongInt | AMQPType::LongUInt | AMQPType::Float => 4,
+        AMQPType::DecimalValue => 5,
+        AMQPType::LongLongInt | AMQPType::LongLongUInt | AMQPType::Double | AMQPType::Timestamp => {
+            8
+        }
+        AMQPType::ShortString => prefixed(1)?,
+        AMQPType::LongString
+        | AMQPType::FieldArray
+        | AMQPType::FieldTable
+        | AMQPType::ByteArray => prefixed(4)?,
+    };
+    (len <= bytes.len()).then_some(len)
+}
+
+/* Lazy tables are equal when they hold the same bytes, whether they have been indexed or not */
+impl PartialEq for LazyFieldTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl fmt::Debug for LazyFieldTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyFieldTable")
+            .field("len", &self.bytes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        generation::{gen_field_table, gen_lazy_field_table},
+        parsing::parse_lazy_field_table,
+    };
+
+    #[test]
+    fn test_lazy_field_table() {
+        let mut nested = FieldTable::default();
+        nested.insert("count".into(), AMQPValue::LongLongInt(1));
+        let mut table = FieldTable::default();
+        table.insert("bool".into(), AMQPValue::Boolean(true));
+        table.insert(
+            "decimal".into(),
+            AMQPValue::DecimalValue(DecimalValue { scale: 2, value: 5 }),
+        );
+        table.insert("nested".into(), AMQPValue::FieldTable(nested));
+        table.insert("string".into(), AMQPValue::LongString("value".into()));
+        table.insert("time".into(), AMQPValue::Timestamp(1700000000));
+        table.insert("void".into(), AMQPValue::Void);
+        let bytes = cookie_factory::gen_simple(gen_field_table(&table), Vec::new()).unwrap();
+
+        let (rest, lazy) = parse_lazy_field_table(&bytes[..]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(lazy.as_bytes(), &bytes[4..]);
+        assert_eq!(
+            lazy.get("string"),
+            Ok(Some(AMQPValue::LongString("value".into())))
+        );
+        assert_eq!(lazy.get("missing"), Ok(None));
+        assert_eq!(lazy.contains_key("void"), Ok(true));
+        assert_eq!(lazy.len(), Ok(6));
+        assert_eq!(
+            lazy.keys().unwrap().collect::<Vec<_>>(),
+            vec!["bool", "decimal", "nested", "string", "time", "void"]
+        );
+        assert_eq!(lazy.decode(), Ok(table.clone()));
+        assert_eq!(
+            cookie_factory::gen_simple(gen_lazy_field_table(&lazy), Vec::new()).unwrap(),
+            bytes
+        );
+
+        /* Repeated keys are decoded in order, the last value winning like with get */
+        let mut repeated = bytes[4..].to_vec();
+        repeated.extend_from_slice(b"\x04boolt\x00");
+        let repeated = LazyFieldTable::new(repeated);
+        assert_eq!(repeated.len(), Ok(7));
+        assert_eq!(repeated.get("bool"), Ok(Some(AMQPValue::Boolean(false))));
+        table.insert("bool".into(), AMQPValue::Boolean(false));
+        assert_eq!(repeated.decode(), Ok(table));
+
+        let truncated = LazyFieldTable::new(bytes[4..bytes.len() - 1].to_vec());
+        assert_eq!(
+            truncated.get("bool"),
+            Err("Truncated field table".to_string())
+        );
+        assert!(LazyFieldTable::default()
+            .decode()
+            .unwrap()
+            .inner()
+            .is_empty());
+    }
+}
@@ -8,17 +8,26 @@
 //! It implements the list of the different kind of types available, a value holder and
 //! serialization.deserialization facilities.
 
+mod lazy;
 mod types;
 mod value;
 
-pub use crate::{types::*, value::*};
+pub use crate::{lazy::*, types::*, value::*};
 
+/// Arena-backed parsing of field tables and arrays.
+#[cfg(feature = "arena")]
+pub mod arena;
+/// Parser-independent Decode and Encode traits.
+pub mod codec;
 /// Helpers to handle AMQP flags.
 pub mod flags;
 /// Generation utilities for the various AMQP types.
 pub mod generation;
 /// Parsing utilities for the various AMQP types.
 pub mod parsing;
+/// Utilities to check that generation and parsing are symmetric.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod roundtrip;
 
 /// A Channel identifier
 pub type ChannelId = Identifier;
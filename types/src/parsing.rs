@@ -1,13 +1,9 @@
-use crate::{flags::*, types::*, value::*};
+use crate::{flags::*, lazy::LazyFieldTable, types::*, value::*};
 use nom::{
     self,
     bytes::streaming::take,
-    combinator::{all_consuming, complete, flat_map, map, map_opt, map_parser, map_res},
+    combinator::{complete, flat_map, map, map_opt, map_res},
     error::{context, ContextError, ErrorKind, ParseError, VerboseErrorKind},
-    multi::fold_many0,
-    number::streaming::{
-        be_f32, be_f64, be_i16, be_i32, be_i64, be_u16, be_u32, be_u64, i8 as be_i8, u8 as be_u8,
-    },
     sequence::pair,
     Parser,
 };
@@ -19,9 +15,42 @@ use traits::*;
 pub struct ParserErrors {
     error: VerboseErrorKind,
     errors: Option<Vec<VerboseErrorKind>>,
+    unknown_type: Option<UnknownType>,
+}
+
+/// An unknown value type id found inside a field table or a field array
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownType {
+    /// The type id
+    pub id: u8,
+    /// The offset of the type id, from the start of the input given to the parser
+    pub offset: usize,
+    /// The number of bytes following the type id in its field table or field array, which
+    /// could not be skipped under UnknownTypePolicy::Skip
+    pub truncated: Option<usize>,
+    /* The number of bytes from the type id to the end of the input, to compute the offset */
+    remaining: usize,
 }
 
 impl ParserErrors {
+    /// The unknown value type id which made the parsing fail, if any
+    pub fn unknown_type(&self) -> Option<&UnknownType> {
+        self.unknown_type.as_ref()
+    }
+
+    fn from_unknown_type(id: u8, remaining: usize) -> Self {
+        Self {
+            error: VerboseErrorKind::Context("unknown value type"),
+            errors: Self::init_errors(),
+            unknown_type: Some(UnknownType {
+                id,
+                offset: 0,
+                truncated: None,
+                remaining,
+            }),
+        }
+    }
+
     #[cfg(not(feature = "verbose-errors"))]
     fn init_errors() -> Option<Vec<VerboseErrorKind>> {
         None
@@ -37,6 +66,7 @@ impl<I> ParseError<I> for ParserErrors {
         Self {
             error: VerboseErrorKind::Nom(kind),
             errors: Self::init_errors(),
+            unknown_type: None,
         }
     }
 
@@ -51,6 +81,7 @@ impl<I> ParseError<I> for ParserErrors {
         Self {
             error: VerboseErrorKind::Char(c),
             errors: Self::init_errors(),
+            unknown_type: None,
         }
     }
 }
@@ -72,7 +103,25 @@ impl<I, E> nom::error::FromExternalError<I, E> for ParserErrors {
 
 impl fmt::Display for ParserErrors {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Parser error: {:?}", self.error)?;
+        match self.unknown_type {
+            Some(UnknownType { id, offset, .. }) => write!(
+                f,
+                "Parser error: unknown value type id {:#04x} ({:?}) at offset {}",
+                id, id as char, offset
+            )?,
+            None => write!(f, "Parser error: {:?}", self.error)?,
+        }
+        if let Some(UnknownType {
+            truncated: Some(truncated),
+            ..
+        }) = self.unknown_type
+        {
+            write!(
+                f,
+                ", skipping it would drop the {} bytes following it in its field table or field array",
+                truncated
+            )?;
+        }
         if let Some(errors) = self.errors.as_ref() {
             for error in errors {
                 writeln!(f)?;
@@ -90,9 +139,49 @@ pub type ParserError = nom::Err<ParserErrors>;
 /// Return type of parsers
 pub type ParserResult<I, T> = Result<(I, T), ParserError>;
 
+/* Account for the bytes following a field table or a field array in the unknown type position */
+pub(crate) fn shift_unknown_type(err: ParserError, len: usize) -> ParserError {
+    err.map(|mut err| {
+        if let Some(unknown_type) = err.unknown_type.as_mut() {
+            unknown_type.remaining += len;
+        }
+        err
+    })
+}
+
+/* Compute the offset of the unknown type from the start of an input of the given length */
+pub(crate) fn locate_unknown_type(err: ParserError, input_len: usize) -> ParserError {
+    err.map(|mut err| {
+        if let Some(unknown_type) = err.unknown_type.as_mut() {
+            unknown_type.offset = input_len.saturating_sub(unknown_type.remaining);
+        }
+        err
+    })
+}
+
+/// Options altering how values are parsed, for compatibility with non-compliant peers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParsingOptions {
+    /// The width of the timestamps found in field tables and field arrays
+    pub timestamp_width: TimestampWidth,
+    /// How to handle invalid UTF-8 in the strings found in field tables and field arrays
+    pub utf8: Utf8Policy,
+    /// How to handle the unknown value types found in field tables and field arrays
+    pub unknown_types: UnknownTypePolicy,
+}
+
 /// Parse the [AMQPValue](../type.AMQPValue.html) of the given [AMQPType](../type.AMQPType.html)
 pub fn parse_raw_value<I: ParsableInput>(
     amqp_type: AMQPType,
+) -> impl Parser<I, Output = AMQPValue, Error = ParserErrors> {
+    parse_raw_value_with(amqp_type, ParsingOptions::default())
+}
+
+/// Parse the [AMQPValue](../type.AMQPValue.html) of the given [AMQPType](../type.AMQPType.html)
+/// using the given [ParsingOptions](struct.ParsingOptions.html)
+pub fn parse_raw_value_with<I: ParsableInput>(
+    amqp_type: AMQPType,
+    options: ParsingOptions,
 ) -> impl Parser<I, Output = AMQPValue, Error = ParserErrors> {
     context("parse_raw_value", move |i| match amqp_type {
         AMQPType::Boolean => map(parse_boolean, AMQPValue::Boolean).parse(i),
@@ -108,11 +197,42 @@ pub fn parse_raw_value<I: ParsableInput>(
         AMQPType::Float => map(parse_float, AMQPValue::Float).parse(i),
         AMQPType::Double => map(parse_double, AMQPValue::Double).parse(i),
         AMQPType::DecimalValue => map(parse_decimal_value, AMQPValue::DecimalValue).parse(i),
-        AMQPType::ShortString => map(parse_short_string, AMQPValue::ShortString).parse(i),
-        AMQPType::LongString => map(parse_long_string, AMQPValue::LongString).parse(i),
-        AMQPType::FieldArray => map(parse_field_array, AMQPValue::FieldArray).parse(i),
-        AMQPType::Timestamp => map(parse_timestamp, AMQPValue::Timestamp).parse(i),
-        AMQPType::FieldTable => map(parse_field_table, AMQPValue::FieldTable).parse(i),
+        AMQPType::ShortString => context(
+            "parse_short_string",
+            map_res(flat_map(parse_short_short_uint, take), move |i: I| {
+                match (make_str(i), invalid_utf8(options.utf8)) {
+                    (Ok(s), _) => Ok(AMQPValue::ShortString(s.into())),
+                    (Err(err), InvalidUtf8::Replace) => {
+                        Ok(AMQPValue::ShortString(make_str_lossy(err).into()))
+                    }
+                    (Err(err), InvalidUtf8::Keep) => {
+                        Ok(AMQPValue::LongString(err.into_bytes().into()))
+                    }
+                    (Err(err), InvalidUtf8::Reject) => Err(err),
+                }
+            }),
+        )
+        .parse(i),
+        AMQPType::LongString => map(
+            |i: I| parse_long_string_with(i, options),
+            AMQPValue::LongString,
+        )
+        .parse(i),
+        AMQPType::FieldArray => map(
+            |i: I| parse_field_array_with(i, options),
+            AMQPValue::FieldArray,
+        )
+        .parse(i),
+        AMQPType::Timestamp => map(
+            |i: I| parse_timestamp_with(i, options),
+            AMQPValue::Timestamp,
+        )
+        .parse(i),
+        AMQPType::FieldTable => map(
+            |i: I| parse_field_table_with(i, options),
+            AMQPValue::FieldTable,
+        )
+        .parse(i),
         AMQPType::ByteArray => map(parse_byte_array, AMQPValue::ByteArray).parse(i),
         AMQPType::Void => Ok((i, AMQPValue::Void)),
     })
@@ -120,14 +240,69 @@ pub fn parse_raw_value<I: ParsableInput>(
 
 /// Parse an [AMQPValue](../type.AMQPValue.html)
 pub fn parse_value<I: ParsableInput>(i: I) -> ParserResult<I, AMQPValue> {
-    context("parse_value", flat_map(parse_type, parse_raw_value)).parse(i)
+    parse_value_with(i, ParsingOptions::default())
+}
+
+/// Parse an [AMQPValue](../type.AMQPValue.html) using the given [ParsingOptions](struct.ParsingOptions.html)
+pub fn parse_value_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, AMQPValue> {
+    let input_len = i.input_len();
+    context("parse_value", move |i: I| {
+        let (i, amqp_type) = parse_value_type(i)?;
+        parse_raw_value_with(amqp_type, options).parse(i)
+    })
+    .parse(i)
+    .map_err(|err| locate_unknown_type(err, input_len))
+}
+
+/* Parse the type of a value, reporting the unknown type so that the policy can handle it */
+pub(crate) fn parse_value_type<I: ParsableInput>(i: I) -> ParserResult<I, AMQPType> {
+    let remaining = i.input_len();
+    let (i, id) = context("parse_type", read_be::<u8, I>).parse(i)?;
+    match AMQPType::from_id(id as char) {
+        Some(amqp_type) => Ok((i, amqp_type)),
+        None => Err(nom::Err::Error(ParserErrors::from_unknown_type(
+            id, remaining,
+        ))),
+    }
+}
+
+/* Parse a value from a field table or a field array with the given parser. When its type is
+ * unknown and the policy allows it, skip it if nothing follows it, otherwise report how many
+ * bytes would be dropped. The unknown types of nested tables and arrays are handled by their own
+ * entries, setting truncated, so only the type of this value can be found without it. */
+pub(crate) fn parse_entry<I: ParsableInput, V>(
+    i: I,
+    options: ParsingOptions,
+    parser: impl FnOnce(I) -> ParserResult<I, V>,
+) -> ParserResult<I, Option<V>> {
+    match parser(i.clone()) {
+        Err(nom::Err::Error(mut err)) if options.unknown_types == UnknownTypePolicy::Skip => {
+            match err.unknown_type.as_mut() {
+                Some(unknown_type) if unknown_type.truncated.is_none() => {
+                    /* remaining counts the type id itself */
+                    match unknown_type.remaining - 1 {
+                        0 => Ok((i.take_from(i.input_len()), None)),
+                        truncated => {
+                            unknown_type.truncated = Some(truncated);
+                            Err(nom::Err::Error(err))
+                        }
+                    }
+                }
+                _ => Err(nom::Err::Error(err)),
+            }
+        }
+        res => res.map(|(i, value)| (i, Some(value))),
+    }
 }
 
 /// Parse an [AMQPType](../type.AMQPType.html)
 pub fn parse_type<I: ParsableInput>(i: I) -> ParserResult<I, AMQPType> {
     context(
         "parse_type",
-        map_opt(be_u8, |t| AMQPType::from_id(t as char)),
+        map_opt(read_be::<u8, I>, |t| AMQPType::from_id(t as char)),
     )
     .parse(i)
 }
@@ -139,57 +314,57 @@ pub fn parse_id<I: ParsableInput>(i: I) -> ParserResult<I, ShortUInt> {
 
 /// Parse a [Boolean](../type.Boolean.html)
 pub fn parse_boolean<I: ParsableInput>(i: I) -> ParserResult<I, Boolean> {
-    context("parse_boolean", map(be_u8, |b| b != 0)).parse(i)
+    context("parse_boolean", map(read_be::<u8, I>, |b| b != 0)).parse(i)
 }
 
 /// Parse a [ShortShortInt](../type.ShortShortInt.html)
 pub fn parse_short_short_int<I: ParsableInput>(i: I) -> ParserResult<I, ShortShortInt> {
-    context("parse_short_short_int", be_i8).parse(i)
+    context("parse_short_short_int", read_be).parse(i)
 }
 
 /// Parse a [ShortShortUInt](../type.ShortShortUInt.html)
 pub fn parse_short_short_uint<I: ParsableInput>(i: I) -> ParserResult<I, ShortShortUInt> {
-    context("parse_short_short_uint", be_u8).parse(i)
+    context("parse_short_short_uint", read_be).parse(i)
 }
 
 /// Parse a [ShortInt](../type.ShortInt.html)
 pub fn parse_short_int<I: ParsableInput>(i: I) -> ParserResult<I, ShortInt> {
-    context("parse_short_int", be_i16).parse(i)
+    context("parse_short_int", read_be).parse(i)
 }
 
 /// Parse a [ShortUInt](../type.ShortUInt.html)
 pub fn parse_short_uint<I: ParsableInput>(i: I) -> ParserResult<I, ShortUInt> {
-    context("parse_short_uint", be_u16).parse(i)
+    context("parse_short_uint", read_be).parse(i)
 }
 
 /// Parse a [LongInt](../type.LongInt.html)
 pub fn parse_long_int<I: ParsableInput>(i: I) -> ParserResult<I, LongInt> {
-    context("parse_long_int", be_i32).parse(i)
+    context("parse_long_int", read_be).parse(i)
 }
 
 /// Parse a [LongUInt](../type.LongUInt.html)
 pub fn parse_long_uint<I: ParsableInput>(i: I) -> ParserResult<I, LongUInt> {
-    context("parse_long_uint", be_u32).parse(i)
+    context("parse_long_uint", read_be).parse(i)
 }
 
 /// Parse a [LongLongInt](../type.LongLongInt.html)
 pub fn parse_long_long_int<I: ParsableInput>(i: I) -> ParserResult<I, LongLongInt> {
-    context("parse_long_long_int", be_i64).parse(i)
+    context("parse_long_long_int", read_be).parse(i)
 }
 
 /// Parse a [LongLongUInt](../type.LongLongUInt.html)
 pub fn parse_long_long_uint<I: ParsableInput>(i: I) -> ParserResult<I, LongLongUInt> {
-    context("parse_long_long_uint", be_u64).parse(i)
+    context("parse_long_long_uint", read_be).parse(i)
 }
 
 /// Parse a [Float](../type.Float.html)
 pub fn parse_float<I: ParsableInput>(i: I) -> ParserResult<I, Float> {
-    context("parse_float", be_f32).parse(i)
+    context("parse_float", read_be).parse(i)
 }
 
 /// Parse a [Double](../type.Double.html)
 pub fn parse_double<I: ParsableInput>(i: I) -> ParserResult<I, Double> {
-    context("parse_double", be_f64).parse(i)
+    context("parse_double", read_be).parse(i)
 }
 
 /// Parse a [DecimalValue](../type.DecimalValue.html)
@@ -208,6 +383,28 @@ fn make_str<I: Input<Item = u8>>(i: I) -> Result<String, std::string::FromUtf8Er
     String::from_utf8(i.iter_elements().collect())
 }
 
+fn make_str_lossy(err: std::string::FromUtf8Error) -> String {
+    String::from_utf8_lossy(err.as_bytes()).into_owned()
+}
+
+/* What becomes of the strings of field tables and field arrays which are not valid UTF-8 */
+pub(crate) enum InvalidUtf8 {
+    /* Replace the invalid sequences with U+FFFD */
+    Replace,
+    /* Keep the bytes, as a LongString */
+    Keep,
+    /* Fail */
+    Reject,
+}
+
+pub(crate) fn invalid_utf8(policy: Utf8Policy) -> InvalidUtf8 {
+    match policy {
+        Utf8Policy::Raw => InvalidUtf8::Keep,
+        Utf8Policy::Strict => InvalidUtf8::Reject,
+        Utf8Policy::Lossy => InvalidUtf8::Replace,
+    }
+}
+
 /// Parse a [ShortString](../type.ShortString.html)
 pub fn parse_short_string<I: ParsableInput>(i: I) -> ParserResult<I, ShortString> {
     context(
@@ -231,48 +428,144 @@ pub fn parse_long_string<I: ParsableInput>(i: I) -> ParserResult<I, LongString>
     .parse(i)
 }
 
-/// Parse a [FieldArray](../type.FieldArray.html)
-pub fn parse_field_array<I: ParsableInput>(i: I) -> ParserResult<I, FieldArray> {
+/// Parse a [ShortString](../type.ShortString.html) using the UTF-8 policy from the given [ParsingOptions](struct.ParsingOptions.html)
+pub fn parse_short_string_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, ShortString> {
     context(
-        "parse_field_array",
-        map_parser(
-            flat_map(parse_long_uint, take),
-            all_consuming(fold_many0(
-                context("parse_field_array_entry", complete(parse_value)),
-                FieldArray::default,
-                |mut acc, elem| {
-                    acc.push(elem);
-                    acc
-                },
-            )),
-        ),
+        "parse_short_string",
+        map_res(flat_map(parse_short_short_uint, take), move |i: I| {
+            match (make_str(i), invalid_utf8(options.utf8)) {
+                (Ok(s), _) => Ok(s.into()),
+                (Err(err), InvalidUtf8::Replace) => Ok(make_str_lossy(err).into()),
+                /* Keys cannot be kept as LongStrings */
+                (Err(err), _) => Err(err),
+            }
+        }),
     )
     .parse(i)
 }
 
+/// Parse a [LongString](../type.LongString.html) using the UTF-8 policy from the given [ParsingOptions](struct.ParsingOptions.html)
+pub fn parse_long_string_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, LongString> {
+    match options.utf8 {
+        Utf8Policy::Raw => parse_long_string(i),
+        policy => context(
+            "parse_long_string",
+            map_res(flat_map(parse_long_uint, take), move |i: I| {
+                match (make_str(i), invalid_utf8(policy)) {
+                    (Ok(s), _) => Ok(s.into()),
+                    (Err(err), InvalidUtf8::Replace) => Ok(make_str_lossy(err).into()),
+                    (Err(err), _) => Err(err),
+                }
+            }),
+        )
+        .parse(i),
+    }
+}
+
+/// Parse a [FieldArray](../type.FieldArray.html)
+pub fn parse_field_array<I: ParsableInput>(i: I) -> ParserResult<I, FieldArray> {
+    parse_field_array_with(i, ParsingOptions::default())
+}
+
+/// Parse a [FieldArray](../type.FieldArray.html) using the given [ParsingOptions](struct.ParsingOptions.html)
+pub fn parse_field_array_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, FieldArray> {
+    let input_len = i.input_len();
+    context("parse_field_array", move |i: I| {
+        let (i, mut entries) = flat_map(parse_long_uint, take).parse(i)?;
+        let mut array = FieldArray::default();
+        while entries.input_len() > 0 {
+            let (rest, elem) = context(
+                "parse_field_array_entry",
+                complete(|i: I| parse_entry(i, options, |i| parse_value_with(i, options))),
+            )
+            .parse(entries)
+            .map_err(|err| shift_unknown_type(err, i.input_len()))?;
+            if let Some(elem) = elem {
+                array.push(elem);
+            }
+            entries = rest;
+        }
+        Ok((i, array))
+    })
+    .parse(i)
+    .map_err(|err| locate_unknown_type(err, input_len))
+}
+
 /// Parse a [Timestamp](../type.Timestamp.html)
 pub fn parse_timestamp<I: ParsableInput>(i: I) -> ParserResult<I, Timestamp> {
     context("parse_timestamp", parse_long_long_uint).parse(i)
 }
 
+/// Parse a [Timestamp](../type.Timestamp.html) using the width from the given [ParsingOptions](struct.ParsingOptions.html)
+pub fn parse_timestamp_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, Timestamp> {
+    let width = match options.timestamp_width {
+        TimestampWidth::Auto if i.input_len() < 8 || i.iter_elements().take(4).any(|b| b != 0) => {
+            TimestampWidth::Bits32
+        }
+        width => width,
+    };
+    match width {
+        TimestampWidth::Bits32 => {
+            context("parse_timestamp", map(parse_long_uint, Timestamp::from)).parse(i)
+        }
+        _ => parse_timestamp(i),
+    }
+}
+
 /// Parse a [FieldTable](../type.FieldTable.html)
 pub fn parse_field_table<I: ParsableInput>(i: I) -> ParserResult<I, FieldTable> {
+    parse_field_table_with(i, ParsingOptions::default())
+}
+
+/// Parse a [FieldTable](../type.FieldTable.html) using the given [ParsingOptions](struct.ParsingOptions.html)
+pub fn parse_field_table_with<I: ParsableInput>(
+    i: I,
+    options: ParsingOptions,
+) -> ParserResult<I, FieldTable> {
+    let input_len = i.input_len();
+    context("parse_field_table", move |i: I| {
+        let (i, mut entries) = flat_map(parse_long_uint, take).parse(i)?;
+        let mut table = FieldTable::default();
+        while entries.input_len() > 0 {
+            let (rest, (key, value)) = context(
+                "parse_field_table_entry",
+                complete(pair(
+                    |i: I| parse_short_string_with(i, options),
+                    |i: I| parse_entry(i, options, |i| parse_value_with(i, options)),
+                )),
+            )
+            .parse(entries)
+            .map_err(|err| shift_unknown_type(err, i.input_len()))?;
+            if let Some(value) = value {
+                table.insert(key, value);
+            }
+            entries = rest;
+        }
+        Ok((i, table))
+    })
+    .parse(i)
+    .map_err(|err| locate_unknown_type(err, input_len))
+}
+
+/// Parse a [LazyFieldTable](../struct.LazyFieldTable.html), keeping its entries encoded
+pub fn parse_lazy_field_table<I: ParsableInput>(i: I) -> ParserResult<I, LazyFieldTable> {
     context(
-        "parse_field_table",
-        map_parser(
-            flat_map(parse_long_uint, take),
-            all_consuming(fold_many0(
-                context(
-                    "parse_field_table_entry",
-                    complete(pair(parse_short_string, parse_value)),
-                ),
-                FieldTable::default,
-                |mut acc, (key, value)| {
-                    acc.insert(key, value);
-                    acc
-                },
-            )),
-        ),
+        "parse_lazy_field_table",
+        map(flat_map(parse_long_uint, take), |i: I| {
+            LazyFieldTable::new(i.iter_elements().collect())
+        }),
     )
     .parse(i)
 }
@@ -299,6 +592,20 @@ pub fn parse_flags<I: ParsableInput>(i: I, names: &[&str]) -> ParserResult<I, AM
     .parse(i)
 }
 
+/// Parse a big-endian primitive, the type being usually inferred
+#[inline]
+pub fn read_be<T: BigEndian, I: ParsableInput>(i: I) -> ParserResult<I, T> {
+    let mut bytes = T::Bytes::default();
+    let (len, size) = (i.input_len(), bytes.as_mut().len());
+    if len < size {
+        return Err(nom::Err::Incomplete(Needed::new(size - len)));
+    }
+    for (dst, src) in bytes.as_mut().iter_mut().zip(i.iter_elements()) {
+        *dst = src;
+    }
+    Ok((i.take_from(size), T::from_be_bytes(bytes)))
+}
+
 /// Traits required for parsing
 pub mod traits {
     /// Reexport nom traits required for parsing
@@ -308,6 +615,32 @@ pub mod traits {
     pub trait ParsableInput: Clone + Compare<&'static [u8]> + Input<Item = u8> + PartialEq {}
 
     impl<T: Clone + Compare<&'static [u8]> + Input<Item = u8> + PartialEq> ParsableInput for T {}
+
+    /// A primitive which can be read from its big-endian representation
+    pub trait BigEndian: Sized {
+        /// The representation, an array of bytes
+        type Bytes: AsMut<[u8]> + Default;
+
+        /// Read the value from its representation
+        fn from_be_bytes(bytes: Self::Bytes) -> Self;
+    }
+
+    macro_rules! impl_big_endian {
+        ($($t:ty),*) => {
+            $(
+                impl BigEndian for $t {
+                    type Bytes = [u8; std::mem::size_of::<$t>()];
+
+                    #[inline]
+                    fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                        <$t>::from_be_bytes(bytes)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_big_endian!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
 }
 
 #[cfg(test)]
@@ -316,6 +649,21 @@ mod test {
 
     const EMPTY: &[u8] = b"";
 
+    #[test]
+    fn test_read_be() {
+        assert_eq!(read_be::<u8, _>(&[42, 1][..]), Ok((&[1][..], 42)));
+        assert_eq!(read_be::<i16, _>(&[255, 254][..]), Ok((EMPTY, -2)));
+        assert_eq!(read_be::<u32, _>(&[0, 0, 1, 0, 7][..]), Ok((&[7][..], 256)));
+        assert_eq!(
+            read_be::<f64, _>(&1.5f64.to_be_bytes()[..]),
+            Ok((EMPTY, 1.5))
+        );
+        assert_eq!(
+            read_be::<u64, _>(&[0, 0, 0][..]),
+            Err(nom::Err::Incomplete(Needed::new(5)))
+        );
+    }
+
     #[test]
     fn test_parse_value() {
         assert_eq!(
@@ -485,6 +833,10 @@ mod test {
             parse_long_string(&[0, 0, 0, 4, 116, 101, 115, 116][..]),
             Ok((EMPTY, "test".into()))
         );
+        assert_eq!(
+            parse_long_string(&[0, 0, 0, 4, 116, 255, 254, 116][..]),
+            Ok((EMPTY, b"t\xff\xfet".into()))
+        );
     }
 
     #[test]
@@ -534,6 +886,173 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_timestamp_width() {
+        let bits32 = ParsingOptions {
+            timestamp_width: TimestampWidth::Bits32,
+            ..ParsingOptions::default()
+        };
+        let auto = ParsingOptions {
+            timestamp_width: TimestampWidth::Auto,
+            ..ParsingOptions::default()
+        };
+        assert_eq!(
+            parse_timestamp_with(&[0, 0, 1, 0][..], bits32),
+            Ok((EMPTY, 256))
+        );
+        assert_eq!(
+            parse_timestamp_with(&[0, 0, 0, 0, 101, 83, 241, 0][..], auto),
+            Ok((EMPTY, 1700000000))
+        );
+        assert_eq!(
+            parse_timestamp_with(&[101, 83, 241, 0, 42][..], auto),
+            Ok((&[42][..], 1700000000))
+        );
+    }
+
+    #[test]
+    fn test_parse_field_table_32bits_timestamp() {
+        let mut table = FieldTable::default();
+        table.insert("ts".into(), AMQPValue::Timestamp(1700000000));
+        table.insert("tt".into(), AMQPValue::Void);
+        let bytes = &[
+            0, 0, 0, 12, 2, 116, 115, 84, 101, 83, 241, 0, 2, 116, 116, 86,
+        ][..];
+        for timestamp_width in [TimestampWidth::Bits32, TimestampWidth::Auto] {
+            assert_eq!(
+                parse_field_table_with(
+                    bytes,
+                    ParsingOptions {
+                        timestamp_width,
+                        ..ParsingOptions::default()
+                    }
+                ),
+                Ok((EMPTY, table.clone()))
+            );
+        }
+        assert_ne!(parse_field_table(bytes), Ok((EMPTY, table)));
+    }
+
+    #[test]
+    fn test_parse_field_table_utf8_policy() {
+        let options = |utf8| ParsingOptions {
+            utf8,
+            ..ParsingOptions::default()
+        };
+        /* {"k\xff": S"v\xff"} */
+        let bytes = &[0, 0, 0, 10, 2, 107, 255, 83, 0, 0, 0, 2, 118, 255][..];
+        assert!(parse_field_table(bytes).is_err());
+        assert!(parse_field_table_with(bytes, options(Utf8Policy::Strict)).is_err());
+        let mut table = FieldTable::default();
+        table.insert(
+            "k\u{fffd}".into(),
+            AMQPValue::LongString("v\u{fffd}".into()),
+        );
+        assert_eq!(
+            parse_field_table_with(bytes, options(Utf8Policy::Lossy)),
+            Ok((EMPTY, table))
+        );
+
+        /* {"k": S"v\xff"} */
+        let bytes = &[0, 0, 0, 9, 1, 107, 83, 0, 0, 0, 2, 118, 255][..];
+        let mut table = FieldTable::default();
+        table.insert("k".into(), AMQPValue::LongString(vec![118, 255].into()));
+        assert_eq!(parse_field_table(bytes), Ok((EMPTY, table)));
+        assert!(parse_field_table_with(bytes, options(Utf8Policy::Strict)).is_err());
+        assert_eq!(
+            parse_long_string_with(&[0, 0, 0, 2, 118, 255][..], options(Utf8Policy::Lossy)),
+            Ok((EMPTY, "v\u{fffd}".into()))
+        );
+        assert_eq!(
+            parse_short_string_with(&[2, 118, 255][..], options(Utf8Policy::Lossy)),
+            Ok((EMPTY, "v\u{fffd}".into()))
+        );
+
+        /* {"k": s"v\xff"} */
+        let bytes = &[0, 0, 0, 6, 1, 107, 115, 2, 118, 255][..];
+        let mut table = FieldTable::default();
+        table.insert("k".into(), AMQPValue::LongString(vec![118, 255].into()));
+        assert_eq!(parse_field_table(bytes), Ok((EMPTY, table)));
+        assert!(parse_field_table_with(bytes, options(Utf8Policy::Strict)).is_err());
+        let mut table = FieldTable::default();
+        table.insert("k".into(), AMQPValue::ShortString("v\u{fffd}".into()));
+        assert_eq!(
+            parse_field_table_with(bytes, options(Utf8Policy::Lossy)),
+            Ok((EMPTY, table))
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_type_policy() {
+        let options = |unknown_types| ParsingOptions {
+            unknown_types,
+            ..ParsingOptions::default()
+        };
+        /* {"a": true, "n": {"z": ?[1, 2]}, "b": false} */
+        let bytes = &[
+            0, 0, 0, 20, 1, 97, 116, 1, 1, 110, 70, 0, 0, 0, 5, 1, 122, 63, 1, 2, 1, 98, 116, 0,
+        ][..];
+        let Err(nom::Err::Error(err)) = parse_field_table(bytes) else {
+            panic!("expected an error");
+        };
+        assert_eq!(err.unknown_type().map(|t| (t.id, t.offset)), Some((63, 17)));
+        assert!(err
+            .to_string()
+            .starts_with("Parser error: unknown value type id 0x3f ('?') at offset 17"));
+        assert!(!err.to_string().contains("skipping"));
+
+        /* The length of ?[1, 2] is unknown, skipping it could drop the entries following it */
+        let Err(nom::Err::Error(err)) =
+            parse_field_table_with(bytes, options(UnknownTypePolicy::Skip))
+        else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            err.unknown_type().map(|t| (t.id, t.offset, t.truncated)),
+            Some((63, 17, Some(2)))
+        );
+        assert!(err.to_string().starts_with(
+            "Parser error: unknown value type id 0x3f ('?') at offset 17, skipping it would drop the 2 bytes following it in its field table or field array"
+        ));
+        let Err(nom::Err::Error(err)) = parse_field_array_with(
+            &[0, 0, 0, 4, 116, 1, 63, 9][..],
+            options(UnknownTypePolicy::Skip),
+        ) else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            err.unknown_type().map(|t| (t.offset, t.truncated)),
+            Some((6, Some(1)))
+        );
+
+        /* {"a": true, "z": ?} */
+        let mut table = FieldTable::default();
+        table.insert("a".into(), AMQPValue::Boolean(true));
+        assert_eq!(
+            parse_field_table_with(
+                &[0, 0, 0, 7, 1, 97, 116, 1, 1, 122, 63][..],
+                options(UnknownTypePolicy::Skip)
+            ),
+            Ok((EMPTY, table))
+        );
+        assert_eq!(
+            parse_field_array_with(
+                &[0, 0, 0, 3, 116, 1, 63][..],
+                options(UnknownTypePolicy::Skip)
+            ),
+            Ok((EMPTY, vec![AMQPValue::Boolean(true)].into()))
+        );
+        let Err(nom::Err::Error(err)) =
+            parse_value_with(&[63, 9][..], options(UnknownTypePolicy::Skip))
+        else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            err.unknown_type().map(|t| (t.id, t.offset, t.truncated)),
+            Some((63, 0, None))
+        );
+    }
+
     #[test]
     fn test_parse_byte_array() {
         assert_eq!(
@@ -0,0 +1,157 @@
+use crate::{generation::*, parsing::*, *};
+use std::{f64, fmt};
+
+/// A type which can be both serialized and parsed, to check that both operations are symmetric
+pub trait Roundtrip: fmt::Debug + PartialEq + Sized {
+    /// Serialize the value into a new buffer
+    fn generate(&self) -> Result<Vec<u8>, GenError>;
+    /// Parse a value from the given buffer
+    fn parse(i: &[u8]) -> ParserResult<&[u8], Self>;
+}
+
+macro_rules! roundtrip_impl {
+    ($ty:ty, |$v:ident| $gen:expr, $parse:expr) => {
+        impl Roundtrip for $ty {
+            fn generate(&self) -> Result<Vec<u8>, GenError> {
+                let $v = self;
+                cookie_factory::gen_simple($gen, Vec::new())
+            }
+
+            fn parse(i: &[u8]) -> ParserResult<&[u8], Self> {
+                $parse(i)
+            }
+        }
+    };
+}
+
+roundtrip_impl!(AMQPValue, |v| gen_value(v), parse_value);
+roundtrip_impl!(FieldTable, |v| gen_field_table(v), parse_field_table);
+roundtrip_impl!(FieldArray, |v| gen_field_array(v), parse_field_array);
+roundtrip_impl!(
+    ShortString,
+    |v| gen_short_string(v.as_str()),
+    parse_short_string
+);
+roundtrip_impl!(
+    LongString,
+    |v| gen_long_string(v.as_bytes()),
+    parse_long_string
+);
+roundtrip_impl!(DecimalValue, |v| gen_decimal_value(*v), parse_decimal_value);
+roundtrip_impl!(ByteArray, |v| gen_byte_array(v), parse_byte_array);
+
+/// Serialize the value, parse it back and assert that we got the same value without leftovers
+pub fn assert_roundtrip<T: Roundtrip>(value: &T) {
+    let buf = match value.generate() {
+        Ok(buf) => buf,
+        Err(err) => panic!("failed to generate {:?}: {:?}", value, err),
+    };
+    match T::parse(&buf) {
+        Ok((rest, parsed)) => {
+            assert_eq!(&parsed, value, "roundtrip altered the value");
+            assert!(
+                rest.is_empty(),
+                "{} bytes left after parsing {:?}",
+                rest.len(),
+                value
+            );
+        }
+        Err(err) => panic!("failed to parse {:?} from {:?}: {:?}", value, buf, err),
+    }
+}
+
+/// A corpus of edge-case values for each AMQPType
+///
+/// NaN is left out as it isn't equal to itself, and so are ShortString values as they have no
+/// type id on the wire.
+pub fn edge_case_values() -> Vec<AMQPValue> {
+    let mut nested = FieldTable::default();
+    nested.insert("".into(), AMQPValue::Void);
+    nested.insert(
+        "array".into(),
+        AMQPValue::FieldArray(
+            vec![
+                AMQPValue::FieldArray(FieldArray::default()),
+                AMQPValue::FieldTable(FieldTable::default()),
+            ]
+            .into(),
+        ),
+    );
+    let mut table = FieldTable::default();
+    table.insert("nested".into(), AMQPValue::FieldTable(nested));
+    table.insert("a".repeat(255).into(), AMQPValue::Boolean(true));
+
+    vec![
+        AMQPValue::Boolean(false),
+        AMQPValue::Boolean(true),
+        AMQPValue::ShortShortInt(ShortShortInt::MIN),
+        AMQPValue::ShortShortInt(ShortShortInt::MAX),
+        AMQPValue::ShortShortUInt(ShortShortUInt::MAX),
+        AMQPValue::ShortInt(ShortInt::MIN),
+        AMQPValue::ShortInt(ShortInt::MAX),
+        AMQPValue::ShortUInt(ShortUInt::MAX),
+        AMQPValue::LongInt(LongInt::MIN),
+        AMQPValue::LongInt(LongInt::MAX),
+        AMQPValue::LongUInt(LongUInt::MAX),
+        AMQPValue::LongLongInt(LongLongInt::MIN),
+        AMQPValue::LongLongInt(LongLongInt::MAX),
+        AMQPValue::Float(-0.0),
+        AMQPValue::Float(Float::MIN_POSITIVE),
+        AMQPValue::Float(Float::INFINITY),
+        AMQPValue::Double(Double::MAX),
+        AMQPValue::Double(f64::consts::PI),
+        AMQPValue::Double(Double::NEG_INFINITY),
+        AMQPValue::DecimalValue(DecimalValue { scale: 0, value: 0 }),
+        AMQPValue::DecimalValue(DecimalValue {
+            scale: ShortShortUInt::MAX,
+            value: LongUInt::MAX,
+        }),
+        AMQPValue::LongString(LongString::default()),
+        AMQPValue::LongString(vec![0u8, 0xff, 0xfe, b'\n'].into()),
+        AMQPValue::FieldArray(FieldArray::default()),
+        AMQPValue::Timestamp(0),
+        AMQPValue::Timestamp(Timestamp::MAX),
+        AMQPValue::FieldTable(FieldTable::default()),
+        AMQPValue::FieldTable(table),
+        AMQPValue::ByteArray(ByteArray::default()),
+        AMQPValue::ByteArray(vec![0u8; 1024].into()),
+        AMQPValue::Void,
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_edge_case_values_roundtrip() {
+        for value in edge_case_values() {
+            assert_roundtrip(&value);
+        }
+        assert_roundtrip(&FieldArray::from(edge_case_values()));
+        assert_roundtrip(&ShortString::from("é".repeat(127)));
+        assert_roundtrip(&LongString::from(vec![0u8, 0xff]));
+    }
+
+    #[test]
+    #[should_panic(expected = "roundtrip altered the value")]
+    fn test_asymmetric_roundtrip() {
+        #[derive(Debug, PartialEq)]
+        struct Lossy(ShortUInt);
+
+        impl Roundtrip for Lossy {
+            fn generate(&self) -> Result<Vec<u8>, GenError> {
+                cookie_factory::gen_simple(
+                    gen_short_short_uint(self.0 as ShortShortUInt),
+                    Vec::new(),
+                )
+            }
+
+            fn parse(i: &[u8]) -> ParserResult<&[u8], Self> {
+                parse_short_short_uint(i).map(|(i, v)| (i, Lossy(v.into())))
+            }
+        }
+
+        assert_roundtrip(&Lossy(300));
+    }
+}
@@ -3,13 +3,16 @@ use crate::value::AMQPValue;
 use std::{
     borrow,
     collections::{btree_map, BTreeMap},
-    fmt, str,
+    fmt, mem, ops, str,
+    sync::Arc,
 };
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Enumeration referencing all the available AMQP types
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum AMQPType {
     /// A bool
     Boolean,
@@ -119,6 +122,124 @@ impl fmt::Display for AMQPType {
     }
 }
 
+/// The width used to encode timestamps inside field tables and field arrays
+///
+/// The specification mandates 64-bit POSIX time, but some non-RabbitMQ peers use 32 bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimestampWidth {
+    /// 64-bit POSIX time, as mandated by the specification
+    #[default]
+    Bits64,
+    /// 32-bit POSIX time
+    Bits32,
+    /// Accept both widths when parsing: a value whose upper 32 bits are all zero is read as a
+    /// 64-bit timestamp, anything else as a 32-bit one. Timestamps are generated using 64 bits.
+    Auto,
+}
+
+/// How to handle invalid UTF-8 in the strings found inside field tables and field arrays
+///
+/// Brokers forward headers set by any client, and some of them are not valid UTF-8.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Utf8Policy {
+    /// Keep LongStrings as is, the specification defining them as binary data. ShortString
+    /// values which are not valid UTF-8 are kept as LongString values holding their bytes, which
+    /// makes them serialize back as LongStrings. Keys can only hold UTF-8 and are validated.
+    #[default]
+    Raw,
+    /// Reject both ShortStrings and LongStrings which are not valid UTF-8
+    Strict,
+    /// Replace the invalid sequences of ShortStrings and LongStrings with U+FFFD
+    Lossy,
+}
+
+/// How to handle the unknown value type ids found inside field tables and field arrays
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownTypePolicy {
+    /// Fail, reporting the unknown type id and its offset
+    #[default]
+    Strict,
+    /// Skip the unknown values ending their field table or field array. The length of the
+    /// other ones is unknown, so they fail as with Strict, reporting how many bytes following
+    /// them would be dropped instead of silently dropping the entries after them. Values
+    /// outside of any table or array fail as with Strict.
+    Skip,
+}
+
+/// How the keys inserted in a FieldTable through try_insert are checked
+///
+/// The AMQP specification wants keys of at most 128 characters, starting with a letter, '$'
+/// or '#' and continuing with letters, digits, '$', '#' or '_'. '-' is accepted too, as
+/// RabbitMQ's own "x-" arguments rely on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KeyPolicy {
+    /// Accept any key
+    #[default]
+    Unchecked,
+    /// Reject the keys breaking the rules
+    Strict,
+    /// Fix the keys breaking the rules: invalid characters become '_', a '#' is prepended
+    /// when the key doesn't start with a valid character and the key is truncated to 128
+    /// characters
+    Sanitize,
+}
+
+impl KeyPolicy {
+    /// The maximum length of a key, in characters
+    pub const MAX_KEY_LENGTH: usize = 128;
+
+    /// Check the key according to this policy, returning the key to use
+    pub fn check(self, key: ShortString) -> Result<ShortString, String> {
+        match self {
+            KeyPolicy::Unchecked => Ok(key),
+            KeyPolicy::Strict => {
+                let mut chars = key.as_str().chars();
+                if !chars.next().is_some_and(is_key_start) {
+                    Err(format!(
+                        "Field table key {:?} must start with a letter, '$' or '#'",
+                        key.as_str()
+                    ))
+                } else if !chars.all(is_key_char) {
+                    Err(format!(
+                        "Field table key {:?} contains invalid characters",
+                        key.as_str()
+                    ))
+                } else if key.as_str().chars().count() > Self::MAX_KEY_LENGTH {
+                    Err(format!(
+                        "Field table key {:?} is longer than {} characters",
+                        key.as_str(),
+                        Self::MAX_KEY_LENGTH
+                    ))
+                } else {
+                    Ok(key)
+                }
+            }
+            KeyPolicy::Sanitize => {
+                let prefix =
+                    (!key.as_str().chars().next().is_some_and(is_key_start)).then_some('#');
+                Ok(prefix
+                    .into_iter()
+                    .chain(
+                        key.as_str()
+                            .chars()
+                            .map(|c| if is_key_char(c) { c } else { '_' }),
+                    )
+                    .take(Self::MAX_KEY_LENGTH)
+                    .collect::<String>()
+                    .into())
+            }
+        }
+    }
+}
+
+fn is_key_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '$' || c == '#'
+}
+
+fn is_key_char(c: char) -> bool {
+    is_key_start(c) || c.is_ascii_digit() || c == '_' || c == '-'
+}
+
 /// A bool
 pub type Boolean = bool;
 /// An i8
@@ -147,23 +268,34 @@ pub type Timestamp = LongLongUInt;
 pub type Void = ();
 
 /// A String (deprecated)
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ShortString(String);
 /// A String
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct LongString(Vec<u8>);
 /// An array of AMQPValue
-#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FieldArray(Vec<AMQPValue>);
 /// A Map<String, AMQPValue>
-#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
-pub struct FieldTable(BTreeMap<ShortString, AMQPValue>);
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct FieldTable {
+    entries: BTreeMap<ShortString, AMQPValue>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    key_policy: KeyPolicy,
+}
 /// An array of bytes (RabbitMQ specific)
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ByteArray(Vec<u8>);
 
 /// A Decimal value composed of a scale and a value
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct DecimalValue {
     /// The scale of the value
     pub scale: ShortShortUInt,
@@ -171,6 +303,18 @@ pub struct DecimalValue {
     pub value: LongUInt,
 }
 
+impl fmt::Display for DecimalValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}", self.value);
+        }
+        let digits = format!("{:0>width$}", self.value, width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        write!(f, "{}.{}", int, frac)
+    }
+}
+
 impl<'a> ShortString {
     /// Get a reference to a ShortString as &str
     pub fn as_str(&'a self) -> &'a str {
@@ -196,6 +340,12 @@ impl borrow::Borrow<str> for ShortString {
     }
 }
 
+impl fmt::Debug for ShortString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl fmt::Display for ShortString {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
@@ -213,6 +363,31 @@ impl<'a> LongString {
     pub fn as_bytes(&'a self) -> &'a [u8] {
         &self.0[..]
     }
+
+    /// Get a reference to a LongString as &str, if it holds valid UTF-8
+    pub fn as_str(&'a self) -> Option<&'a str> {
+        str::from_utf8(&self.0).ok()
+    }
+
+    /// Get the LongString as a str, replacing invalid UTF-8 sequences
+    pub fn to_str_lossy(&'a self) -> borrow::Cow<'a, str> {
+        String::from_utf8_lossy(&self.0)
+    }
+
+    /// Consume the LongString, returning the inner bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Get the length of the LongString in bytes
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether the LongString is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 impl<B> From<B> for LongString
@@ -224,9 +399,15 @@ where
     }
 }
 
+impl fmt::Debug for LongString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_str_lossy().fmt(f)
+    }
+}
+
 impl fmt::Display for LongString {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        String::from_utf8_lossy(&self.0).fmt(f)
+        self.to_str_lossy().fmt(f)
     }
 }
 
@@ -242,6 +423,25 @@ impl FieldArray {
     }
 }
 
+impl fmt::Debug for FieldArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.0).finish()
+    }
+}
+
+impl fmt::Display for FieldArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (idx, value) in self.0.iter().enumerate() {
+            if idx != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        f.write_str("]")
+    }
+}
+
 impl From<Vec<AMQPValue>> for FieldArray {
     fn from(v: Vec<AMQPValue>) -> Self {
         Self(v)
@@ -249,19 +449,89 @@ impl From<Vec<AMQPValue>> for FieldArray {
 }
 
 impl FieldTable {
+    /// Check the keys inserted through try_insert with the given policy
+    pub fn with_key_policy(mut self, key_policy: KeyPolicy) -> Self {
+        self.key_policy = key_policy;
+        self
+    }
+
+    /// The policy checking the keys inserted through try_insert
+    pub fn key_policy(&self) -> KeyPolicy {
+        self.key_policy
+    }
+
     /// Insert a new entry in the table
     pub fn insert(&mut self, k: ShortString, v: AMQPValue) {
-        self.0.insert(k, v);
+        self.entries.insert(k, v);
+    }
+
+    /// Insert a new entry in the table, checking its key according to the key policy of the
+    /// table, and return the key which was used
+    pub fn try_insert(&mut self, k: ShortString, v: AMQPValue) -> Result<ShortString, String> {
+        let k = self.key_policy.check(k)?;
+        self.entries.insert(k.clone(), v);
+        Ok(k)
+    }
+
+    /// Insert a new entry in the table, coercing its value to a type RabbitMQ accepts, and
+    /// return the key which was used. The key is checked according to the key policy of the
+    /// table.
+    pub fn insert_coerced<V: CoerceValue>(
+        &mut self,
+        k: ShortString,
+        v: V,
+    ) -> Result<ShortString, String> {
+        let v = v.coerce().map_err(|err| {
+            format!(
+                "Invalid value for field table key {:?}: {}",
+                k.as_str(),
+                err
+            )
+        })?;
+        self.try_insert(k, v)
+    }
+
+    /// Remove an entry from the table, returning its value if it was there
+    pub fn remove(&mut self, k: &str) -> Option<AMQPValue> {
+        self.entries.remove(k)
     }
 
     /// Check whether the table contains the given key
     pub fn contains_key(&self, k: &str) -> bool {
-        self.0.contains_key(k)
+        self.entries.contains_key(k)
     }
 
     /// Access the inner BTreeMap to perform lookups
     pub fn inner(&self) -> &BTreeMap<ShortString, AMQPValue> {
-        &self.0
+        &self.entries
+    }
+}
+
+/* The key policy is a construction setting, not part of the content of the table */
+impl PartialEq for FieldTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl fmt::Debug for FieldTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|(k, v)| (k.as_str(), v)))
+            .finish()
+    }
+}
+
+impl fmt::Display for FieldTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+        for (idx, (key, value)) in self.entries.iter().enumerate() {
+            if idx != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:?}: {}", key.as_str(), value)?;
+        }
+        f.write_str("}")
     }
 }
 
@@ -270,13 +540,77 @@ impl<'a> IntoIterator for &'a FieldTable {
     type IntoIter = btree_map::Iter<'a, ShortString, AMQPValue>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter()
+        self.entries.iter()
     }
 }
 
 impl From<BTreeMap<ShortString, AMQPValue>> for FieldTable {
-    fn from(m: BTreeMap<ShortString, AMQPValue>) -> Self {
-        Self(m)
+    fn from(entries: BTreeMap<ShortString, AMQPValue>) -> Self {
+        Self {
+            entries,
+            key_policy: KeyPolicy::default(),
+        }
+    }
+}
+
+/// A FieldTable which can be shared between many messages: cloning it only increments a
+/// reference count, and the table is only copied when a shared instance gets mutated
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SharedFieldTable(Arc<FieldTable>);
+
+impl SharedFieldTable {
+    /// Insert a new entry in the table, copying it first if it is shared
+    pub fn insert(&mut self, k: ShortString, v: AMQPValue) {
+        self.make_mut().insert(k, v);
+    }
+
+    /// Remove an entry from the table, copying it first if it is shared and holds the key
+    pub fn remove(&mut self, k: &str) -> Option<AMQPValue> {
+        if !self.0.contains_key(k) {
+            return None;
+        }
+        self.make_mut().remove(k)
+    }
+
+    /// Get a mutable reference to the table, copying it first if it is shared
+    pub fn make_mut(&mut self) -> &mut FieldTable {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Get the table, copying it only if it is still shared
+    pub fn into_field_table(self) -> FieldTable {
+        Arc::try_unwrap(self.0).unwrap_or_else(|table| (*table).clone())
+    }
+
+    /// Check whether both instances share the same table
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl ops::Deref for SharedFieldTable {
+    type Target = FieldTable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for SharedFieldTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<FieldTable> for SharedFieldTable {
+    fn from(table: FieldTable) -> Self {
+        Self(Arc::new(table))
+    }
+}
+
+impl From<SharedFieldTable> for FieldTable {
+    fn from(table: SharedFieldTable) -> Self {
+        table.into_field_table()
     }
 }
 
@@ -297,6 +631,16 @@ impl ByteArray {
     }
 }
 
+impl fmt::Display for ByteArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("x\"")?;
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        f.write_str("\"")
+    }
+}
+
 impl From<Vec<u8>> for ByteArray {
     fn from(v: Vec<u8>) -> Self {
         Self(v)
@@ -309,6 +653,102 @@ impl From<&[u8]> for ByteArray {
     }
 }
 
+/// A value which can be stored in a FieldTable once coerced to a type RabbitMQ accepts
+///
+/// RabbitMQ has no unsigned 64 bits integers nor short strings in field tables: unsigned
+/// integers become LongLongInt when they fit and short strings become LongString, nested
+/// arrays and tables included.
+pub trait CoerceValue {
+    /// Coerce the value, failing when no type accepted by RabbitMQ can hold it
+    fn coerce(self) -> Result<AMQPValue, String>;
+}
+
+impl CoerceValue for AMQPValue {
+    fn coerce(self) -> Result<AMQPValue, String> {
+        Ok(match self {
+            AMQPValue::ShortString(v) => AMQPValue::LongString(v.as_str().into()),
+            AMQPValue::FieldArray(v) => AMQPValue::FieldArray(
+                v.0.into_iter()
+                    .map(CoerceValue::coerce)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into(),
+            ),
+            AMQPValue::FieldTable(mut v) => {
+                for (k, v) in v.entries.iter_mut() {
+                    *v = mem::replace(v, AMQPValue::Void).coerce().map_err(|err| {
+                        format!(
+                            "Invalid value for field table key {:?}: {}",
+                            k.as_str(),
+                            err
+                        )
+                    })?;
+                }
+                AMQPValue::FieldTable(v)
+            }
+            v => v,
+        })
+    }
+}
+
+macro_rules! coerce_from {
+    ($($ty:ty),*) => {
+        $(
+            impl CoerceValue for $ty {
+                fn coerce(self) -> Result<AMQPValue, String> {
+                    AMQPValue::from(self).coerce()
+                }
+            }
+        )*
+    };
+}
+
+coerce_from!(
+    Boolean,
+    ShortShortInt,
+    ShortShortUInt,
+    ShortInt,
+    ShortUInt,
+    LongInt,
+    LongUInt,
+    LongLongInt,
+    Float,
+    Double,
+    DecimalValue,
+    ShortString,
+    LongString,
+    FieldArray,
+    FieldTable,
+    ByteArray
+);
+
+impl CoerceValue for String {
+    fn coerce(self) -> Result<AMQPValue, String> {
+        Ok(AMQPValue::LongString(self.into()))
+    }
+}
+
+impl CoerceValue for &str {
+    fn coerce(self) -> Result<AMQPValue, String> {
+        Ok(AMQPValue::LongString(self.into()))
+    }
+}
+
+macro_rules! coerce_long_long_int {
+    ($($ty:ty),*) => {
+        $(
+            impl CoerceValue for $ty {
+                fn coerce(self) -> Result<AMQPValue, String> {
+                    LongLongInt::try_from(self)
+                        .map(AMQPValue::LongLongInt)
+                        .map_err(|_| format!("{} doesn't fit in a LongLongInt", self))
+                }
+            }
+        )*
+    };
+}
+
+coerce_long_long_int!(LongLongUInt, u128, i128, usize, isize);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -360,4 +800,173 @@ mod test {
             assert_eq!(left, right);
         }
     }
+
+    #[test]
+    fn test_long_string_non_utf8() {
+        let valid: LongString = "valid".into();
+        let invalid: LongString = b"in\xffvalid".into();
+
+        assert_eq!(valid.as_str(), Some("valid"));
+        assert_eq!(invalid.as_str(), None);
+        assert_eq!(invalid.to_str_lossy(), "in\u{FFFD}valid");
+        assert_eq!(invalid.len(), 8);
+        assert_eq!(invalid.into_bytes(), b"in\xffvalid".to_vec());
+    }
+
+    #[test]
+    fn test_decimal_value_to_string() {
+        assert_eq!(
+            DecimalValue {
+                scale: 0,
+                value: 42
+            }
+            .to_string(),
+            "42"
+        );
+        assert_eq!(
+            DecimalValue {
+                scale: 2,
+                value: 4242
+            }
+            .to_string(),
+            "42.42"
+        );
+        assert_eq!(
+            DecimalValue {
+                scale: 3,
+                value: 42
+            }
+            .to_string(),
+            "0.042"
+        );
+    }
+
+    #[test]
+    fn test_field_table_to_string() {
+        let mut table = FieldTable::default();
+        table.insert("x-match".into(), AMQPValue::LongString("all".into()));
+        table.insert("retries".into(), AMQPValue::LongInt(3));
+        table.insert(
+            "nested".into(),
+            AMQPValue::FieldArray(vec![AMQPValue::Boolean(true), AMQPValue::Void].into()),
+        );
+        assert_eq!(
+            table.to_string(),
+            r#"{"nested": [true, void], "retries": 3i32, "x-match": "all"}"#
+        );
+        assert_eq!(
+            format!("{:?}", table),
+            r#"{"nested": FieldArray([Boolean(true), Void]), "retries": LongInt(3), "x-match": LongString("all")}"#
+        );
+    }
+
+    #[test]
+    fn test_field_table_key_policy() {
+        let mut table = FieldTable::default().with_key_policy(KeyPolicy::Strict);
+        assert_eq!(
+            table.try_insert("x-match".into(), AMQPValue::Boolean(true)),
+            Ok("x-match".into())
+        );
+        assert!(table
+            .try_insert("1st".into(), AMQPValue::Boolean(true))
+            .is_err());
+        assert!(table
+            .try_insert("a b".into(), AMQPValue::Boolean(true))
+            .is_err());
+        assert!(table
+            .try_insert("a".repeat(129).into(), AMQPValue::Boolean(true))
+            .is_err());
+        assert!(table
+            .try_insert("".into(), AMQPValue::Boolean(true))
+            .is_err());
+        assert_eq!(table.inner().len(), 1);
+
+        let mut table = FieldTable::default().with_key_policy(KeyPolicy::Sanitize);
+        assert_eq!(
+            table.try_insert("1st key".into(), AMQPValue::Boolean(true)),
+            Ok("#1st_key".into())
+        );
+        assert_eq!(
+            table.try_insert("$valid#".into(), AMQPValue::Boolean(true)),
+            Ok("$valid#".into())
+        );
+        assert_eq!(
+            KeyPolicy::Sanitize.check("é".repeat(200).into()),
+            Ok(format!("#{}", "_".repeat(127)).into())
+        );
+        assert!(table.contains_key("#1st_key"));
+
+        let mut unchecked = FieldTable::default();
+        unchecked.insert("#1st_key".into(), AMQPValue::Boolean(true));
+        unchecked.insert("$valid#".into(), AMQPValue::Boolean(true));
+        assert_eq!(table, unchecked);
+        assert_eq!(
+            FieldTable::default().try_insert("1st key".into(), AMQPValue::Void),
+            Ok("1st key".into())
+        );
+    }
+
+    #[test]
+    fn test_shared_field_table() {
+        let mut table = FieldTable::default();
+        table.insert("x-origin".into(), AMQPValue::LongString("batch".into()));
+        let shared = SharedFieldTable::from(table.clone());
+        let mut stamped = shared.clone();
+        assert!(stamped.ptr_eq(&shared));
+        assert_eq!(stamped.remove("x-missing"), None);
+        assert!(stamped.ptr_eq(&shared));
+        stamped.insert("x-retries".into(), AMQPValue::LongUInt(1));
+        assert!(!stamped.ptr_eq(&shared));
+        assert_eq!(shared.inner().len(), 1);
+        assert_eq!(stamped.inner().len(), 2);
+        assert_eq!(shared.clone().into_field_table(), table);
+        assert_eq!(FieldTable::from(shared), table);
+    }
+
+    #[test]
+    fn test_insert_coerced() {
+        let mut table = FieldTable::default();
+        assert!(table.insert_coerced("x-max-length".into(), 42u64).is_ok());
+        assert!(table.insert_coerced("x-name".into(), "test").is_ok());
+        assert!(table
+            .insert_coerced("x-short".into(), ShortString::from("short"))
+            .is_ok());
+        assert_eq!(
+            table.insert_coerced("x-too-big".into(), u64::MAX),
+            Err(format!(
+                "Invalid value for field table key \"x-too-big\": {} doesn't fit in a LongLongInt",
+                u64::MAX
+            ))
+        );
+        assert!(table.insert_coerced("x-neg".into(), -1i128).is_ok());
+        assert_eq!(
+            table.inner().get("x-max-length"),
+            Some(&AMQPValue::LongLongInt(42))
+        );
+        assert_eq!(
+            table.inner().get("x-name"),
+            Some(&AMQPValue::LongString("test".into()))
+        );
+        assert_eq!(
+            table.inner().get("x-short"),
+            Some(&AMQPValue::LongString("short".into()))
+        );
+        assert!(!table.contains_key("x-too-big"));
+
+        let mut nested = FieldTable::default();
+        nested.insert("key".into(), AMQPValue::ShortString("value".into()));
+        let array = FieldArray::from(vec![AMQPValue::FieldTable(nested)]);
+        assert!(table.insert_coerced("x-array".into(), array).is_ok());
+        let mut expected = FieldTable::default();
+        expected.insert("key".into(), AMQPValue::LongString("value".into()));
+        assert_eq!(
+            table.inner().get("x-array"),
+            Some(&AMQPValue::FieldArray(
+                vec![AMQPValue::FieldTable(expected)].into()
+            ))
+        );
+
+        let mut strict = FieldTable::default().with_key_policy(KeyPolicy::Strict);
+        assert!(strict.insert_coerced("1st".into(), 1u64).is_err());
+    }
 }
@@ -1,10 +1,15 @@
 use crate::types::*;
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
 use serde_json::Value;
 
+use std::fmt;
+
 /// Enumeration referencing the possible AMQP values depending on the types
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum AMQPValue {
     /// A bool
     Boolean(Boolean),
@@ -70,6 +75,7 @@ impl AMQPValue {
     }
 
     /// Convert a serde_json::Value into an AMQPValue
+    #[cfg(feature = "serde")]
     pub fn try_from(value: &Value, amqp_type: AMQPType) -> Option<AMQPValue> {
         match amqp_type {
             AMQPType::Boolean => value.as_bool().map(AMQPValue::Boolean),
@@ -108,6 +114,72 @@ impl AMQPValue {
         }
     }
 
+    /// Convert a serde_json::Value into an AMQPValue, guessing its type: integers become
+    /// LongLongInt, other numbers Double, strings LongString, arrays FieldArray and objects
+    /// FieldTable
+    #[cfg(feature = "serde")]
+    pub fn from_json(value: &Value) -> AMQPValue {
+        match value {
+            Value::Null => AMQPValue::Void,
+            Value::Bool(value) => AMQPValue::Boolean(*value),
+            Value::Number(number) => number
+                .as_i64()
+                .map(AMQPValue::LongLongInt)
+                .unwrap_or_else(|| AMQPValue::Double(number.as_f64().unwrap_or_default())),
+            Value::String(value) => AMQPValue::LongString(value.as_str().into()),
+            Value::Array(values) => AMQPValue::FieldArray(
+                values
+                    .iter()
+                    .map(AMQPValue::from_json)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            Value::Object(values) => AMQPValue::FieldTable(values.iter().fold(
+                FieldTable::default(),
+                |mut table, (key, value)| {
+                    table.insert(key.as_str().into(), AMQPValue::from_json(value));
+                    table
+                },
+            )),
+        }
+    }
+
+    /// Convert the AMQPValue into a serde_json::Value, the way the management API of RabbitMQ
+    /// does: strings are decoded lossily and byte arrays become arrays of numbers
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Value {
+        let float = |f: Double| serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number);
+        match self {
+            AMQPValue::Boolean(v) => Value::Bool(*v),
+            AMQPValue::ShortShortInt(v) => Value::from(*v),
+            AMQPValue::ShortShortUInt(v) => Value::from(*v),
+            AMQPValue::ShortInt(v) => Value::from(*v),
+            AMQPValue::ShortUInt(v) => Value::from(*v),
+            AMQPValue::LongInt(v) => Value::from(*v),
+            AMQPValue::LongUInt(v) => Value::from(*v),
+            AMQPValue::LongLongInt(v) => Value::from(*v),
+            AMQPValue::Float(v) => float(Double::from(*v)),
+            AMQPValue::Double(v) => float(*v),
+            AMQPValue::DecimalValue(v) => {
+                float(Double::from(v.value) / 10f64.powi(i32::from(v.scale)))
+            }
+            AMQPValue::ShortString(v) => Value::String(v.to_string()),
+            AMQPValue::LongString(v) => Value::String(v.to_str_lossy().into()),
+            AMQPValue::FieldArray(v) => {
+                Value::Array(v.as_slice().iter().map(Self::to_json).collect())
+            }
+            AMQPValue::Timestamp(v) => Value::from(*v),
+            AMQPValue::FieldTable(v) => Value::Object(
+                v.inner()
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_json()))
+                    .collect(),
+            ),
+            AMQPValue::ByteArray(v) => Value::from(v.as_slice()),
+            AMQPValue::Void => Value::Null,
+        }
+    }
+
     /// If the value is bool, returns associated value. Returns None otherwise.
     pub fn as_bool(&self) -> Option<Boolean> {
         match self {
@@ -253,6 +325,31 @@ impl AMQPValue {
     }
 }
 
+impl fmt::Display for AMQPValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AMQPValue::Boolean(v) => write!(f, "{}", v),
+            AMQPValue::ShortShortInt(v) => write!(f, "{}i8", v),
+            AMQPValue::ShortShortUInt(v) => write!(f, "{}u8", v),
+            AMQPValue::ShortInt(v) => write!(f, "{}i16", v),
+            AMQPValue::ShortUInt(v) => write!(f, "{}u16", v),
+            AMQPValue::LongInt(v) => write!(f, "{}i32", v),
+            AMQPValue::LongUInt(v) => write!(f, "{}u32", v),
+            AMQPValue::LongLongInt(v) => write!(f, "{}i64", v),
+            AMQPValue::Float(v) => write!(f, "{}f32", v),
+            AMQPValue::Double(v) => write!(f, "{}f64", v),
+            AMQPValue::DecimalValue(v) => write!(f, "{}d", v),
+            AMQPValue::ShortString(v) => write!(f, "{:?}", v),
+            AMQPValue::LongString(v) => write!(f, "{:?}", v),
+            AMQPValue::FieldArray(v) => write!(f, "{}", v),
+            AMQPValue::Timestamp(v) => write!(f, "timestamp({})", v),
+            AMQPValue::FieldTable(v) => write!(f, "{}", v),
+            AMQPValue::ByteArray(v) => write!(f, "{}", v),
+            AMQPValue::Void => f.write_str("void"),
+        }
+    }
+}
+
 impl From<Boolean> for AMQPValue {
     fn from(v: Boolean) -> Self {
         AMQPValue::Boolean(v)
@@ -359,9 +456,39 @@ impl From<ByteArray> for AMQPValue {
 mod test {
     use super::*;
 
+    #[cfg(feature = "serde")]
     use serde_json::Number;
 
     #[test]
+    #[cfg(feature = "serde")]
+    fn test_json() {
+        let json = serde_json::json!({
+            "x-queue-type": "quorum",
+            "x-max-length": 1000,
+            "ratio": 0.5,
+            "keys": ["a", true, null],
+        });
+        let value = AMQPValue::from_json(&json);
+        let table = value.as_field_table().unwrap();
+        assert_eq!(
+            table.inner().get("x-max-length"),
+            Some(&AMQPValue::LongLongInt(1000))
+        );
+        assert_eq!(table.inner().get("ratio"), Some(&AMQPValue::Double(0.5)));
+        assert_eq!(value.to_json(), json);
+        assert_eq!(
+            AMQPValue::DecimalValue(DecimalValue {
+                scale: 2,
+                value: 125
+            })
+            .to_json(),
+            serde_json::json!(1.25)
+        );
+        assert_eq!(AMQPValue::Double(f64::NAN).to_json(), Value::Null);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
     fn test_from_bool_value() {
         assert_eq!(
             AMQPValue::try_from(&Value::Bool(false), AMQPType::Boolean),
@@ -374,6 +501,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
     fn test_from_number_value() {
         assert_eq!(
             AMQPValue::try_from(&Value::Number(Number::from(42)), AMQPType::LongLongUInt),
@@ -393,6 +521,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
     fn test_from_string_value() {
         assert_eq!(
             AMQPValue::try_from(&Value::String(String::new()), AMQPType::LongString),
@@ -405,6 +534,23 @@ mod test {
     }
 
     #[test]
+    fn test_value_to_string() {
+        assert_eq!(AMQPValue::ShortShortUInt(42).to_string(), "42u8");
+        assert_eq!(AMQPValue::LongLongInt(-42).to_string(), "-42i64");
+        assert_eq!(AMQPValue::Double(42.5).to_string(), "42.5f64");
+        assert_eq!(
+            AMQPValue::LongString("a \"b\"".into()).to_string(),
+            r#""a \"b\"""#
+        );
+        assert_eq!(AMQPValue::Timestamp(42).to_string(), "timestamp(42)");
+        assert_eq!(
+            AMQPValue::ByteArray(vec![42, 1, 255].into()).to_string(),
+            r#"x"2a01ff""#
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
     fn test_from_null_value() {
         assert_eq!(
             AMQPValue::try_from(&Value::Null, AMQPType::Void),